@@ -0,0 +1,54 @@
+/*
+ * assemble.rs
+ * Fuzz target for assembling and compiling arbitrary text
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+
+#![no_main]
+
+//usage statements
+use libfuzzer_sys::fuzz_target;
+use ch8_isa::asm;
+use ch8_isa::octo;
+use ch8_isa::compiler;
+use ch8_isa::codegen::Binary;
+
+//every front end must reject bad source with an error
+//rather than a panic, and must emit what it accepts
+fuzz_target!(|data: &[u8]| {
+    let src = match std::str::from_utf8(data) {
+        Ok(src) => src,
+        Err(_) => return
+    };
+    if let Ok(img) = asm::assemble(src, "fuzz.s") {
+        let mut bin = Binary::from_bytes("fuzz.c8", &[]).unwrap();
+        let _ = img.write_to_binary(&mut bin);
+    }
+    if let Ok(img) = octo::compile(src) {
+        let mut bin = Binary::from_bytes("fuzz.c8", &[]).unwrap();
+        let _ = img.write_to_binary(&mut bin);
+    }
+    if let Ok(prog) = compiler::compile(src) {
+        let mut bin = Binary::from_bytes("fuzz.c8", &[]).unwrap();
+        let _ = prog.write_to_binary(&mut bin);
+    }
+});
+
+//end of file
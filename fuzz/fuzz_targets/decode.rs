@@ -0,0 +1,55 @@
+/*
+ * decode.rs
+ * Fuzz target for decoding arbitrary words into instructions
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+
+#![no_main]
+
+//usage statements
+use libfuzzer_sys::fuzz_target;
+use ch8_isa::asm;
+use ch8_isa::codegen::Binary;
+use ch8_isa::codegen::Instruction;
+
+//every decodable word must encode back to itself, and its
+//disassembly must reassemble to the same word
+fuzz_target!(|data: &[u8]| {
+    for pair in data.chunks_exact(2) {
+        let word = u16::from_be_bytes([pair[0], pair[1]]);
+        let instr = match Instruction::decode(word) {
+            Some(instr) => instr,
+            None => continue
+        };
+        let mut bin = Binary::from_bytes("decode.c8", &[]).unwrap();
+        bin.add_instruction(&instr).unwrap();
+        assert_eq!(bin.get_data(), pair);
+        let text = instr.to_string();
+        let img = asm::assemble(&text, "decode.s").unwrap();
+        assert_eq!(img.to_bytes(), pair, "{}", text);
+    }
+
+    //arbitrary bytes, including data, must still list
+    if let Ok(bin) = Binary::from_bytes("decode.c8", data) {
+        let _ = bin.listing();
+    }
+});
+
+//end of file
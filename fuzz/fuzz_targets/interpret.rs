@@ -0,0 +1,99 @@
+/*
+ * interpret.rs
+ * Fuzz target for interpreting arbitrary ROMs
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+
+#![no_main]
+
+//usage statements
+use libfuzzer_sys::fuzz_target;
+use ch8_isa::codegen::Instruction;
+use ch8_isa::vm::Machine;
+use ch8_isa::vm::Quirks;
+use ch8_isa::vm::MEMORY_SIZE;
+use ch8_isa::vm::STACK_DEPTH;
+
+/// The most instructions run for one input
+const MAX_STEPS: usize = 10_000;
+
+/// The instructions run between timer ticks
+const TICK_INTERVAL: usize = 10;
+
+//the first byte picks the quirks and the second seeds the
+//random source; the rest is the ROM, which runs for a bounded
+//number of steps with the keypad changing as it goes. Each
+//instruction may only write the memory its effects describe,
+//wrapping at the 64 KiB memory of XO-CHIP rather than at the
+//4 KiB of the COSMAC VIP.
+fuzz_target!(|data: &[u8]| {
+    if data.len() < 2 {
+        return;
+    }
+    let flags = data[0];
+    let quirks = Quirks {
+        shift_vy: flags & 0x01 != 0,
+        load_store_inc_i: flags & 0x02 != 0,
+        vf_reset: flags & 0x04 != 0,
+        jump_vx: flags & 0x08 != 0,
+        clip: flags & 0x10 != 0,
+        large_sprites: flags & 0x20 != 0
+    };
+    let mut vm = Machine::new(quirks);
+    vm.set_seed(data[1] as u64);
+    if vm.load(&data[2..]).is_err() {
+        return;
+    }
+    let mut before = vm.get_memory().to_vec();
+    for step in 0..MAX_STEPS {
+        if step % TICK_INTERVAL == 0 {
+            vm.tick_timers();
+            vm.set_keypad((step / TICK_INTERVAL) as u16);
+        }
+
+        //find the bytes the next instruction may write
+        let pc = vm.get_pc() as usize;
+        let word = u16::from_be_bytes([before[pc],
+                                       before[(pc + 1) % MEMORY_SIZE]]);
+        let writes = Instruction::decode(word).map(|instr| {
+            (vm.get_i() as usize, instr.effects(quirks).get_memory_write())
+        });
+        if vm.step().is_err() {
+            break;
+        }
+
+        //and check that no other byte changed
+        for (addr, (old, new)) in before.iter_mut()
+            .zip(vm.get_memory().iter()).enumerate() {
+            if *old == *new {
+                continue;
+            }
+            if let Some((i, len)) = writes {
+                let offset = (addr + MEMORY_SIZE - i) % MEMORY_SIZE;
+                assert!(offset < len as usize, "{:04X} wrote 0x{:04X}",
+                        word, addr);
+            }
+            *old = *new;
+        }
+        assert!(vm.get_stack().len() <= STACK_DEPTH);
+    }
+});
+
+//end of file
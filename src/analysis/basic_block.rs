@@ -0,0 +1,173 @@
+/*
+ * basic_block.rs
+ * Defines a basic block of a control-flow graph
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+
+//usage statements
+use super::super::codegen::Instruction;
+
+/// A straight-line run of instructions that is only entered at
+/// its first instruction and only left after its last one. Blocks
+/// end at jumps, skips, calls and returns.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BasicBlock {
+    /// The address of the first instruction
+    start: u16,
+
+    /// The instructions of the block
+    instructions: Vec<Instruction>,
+
+    /// The addresses control may continue at, which
+    /// may lie outside the program
+    successors: Vec<u16>,
+
+    /// The subroutine called by the last instruction
+    call: Option<u16>
+}
+
+//implementation
+impl BasicBlock {
+    /// Constructs a new `BasicBlock` instance
+    ///
+    /// # Arguments
+    ///
+    /// * `new_start` - The address of the first instruction
+    /// * `new_instructions` - The instructions of the block
+    /// * `new_successors` - The addresses control continues at
+    /// * `new_call` - The subroutine called by the last instruction
+    ///
+    /// # Returns
+    ///
+    /// A new `BasicBlock` instance with the given properties
+    pub fn new(new_start: u16, new_instructions: Vec<Instruction>,
+               new_successors: Vec<u16>, new_call: Option<u16>)
+        -> BasicBlock {
+        return BasicBlock {
+            start: new_start,
+            instructions: new_instructions,
+            successors: new_successors,
+            call: new_call
+        };
+    }
+
+    /// Gets the address of the first instruction
+    ///
+    /// # Returns
+    ///
+    /// The start address of the block
+    pub fn get_start(&self) -> u16 {
+        return self.start;
+    }
+
+    /// Gets the address after the last instruction
+    ///
+    /// # Returns
+    ///
+    /// The end address of the block
+    pub fn get_end(&self) -> u16 {
+        return self.start.wrapping_add(2 * self.instructions.len() as u16);
+    }
+
+    /// Gets the instructions of the block
+    ///
+    /// # Returns
+    ///
+    /// The instructions in address order
+    pub fn get_instructions(&self) -> &[Instruction] {
+        return &self.instructions;
+    }
+
+    /// Gets the addresses of the instructions of the block
+    ///
+    /// # Returns
+    ///
+    /// Each instruction paired with its address
+    pub fn addressed(&self) -> impl Iterator<Item = (u16, &Instruction)> {
+        let start = self.start;
+        return self.instructions.iter().enumerate().map(move |(k, i)| {
+            (start.wrapping_add(2 * k as u16), i)
+        });
+    }
+
+    /// Gets the last instruction of the block
+    ///
+    /// # Returns
+    ///
+    /// The instruction that ends the block
+    pub fn get_last(&self) -> &Instruction {
+        return &self.instructions[self.instructions.len() - 1];
+    }
+
+    /// Gets the addresses control may continue at after the block.
+    /// A skip lists the next instruction before the one it skips to.
+    ///
+    /// # Returns
+    ///
+    /// The successor addresses, which are empty after
+    /// a return or an indirect jump
+    pub fn get_successors(&self) -> &[u16] {
+        return &self.successors;
+    }
+
+    /// Gets the subroutine called at the end of the block
+    ///
+    /// # Returns
+    ///
+    /// The address called, or `None` if the block ends otherwise
+    pub fn get_call(&self) -> Option<u16> {
+        return self.call;
+    }
+
+    /// Gets whether the block ends by returning from a subroutine
+    ///
+    /// # Returns
+    ///
+    /// Whether the last instruction is `RET`
+    pub fn returns(&self) -> bool {
+        return *self.get_last() == Instruction::RET;
+    }
+
+    /// Gets whether the block ends in a jump whose target
+    /// depends on a register
+    ///
+    /// # Returns
+    ///
+    /// Whether the last instruction is `JPC`
+    pub fn is_indirect(&self) -> bool {
+        return matches!(self.get_last(), Instruction::JPC(_));
+    }
+
+    /// Gets whether the block ends in a jump to itself, the
+    /// usual way to stop a Chip-8 program
+    ///
+    /// # Returns
+    ///
+    /// Whether the block halts
+    pub fn halts(&self) -> bool {
+        let last = self.get_end().wrapping_sub(2);
+        return match self.get_last() {
+            Instruction::JMP(d) => d.get_addr() == last,
+            _ => false
+        };
+    }
+}
+
+//end of file
@@ -0,0 +1,574 @@
+/*
+ * cfg.rs
+ * Defines a control-flow graph over instructions
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+
+//usage statements
+use std::collections::{BTreeMap, BTreeSet};
+use super::{BasicBlock, NaturalLoop};
+use super::super::codegen::{Element, Image, Instruction};
+
+/// A control-flow graph of the instructions of a program, entered
+/// at an origin address. Every instruction belongs to
+/// a block, whether or not it can be reached. Calls end their
+/// blocks and continue at the next instruction, with the called
+/// subroutine analysed as a function of its own.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cfg {
+    /// The address of the first instruction
+    origin: u16,
+
+    /// The blocks, by start address
+    blocks: BTreeMap<u16, BasicBlock>
+}
+
+//implementation
+impl Cfg {
+    /// Constructs a new `Cfg` instance
+    ///
+    /// # Arguments
+    ///
+    /// * `new_origin` - The address of the first instruction
+    /// * `instrs` - The instructions, two bytes apart
+    ///
+    /// # Returns
+    ///
+    /// A new `Cfg` instance splitting the instructions into blocks
+    pub fn new(new_origin: u16, instrs: &[Instruction]) -> Cfg {
+        let code = instrs.iter().enumerate().map(|(k, instr)| {
+            (new_origin.wrapping_add(2 * k as u16), instr.clone())
+        }).collect();
+        return Cfg::build(new_origin, code);
+    }
+
+    /// Constructs a `Cfg` instance from the instructions of an
+    /// image, leaving out its data. Runs of instructions between
+    /// data each start a block, and control that reaches data
+    /// leaves the graph.
+    ///
+    /// # Arguments
+    ///
+    /// * `new_origin` - The address execution starts at
+    /// * `image` - The program image
+    ///
+    /// # Returns
+    ///
+    /// A new `Cfg` instance splitting the instructions into blocks
+    pub fn from_image(new_origin: u16, image: &Image) -> Cfg {
+        let code = image.elements().filter_map(|(addr, elem)| match *elem {
+            Element::Instr(ref instr) => Some((addr, instr.clone())),
+            _ => None
+        }).collect();
+        return Cfg::build(new_origin, code);
+    }
+
+    /// Splits instructions, by address, into blocks
+    fn build(new_origin: u16, code: BTreeMap<u16, Instruction>) -> Cfg {
+        //find the first instruction of each block
+        let mut leaders = BTreeSet::new();
+        if code.contains_key(&new_origin) {
+            leaders.insert(new_origin);
+        }
+        for (addr, instr) in code.iter() {
+            let target = match *instr {
+                Instruction::JMP(ref d) => Some(d.get_addr()),
+                Instruction::CALL(ref d) => Some(d.get_addr()),
+                _ => None
+            };
+            if let Some(t) = target {
+                leaders.insert(t);
+            }
+            if !code.contains_key(&addr.wrapping_sub(2)) {
+                leaders.insert(*addr);
+            }
+            if Cfg::ends_block(instr) {
+                leaders.insert(addr.wrapping_add(2));
+                if let Instruction::SKIP(_) = *instr {
+                    leaders.insert(addr.wrapping_add(4));
+                }
+            }
+        }
+        leaders.retain(|l| code.contains_key(l));
+
+        //and split the instructions at them
+        let mut blocks = BTreeMap::new();
+        for start in leaders.iter().cloned() {
+            let mut instrs = Vec::new();
+            let mut last = start;
+            loop {
+                let instr = &code[&last];
+                instrs.push(instr.clone());
+                let next = last.wrapping_add(2);
+                if Cfg::ends_block(instr) || leaders.contains(&next) ||
+                    !code.contains_key(&next) {
+                    break;
+                }
+                last = next;
+            }
+            let next = last.wrapping_add(2);
+            let (successors, call) = match code[&last] {
+                Instruction::JMP(ref d) => (vec![d.get_addr()], None),
+                Instruction::CALL(ref d) => (vec![next], Some(d.get_addr())),
+                Instruction::SKIP(_) => {
+                    (vec![next, next.wrapping_add(2)], None)
+                },
+                Instruction::RET | Instruction::JPC(_) => (Vec::new(), None),
+                _ => (vec![next], None)
+            };
+            blocks.insert(start, BasicBlock::new(start, instrs, successors,
+                                                 call));
+        }
+        return Cfg { origin: new_origin, blocks };
+    }
+
+    /// Gets whether an instruction ends its block
+    fn ends_block(instr: &Instruction) -> bool {
+        return matches!(*instr, Instruction::JMP(_) | Instruction::CALL(_) |
+                        Instruction::SKIP(_) | Instruction::RET |
+                        Instruction::JPC(_));
+    }
+
+    /// Gets the address of the first instruction
+    ///
+    /// # Returns
+    ///
+    /// The origin of the graph
+    pub fn get_origin(&self) -> u16 {
+        return self.origin;
+    }
+
+    /// Gets the blocks of the graph
+    ///
+    /// # Returns
+    ///
+    /// An iterator over the blocks in address order
+    pub fn blocks(&self) -> impl Iterator<Item = &BasicBlock> {
+        return self.blocks.values();
+    }
+
+    /// Gets the block starting at an address
+    ///
+    /// # Argument
+    ///
+    /// * `start` - The start address of the block
+    ///
+    /// # Returns
+    ///
+    /// The block, or `None` if no block starts there
+    pub fn get_block(&self, start: u16) -> Option<&BasicBlock> {
+        return self.blocks.get(&start);
+    }
+
+    /// Gets the block containing an instruction
+    ///
+    /// # Argument
+    ///
+    /// * `addr` - The address of the instruction
+    ///
+    /// # Returns
+    ///
+    /// The block, or `None` if no instruction is at the address
+    pub fn block_of(&self, addr: u16) -> Option<&BasicBlock> {
+        let (_, block) = self.blocks.range(..=addr).next_back()?;
+        let offset = addr.wrapping_sub(block.get_start());
+        if !offset.is_multiple_of(2) ||
+           offset >= block.get_end().wrapping_sub(block.get_start()) {
+            return None;
+        }
+        return Some(block);
+    }
+
+    /// Gets the successors of a block that are blocks of the graph
+    ///
+    /// # Argument
+    ///
+    /// * `start` - The start address of the block
+    ///
+    /// # Returns
+    ///
+    /// The start addresses of the successor blocks
+    pub fn successors(&self, start: u16) -> Vec<u16> {
+        return match self.blocks.get(&start) {
+            Some(b) => b.get_successors().iter().cloned()
+                        .filter(|s| self.blocks.contains_key(s)).collect(),
+            None => Vec::new()
+        };
+    }
+
+    /// Gets the predecessors of a block
+    ///
+    /// # Argument
+    ///
+    /// * `start` - The start address of the block
+    ///
+    /// # Returns
+    ///
+    /// The start addresses of the blocks that may continue at it
+    pub fn predecessors(&self, start: u16) -> Vec<u16> {
+        return self.blocks.values()
+                   .filter(|b| b.get_successors().contains(&start))
+                   .map(|b| b.get_start()).collect();
+    }
+
+    /// Gets the entry points of the functions in the graph:
+    /// the origin and every subroutine called
+    ///
+    /// # Returns
+    ///
+    /// The start addresses of the function entries
+    pub fn functions(&self) -> Vec<u16> {
+        let mut entries = BTreeSet::new();
+        if self.blocks.contains_key(&self.origin) {
+            entries.insert(self.origin);
+        }
+        for b in self.blocks.values() {
+            if let Some(c) = b.get_call() {
+                if self.blocks.contains_key(&c) {
+                    entries.insert(c);
+                }
+            }
+        }
+        return entries.into_iter().collect();
+    }
+
+    /// Gets whether control may leave the graph after a block,
+    /// through an indirect jump or to an address with no block
+    ///
+    /// # Argument
+    ///
+    /// * `start` - The start address of the block
+    ///
+    /// # Returns
+    ///
+    /// Whether the block may continue outside the graph
+    pub fn leaves(&self, start: u16) -> bool {
+        return match self.blocks.get(&start) {
+            Some(b) => b.is_indirect() || b.get_successors().iter()
+                           .any(|s| !self.blocks.contains_key(s)),
+            None => false
+        };
+    }
+
+    /// Gets the edges control may follow between blocks across
+    /// calls and returns, for analysing the whole program. A call
+    /// continues at its subroutine, and a return continues after
+    /// each call to a subroutine that can reach it. Edges out of
+    /// the graph are left out.
+    ///
+    /// # Returns
+    ///
+    /// The successor blocks of each block
+    pub fn flow_graph(&self) -> BTreeMap<u16, Vec<u16>> {
+        let mut sites: BTreeMap<u16, Vec<u16>> = BTreeMap::new();
+        for b in self.blocks.values() {
+            if let Some(c) = b.get_call() {
+                if self.blocks.contains_key(&c) &&
+                    self.blocks.contains_key(&b.get_end()) {
+                    sites.entry(c).or_default().push(b.get_end());
+                }
+            }
+        }
+        let mut returns: BTreeMap<u16, BTreeSet<u16>> = BTreeMap::new();
+        for (entry, after) in sites.iter() {
+            for r in self.reachable(*entry) {
+                if self.blocks[&r].returns() {
+                    returns.entry(r).or_default().extend(after);
+                }
+            }
+        }
+        let mut graph = BTreeMap::new();
+        for b in self.blocks.values() {
+            let start = b.get_start();
+            let succs = match b.get_call() {
+                Some(c) if self.blocks.contains_key(&c) => vec![c],
+                _ if b.returns() => match returns.get(&start) {
+                    Some(r) => r.iter().cloned().collect(),
+                    None => Vec::new()
+                },
+                _ => self.successors(start)
+            };
+            graph.insert(start, succs);
+        }
+        return graph;
+    }
+
+    /// Gets the blocks the program can reach from the origin
+    /// across calls and returns
+    ///
+    /// # Returns
+    ///
+    /// The start addresses of the reachable blocks
+    pub fn flow_reachable(&self) -> BTreeSet<u16> {
+        let graph = self.flow_graph();
+        let mut seen = BTreeSet::new();
+        let mut work = Vec::new();
+        if self.blocks.contains_key(&self.origin) {
+            work.push(self.origin);
+        }
+        while let Some(b) = work.pop() {
+            if seen.insert(b) {
+                work.extend(graph[&b].iter().cloned());
+            }
+        }
+        return seen;
+    }
+
+    /// Gets the blocks that can be reached from an entry
+    /// without following calls
+    ///
+    /// # Argument
+    ///
+    /// * `entry` - The start address of the entry block
+    ///
+    /// # Returns
+    ///
+    /// The start addresses of the reachable blocks
+    /// in reverse postorder
+    pub fn reachable(&self, entry: u16) -> Vec<u16> {
+        let mut order = Vec::new();
+        if !self.blocks.contains_key(&entry) {
+            return order;
+        }
+        let mut seen = BTreeSet::new();
+        let mut stack = vec![(entry, 0)];
+        seen.insert(entry);
+        while let Some((block, next)) = stack.pop() {
+            let succs = self.successors(block);
+            if next < succs.len() {
+                stack.push((block, next + 1));
+                if seen.insert(succs[next]) {
+                    stack.push((succs[next], 0));
+                }
+            } else {
+                order.push(block);
+            }
+        }
+        order.reverse();
+        return order;
+    }
+
+    /// Gets the dominators of the blocks reachable from an entry.
+    /// A block dominates another when every path from the entry
+    /// to the other passes through it.
+    ///
+    /// # Argument
+    ///
+    /// * `entry` - The start address of the entry block
+    ///
+    /// # Returns
+    ///
+    /// The dominators of each reachable block, including itself
+    pub fn dominators(&self, entry: u16)
+        -> BTreeMap<u16, BTreeSet<u16>> {
+        let order = self.reachable(entry);
+        let all: BTreeSet<u16> = order.iter().cloned().collect();
+        let mut doms = BTreeMap::new();
+        for b in order.iter() {
+            doms.insert(*b, all.clone());
+        }
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for b in order.iter() {
+                let mut set: Option<BTreeSet<u16>> = None;
+                if *b != entry {
+                    for p in self.predecessors(*b) {
+                        if let Some(d) = doms.get(&p) {
+                            set = Some(match set {
+                                Some(s) => s.intersection(d).cloned()
+                                            .collect(),
+                                None => d.clone()
+                            });
+                        }
+                    }
+                }
+                let mut set = set.unwrap_or_default();
+                set.insert(*b);
+                if doms[b] != set {
+                    doms.insert(*b, set);
+                    changed = true;
+                }
+            }
+        }
+        return doms;
+    }
+
+    /// Gets the natural loops reachable from an entry, merging
+    /// loops that share a header. Jumps of a block to itself
+    /// halt the program and do not form loops.
+    ///
+    /// # Argument
+    ///
+    /// * `entry` - The start address of the entry block
+    ///
+    /// # Returns
+    ///
+    /// The loops, with inner loops before the loops containing them
+    pub fn loops(&self, entry: u16) -> Vec<NaturalLoop> {
+        let doms = self.dominators(entry);
+        let mut latches: BTreeMap<u16, Vec<u16>> = BTreeMap::new();
+        for (b, d) in doms.iter() {
+            if self.blocks[b].halts() {
+                continue;
+            }
+            for s in self.successors(*b) {
+                if d.contains(&s) {
+                    latches.entry(s).or_default().push(*b);
+                }
+            }
+        }
+
+        //collect each loop's blocks by walking back from its latches
+        let mut loops = Vec::new();
+        for (header, ls) in latches {
+            let mut body = BTreeSet::new();
+            body.insert(header);
+            let mut work = ls.clone();
+            while let Some(b) = work.pop() {
+                if body.insert(b) {
+                    work.extend(self.predecessors(b).into_iter()
+                                    .filter(|p| doms.contains_key(p)));
+                }
+            }
+            loops.push(NaturalLoop::new(header, body, ls));
+        }
+        loops.sort_by_key(|l| l.get_blocks().len());
+        return loops;
+    }
+}
+
+//unit tests
+#[cfg(test)]
+mod tests {
+    //import the Cfg struct
+    use super::*;
+    use super::super::super::data::*;
+    use super::super::super::vm::Quirks;
+    use super::super::{ConstantPropagation, Estimator, Liveness,
+                       ReachingDefinitions};
+    use proptest::prelude::*;
+
+    //creates a counting loop with a subroutine call
+    fn program() -> Vec<Instruction> {
+        return vec![
+            Instruction::MOV(MovData::with_constant(Register::V0, 0)),
+            Instruction::CALL(CallData::new(0x20C)),
+            Instruction::ADD(AddData::with_constant(Register::V0, 1)),
+            Instruction::SKIP(SkipData::with_constant(
+                    Register::V0, 10, SkipType::Equals)),
+            Instruction::JMP(JmpData::new(0x202)),
+            Instruction::JMP(JmpData::new(0x20A)),
+            Instruction::CLS,
+            Instruction::RET
+        ];
+    }
+
+    //this test checks splitting instructions into blocks
+    #[test]
+    fn test_blocks() {
+        let cfg = Cfg::new(0x200, &program());
+        let starts: Vec<u16> = cfg.blocks().map(|b| b.get_start())
+                                  .collect();
+        assert_eq!(starts, vec![0x200, 0x202, 0x204, 0x208, 0x20A, 0x20C]);
+        let call = cfg.get_block(0x202).unwrap();
+        assert_eq!(call.get_successors(), &[0x204]);
+        assert_eq!(call.get_call(), Some(0x20C));
+        assert_eq!(cfg.get_block(0x204).unwrap().get_successors(),
+                   &[0x208, 0x20A]);
+        assert!(cfg.get_block(0x20A).unwrap().halts());
+        assert!(cfg.get_block(0x20C).unwrap().returns());
+        assert_eq!(cfg.block_of(0x206).unwrap().get_start(), 0x204);
+        assert!(cfg.block_of(0x207).is_none());
+        assert!(cfg.block_of(0x210).is_none());
+        assert_eq!(cfg.functions(), vec![0x200, 0x20C]);
+        assert_eq!(cfg.predecessors(0x202), vec![0x200, 0x208]);
+    }
+
+    //this test checks graphs of images with data between code
+    #[test]
+    fn test_from_image() {
+        let mut img = Image::new();
+        img.place(0x200, Element::Instr(Instruction::CLS));
+        img.place(0x202, Element::Byte(0xFF));
+        img.place(0x203, Element::Instr(Instruction::RET));
+        img.place(0x205, Element::Instr(Instruction::CLS));
+        let cfg = Cfg::from_image(0x200, &img);
+        let starts: Vec<u16> = cfg.blocks().map(|b| b.get_start())
+                                  .collect();
+        assert_eq!(starts, vec![0x200, 0x203, 0x205]);
+        assert!(cfg.leaves(0x200));
+        assert_eq!(cfg.flow_reachable().len(), 1);
+    }
+
+    //this test checks dominators and loops
+    #[test]
+    fn test_loops() {
+        let cfg = Cfg::new(0x200, &program());
+        assert_eq!(cfg.reachable(0x200),
+                   vec![0x200, 0x202, 0x204, 0x20A, 0x208]);
+        let doms = cfg.dominators(0x200);
+        assert!(doms[&0x208].contains(&0x202));
+        assert!(!doms[&0x204].contains(&0x208));
+        let loops = cfg.loops(0x200);
+        assert_eq!(loops.len(), 1);
+        assert_eq!(loops[0].get_header(), 0x202);
+        assert_eq!(loops[0].get_latches(), &[0x208]);
+        assert!(loops[0].contains(0x204));
+        assert!(!loops[0].contains(0x20A));
+    }
+
+    //this test checks the edges across calls and returns
+    #[test]
+    fn test_flow_graph() {
+        let cfg = Cfg::new(0x200, &program());
+        let graph = cfg.flow_graph();
+        assert_eq!(graph[&0x202], vec![0x20C]);
+        assert_eq!(graph[&0x20C], vec![0x204]);
+        assert_eq!(graph[&0x204], vec![0x208, 0x20A]);
+        assert_eq!(cfg.flow_reachable().len(), 6);
+        assert!(!cfg.leaves(0x20C));
+        let cfg = Cfg::new(0x200, &[Instruction::CLS]);
+        assert!(cfg.leaves(0x200));
+    }
+
+    proptest! {
+        //this test checks that every analysis accepts
+        //arbitrary programs
+        #[test]
+        fn test_arbitrary_programs(instrs in prop::collection::vec(
+                any::<Instruction>(), 0..64)) {
+            let cfg = Cfg::new(0x200, &instrs);
+            let count: usize = cfg.blocks()
+                                  .map(|b| b.get_instructions().len())
+                                  .sum();
+            prop_assert_eq!(count, instrs.len());
+            for f in cfg.functions() {
+                let _loops = cfg.loops(f);
+            }
+            let _cycles = Estimator::new(cfg.clone()).estimate();
+            let _live = Liveness::new(&cfg, Quirks::vip());
+            let _defs = ReachingDefinitions::new(&cfg, Quirks::vip());
+            let _values = ConstantPropagation::new(&cfg, Quirks::schip());
+        }
+    }
+}
+
+//end of file
@@ -0,0 +1,321 @@
+/*
+ * constant_propagation.rs
+ * Defines a constant propagation analysis
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+
+//usage statements
+use std::collections::BTreeMap;
+use super::Cfg;
+use super::super::codegen::{CodeGen, Instruction, REGISTER_COUNT};
+use super::super::data::Register;
+use super::super::vm::{Quirks, SMALL_FONT_ADDR};
+
+/// The value of each register, `V0` to `VF` and then `I`,
+/// where it is the same on every path, or `None` otherwise
+type Values = [Option<u16>; REGISTER_COUNT as usize];
+
+/// The ID of the index register
+const I: usize = 0x10;
+
+/// Finds the registers that hold the same value on every path to
+/// each instruction of a program, following calls and returns
+/// from the origin. Registers start unknown, since interpreters
+/// differ in how they initialise them. The values let the target
+/// of a `JPC` be resolved and some skips be decided statically,
+/// and paths past skips that are decided are not followed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConstantPropagation {
+    /// The interpreter behaviours assumed
+    quirks: Quirks,
+
+    /// Each reachable instruction with the values before it
+    before: BTreeMap<u16, (Instruction, Values)>
+}
+
+//implementation
+impl ConstantPropagation {
+    /// Constructs a new `ConstantPropagation` instance
+    ///
+    /// # Arguments
+    ///
+    /// * `cfg` - The graph of the program
+    /// * `quirks` - The interpreter behaviours to assume
+    ///
+    /// # Returns
+    ///
+    /// A new `ConstantPropagation` instance analysing the program
+    pub fn new(cfg: &Cfg, quirks: Quirks) -> ConstantPropagation {
+        let graph = cfg.flow_graph();
+
+        //solve forwards from unknown values at the origin
+        let mut ins: BTreeMap<u16, Values> = BTreeMap::new();
+        let mut work = Vec::new();
+        if cfg.get_block(cfg.get_origin()).is_some() {
+            ins.insert(cfg.get_origin(), [None; REGISTER_COUNT as usize]);
+            work.push(cfg.get_origin());
+        }
+        while let Some(start) = work.pop() {
+            let block = cfg.get_block(start).unwrap();
+            let mut values = ins[&start];
+            let mut skip = None;
+            for instr in block.get_instructions() {
+                skip = ConstantPropagation::decide(instr, &values);
+                ConstantPropagation::transfer(instr, quirks, &mut values);
+            }
+            let next = block.get_end();
+            for s in graph[&start].iter() {
+                if skip == Some(*s == next) {
+                    continue;
+                }
+                let joined = match ins.get(s) {
+                    Some(old) => {
+                        let mut j = *old;
+                        for (v, n) in j.iter_mut().zip(values.iter()) {
+                            if *v != *n {
+                                *v = None;
+                            }
+                        }
+                        j
+                    },
+                    None => values
+                };
+                if ins.get(s) != Some(&joined) {
+                    ins.insert(*s, joined);
+                    work.push(*s);
+                }
+            }
+        }
+
+        //then record the values before each instruction
+        let mut before = BTreeMap::new();
+        for (start, values) in ins {
+            let mut values = values;
+            for (addr, instr) in cfg.get_block(start).unwrap().addressed() {
+                before.insert(addr, (instr.clone(), values));
+                ConstantPropagation::transfer(instr, quirks, &mut values);
+            }
+        }
+        return ConstantPropagation { quirks, before };
+    }
+
+    /// Decides whether a skip is taken given the values before it
+    fn decide(instr: &Instruction, values: &Values) -> Option<bool> {
+        let opcode = match *instr {
+            Instruction::SKIP(ref d) => d.gen_opcode(),
+            _ => return None
+        };
+        let vx = values[((opcode >> 8) & 0xF) as usize]?;
+        let rhs = match opcode >> 12 {
+            0x3 | 0x4 => opcode & 0xFF,
+            0x5 | 0x9 => values[((opcode >> 4) & 0xF) as usize]?,
+            _ => return None
+        };
+        return Some(match opcode >> 12 {
+            0x3 | 0x5 => vx == rhs,
+            _ => vx != rhs
+        });
+    }
+
+    /// Applies an instruction to the register values
+    fn transfer(instr: &Instruction, quirks: Quirks, v: &mut Values) {
+        let opcode = instr.gen_opcode();
+        let x = ((opcode >> 8) & 0xF) as usize;
+        let y = ((opcode >> 4) & 0xF) as usize;
+        let nn = opcode & 0xFF;
+        let (vx, vy) = (v[x], v[y]);
+        let both = |f: fn(u16, u16) -> u16| match (vx, vy) {
+            (Some(a), Some(b)) => Some(f(a, b)),
+            _ => None
+        };
+        match *instr {
+            Instruction::MOV(_) => match opcode >> 12 {
+                0xA => v[I] = Some(opcode & 0xFFF),
+                0x6 => v[x] = Some(nn),
+                _ => v[x] = vy
+            },
+            Instruction::ADD(_) => match opcode >> 12 {
+                0x7 => v[x] = vx.map(|a| (a + nn) & 0xFF),
+                0xF => v[I] = match (v[I], vx) {
+                    (Some(i), Some(a)) => Some(i.wrapping_add(a)),
+                    _ => None
+                },
+                _ => {
+                    v[x] = both(|a, b| (a + b) & 0xFF);
+                    v[0xF] = both(|a, b| (a + b > 0xFF) as u16);
+                }
+            },
+            Instruction::OR(_) | Instruction::AND(_) |
+                Instruction::XOR(_) => {
+                v[x] = match *instr {
+                    Instruction::OR(_) => both(|a, b| a | b),
+                    Instruction::AND(_) => both(|a, b| a & b),
+                    _ => both(|a, b| a ^ b)
+                };
+                if quirks.vf_reset {
+                    v[0xF] = Some(0);
+                }
+            },
+            Instruction::SUB(_) => {
+                v[x] = both(|a, b| a.wrapping_sub(b) & 0xFF);
+                v[0xF] = both(|a, b| (a >= b) as u16);
+            },
+            Instruction::SUBN(_) => {
+                v[x] = both(|a, b| b.wrapping_sub(a) & 0xFF);
+                v[0xF] = both(|a, b| (b >= a) as u16);
+            },
+            Instruction::SHR(_) | Instruction::SHL(_) => {
+                let src = if quirks.shift_vy { vy } else { vx };
+                if let Instruction::SHR(_) = *instr {
+                    v[x] = src.map(|s| s >> 1);
+                    v[0xF] = src.map(|s| s & 1);
+                } else {
+                    v[x] = src.map(|s| (s << 1) & 0xFF);
+                    v[0xF] = src.map(|s| s >> 7);
+                }
+            },
+            Instruction::RAND(_) | Instruction::GDL(_) |
+                Instruction::KEY(_) => v[x] = None,
+            Instruction::DRAW(_) => v[0xF] = None,
+            Instruction::SCH(_) => {
+                v[I] = vx.map(|a| SMALL_FONT_ADDR + (a & 0xF) * 5);
+            },
+            Instruction::RDP(_) | Instruction::RLD(_) => {
+                if let Instruction::RLD(_) = *instr {
+                    for r in v.iter_mut().take(x + 1) {
+                        *r = None;
+                    }
+                }
+                if quirks.load_store_inc_i {
+                    v[I] = v[I].map(|i| i.wrapping_add(x as u16 + 1));
+                }
+            },
+            _ => {}
+        };
+    }
+
+    /// Gets the value a register holds before an instruction
+    ///
+    /// # Arguments
+    ///
+    /// * `addr` - The address of the instruction
+    /// * `reg` - The register
+    ///
+    /// # Returns
+    ///
+    /// The value, or `None` if it differs between paths, is not
+    /// known, or the program cannot reach the instruction
+    pub fn get_value(&self, addr: u16, reg: &Register) -> Option<u16> {
+        let (_, values) = self.before.get(&addr)?;
+        return values[reg.to_id() as usize];
+    }
+
+    /// Decides whether a skip is taken
+    ///
+    /// # Argument
+    ///
+    /// * `addr` - The address of the skip
+    ///
+    /// # Returns
+    ///
+    /// `Some(true)` if the skip is always taken, `Some(false)` if
+    /// it never is, or `None` if it depends on unknown values, on
+    /// the keypad, or the address holds no reachable skip
+    pub fn get_skip(&self, addr: u16) -> Option<bool> {
+        let (instr, values) = self.before.get(&addr)?;
+        return ConstantPropagation::decide(instr, values);
+    }
+
+    /// Resolves the target of a `JPC`
+    ///
+    /// # Argument
+    ///
+    /// * `addr` - The address of the jump
+    ///
+    /// # Returns
+    ///
+    /// The address jumped to, or `None` if the offset register is
+    /// not known or the address holds no reachable `JPC`
+    pub fn get_jump_target(&self, addr: u16) -> Option<u16> {
+        let (instr, values) = self.before.get(&addr)?;
+        let base = match *instr {
+            Instruction::JPC(ref d) => d.gen_opcode() & 0xFFF,
+            _ => return None
+        };
+        let reg = if self.quirks.jump_vx { (base >> 8) as usize } else {
+            0
+        };
+        return Some(base + values[reg]?);
+    }
+}
+
+//unit tests
+#[cfg(test)]
+mod tests {
+    //import the ConstantPropagation struct
+    use super::*;
+    use super::super::super::data::*;
+
+    //this test checks values, skips and jump targets
+    #[test]
+    fn test_constants() {
+        let cfg = Cfg::new(0x200, &[
+            Instruction::MOV(MovData::with_constant(Register::V0, 2)),
+            Instruction::ADD(AddData::with_constant(Register::V0, 2)),
+            Instruction::SKIP(SkipData::with_constant(
+                    Register::V0, 4, SkipType::Equals)),
+            Instruction::RAND(RandData::new(Register::V1, 0xFF)),
+            Instruction::SKIP(SkipData::with_register(
+                    Register::V0, Register::V1, SkipType::NotEquals)),
+            Instruction::JPC(JpcData::new(0x300))
+        ]);
+        let vip = ConstantPropagation::new(&cfg, Quirks::vip());
+        assert_eq!(vip.get_value(0x204, &Register::V0), Some(4));
+        assert_eq!(vip.get_skip(0x204), Some(true));
+        assert_eq!(vip.get_skip(0x208), None);
+        assert_eq!(vip.get_jump_target(0x20A), Some(0x304));
+        let schip = ConstantPropagation::new(&cfg, Quirks::schip());
+        assert_eq!(schip.get_jump_target(0x20A), None);
+    }
+
+    //this test checks values joined across paths and calls
+    #[test]
+    fn test_joins() {
+        let cfg = Cfg::new(0x200, &[
+            Instruction::CALL(CallData::new(0x20A)),
+            Instruction::SKIP(SkipData::with_constant(
+                    Register::V3, 7, SkipType::NotEquals)),
+            Instruction::MOV(MovData::with_constant(Register::V3, 8)),
+            Instruction::SUB(SubData::new(Register::V3, Register::V4)),
+            Instruction::JMP(JmpData::new(0x208)),
+            Instruction::MOV(MovData::with_constant(Register::V3, 7)),
+            Instruction::MOV(MovData::with_constant(Register::V4, 9)),
+            Instruction::RET
+        ]);
+        let cp = ConstantPropagation::new(&cfg, Quirks::vip());
+        assert_eq!(cp.get_skip(0x202), Some(false));
+        assert_eq!(cp.get_value(0x206, &Register::V3), Some(8));
+        assert_eq!(cp.get_value(0x206, &Register::V4), Some(9));
+        assert_eq!(cp.get_value(0x208, &Register::VF), Some(0));
+        assert_eq!(cp.get_value(0x20A, &Register::V3), None);
+    }
+}
+
+//end of file
@@ -0,0 +1,194 @@
+/*
+ * cycle_range.rs
+ * Defines a range of estimated machine cycles
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+
+//usage statements
+use std::fmt;
+
+/// The fewest and most machine cycles a piece of code may take.
+/// The maximum is `None` when it has no bound, such as for code
+/// that waits for a key or loops without an iteration bound.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CycleRange {
+    /// The fewest cycles
+    min: u64,
+
+    /// The most cycles, if bounded
+    max: Option<u64>
+}
+
+//implementation
+impl CycleRange {
+    /// Constructs a new `CycleRange` instance
+    ///
+    /// # Arguments
+    ///
+    /// * `new_min` - The fewest cycles
+    /// * `new_max` - The most cycles, or `None` for no bound
+    ///
+    /// # Returns
+    ///
+    /// A new `CycleRange` instance with the given bounds
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `new_max` is less than `new_min`
+    pub fn new(new_min: u64, new_max: Option<u64>) -> CycleRange {
+        if let Some(m) = new_max {
+            if m < new_min {
+                panic!("Cycle range maximum is below its minimum");
+            }
+        }
+        return CycleRange { min: new_min, max: new_max };
+    }
+
+    /// Constructs a range of exactly one cycle count
+    ///
+    /// # Argument
+    ///
+    /// * `cycles` - The cycle count
+    ///
+    /// # Returns
+    ///
+    /// A range with equal minimum and maximum
+    pub fn exact(cycles: u64) -> CycleRange {
+        return CycleRange::new(cycles, Some(cycles));
+    }
+
+    /// Gets the fewest cycles
+    ///
+    /// # Returns
+    ///
+    /// The minimum of the range
+    pub fn get_min(&self) -> u64 {
+        return self.min;
+    }
+
+    /// Gets the most cycles
+    ///
+    /// # Returns
+    ///
+    /// The maximum of the range, or `None` if it has no bound
+    pub fn get_max(&self) -> Option<u64> {
+        return self.max;
+    }
+
+    /// Gets whether the range has a maximum
+    ///
+    /// # Returns
+    ///
+    /// Whether the maximum is bounded
+    pub fn is_bounded(&self) -> bool {
+        return self.max.is_some();
+    }
+
+    /// Gets the range of running this code and then other code
+    ///
+    /// # Argument
+    ///
+    /// * `other` - The range of the code run afterwards
+    ///
+    /// # Returns
+    ///
+    /// The sum of the two ranges
+    pub fn then(&self, other: CycleRange) -> CycleRange {
+        let max = match (self.max, other.max) {
+            (Some(a), Some(b)) => Some(a.saturating_add(b)),
+            _ => None
+        };
+        return CycleRange::new(self.min.saturating_add(other.min), max);
+    }
+
+    /// Gets the range of running either this code or other code
+    ///
+    /// # Argument
+    ///
+    /// * `other` - The range of the alternative code
+    ///
+    /// # Returns
+    ///
+    /// The smaller minimum and the larger maximum of the ranges
+    pub fn join(&self, other: CycleRange) -> CycleRange {
+        let max = match (self.max, other.max) {
+            (Some(a), Some(b)) => Some(a.max(b)),
+            _ => None
+        };
+        return CycleRange::new(self.min.min(other.min), max);
+    }
+
+    /// Gets this range with its maximum removed
+    ///
+    /// # Returns
+    ///
+    /// A range with the same minimum and no maximum
+    pub fn unbounded(&self) -> CycleRange {
+        return CycleRange::new(self.min, None);
+    }
+}
+
+//Display implementation
+impl fmt::Display for CycleRange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return match self.max {
+            Some(m) if m == self.min => write!(f, "{} cycles", m),
+            Some(m) => write!(f, "{} to {} cycles", self.min, m),
+            None => write!(f, "at least {} cycles", self.min)
+        };
+    }
+}
+
+//unit tests
+#[cfg(test)]
+mod tests {
+    //import the CycleRange struct
+    use super::*;
+
+    //this test checks combining ranges in sequence and as choices
+    #[test]
+    fn test_combine() {
+        let a = CycleRange::new(10, Some(20));
+        let b = CycleRange::exact(5);
+        assert_eq!(a.then(b), CycleRange::new(15, Some(25)));
+        assert_eq!(a.join(b), CycleRange::new(5, Some(20)));
+        assert_eq!(a.then(b.unbounded()), CycleRange::new(15, None));
+        assert_eq!(a.join(b.unbounded()), CycleRange::new(5, None));
+    }
+
+    //this test checks the display format
+    #[test]
+    fn test_display() {
+        assert_eq!(CycleRange::exact(24).to_string(), "24 cycles");
+        assert_eq!(CycleRange::new(24, Some(30)).to_string(),
+                   "24 to 30 cycles");
+        assert_eq!(CycleRange::new(24, None).to_string(),
+                   "at least 24 cycles");
+    }
+
+    //this test checks that an inverted range panics
+    #[test]
+    #[should_panic]
+    fn test_inverted() {
+        let _r = CycleRange::new(10, Some(5));
+    }
+}
+
+//end of file
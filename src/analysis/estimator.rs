@@ -0,0 +1,430 @@
+/*
+ * estimator.rs
+ * Defines a static estimator of COSMAC VIP running time
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+
+//usage statements
+use std::collections::{BTreeMap, BTreeSet};
+use super::{vip_cost, Cfg, CycleRange, NaturalLoop};
+
+/// The cost of leaving a region of a function, by the block
+/// control continues at, or `None` where the function returns,
+/// halts or jumps out of the graph
+type Exits = BTreeMap<Option<u16>, CycleRange>;
+
+/// The costs of the paths through a region from its start block
+struct Paths {
+    /// The cost of the paths back to the start block
+    iteration: Option<CycleRange>,
+
+    /// The cost of the paths out of the region
+    exits: Exits,
+
+    /// Whether the region has cycles that are not natural loops
+    irreducible: bool
+}
+
+/// Statically estimates how many COSMAC VIP machine cycles code
+/// takes. Blocks cost the sum of their instructions' `vip_cost`
+/// plus the subroutines they call. Each natural loop runs at most
+/// the number of times given by its bound and at least once, and
+/// a loop without a bound has no maximum. Recursion, indirect
+/// jumps and cycles that are not natural loops have no maximum
+/// either.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Estimator {
+    /// The graph being estimated
+    cfg: Cfg,
+
+    /// The most times each loop header runs, by start address
+    bounds: BTreeMap<u16, u64>
+}
+
+//implementation
+impl Estimator {
+    /// Constructs a new `Estimator` instance
+    ///
+    /// # Argument
+    ///
+    /// * `new_cfg` - The graph to estimate
+    ///
+    /// # Returns
+    ///
+    /// A new `Estimator` instance with no loop bounds
+    pub fn new(new_cfg: Cfg) -> Estimator {
+        return Estimator {
+            cfg: new_cfg,
+            bounds: BTreeMap::new()
+        };
+    }
+
+    /// Gets the graph being estimated
+    ///
+    /// # Returns
+    ///
+    /// A reference to the graph
+    pub fn get_cfg(&self) -> &Cfg {
+        return &self.cfg;
+    }
+
+    /// Bounds the iterations of a loop
+    ///
+    /// # Arguments
+    ///
+    /// * `header` - The start address of the loop's header block
+    /// * `iterations` - The most times the header runs each time
+    ///   the loop is entered
+    pub fn set_bound(&mut self, header: u16, iterations: u64) {
+        self.bounds.insert(header, iterations);
+    }
+
+    /// Gets the bound of a loop
+    ///
+    /// # Argument
+    ///
+    /// * `header` - The start address of the loop's header block
+    ///
+    /// # Returns
+    ///
+    /// The most iterations of the loop, or `None` if unbounded
+    pub fn get_bound(&self, header: u16) -> Option<u64> {
+        return self.bounds.get(&header).cloned();
+    }
+
+    /// Estimates the instructions of a block, not counting
+    /// the subroutine it calls
+    ///
+    /// # Argument
+    ///
+    /// * `start` - The start address of the block
+    ///
+    /// # Returns
+    ///
+    /// The cycle range of the block, or `None` if no block
+    /// starts at the address
+    pub fn block_cost(&self, start: u16) -> Option<CycleRange> {
+        let block = self.cfg.get_block(start)?;
+        let mut cost = CycleRange::exact(0);
+        for instr in block.get_instructions() {
+            cost = cost.then(vip_cost(instr));
+        }
+        return Some(cost);
+    }
+
+    /// Estimates the program from the origin of the graph
+    /// until it returns or halts
+    ///
+    /// # Returns
+    ///
+    /// The cycle range of the program
+    pub fn estimate(&self) -> CycleRange {
+        return self.estimate_from(self.cfg.get_origin());
+    }
+
+    /// Estimates a function from its entry until it returns or halts
+    ///
+    /// # Argument
+    ///
+    /// * `entry` - The start address of the entry block
+    ///
+    /// # Returns
+    ///
+    /// The cycle range of the function, which has no maximum
+    /// if no block starts at the entry
+    pub fn estimate_from(&self, entry: u16) -> CycleRange {
+        return self.function(entry, &mut Vec::new(), &mut BTreeMap::new());
+    }
+
+    /// Estimates a function, tracking the functions being
+    /// estimated to detect recursion
+    fn function(&self, entry: u16, stack: &mut Vec<u16>,
+                memo: &mut BTreeMap<u16, CycleRange>) -> CycleRange {
+        if let Some(cost) = memo.get(&entry) {
+            return *cost;
+        }
+        if self.cfg.get_block(entry).is_none() || stack.contains(&entry) {
+            return CycleRange::new(0, None);
+        }
+        stack.push(entry);
+
+        //cost each block with the subroutine it calls
+        let order = self.cfg.reachable(entry);
+        let mut costs = BTreeMap::new();
+        for b in order.iter() {
+            let mut cost = self.block_cost(*b).unwrap();
+            if let Some(callee) = self.cfg.get_block(*b).unwrap().get_call() {
+                cost = cost.then(self.function(callee, stack, memo));
+            }
+            costs.insert(*b, cost);
+        }
+
+        //summarise the loops from the innermost out
+        let mut summaries = BTreeMap::new();
+        for l in self.cfg.loops(entry) {
+            let paths = self.paths(l.get_header(), l.get_blocks(), &costs,
+                                   &summaries, false);
+            let exits = self.summarise(&l, paths);
+            summaries.insert(l.get_header(), exits);
+        }
+
+        //and take the costliest and cheapest ways out
+        let region: BTreeSet<u16> = order.into_iter().collect();
+        let paths = self.paths(entry, &region, &costs, &summaries, true);
+        let mut total = match paths.exits.values().next() {
+            Some(first) => paths.exits.values()
+                                .fold(*first, |t, c| t.join(*c)),
+            None => CycleRange::new(0, None)
+        };
+        if paths.irreducible {
+            total = total.unbounded();
+        }
+        stack.pop();
+        memo.insert(entry, total);
+        return total;
+    }
+
+    /// Finds the costs of the paths through a region from its
+    /// start block, treating summarised loops as single blocks
+    fn paths(&self, start: u16, region: &BTreeSet<u16>,
+             costs: &BTreeMap<u16, CycleRange>,
+             summaries: &BTreeMap<u16, Exits>, collapse_start: bool)
+        -> Paths {
+        let edges = |b: u16| -> Vec<(Option<u16>, CycleRange)> {
+            if b != start || collapse_start {
+                if let Some(exits) = summaries.get(&b) {
+                    return exits.iter().map(|(t, c)| (*t, *c)).collect();
+                }
+            }
+            let block = self.cfg.get_block(b).unwrap();
+            let cost = costs[&b];
+            if block.halts() || block.returns() {
+                return vec![(None, cost)];
+            }
+            if block.is_indirect() {
+                return vec![(None, cost.unbounded())];
+            }
+            return block.get_successors().iter().map(|s| {
+                match self.cfg.get_block(*s) {
+                    Some(_) => (Some(*s), cost),
+                    None => (None, cost)
+                }
+            }).collect();
+        };
+        let inside = |t: &Option<u16>| match *t {
+            Some(t) => t != start && region.contains(&t),
+            None => false
+        };
+
+        //order the region so that each block follows
+        //the blocks that lead to it
+        let mut order = Vec::new();
+        let mut seen = BTreeSet::new();
+        let mut stack = vec![(start, edges(start), 0)];
+        seen.insert(start);
+        while let Some((b, out, next)) = stack.pop() {
+            if next < out.len() {
+                let target = out[next].0;
+                stack.push((b, out, next + 1));
+                if inside(&target) && seen.insert(target.unwrap()) {
+                    let t = target.unwrap();
+                    stack.push((t, edges(t), 0));
+                }
+            } else {
+                order.push(b);
+            }
+        }
+        order.reverse();
+        let position: BTreeMap<u16, usize> = order.iter().enumerate()
+                                                  .map(|(k, b)| (*b, k))
+                                                  .collect();
+
+        //then accumulate the costs of reaching each block
+        let mut paths = Paths {
+            iteration: None,
+            exits: BTreeMap::new(),
+            irreducible: false
+        };
+        let mut reach = BTreeMap::new();
+        reach.insert(start, CycleRange::exact(0));
+        for b in order.iter() {
+            let so_far = match reach.get(b) {
+                Some(r) => *r,
+                None => continue
+            };
+            for (target, cost) in edges(*b) {
+                let total = so_far.then(cost);
+                if target == Some(start) {
+                    paths.iteration = Some(match paths.iteration {
+                        Some(i) => i.join(total),
+                        None => total
+                    });
+                } else if inside(&target) {
+                    let t = target.unwrap();
+                    match position.get(&t) {
+                        Some(p) if *p > position[b] => {
+                            let r = match reach.get(&t) {
+                                Some(r) => total.join(*r),
+                                None => total
+                            };
+                            reach.insert(t, r);
+                        },
+                        _ => paths.irreducible = true
+                    };
+                } else {
+                    let e = match paths.exits.get(&target) {
+                        Some(e) => total.join(*e),
+                        None => total
+                    };
+                    paths.exits.insert(target, e);
+                }
+            }
+        }
+        return paths;
+    }
+
+    /// Summarises a loop as the cost of leaving it by each exit
+    fn summarise(&self, l: &NaturalLoop, paths: Paths) -> Exits {
+        let bound = self.get_bound(l.get_header());
+        let iteration = paths.iteration.unwrap_or(CycleRange::exact(0));
+
+        //every iteration but the last goes round the loop
+        let mut exits = Exits::new();
+        for (target, cost) in paths.exits {
+            let max = match (bound, iteration.get_max(), cost.get_max()) {
+                (Some(n), Some(i), Some(m)) => {
+                    Some(i.saturating_mul(n.saturating_sub(1))
+                          .saturating_add(m))
+                },
+                _ => None
+            };
+            exits.insert(target, CycleRange::new(cost.get_min(), max));
+        }
+
+        //a loop that cannot be left ends the program
+        //after its last iteration
+        if exits.is_empty() {
+            let max = match (bound, iteration.get_max()) {
+                (Some(n), Some(i)) => Some(i.saturating_mul(n.max(1))),
+                _ => None
+            };
+            exits.insert(None, CycleRange::new(iteration.get_min(), max));
+        }
+        if paths.irreducible {
+            for cost in exits.values_mut() {
+                *cost = cost.unbounded();
+            }
+        }
+        return exits;
+    }
+}
+
+//unit tests
+#[cfg(test)]
+mod tests {
+    //import the Estimator struct
+    use super::*;
+    use super::super::super::codegen::Instruction;
+    use super::super::super::data::*;
+
+    //creates a loop that counts V0 up to ten, then halts
+    fn counter() -> Vec<Instruction> {
+        return vec![
+            Instruction::MOV(MovData::with_constant(Register::V0, 0)),
+            Instruction::ADD(AddData::with_constant(Register::V0, 1)),
+            Instruction::SKIP(SkipData::with_constant(
+                    Register::V0, 10, SkipType::Equals)),
+            Instruction::JMP(JmpData::new(0x202)),
+            Instruction::JMP(JmpData::new(0x208))
+        ];
+    }
+
+    //this test checks straight-line code ending in a halt
+    #[test]
+    fn test_straight_line() {
+        let est = Estimator::new(Cfg::new(0x200, &[
+            Instruction::CLS,
+            Instruction::MOV(MovData::with_constant(Register::V0, 1)),
+            Instruction::JMP(JmpData::new(0x204))
+        ]));
+        assert_eq!(est.estimate(), CycleRange::exact(24 + 6 + 23));
+    }
+
+    //this test checks loops with and without a bound
+    #[test]
+    fn test_loop() {
+        let mut est = Estimator::new(Cfg::new(0x200, &counter()));
+        assert_eq!(est.block_cost(0x202), Some(CycleRange::exact(22)));
+        assert_eq!(est.estimate(), CycleRange::new(6 + 22 + 23, None));
+        est.set_bound(0x202, 10);
+        let max = 6 + 9 * (22 + 23) + 22 + 23;
+        assert_eq!(est.estimate(), CycleRange::new(6 + 22 + 23, Some(max)));
+    }
+
+    //this test checks nested loops and endless loops
+    #[test]
+    fn test_nested_loops() {
+        let mut est = Estimator::new(Cfg::new(0x200, &[
+            Instruction::MOV(MovData::with_constant(Register::V1, 0)),
+            Instruction::ADD(AddData::with_constant(Register::V1, 1)),
+            Instruction::SKIP(SkipData::with_constant(
+                    Register::V1, 4, SkipType::Equals)),
+            Instruction::JMP(JmpData::new(0x202)),
+            Instruction::JMP(JmpData::new(0x200))
+        ]));
+        est.set_bound(0x202, 4);
+        assert_eq!(est.estimate().get_max(), None);
+        est.set_bound(0x200, 3);
+        let inner = 3 * (22 + 23) + 22;
+        assert_eq!(est.estimate(),
+                   CycleRange::new(6 + 22 + 23, Some(3 * (6 + inner + 23))));
+    }
+
+    //this test checks that calls include their subroutines
+    #[test]
+    fn test_calls() {
+        let est = Estimator::new(Cfg::new(0x200, &[
+            Instruction::CALL(CallData::new(0x204)),
+            Instruction::JMP(JmpData::new(0x202)),
+            Instruction::CLS,
+            Instruction::RET
+        ]));
+        assert_eq!(est.estimate(), CycleRange::exact(23 + 24 + 23 + 23));
+        assert_eq!(est.estimate_from(0x204), CycleRange::exact(24 + 23));
+        let est = Estimator::new(Cfg::new(0x200, &[
+            Instruction::CALL(CallData::new(0x200)),
+            Instruction::RET
+        ]));
+        assert!(!est.estimate().is_bounded());
+    }
+
+    //this test checks the variable cost of drawing
+    #[test]
+    fn test_draw() {
+        let est = Estimator::new(Cfg::new(0x200, &[
+            Instruction::DRAW(DrawData::new(Register::V0, Register::V1, 3)),
+            Instruction::JMP(JmpData::new(0x202))
+        ]));
+        assert_eq!(est.estimate(),
+                   CycleRange::new(38 + 26 * 3 + 23, Some(38 + 46 * 3 + 23)));
+    }
+}
+
+//end of file
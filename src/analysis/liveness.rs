@@ -0,0 +1,251 @@
+/*
+ * liveness.rs
+ * Defines a register liveness analysis
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+
+//usage statements
+use std::collections::BTreeMap;
+use super::Cfg;
+use super::super::codegen::{Effects, Instruction, REGISTER_COUNT};
+use super::super::data::Register;
+use super::super::vm::Quirks;
+
+/// A mask of every register
+const ALL_REGISTERS: u32 = (1 << REGISTER_COUNT) - 1;
+
+/// The registers an instruction deliberately stores to, leaving
+/// out `VF` set as a flag and the registers of `RDP` and `RLD`
+///
+/// # Arguments
+///
+/// * `instr` - The instruction
+/// * `effects` - The effects of the instruction
+///
+/// # Returns
+///
+/// A mask of the registers stored to
+fn stores(instr: &Instruction, effects: &Effects) -> u32 {
+    return match *instr {
+        Instruction::RDP(_) | Instruction::RLD(_) => 0,
+        _ if effects.sets_flag() => effects.get_write_mask() & !(1 << 0xF),
+        _ => effects.get_write_mask()
+    };
+}
+
+/// Which registers hold values that may still be read, before and
+/// after each block of a program. Registers are live wherever
+/// control may leave the graph. Masks have bit `1 << id` set for
+/// each live register, as in `Effects::get_read_mask`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Liveness {
+    /// The registers live on entry to each block
+    live_in: BTreeMap<u16, u32>,
+
+    /// The registers live on exit from each block
+    live_out: BTreeMap<u16, u32>,
+
+    /// The reachable stores whose values are never read
+    dead_stores: Vec<(u16, Register)>,
+
+    /// The registers that may be read before they are written
+    uninitialized: Vec<Register>
+}
+
+//implementation
+impl Liveness {
+    /// Constructs a new `Liveness` instance
+    ///
+    /// # Arguments
+    ///
+    /// * `cfg` - The graph of the program
+    /// * `quirks` - The interpreter behaviours to assume
+    ///
+    /// # Returns
+    ///
+    /// A new `Liveness` instance analysing the program
+    pub fn new(cfg: &Cfg, quirks: Quirks) -> Liveness {
+        let graph = cfg.flow_graph();
+
+        //find the registers each block reads before
+        //writing and the registers it writes
+        let mut uses = BTreeMap::new();
+        let mut defs = BTreeMap::new();
+        for b in cfg.blocks() {
+            let (mut used, mut defined) = (0u32, 0u32);
+            for instr in b.get_instructions().iter().rev() {
+                let e = instr.effects(quirks);
+                used = (used & !e.get_write_mask()) | e.get_read_mask();
+                defined |= e.get_write_mask();
+            }
+            uses.insert(b.get_start(), used);
+            defs.insert(b.get_start(), defined);
+        }
+
+        //then solve backwards until nothing changes
+        let mut live_in: BTreeMap<u16, u32> = BTreeMap::new();
+        let mut live_out: BTreeMap<u16, u32> = BTreeMap::new();
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for b in cfg.blocks().collect::<Vec<_>>().into_iter().rev() {
+                let start = b.get_start();
+                let mut out = if cfg.leaves(start) { ALL_REGISTERS } else {
+                    0
+                };
+                for s in graph[&start].iter() {
+                    out |= live_in.get(s).cloned().unwrap_or(0);
+                }
+                let inn = uses[&start] | (out & !defs[&start]);
+                if live_in.get(&start) != Some(&inn) ||
+                    live_out.get(&start) != Some(&out) {
+                    live_in.insert(start, inn);
+                    live_out.insert(start, out);
+                    changed = true;
+                }
+            }
+        }
+
+        //and look for stores that are never read
+        let mut dead_stores = Vec::new();
+        for start in cfg.flow_reachable() {
+            let block = cfg.get_block(start).unwrap();
+            let mut live = live_out[&start];
+            let mut found = Vec::new();
+            for (addr, instr) in block.addressed().collect::<Vec<_>>()
+                                      .into_iter().rev() {
+                let e = instr.effects(quirks);
+                let dead = stores(instr, &e) & !live;
+                for reg in Effects::registers(dead).into_iter().rev() {
+                    found.push((addr, reg));
+                }
+                live = (live & !e.get_write_mask()) | e.get_read_mask();
+            }
+            found.reverse();
+            dead_stores.extend(found);
+        }
+        let entry = live_in.get(&cfg.get_origin()).cloned().unwrap_or(0);
+        return Liveness {
+            live_in,
+            live_out,
+            dead_stores,
+            uninitialized: Effects::registers(entry)
+        };
+    }
+
+    /// Gets the registers live on entry to a block
+    ///
+    /// # Argument
+    ///
+    /// * `start` - The start address of the block
+    ///
+    /// # Returns
+    ///
+    /// A mask of the live registers, which is empty for
+    /// addresses that start no block
+    pub fn get_live_in(&self, start: u16) -> u32 {
+        return self.live_in.get(&start).cloned().unwrap_or(0);
+    }
+
+    /// Gets the registers live on exit from a block
+    ///
+    /// # Argument
+    ///
+    /// * `start` - The start address of the block
+    ///
+    /// # Returns
+    ///
+    /// A mask of the live registers, which is empty for
+    /// addresses that start no block
+    pub fn get_live_out(&self, start: u16) -> u32 {
+        return self.live_out.get(&start).cloned().unwrap_or(0);
+    }
+
+    /// Gets the reachable stores whose values are never read.
+    /// Flags set in `VF` and the registers of `RDP` and `RLD`
+    /// are not reported.
+    ///
+    /// # Returns
+    ///
+    /// The address and register of each dead store, in order
+    pub fn get_dead_stores(&self) -> &[(u16, Register)] {
+        return &self.dead_stores;
+    }
+
+    /// Gets the registers that may be read before the program
+    /// writes them
+    ///
+    /// # Returns
+    ///
+    /// The registers live at the origin, in ID order
+    pub fn get_uninitialized(&self) -> &[Register] {
+        return &self.uninitialized;
+    }
+}
+
+//unit tests
+#[cfg(test)]
+mod tests {
+    //import the Liveness struct
+    use super::*;
+    use super::super::super::data::*;
+
+    //this test checks dead stores and uninitialized registers
+    #[test]
+    fn test_liveness() {
+        let cfg = Cfg::new(0x200, &[
+            Instruction::MOV(MovData::with_constant(Register::V0, 5)),
+            Instruction::MOV(MovData::with_constant(Register::V0, 6)),
+            Instruction::ADD(AddData::with_register(Register::V1,
+                                                    Register::V0)),
+            Instruction::MOV(MovData::with_constant(Register::V2, 1)),
+            Instruction::JMP(JmpData::new(0x208))
+        ]);
+        let live = Liveness::new(&cfg, Quirks::vip());
+        assert_eq!(live.get_dead_stores(), &[(0x200, Register::V0),
+                                             (0x204, Register::V1),
+                                             (0x206, Register::V2)]);
+        assert_eq!(live.get_uninitialized(), &[Register::V1]);
+        assert_eq!(live.get_live_in(0x200), 1 << 1);
+        assert_eq!(live.get_live_out(0x208), 0);
+    }
+
+    //this test checks liveness across calls and loops
+    #[test]
+    fn test_calls() {
+        let cfg = Cfg::new(0x200, &[
+            Instruction::MOV(MovData::with_constant(Register::V3, 1)),
+            Instruction::CALL(CallData::new(0x208)),
+            Instruction::ADD(AddData::with_constant(Register::V4, 1)),
+            Instruction::JMP(JmpData::new(0x202)),
+            Instruction::DRAW(DrawData::new(Register::V3, Register::V4,
+                                            5)),
+            Instruction::RET
+        ]);
+        let live = Liveness::new(&cfg, Quirks::vip());
+        assert!(live.get_dead_stores().is_empty());
+        assert_eq!(live.get_uninitialized(),
+                   &[Register::V4, Register::I]);
+        assert_eq!(live.get_live_in(0x208),
+                   (1 << 3) | (1 << 4) | (1 << 0x10));
+    }
+}
+
+//end of file
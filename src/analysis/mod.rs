@@ -0,0 +1,60 @@
+/*
+ * mod.rs
+ * Module export file for the analysis module
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+
+//! Static analysis of Chip-8 programs.
+//!
+//! A `Cfg` splits instructions into `BasicBlock`s joined by
+//! jumps, skips and fall-through, with dominators and the
+//! `NaturalLoop`s they reveal. An `Estimator` sums the COSMAC
+//! VIP cost of each instruction, given by `vip_cost`, over the
+//! blocks and loops of a program as a `CycleRange`, running each
+//! loop up to a bound the caller supplies.
+//!
+//! Whole-program data-flow analyses follow calls and returns
+//! using the `Effects` of each instruction: `Liveness` finds dead
+//! stores and registers read before they are written,
+//! `ReachingDefinitions` finds the writes each read may see, and
+//! `ConstantPropagation` finds register values that resolve `JPC`
+//! targets and decide skips.
+
+//exports
+mod cycle_range;
+pub use cycle_range::CycleRange;
+mod vip_cost;
+pub use vip_cost::{vip_cost, DRAW_ALIGNED_SAVING};
+mod basic_block;
+pub use basic_block::BasicBlock;
+mod natural_loop;
+pub use natural_loop::NaturalLoop;
+mod cfg;
+pub use cfg::Cfg;
+mod estimator;
+pub use estimator::Estimator;
+mod liveness;
+pub use liveness::Liveness;
+mod reaching_definitions;
+pub use reaching_definitions::ReachingDefinitions;
+mod constant_propagation;
+pub use constant_propagation::ConstantPropagation;
+
+//end of file
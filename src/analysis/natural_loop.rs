@@ -0,0 +1,106 @@
+/*
+ * natural_loop.rs
+ * Defines a natural loop of a control-flow graph
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+
+//usage statements
+use std::collections::BTreeSet;
+
+/// A loop found from the back edges to a header block
+/// that dominates every block of the loop
+#[derive(Debug, Clone, PartialEq)]
+pub struct NaturalLoop {
+    /// The start address of the header block
+    header: u16,
+
+    /// The start addresses of the blocks in the loop,
+    /// including the header
+    blocks: BTreeSet<u16>,
+
+    /// The start addresses of the blocks that jump back
+    /// to the header
+    latches: Vec<u16>
+}
+
+//implementation
+impl NaturalLoop {
+    /// Constructs a new `NaturalLoop` instance
+    ///
+    /// # Arguments
+    ///
+    /// * `new_header` - The header block
+    /// * `new_blocks` - The blocks in the loop
+    /// * `new_latches` - The blocks that jump back to the header
+    ///
+    /// # Returns
+    ///
+    /// A new `NaturalLoop` instance with the given properties
+    pub fn new(new_header: u16, new_blocks: BTreeSet<u16>,
+               new_latches: Vec<u16>) -> NaturalLoop {
+        return NaturalLoop {
+            header: new_header,
+            blocks: new_blocks,
+            latches: new_latches
+        };
+    }
+
+    /// Gets the header block
+    ///
+    /// # Returns
+    ///
+    /// The start address of the header
+    pub fn get_header(&self) -> u16 {
+        return self.header;
+    }
+
+    /// Gets the blocks in the loop
+    ///
+    /// # Returns
+    ///
+    /// The start addresses of the blocks, including the header
+    pub fn get_blocks(&self) -> &BTreeSet<u16> {
+        return &self.blocks;
+    }
+
+    /// Gets the blocks that jump back to the header
+    ///
+    /// # Returns
+    ///
+    /// The start addresses of the latches
+    pub fn get_latches(&self) -> &[u16] {
+        return &self.latches;
+    }
+
+    /// Gets whether a block is in the loop
+    ///
+    /// # Argument
+    ///
+    /// * `block` - The start address of the block
+    ///
+    /// # Returns
+    ///
+    /// Whether the block is in the loop
+    pub fn contains(&self, block: u16) -> bool {
+        return self.blocks.contains(&block);
+    }
+}
+
+//end of file
@@ -0,0 +1,181 @@
+/*
+ * reaching_definitions.rs
+ * Defines a reaching definitions analysis
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+
+//usage statements
+use std::collections::{BTreeMap, BTreeSet};
+use super::Cfg;
+use super::super::codegen::{Effects, REGISTER_COUNT};
+use super::super::data::Register;
+use super::super::vm::Quirks;
+
+/// A register and the address of the instruction that last wrote
+/// it, or `None` for the value the register starts with
+type Definition = (u8, Option<u16>);
+
+/// Which writes of each register may reach each instruction of a
+/// program, following calls and returns from the origin. The
+/// value a register holds when the program starts counts as a
+/// definition of its own, so reads it reaches may be reads of
+/// uninitialized registers.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReachingDefinitions {
+    /// The definitions reaching each reachable instruction
+    before: BTreeMap<u16, BTreeSet<Definition>>,
+
+    /// The reads that an initial value may reach
+    uninitialized_reads: Vec<(u16, Register)>
+}
+
+//implementation
+impl ReachingDefinitions {
+    /// Constructs a new `ReachingDefinitions` instance
+    ///
+    /// # Arguments
+    ///
+    /// * `cfg` - The graph of the program
+    /// * `quirks` - The interpreter behaviours to assume
+    ///
+    /// # Returns
+    ///
+    /// A new `ReachingDefinitions` instance analysing the program
+    pub fn new(cfg: &Cfg, quirks: Quirks) -> ReachingDefinitions {
+        let graph = cfg.flow_graph();
+        let transfer = |addr: u16, e: &Effects,
+                        defs: &mut BTreeSet<Definition>| {
+            let writes = e.get_write_mask();
+            defs.retain(|(r, _)| writes & (1 << r) == 0);
+            for r in 0..REGISTER_COUNT {
+                if writes & (1 << r) != 0 {
+                    defs.insert((r, Some(addr)));
+                }
+            }
+        };
+
+        //solve forwards from the initial values at the origin
+        let mut ins: BTreeMap<u16, BTreeSet<Definition>> = BTreeMap::new();
+        let mut work = Vec::new();
+        if cfg.get_block(cfg.get_origin()).is_some() {
+            let initial = (0..REGISTER_COUNT).map(|r| (r, None)).collect();
+            ins.insert(cfg.get_origin(), initial);
+            work.push(cfg.get_origin());
+        }
+        while let Some(start) = work.pop() {
+            let mut defs = ins[&start].clone();
+            for (addr, instr) in cfg.get_block(start).unwrap().addressed() {
+                transfer(addr, &instr.effects(quirks), &mut defs);
+            }
+            for s in graph[&start].iter() {
+                let entry = ins.entry(*s).or_default();
+                let before = entry.len();
+                entry.extend(defs.iter().cloned());
+                if entry.len() != before {
+                    work.push(*s);
+                }
+            }
+        }
+
+        //then record the definitions before each instruction
+        let mut before = BTreeMap::new();
+        let mut uninitialized_reads = Vec::new();
+        for (start, defs) in ins {
+            let mut defs = defs;
+            for (addr, instr) in cfg.get_block(start).unwrap().addressed() {
+                let e = instr.effects(quirks);
+                for reg in e.get_reads() {
+                    if defs.contains(&(reg.to_id(), None)) {
+                        uninitialized_reads.push((addr, reg));
+                    }
+                }
+                before.insert(addr, defs.clone());
+                transfer(addr, &e, &mut defs);
+            }
+        }
+        return ReachingDefinitions { before, uninitialized_reads };
+    }
+
+    /// Gets the definitions of a register that may reach
+    /// an instruction
+    ///
+    /// # Arguments
+    ///
+    /// * `addr` - The address of the instruction
+    /// * `reg` - The register
+    ///
+    /// # Returns
+    ///
+    /// The addresses of the instructions that may have last written
+    /// the register, with `None` for its initial value first. This
+    /// is empty for instructions the program cannot reach.
+    pub fn get_reaching(&self, addr: u16, reg: &Register)
+        -> Vec<Option<u16>> {
+        return match self.before.get(&addr) {
+            Some(defs) => defs.iter().filter(|(r, _)| *r == reg.to_id())
+                              .map(|(_, a)| *a).collect(),
+            None => Vec::new()
+        };
+    }
+
+    /// Gets the reads that may see a register's initial value
+    ///
+    /// # Returns
+    ///
+    /// The address and register of each read, in order
+    pub fn get_uninitialized_reads(&self) -> &[(u16, Register)] {
+        return &self.uninitialized_reads;
+    }
+}
+
+//unit tests
+#[cfg(test)]
+mod tests {
+    //import the ReachingDefinitions struct
+    use super::*;
+    use super::super::super::codegen::Instruction;
+    use super::super::super::data::*;
+
+    //this test checks definitions that reach along two paths
+    #[test]
+    fn test_reaching() {
+        let cfg = Cfg::new(0x200, &[
+            Instruction::SKIP(SkipData::with_constant(
+                    Register::V0, 0, SkipType::Equals)),
+            Instruction::MOV(MovData::with_constant(Register::V1, 1)),
+            Instruction::MOV(MovData::with_register(Register::V2,
+                                                    Register::V1)),
+            Instruction::MOV(MovData::with_constant(Register::V1, 2)),
+            Instruction::JMP(JmpData::new(0x208))
+        ]);
+        let defs = ReachingDefinitions::new(&cfg, Quirks::vip());
+        assert_eq!(defs.get_reaching(0x204, &Register::V1),
+                   vec![None, Some(0x202)]);
+        assert_eq!(defs.get_reaching(0x208, &Register::V1),
+                   vec![Some(0x206)]);
+        assert_eq!(defs.get_reaching(0x208, &Register::V2),
+                   vec![Some(0x204)]);
+        assert_eq!(defs.get_uninitialized_reads(),
+                   &[(0x200, Register::V0), (0x204, Register::V1)]);
+        assert!(defs.get_reaching(0x20A, &Register::V1).is_empty());
+    }
+}
+
+//end of file
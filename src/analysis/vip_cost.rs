@@ -0,0 +1,80 @@
+/*
+ * vip_cost.rs
+ * Defines the estimated COSMAC VIP cost of an instruction
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+
+//usage statements
+use super::CycleRange;
+use super::super::codegen::{CodeGen, Instruction};
+use super::super::vm::vip_cycles;
+
+/// The cycles saved per sprite row when `DRAW`'s X coordinate is
+/// a multiple of eight, so each row covers a single display byte
+/// and needs no shifting
+pub const DRAW_ALIGNED_SAVING: u64 = 20;
+
+/// Estimates the range of COSMAC VIP machine cycles an instruction
+/// takes, using the costs of `vm::vip_cycles`. `DRAW` ranges from
+/// sprites at byte-aligned X coordinates to unaligned ones, and
+/// `KEY` has no maximum since it waits for a key press.
+///
+/// # Argument
+///
+/// * `instr` - The instruction
+///
+/// # Returns
+///
+/// The cycle range of the instruction
+pub fn vip_cost(instr: &Instruction) -> CycleRange {
+    let opcode = instr.gen_opcode();
+    let cycles = vip_cycles(opcode) as u64;
+    return match *instr {
+        Instruction::DRAW(_) => {
+            let rows = (opcode & 0xF) as u64;
+            CycleRange::new(cycles - DRAW_ALIGNED_SAVING * rows,
+                            Some(cycles))
+        },
+        Instruction::KEY(_) => CycleRange::new(cycles, None),
+        _ => CycleRange::exact(cycles)
+    };
+}
+
+//unit tests
+#[cfg(test)]
+mod tests {
+    //import the vip_cost function
+    use super::*;
+    use super::super::super::data::{DrawData, KeyData, Register};
+
+    //this test checks the fixed and variable costs
+    #[test]
+    fn test_costs() {
+        assert_eq!(vip_cost(&Instruction::CLS), CycleRange::exact(24));
+        let draw = DrawData::new(Register::V0, Register::V1, 5);
+        assert_eq!(vip_cost(&Instruction::DRAW(draw)),
+                   CycleRange::new(38 + 26 * 5, Some(38 + 46 * 5)));
+        let key = KeyData::new(Register::V0);
+        assert_eq!(vip_cost(&Instruction::KEY(key)),
+                   CycleRange::new(10, None));
+    }
+}
+
+//end of file
@@ -0,0 +1,624 @@
+/*
+ * assembler.rs
+ * Assembles Chip-8 assembly source into a program image
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//usage statements
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use super::expr;
+use super::expr::Expr;
+use super::lexer::{tokenize, Line, TokenKind};
+use super::preprocessor::{resolve, split_labels, Equ, Preprocessor};
+use super::super::codegen::{Element, Image, Instruction, PatchPoint,
+                            SourceSpan, PROGRAM_START};
+use super::super::data;
+use super::super::data::{Register, SkipType};
+use super::super::error::{CompileError, CompileErrorType};
+
+/// The instruction mnemonics understood by the assembler
+const MNEMONICS: [&str; 28] = [
+    "CLS", "RET", "JMP", "CALL", "JPC", "SKIP.EQ", "SKIP.NE", "SKIP.KD",
+    "SKIP.KU", "MOV", "ADD", "OR", "AND", "XOR", "SUB", "SUBN", "SHR",
+    "SHL", "RAND", "DRAW", "GDL", "KEY", "SDL", "SND", "SCH", "BCD", 
+    "RDP", "RLD"
+];
+
+/// Assembles source text into a program image
+///
+/// # Arguments
+///
+/// * `src` - The assembly source
+/// * `file` - The name of the source file, used in error messages
+///   and to resolve `.include` paths
+///
+/// # Returns
+///
+/// The assembled `Image`, or the first `CompileError` encountered
+pub fn assemble(src: &str, file: &str) -> Result<Image, CompileError> {
+    let lines = tokenize(src, file)?;
+    let mut pp = Preprocessor::new();
+    let lines = pp.process(lines, 0)?;
+    return Assembler::new(pp.into_equs()).assemble(&lines);
+}
+
+/// Reads and assembles a source file
+///
+/// # Argument
+///
+/// * `path` - The path to the source file
+///
+/// # Returns
+///
+/// The assembled `Image`, or the first `CompileError` encountered
+pub fn assemble_file(path: &str) -> Result<Image, CompileError> {
+    return match fs::read_to_string(path) {
+        Ok(src) => assemble(&src, path),
+        Err(_) => Err(CompileError::new(
+                CompileErrorType::IncludeFailed(String::from(path)), 0, 0)
+                .with_file(path))
+    };
+}
+
+/// Parses a register name
+///
+/// # Argument
+///
+/// * `name` - The name to parse
+///
+/// # Returns
+///
+/// The named `Register`, or `None` if the name is not a register
+fn register(name: &str) -> Option<Register> {
+    let upper = name.to_ascii_uppercase();
+    if upper == "I" {
+        return Some(Register::I);
+    }
+    let mut chars = upper.chars();
+    return match (chars.next(), chars.next(), chars.next()) {
+        (Some('V'), Some(c), None) => {
+            c.to_digit(16).and_then(|id| Register::from_id(id as u8))
+        },
+        _ => None
+    };
+}
+
+/// Reports a `.patch` directive not followed by an instruction
+fn unpatched(line: &Line, col: usize) -> CompileError {
+    return line.error(CompileErrorType::InvalidOperand(String::from(
+                ".patch must be followed by an instruction")), col);
+}
+
+/// An instruction or directive operand
+enum Operand {
+    /// A register
+    Reg(Register, usize),
+
+    /// A constant expression
+    Value(Expr, usize),
+
+    /// A string literal
+    Str(String, usize)
+}
+
+//implementation
+impl Operand {
+    /// Returns the column of the operand
+    fn col(&self) -> usize {
+        return match *self {
+            Operand::Reg(_, col) => col,
+            Operand::Value(_, col) => col,
+            Operand::Str(_, col) => col
+        };
+    }
+}
+
+/// Lays out and encodes preprocessed lines
+struct Assembler {
+    /// The symbols defined with `.equ` and `.const`
+    equs: HashMap<String, Equ>,
+
+    /// The label addresses
+    labels: HashMap<String, u16>,
+
+    /// The image being built
+    image: Image
+}
+
+//implementation
+impl Assembler {
+    /// Creates a new `Assembler` with a set of symbols
+    fn new(equs: HashMap<String, Equ>) -> Assembler {
+        return Assembler {
+            equs,
+            labels: HashMap::new(),
+            image: Image::new()
+        };
+    }
+
+    /// Assembles preprocessed lines in two passes
+    fn assemble(mut self, lines: &[Line]) -> Result<Image, CompileError> {
+        //first pass: assign addresses to labels and statements
+        let mut here = PROGRAM_START as i64;
+        let mut stmts = Vec::new();
+        let mut patches = BTreeMap::new();
+        let mut pending: Option<(String, &Line, usize)> = None;
+        for (idx, line) in lines.iter().enumerate() {
+            let (labels, start) = split_labels(line);
+            for l in labels.iter() {
+                self.define(l.ident().unwrap(), here, line, l.col)?;
+            }
+            if start == line.tokens.len() {
+                continue;
+            }
+            let word = self.mnemonic(line, start)?;
+            if word.starts_with('.') && word != ".ORG" {
+                if let Some((_, at, col)) = pending.take() {
+                    return Err(unpatched(at, col));
+                }
+            }
+            let size = match word.as_str() {
+                ".PATCH" => {
+                    let head = &line.tokens[start];
+                    let name = match line.tokens.get(start + 1) {
+                        Some(t) if line.tokens.len() == start + 2 => {
+                            t.ident()
+                        },
+                        _ => None
+                    };
+                    match name {
+                        Some(n) => {
+                            pending = Some((String::from(n), line, head.col))
+                        },
+                        None => return Err(line.error(
+                                CompileErrorType::InvalidOperand(
+                                    String::from(".patch expects a name")),
+                                head.col))
+                    };
+                    continue;
+                },
+                ".ORG" => {
+                    let ops = self.operands(line, start + 1)?;
+                    self.arity(&ops, 1, line, start)?;
+                    here = self.value(&ops[0], line, Some(here), 
+                                      0, 0xFFFF)?;
+                    continue;
+                },
+                ".BYTE" => {
+                    let ops = self.operands(line, start + 1)?;
+                    ops.iter().map(|op| match *op {
+                        Operand::Str(ref s, _) => s.len() as i64,
+                        _ => 1
+                    }).sum()
+                },
+                ".WORD" => self.operands(line, start + 1)?.len() as i64 * 2,
+                _ => 2
+            };
+            if let Some((name, at, col)) = pending.take() {
+                self.define(&name, here, at, col)?;
+                self.define(&format!("{}.operand", name), here + 1, at,
+                            col)?;
+                patches.insert(here, name);
+            }
+            stmts.push((here, idx, start));
+            here += size;
+            if here > 0x10000 {
+                return Err(line.error(CompileErrorType::ProgramTooLarge(
+                            (here - PROGRAM_START as i64) as usize),
+                            line.tokens[start].col));
+            }
+        }
+        if let Some((_, at, col)) = pending {
+            return Err(unpatched(at, col));
+        }
+
+        //second pass: encode each statement
+        for (addr, idx, start) in stmts {
+            let line = &lines[idx];
+            let word = self.mnemonic(line, start)?;
+            let ops = self.operands(line, start + 1)?;
+            let mut elems = Vec::new();
+            match word.as_str() {
+                ".BYTE" => {
+                    for op in ops.iter() {
+                        match *op {
+                            Operand::Str(ref s, _) => {
+                                elems.extend(s.bytes().map(Element::Byte));
+                            },
+                            _ => {
+                                let v = self.value(op, line, Some(addr), 
+                                                   -0x80, 0xFF)?;
+                                elems.push(Element::Byte(v as u8));
+                            }
+                        };
+                    }
+                },
+                ".WORD" => {
+                    for op in ops.iter() {
+                        let v = self.value(op, line, Some(addr), 
+                                           -0x8000, 0xFFFF)?;
+                        elems.push(Element::Word(v as u16));
+                    }
+                },
+                _ => {
+                    let instr = self.instruction(&word, line, start, &ops, 
+                                                 addr)?;
+                    if let (Some(name), Element::Instr(ref i)) =
+                        (patches.get(&addr), &instr) {
+                        self.image.add_patch(PatchPoint::new(
+                                name, addr as u16, i));
+                    }
+                    elems.push(instr);
+                }
+            };
+            let span = SourceSpan::new(line.line, &line.text)
+                           .with_file(&line.file);
+            let mut here = addr;
+            for elem in elems {
+                let len = elem.len() as i64;
+                if !self.image.place(here as u16, elem) {
+                    return Err(line.error(CompileErrorType::AddressConflict(
+                                here as u16), line.tokens[start].col));
+                }
+                self.image.set_span(here as u16, span.clone());
+                here += len;
+            }
+        }
+        for (name, addr) in self.labels.iter() {
+            self.image.add_label(name, *addr);
+        }
+        return Ok(self.image);
+    }
+
+    /// Defines a label, checking that the name is free
+    fn define(&mut self, name: &str, addr: i64, line: &Line, col: usize)
+        -> Result<(), CompileError> {
+        if register(name).is_some() {
+            return Err(line.error(CompileErrorType::InvalidOperand(
+                        format!("register {} used as a label", name)), col));
+        }
+        if self.labels.contains_key(name) || self.equs.contains_key(name) {
+            return Err(line.error(CompileErrorType::Redefinition(
+                        String::from(name)), col));
+        }
+        self.labels.insert(String::from(name), addr as u16);
+        return Ok(());
+    }
+
+    /// Returns the upper-case mnemonic of a statement
+    fn mnemonic(&self, line: &Line, start: usize) 
+        -> Result<String, CompileError> {
+        let head = &line.tokens[start];
+        let word = head.ident().map(|s| s.to_ascii_uppercase())
+                       .unwrap_or_default();
+        if word == ".ORG" || word == ".BYTE" || word == ".WORD" ||
+            word == ".PATCH" ||
+            MNEMONICS.contains(&word.as_str()) {
+            return Ok(word);
+        }
+        return Err(line.error(CompileErrorType::UnexpectedToken(head.text()),
+                              head.col));
+    }
+
+    /// Parses the comma-separated operands of a statement
+    fn operands(&self, line: &Line, start: usize) 
+        -> Result<Vec<Operand>, CompileError> {
+        let mut ops = Vec::new();
+        let mut pos = start;
+        let len = line.tokens.len();
+        while pos < len {
+            let t = &line.tokens[pos];
+            let alone = pos + 1 == len || line.tokens[pos + 1].is_punct(",");
+            let reg = t.ident().and_then(register);
+            match t.kind {
+                _ if alone && reg.is_some() => {
+                    ops.push(Operand::Reg(reg.unwrap(), t.col));
+                    pos += 1;
+                },
+                TokenKind::Str(ref s) if alone => {
+                    ops.push(Operand::Str(s.clone(), t.col));
+                    pos += 1;
+                },
+                _ => ops.push(Operand::Value(expr::parse(line, &mut pos)?, 
+                                             t.col))
+            };
+            if pos == len {
+                break;
+            }
+            let sep = &line.tokens[pos];
+            if !sep.is_punct(",") {
+                return Err(line.error(CompileErrorType::UnexpectedToken(
+                            sep.text()), sep.col));
+            }
+            pos += 1;
+            if pos == len {
+                return Err(line.error(CompileErrorType::UnexpectedEnd, 
+                                      sep.col));
+            }
+        }
+        return Ok(ops);
+    }
+
+    /// Checks the number of operands of a statement
+    fn arity(&self, ops: &[Operand], n: usize, line: &Line, start: usize)
+        -> Result<(), CompileError> {
+        if ops.len() != n {
+            let head = &line.tokens[start];
+            return Err(line.error(CompileErrorType::InvalidOperand(
+                        format!("{} expects {} operand(s), found {}",
+                                head.text(), n, ops.len())), head.col));
+        }
+        return Ok(());
+    }
+
+    /// Evaluates a value operand and checks its range
+    fn value(&self, op: &Operand, line: &Line, here: Option<i64>,
+             min: i64, max: i64) -> Result<i64, CompileError> {
+        let (e, col) = match *op {
+            Operand::Value(ref e, col) => (e, col),
+            _ => return Err(line.error(CompileErrorType::InvalidOperand(
+                        String::from("expected a value")), op.col()))
+        };
+        let equs = &self.equs;
+        let labels = &self.labels;
+        let v = expr::eval(e, line, here, &mut |n, c| {
+            resolve(equs, labels, n, line, c, 0)
+        })?;
+        if v < min || v > max {
+            return Err(line.error(CompileErrorType::ConstantOutOfRange(v),
+                        col));
+        }
+        return Ok(v);
+    }
+
+    /// Returns the `V` register named by an operand
+    fn vreg(&self, op: &Operand, line: &Line) 
+        -> Result<Register, CompileError> {
+        return match *op {
+            Operand::Reg(ref r, _) if *r != Register::I => Ok(r.clone()),
+            _ => Err(line.error(CompileErrorType::InvalidOperand(
+                        String::from("expected a V register")), op.col()))
+        };
+    }
+
+    /// Encodes an instruction
+    fn instruction(&self, word: &str, line: &Line, start: usize, 
+                   ops: &[Operand], addr: i64) 
+        -> Result<Element, CompileError> {
+        let here = Some(addr);
+        let arity = match word {
+            "CLS" | "RET" => 0,
+            "JMP" | "CALL" | "JPC" | "SKIP.KD" | "SKIP.KU" | "GDL" |
+            "KEY" | "SDL" | "SND" | "SCH" | "BCD" | "RDP" | "RLD" => 1,
+            "SHR" | "SHL" if ops.len() == 1 => 1,
+            "DRAW" => 3,
+            _ => 2
+        };
+        self.arity(ops, arity, line, start)?;
+        let instr = match word {
+            "CLS" => Instruction::CLS,
+            "RET" => Instruction::RET,
+            "JMP" => Instruction::JMP(data::JmpData::new(
+                    self.value(&ops[0], line, here, 0, 0xFFF)? as u16)),
+            "CALL" => Instruction::CALL(data::CallData::new(
+                    self.value(&ops[0], line, here, 0, 0xFFF)? as u16)),
+            "JPC" => Instruction::JPC(data::JpcData::new(
+                    self.value(&ops[0], line, here, 0, 0xFFF)? as u16)),
+            "SKIP.EQ" | "SKIP.NE" => {
+                let kind = if word == "SKIP.EQ" {
+                    SkipType::Equals
+                } else {
+                    SkipType::NotEquals
+                };
+                let vx = self.vreg(&ops[0], line)?;
+                match ops[1] {
+                    Operand::Reg(..) => {
+                        let vy = self.vreg(&ops[1], line)?;
+                        Instruction::SKIP(data::SkipData::with_register(
+                                vx, vy, kind))
+                    },
+                    _ => {
+                        let nn = self.value(&ops[1], line, here, 
+                                            -0x80, 0xFF)?;
+                        Instruction::SKIP(data::SkipData::with_constant(
+                                vx, nn as u8, kind))
+                    }
+                }
+            },
+            "SKIP.KD" | "SKIP.KU" => {
+                let kind = if word == "SKIP.KD" {
+                    SkipType::KeyDown
+                } else {
+                    SkipType::KeyUp
+                };
+                Instruction::SKIP(data::SkipData::with_key(
+                        self.vreg(&ops[0], line)?, kind))
+            },
+            "MOV" => match (&ops[0], &ops[1]) {
+                (Operand::Reg(Register::I, _), _) => {
+                    let nnn = self.value(&ops[1], line, here, 0, 0xFFF)?;
+                    Instruction::MOV(data::MovData::with_constant(
+                            Register::I, nnn as u16))
+                },
+                (_, Operand::Reg(..)) => {
+                    Instruction::MOV(data::MovData::with_register(
+                            self.vreg(&ops[0], line)?, 
+                            self.vreg(&ops[1], line)?))
+                },
+                _ => {
+                    let vx = self.vreg(&ops[0], line)?;
+                    let nn = self.value(&ops[1], line, here, -0x80, 0xFF)?;
+                    Instruction::MOV(data::MovData::with_constant(
+                            vx, nn as u16 & 0xFF))
+                }
+            },
+            "ADD" => match (&ops[0], &ops[1]) {
+                (Operand::Reg(Register::I, _), _) => {
+                    Instruction::ADD(data::AddData::with_register(
+                            Register::I, self.vreg(&ops[1], line)?))
+                },
+                (_, Operand::Reg(..)) => {
+                    Instruction::ADD(data::AddData::with_register(
+                            self.vreg(&ops[0], line)?, 
+                            self.vreg(&ops[1], line)?))
+                },
+                _ => {
+                    let vx = self.vreg(&ops[0], line)?;
+                    let nn = self.value(&ops[1], line, here, -0x80, 0xFF)?;
+                    Instruction::ADD(data::AddData::with_constant(
+                            vx, nn as u16 & 0xFF))
+                }
+            },
+            "OR" | "AND" | "XOR" | "SUB" | "SUBN" => {
+                let vx = self.vreg(&ops[0], line)?;
+                let vy = self.vreg(&ops[1], line)?;
+                match word {
+                    "OR" => Instruction::OR(data::OrData::new(vx, vy)),
+                    "AND" => Instruction::AND(data::AndData::new(vx, vy)),
+                    "XOR" => Instruction::XOR(data::XorData::new(vx, vy)),
+                    "SUB" => Instruction::SUB(data::SubData::new(vx, vy)),
+                    _ => Instruction::SUBN(data::SubnData::new(vx, vy))
+                }
+            },
+            "SHR" | "SHL" => {
+                let vx = self.vreg(&ops[0], line)?;
+                let vy = match ops.get(1) {
+                    Some(op) => self.vreg(op, line)?,
+                    None => vx.clone()
+                };
+                if word == "SHR" {
+                    Instruction::SHR(data::ShrData::with_source(vx, vy))
+                } else {
+                    Instruction::SHL(data::ShlData::with_source(vx, vy))
+                }
+            },
+            "RAND" => {
+                let vx = self.vreg(&ops[0], line)?;
+                let nn = self.value(&ops[1], line, here, 0, 0xFF)?;
+                Instruction::RAND(data::RandData::new(vx, nn as u8))
+            },
+            "DRAW" => {
+                let vx = self.vreg(&ops[0], line)?;
+                let vy = self.vreg(&ops[1], line)?;
+                let n = self.value(&ops[2], line, here, 0, 0xF)?;
+                Instruction::DRAW(data::DrawData::new(vx, vy, n as u8))
+            },
+            _ => {
+                let vx = self.vreg(&ops[0], line)?;
+                match word {
+                    "GDL" => Instruction::GDL(data::GdlData::new(vx)),
+                    "KEY" => Instruction::KEY(data::KeyData::new(vx)),
+                    "SDL" => Instruction::SDL(data::SdlData::new(vx)),
+                    "SND" => Instruction::SND(data::SndData::new(vx)),
+                    "SCH" => Instruction::SCH(data::SchData::new(vx)),
+                    "BCD" => Instruction::BCD(data::BcdData::new(vx)),
+                    "RDP" => Instruction::RDP(data::RdpData::new(vx)),
+                    _ => Instruction::RLD(data::RldData::new(vx))
+                }
+            }
+        };
+        return Ok(Element::Instr(instr));
+    }
+}
+
+//unit tests
+#[cfg(test)]
+mod tests {
+    //import the assembler
+    use super::*;
+
+    //assembles source and returns the program bytes
+    fn bytes(src: &str) -> Vec<u8> {
+        return assemble(src, "t.s").unwrap().to_bytes();
+    }
+
+    //assembles source and returns the error type
+    fn error(src: &str) -> CompileErrorType {
+        return assemble(src, "t.s").unwrap_err().get_type();
+    }
+
+    //this test checks instruction encoding
+    #[test]
+    fn test_instructions() {
+        let src = "start: CLS\nMOV V1, 0x2A\nMOV I, sprite\n\
+                   ADD V1, -1\nADD I, V1\nSKIP.NE V1, V2\nSKIP.KU v3\n\
+                   SHR V4, V5\nDRAW V0, V1, 5\nJMP start\nsprite: .byte 0xF0";
+        assert_eq!(bytes(src), vec![0x00, 0xE0, 0x61, 0x2A, 0xA2, 0x14,
+                                    0x71, 0xFF, 0xF1, 0x1E, 0x91, 0x20,
+                                    0xE3, 0xA1, 0x84, 0x56, 0xD0, 0x15,
+                                    0x12, 0x00, 0xF0]);
+    }
+
+    //this test checks data directives and constants
+    #[test]
+    fn test_directives() {
+        let src = ".const WIDTH = 8\n.equ END, data + WIDTH\nJMP END\n\
+                   .org 0x300\ndata: .byte \"Hi\", WIDTH * 2\n.word $";
+        let image = assemble(src, "t.s").unwrap();
+        assert_eq!(image.get_label("data"), Some(0x300));
+        let bytes = image.to_bytes();
+        assert_eq!(&bytes[..2], &[0x13, 0x08]);
+        assert_eq!(&bytes[0x100..], &[b'H', b'i', 16, 0x03, 0x03]);
+    }
+
+    //this test checks labelling instructions that are rewritten
+    #[test]
+    fn test_patch() {
+        let src = "MOV V0, 0x13\nMOV V1, 0x00\nMOV I, next\nRDP V1\n\
+                   .patch next\nJMP 0x200";
+        let image = assemble(src, "t.s").unwrap();
+        assert_eq!(image.get_label("next"), Some(0x208));
+        assert_eq!(image.get_label("next.operand"), Some(0x209));
+        assert_eq!(image.get_patches()[0].get_name(), "next");
+        assert!(image.is_writable(0x208) && image.is_writable(0x209));
+        assert!(!image.is_writable(0x206));
+        assert!(matches!(error(".patch a\n.byte 1"),
+                         CompileErrorType::InvalidOperand(_)));
+        assert!(matches!(error("CLS\n.patch a"),
+                         CompileErrorType::InvalidOperand(_)));
+        assert!(matches!(error("a: CLS\n.patch a\nCLS"),
+                         CompileErrorType::Redefinition(_)));
+    }
+
+    //this test checks operand validation
+    #[test]
+    fn test_errors() {
+        assert_eq!(error("MOV I, V0"), CompileErrorType::InvalidOperand(
+                       String::from("expected a value")));
+        assert!(matches!(error("OR V0, I"), 
+                         CompileErrorType::InvalidOperand(_)));
+        assert!(matches!(error("DRAW V0, V1"), 
+                         CompileErrorType::InvalidOperand(_)));
+        assert_eq!(error("DRAW V0, V1, 16"), 
+                   CompileErrorType::ConstantOutOfRange(16));
+        assert_eq!(error("CALL -1"), 
+                   CompileErrorType::ConstantOutOfRange(-1));
+        assert_eq!(error("JMP nowhere"), 
+                   CompileErrorType::UnknownName(String::from("nowhere")));
+        assert_eq!(error("a: CLS\na: RET"), 
+                   CompileErrorType::Redefinition(String::from("a")));
+        assert_eq!(error("FOO V0"), 
+                   CompileErrorType::UnexpectedToken(String::from("FOO")));
+        assert_eq!(error(".org 0x200\nCLS\n.org 0x200\nRET"), 
+                   CompileErrorType::AddressConflict(0x200));
+    }
+}
+
+//end of file
@@ -0,0 +1,246 @@
+/*
+ * expr.rs
+ * Parses and evaluates assembly-time constant expressions
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//usage statements
+use super::lexer::{Line, Token, TokenKind};
+use super::super::error::{CompileError, CompileErrorType};
+
+/// A constant expression
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    /// A literal value
+    Num(i64),
+
+    /// A reference to a label or symbol, with its column
+    Name(String, usize),
+
+    /// The address of the current line (`$`)
+    Here,
+
+    /// A unary operation
+    Unary(&'static str, Box<Expr>),
+
+    /// A binary operation, with the column of the operator
+    Binary(&'static str, Box<Expr>, Box<Expr>, usize)
+}
+
+/// The binary operators, from lowest to highest precedence
+const LEVELS: [&[&str]; 9] = [&["||"], &["&&"], &["|"], &["^"], &["&"],
+                              &["==", "!="], &["<", "<=", ">", ">="],
+                              &["<<", ">>"], &["+", "-"]];
+
+/// The multiplicative operators, which bind tightest
+const FACTORS: [&str; 3] = ["*", "/", "%"];
+
+/// Parses an expression from a line's tokens. Parsing stops
+/// at a comma, an unmatched closing parenthesis, or the end
+/// of the line.
+///
+/// # Arguments
+///
+/// * `line` - The line being parsed
+/// * `pos` - The index of the first token, updated past the expression
+///
+/// # Returns
+///
+/// The parsed expression, or a `CompileError`
+pub fn parse(line: &Line, pos: &mut usize) -> Result<Expr, CompileError> {
+    return parse_level(line, pos, 0);
+}
+
+/// Gets the punctuation of the token at a position
+fn punct_at(line: &Line, pos: usize) -> Option<&'static str> {
+    return match line.tokens.get(pos) {
+        Some(Token { kind: TokenKind::Punct(p), .. }) => Some(p),
+        _ => None
+    };
+}
+
+/// Parses a chain of binary operators at a precedence level
+fn parse_level(line: &Line, pos: &mut usize, level: usize) 
+    -> Result<Expr, CompileError> {
+    let ops: &[&str] = if level < LEVELS.len() { 
+        LEVELS[level] 
+    } else { 
+        &FACTORS 
+    };
+    let next = |line: &Line, pos: &mut usize| {
+        if level < LEVELS.len() {
+            return parse_level(line, pos, level + 1);
+        }
+        return parse_unary(line, pos);
+    };
+    let mut lhs = next(line, pos)?;
+    while let Some(p) = punct_at(line, *pos) {
+        if !ops.contains(&p) {
+            break;
+        }
+        let col = line.tokens[*pos].col;
+        *pos += 1;
+        let rhs = next(line, pos)?;
+        lhs = Expr::Binary(p, Box::new(lhs), Box::new(rhs), col);
+    }
+    return Ok(lhs);
+}
+
+/// Parses a unary operation or a primary expression
+fn parse_unary(line: &Line, pos: &mut usize) -> Result<Expr, CompileError> {
+    let tok = match line.tokens.get(*pos) {
+        Some(t) => t.clone(),
+        None => {
+            let col = line.tokens.last().map(|t| t.col).unwrap_or(1);
+            return Err(line.error(CompileErrorType::UnexpectedEnd, col));
+        }
+    };
+    *pos += 1;
+    return match tok.kind {
+        TokenKind::Number(n) => Ok(Expr::Num(n)),
+        TokenKind::Ident(ref name) => Ok(Expr::Name(name.clone(), tok.col)),
+        TokenKind::Punct("$") => Ok(Expr::Here),
+        TokenKind::Punct(op @ "-") | TokenKind::Punct(op @ "~") |
+            TokenKind::Punct(op @ "!") | TokenKind::Punct(op @ "+") => {
+            let inner = parse_unary(line, pos)?;
+            Ok(Expr::Unary(op, Box::new(inner)))
+        },
+        TokenKind::Punct("(") => {
+            let inner = parse_level(line, pos, 0)?;
+            if punct_at(line, *pos) != Some(")") {
+                let col = line.tokens.get(*pos).unwrap_or(&tok).col;
+                return Err(line.error(CompileErrorType::UnexpectedToken(
+                            String::from("expected ')'")), col));
+            }
+            *pos += 1;
+            Ok(inner)
+        },
+        _ => Err(line.error(CompileErrorType::UnexpectedToken(tok.text()), 
+                            tok.col))
+    };
+}
+
+/// Evaluates an expression
+///
+/// # Arguments
+///
+/// * `expr` - The expression to evaluate
+/// * `line` - The line the expression came from, for errors
+/// * `here` - The address of the line, if it has one
+/// * `lookup` - Resolves a name at a column to its value
+///
+/// # Returns
+///
+/// The value of the expression, or a `CompileError`
+pub fn eval(expr: &Expr, line: &Line, here: Option<i64>,
+            lookup: &mut dyn FnMut(&str, usize) -> Result<i64, CompileError>)
+    -> Result<i64, CompileError> {
+    return match *expr {
+        Expr::Num(n) => Ok(n),
+        Expr::Here => match here {
+            Some(h) => Ok(h),
+            None => Err(line.error(CompileErrorType::NotConstant(
+                        String::from("'$' outside of code")), 
+                        line.tokens[0].col))
+        },
+        Expr::Name(ref name, col) => lookup(name, col),
+        Expr::Unary(op, ref inner) => {
+            let v = eval(inner, line, here, lookup)?;
+            Ok(match op {
+                "-" => v.wrapping_neg(),
+                "~" => !v,
+                "!" => (v == 0) as i64,
+                _ => v
+            })
+        },
+        Expr::Binary(op, ref lhs, ref rhs, col) => {
+            let a = eval(lhs, line, here, lookup)?;
+            let b = eval(rhs, line, here, lookup)?;
+            Ok(match op {
+                "+" => a.wrapping_add(b),
+                "-" => a.wrapping_sub(b),
+                "*" => a.wrapping_mul(b),
+                "/" | "%" => {
+                    if b == 0 {
+                        return Err(line.error(CompileErrorType::DivideByZero,
+                                              col));
+                    }
+                    if op == "/" { a.wrapping_div(b) } else { a.wrapping_rem(b) }
+                },
+                "<<" => a.checked_shl(b as u32).unwrap_or(0),
+                ">>" => a.checked_shr(b as u32).unwrap_or(0),
+                "&" => a & b,
+                "|" => a | b,
+                "^" => a ^ b,
+                "==" => (a == b) as i64,
+                "!=" => (a != b) as i64,
+                "<" => (a < b) as i64,
+                "<=" => (a <= b) as i64,
+                ">" => (a > b) as i64,
+                ">=" => (a >= b) as i64,
+                "&&" => (a != 0 && b != 0) as i64,
+                _ => (a != 0 || b != 0) as i64
+            })
+        }
+    };
+}
+
+//unit tests
+#[cfg(test)]
+mod tests {
+    //import the expression functions
+    use super::*;
+    use super::super::lexer::tokenize;
+
+    //evaluates an expression with WIDTH defined as 64
+    fn calc(src: &str) -> Result<i64, CompileError> {
+        let line = tokenize(src, "t.s").unwrap().remove(0);
+        let mut pos = 0;
+        let e = parse(&line, &mut pos)?;
+        let mut lookup = |name: &str, col: usize| {
+            if name == "WIDTH" {
+                return Ok(64);
+            }
+            return Err(line.error(CompileErrorType::UnknownName(
+                        String::from(name)), col));
+        };
+        return eval(&e, &line, Some(0x200), &mut lookup);
+    }
+
+    //this test checks operator precedence
+    #[test]
+    fn test_precedence() {
+        assert_eq!(calc("(WIDTH - 8) / 2").unwrap(), 28);
+        assert_eq!(calc("1 + 2 * 3 << 1").unwrap(), 14);
+        assert_eq!(calc("$ + 2 == 0x202 && -1 < 0").unwrap(), 1);
+        assert_eq!(calc("~0 & 0xFF | 1 ^ 1").unwrap(), 0xFF);
+    }
+
+    //this test checks error reporting
+    #[test]
+    fn test_errors() {
+        assert_eq!(calc("4 / (2 - 2)").unwrap_err().get_type(),
+                   CompileErrorType::DivideByZero);
+        assert_eq!(calc("HEIGHT").unwrap_err().get_type(),
+                   CompileErrorType::UnknownName(String::from("HEIGHT")));
+        assert!(calc("(1 + 2").is_err());
+    }
+}
+
+//end of file
@@ -0,0 +1,315 @@
+/*
+ * lexer.rs
+ * Splits assembly source lines into tokens
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//usage statements
+use super::super::error::{CompileError, CompileErrorType};
+
+/// The kinds of token in assembly source
+#[derive(Debug, Clone, PartialEq)]
+pub enum TokenKind {
+    /// A mnemonic, directive, register, label or symbol name
+    Ident(String),
+
+    /// A numeric or character literal
+    Number(i64),
+
+    /// A quoted string
+    Str(String),
+
+    /// An operator or punctuation mark
+    Punct(&'static str)
+}
+
+/// A token along with its column
+#[derive(Debug, Clone, PartialEq)]
+pub struct Token {
+    /// The kind of the token
+    pub kind: TokenKind,
+
+    /// The 1-based column of the token
+    pub col: usize
+}
+
+//implementation
+impl Token {
+    /// Gets the identifier text of the token
+    ///
+    /// # Returns
+    ///
+    /// The name if the token is an identifier, or `None`
+    pub fn ident(&self) -> Option<&str> {
+        return match self.kind {
+            TokenKind::Ident(ref s) => Some(s.as_str()),
+            _ => None
+        };
+    }
+
+    /// Checks whether the token is a given punctuation mark
+    ///
+    /// # Argument
+    ///
+    /// * `p` - The punctuation to check for
+    ///
+    /// # Returns
+    ///
+    /// `true` if the token is `p`
+    pub fn is_punct(&self, p: &str) -> bool {
+        return matches!(self.kind, TokenKind::Punct(q) if q == p);
+    }
+
+    /// Gets a printable form of the token
+    ///
+    /// # Returns
+    ///
+    /// The token as it might appear in source
+    pub fn text(&self) -> String {
+        return match self.kind {
+            TokenKind::Ident(ref s) => s.clone(),
+            TokenKind::Number(n) => format!("{}", n),
+            TokenKind::Str(ref s) => format!("\"{}\"", s),
+            TokenKind::Punct(p) => String::from(p)
+        };
+    }
+}
+
+/// The punctuation marks, longest first so that
+/// they can be matched greedily
+const PUNCT: [&str; 26] = ["<<", ">>", "==", "!=", "<=", ">=", "&&", "||",
+                           "+", "-", "*", "/", "%", "&", "|", "^", "~",
+                           "!", "<", ">", "=", "(", ")", ",", ":", "$"];
+
+/// A line of assembly source split into tokens
+#[derive(Debug, Clone, PartialEq)]
+pub struct Line {
+    /// The tokens of the line
+    pub tokens: Vec<Token>,
+
+    /// The name of the file the line came from
+    pub file: String,
+
+    /// The 1-based line number within the file
+    pub line: usize,
+
+    /// The source text of the line
+    pub text: String
+}
+
+//implementation
+impl Line {
+    /// Builds an error located at a token of this line
+    ///
+    /// # Arguments
+    ///
+    /// * `err_type` - The type of the error
+    /// * `col` - The column of the error
+    ///
+    /// # Returns
+    ///
+    /// A `CompileError` carrying this line's file and line number
+    pub fn error(&self, err_type: CompileErrorType, col: usize) 
+        -> CompileError {
+        return CompileError::new(err_type, self.line, col)
+                   .with_file(&self.file);
+    }
+}
+
+/// Splits assembly source into tokenized lines.
+/// Comments start with `;` and run to the end of the line.
+///
+/// # Arguments
+///
+/// * `src` - The source text
+/// * `file` - The name of the file the source came from
+///
+/// # Returns
+///
+/// The non-empty lines of the source, or a `CompileError`
+pub fn tokenize(src: &str, file: &str) -> Result<Vec<Line>, CompileError> {
+    let mut lines = Vec::new();
+    for (idx, text) in src.lines().enumerate() {
+        let line_no = idx + 1;
+        let err = |t: CompileErrorType, col: usize| {
+            return CompileError::new(t, line_no, col).with_file(file);
+        };
+        let chars: Vec<char> = text.chars().collect();
+        let mut tokens = Vec::new();
+        let mut pos = 0;
+        while pos < chars.len() {
+            let c = chars[pos];
+            let col = pos + 1;
+            if c.is_whitespace() {
+                pos += 1;
+                continue;
+            }
+            if c == ';' {
+                break;
+            }
+
+            //identifiers
+            if c.is_ascii_alphabetic() || c == '_' || c == '.' || c == '@' {
+                let mut name = String::new();
+                while pos < chars.len() && (chars[pos].is_ascii_alphanumeric()
+                    || "_.@".contains(chars[pos])) {
+                    name.push(chars[pos]);
+                    pos += 1;
+                }
+                tokens.push(Token { kind: TokenKind::Ident(name), col });
+                continue;
+            }
+
+            //numbers
+            if c.is_ascii_digit() {
+                let mut digits = String::new();
+                while pos < chars.len() && (chars[pos].is_ascii_alphanumeric()
+                                            || chars[pos] == '_') {
+                    if chars[pos] != '_' {
+                        digits.push(chars[pos]);
+                    }
+                    pos += 1;
+                }
+                let lower = digits.to_ascii_lowercase();
+                let parsed = if let Some(hex) = lower.strip_prefix("0x") {
+                    i64::from_str_radix(hex, 16)
+                } else if let Some(bin) = lower.strip_prefix("0b") {
+                    i64::from_str_radix(bin, 2)
+                } else {
+                    lower.parse::<i64>()
+                };
+                match parsed {
+                    Ok(n) => tokens.push(Token { 
+                        kind: TokenKind::Number(n), 
+                        col 
+                    }),
+                    Err(_) => return Err(err(
+                            CompileErrorType::UnexpectedToken(digits), col))
+                };
+                continue;
+            }
+
+            //strings and character literals
+            if c == '"' || c == '\'' {
+                let mut s = String::new();
+                pos += 1;
+                loop {
+                    match chars.get(pos) {
+                        None => return Err(err(
+                                CompileErrorType::UnexpectedEnd, col)),
+                        Some(q) if *q == c => break,
+                        Some('\\') => {
+                            pos += 1;
+                            match chars.get(pos) {
+                                Some('n') => s.push('\n'),
+                                Some('0') => s.push('\0'),
+                                Some(e) => s.push(*e),
+                                None => return Err(err(
+                                        CompileErrorType::UnexpectedEnd, 
+                                        col))
+                            };
+                        },
+                        Some(ch) => s.push(*ch)
+                    };
+                    pos += 1;
+                }
+                pos += 1;
+                if c == '"' {
+                    tokens.push(Token { kind: TokenKind::Str(s), col });
+                } else {
+                    let mut it = s.chars();
+                    match (it.next(), it.next()) {
+                        (Some(ch), None) => tokens.push(Token {
+                            kind: TokenKind::Number(ch as i64),
+                            col
+                        }),
+                        _ => return Err(err(
+                                CompileErrorType::UnexpectedToken(
+                                    format!("'{}'", s)), col))
+                    };
+                }
+                continue;
+            }
+
+            //punctuation
+            let rest: String = chars[pos..].iter().take(2).collect();
+            match PUNCT.iter().find(|p| rest.starts_with(*p)) {
+                Some(p) => {
+                    tokens.push(Token { kind: TokenKind::Punct(p), col });
+                    pos += p.len();
+                },
+                None => return Err(err(CompileErrorType::UnexpectedChar(c), 
+                                       col))
+            };
+        }
+        if !tokens.is_empty() {
+            lines.push(Line {
+                tokens,
+                file: String::from(file),
+                line: line_no,
+                text: String::from(text)
+            });
+        }
+    }
+    return Ok(lines);
+}
+
+//unit tests
+#[cfg(test)]
+mod tests {
+    //import the tokenizer
+    use super::*;
+
+    //this test checks tokenizing an instruction with a label
+    #[test]
+    fn test_tokenize_line() {
+        let lines = tokenize("\n loop: ADD V0, (W-8)>>1 ; step", "a.s")
+                        .unwrap();
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0].line, 2);
+        let texts: Vec<String> = lines[0].tokens.iter()
+                                     .map(|t| t.text()).collect();
+        assert_eq!(texts, vec!["loop", ":", "ADD", "V0", ",", "(", "W", "-",
+                               "8", ")", ">>", "1"]);
+        assert_eq!(lines[0].tokens[2].col, 8);
+    }
+
+    //this test checks literals
+    #[test]
+    fn test_literals() {
+        let lines = tokenize(".byte 0x1F, 0b11, 'A', \"hi\\n\"", "a.s")
+                        .unwrap();
+        let kinds: Vec<TokenKind> = lines[0].tokens.iter()
+                                        .map(|t| t.kind.clone()).collect();
+        assert_eq!(kinds[1], TokenKind::Number(0x1F));
+        assert_eq!(kinds[3], TokenKind::Number(3));
+        assert_eq!(kinds[5], TokenKind::Number(65));
+        assert_eq!(kinds[7], TokenKind::Str(String::from("hi\n")));
+    }
+
+    //this test checks that errors carry the file name
+    #[test]
+    fn test_error_location() {
+        let err = tokenize("MOV V0, #1", "game.s").unwrap_err();
+        assert_eq!(format!("{}", err), 
+                   "game.s:1:9: Unexpected character '#'");
+    }
+}
+
+//end of file
@@ -0,0 +1,52 @@
+/*
+ * mod.rs
+ * Module header for the asm module in ch8-isa
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! A macro assembler for Chip-8 assembly.
+//!
+//! Each line holds optional `label:` definitions followed by an
+//! instruction or directive; `;` starts a comment. Mnemonics are
+//! named after `codegen::Instruction` (`MOV V1, 0x2A`, `ADD I, V1`,
+//! `SKIP.EQ V0, V1`, `SKIP.KD V2`, `DRAW V0, V1, 5`, ...) and, like
+//! registers, are case-insensitive. Operands may be constant
+//! expressions with C operators and precedence over numbers, labels,
+//! symbols, character literals and `$`, the current address.
+//!
+//! The directives are:
+//!
+//! * `.org addr` - continues assembly at an address
+//! * `.byte v, "str", ...` and `.word v, ...` - emit data
+//! * `.equ NAME, expr` or `.const NAME = expr` - define a symbol
+//! * `.macro NAME p1, p2` ... `.endm` - define a macro; labels in the
+//!   body starting with `@` are local to each expansion
+//! * `.if expr` ... `.else` ... `.endif` - conditional assembly over
+//!   symbols
+//! * `.include "file"` - assemble another file, relative to the
+//!   including one
+
+//exports
+mod lexer;
+mod expr;
+mod preprocessor;
+mod assembler;
+pub use assembler::{assemble, assemble_file};
+
+//end of file
@@ -0,0 +1,541 @@
+/*
+ * preprocessor.rs
+ * Expands includes, macros and conditional assembly
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//usage statements
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use super::expr;
+use super::expr::Expr;
+use super::lexer::{tokenize, Line, Token, TokenKind};
+use super::super::error::{CompileError, CompileErrorType};
+
+/// The deepest that includes and macro expansions may nest
+const MAX_DEPTH: usize = 32;
+
+/// The most macro expansions in one source, which stops macros
+/// that invoke themselves more than once from growing without bound
+const MAX_EXPANSIONS: usize = 4096;
+
+/// The most tokens on a line produced by a macro expansion
+const MAX_TOKENS: usize = 4096;
+
+/// A macro defined with `.macro` and `.endm`
+struct MacroDef {
+    /// The names of the parameters
+    params: Vec<String>,
+
+    /// The lines of the body
+    body: Vec<Line>
+}
+
+/// A symbol defined with `.equ` or `.const`
+pub struct Equ {
+    /// The expression giving the symbol's value
+    pub value: Expr,
+
+    /// The line the symbol was defined on
+    pub line: Line
+}
+
+/// The state of an open `.if` block
+struct Cond {
+    /// Whether lines in the current branch are assembled
+    active: bool,
+
+    /// Whether a branch of the block has already been taken
+    taken: bool,
+
+    /// Whether the enclosing code is assembled
+    parent_active: bool,
+
+    /// The line that opened the block
+    line: Line
+}
+
+/// Expands includes, macros and conditional assembly,
+/// and collects symbol definitions
+pub struct Preprocessor {
+    /// The defined macros, keyed by upper-case name
+    macros: HashMap<String, MacroDef>,
+
+    /// The defined symbols
+    equs: HashMap<String, Equ>,
+
+    /// The number of macro expansions performed so far
+    expansions: usize
+}
+
+/// Splits a line into its label definitions and the index
+/// of its first token after them
+///
+/// # Argument
+///
+/// * `line` - The line to split
+///
+/// # Returns
+///
+/// The label tokens and the index of the first statement token
+pub fn split_labels(line: &Line) -> (Vec<Token>, usize) {
+    let mut labels = Vec::new();
+    let mut k = 0;
+    while k + 1 < line.tokens.len() && line.tokens[k].ident().is_some() &&
+        line.tokens[k + 1].is_punct(":") {
+        labels.push(line.tokens[k].clone());
+        k += 2;
+    }
+    return (labels, k);
+}
+
+/// Evaluates a symbol defined by `.equ`, following references
+/// to other symbols
+///
+/// # Arguments
+///
+/// * `equs` - The defined symbols
+/// * `labels` - The label addresses known so far
+/// * `name` - The symbol to evaluate
+/// * `line` - The line the reference appears on
+/// * `col` - The column of the reference
+/// * `depth` - The number of symbols being evaluated already
+///
+/// # Returns
+///
+/// The value of the symbol, or a `CompileError`
+pub fn resolve(equs: &HashMap<String, Equ>, labels: &HashMap<String, u16>,
+               name: &str, line: &Line, col: usize, depth: usize) 
+    -> Result<i64, CompileError> {
+    if let Some(addr) = labels.get(name) {
+        return Ok(*addr as i64);
+    }
+    return match equs.get(name) {
+        Some(equ) => {
+            if depth > MAX_DEPTH {
+                return Err(line.error(CompileErrorType::RecursionLimit(
+                            String::from(name)), col));
+            }
+            expr::eval(&equ.value, &equ.line, None, &mut |n, c| {
+                resolve(equs, labels, n, &equ.line, c, depth + 1)
+            })
+        },
+        None => Err(line.error(CompileErrorType::UnknownName(
+                    String::from(name)), col))
+    };
+}
+
+//implementation
+impl Preprocessor {
+    /// Constructs a new `Preprocessor` instance
+    ///
+    /// # Returns
+    ///
+    /// A new `Preprocessor` with no macros or symbols defined
+    pub fn new() -> Preprocessor {
+        return Preprocessor {
+            macros: HashMap::new(),
+            equs: HashMap::new(),
+            expansions: 0
+        };
+    }
+
+    /// Consumes the preprocessor and returns its symbols
+    ///
+    /// # Returns
+    ///
+    /// The symbols defined with `.equ` and `.const`
+    pub fn into_equs(self) -> HashMap<String, Equ> {
+        return self.equs;
+    }
+
+    /// Expands a list of lines
+    ///
+    /// # Arguments
+    ///
+    /// * `lines` - The lines to expand
+    /// * `depth` - The current include and macro nesting depth
+    ///
+    /// # Returns
+    ///
+    /// The expanded lines, which contain only labels,
+    /// instructions and data directives
+    pub fn process(&mut self, lines: Vec<Line>, depth: usize) 
+        -> Result<Vec<Line>, CompileError> {
+        let mut out = Vec::new();
+        let mut conds: Vec<Cond> = Vec::new();
+        let mut iter = lines.into_iter();
+        while let Some(line) = iter.next() {
+            let (labels, start) = split_labels(&line);
+            let head = line.tokens.get(start).cloned();
+            let word = head.as_ref().and_then(|t| t.ident())
+                           .map(|s| s.to_ascii_uppercase())
+                           .unwrap_or_default();
+            let active = conds.last().map(|c| c.active).unwrap_or(true);
+
+            //handle conditional assembly
+            match word.as_str() {
+                ".IF" => {
+                    let cond = if active {
+                        self.eval(&line, start + 1)? != 0
+                    } else {
+                        false
+                    };
+                    conds.push(Cond { 
+                        active: cond, 
+                        taken: cond || !active, 
+                        parent_active: active,
+                        line: line.clone()
+                    });
+                    continue;
+                },
+                ".ELSE" | ".ENDIF" => {
+                    let col = head.as_ref().unwrap().col;
+                    let top = match conds.last_mut() {
+                        Some(c) => c,
+                        None => return Err(line.error(
+                                CompileErrorType::UnexpectedToken(
+                                    word.to_ascii_lowercase()), col))
+                    };
+                    if word == ".ELSE" {
+                        top.active = top.parent_active && !top.taken;
+                        top.taken = true;
+                    } else {
+                        conds.pop();
+                    }
+                    continue;
+                },
+                _ => {}
+            };
+            if !active {
+                continue;
+            }
+
+            //keep the labels of lines whose statements expand
+            let label_line = Line {
+                tokens: line.tokens[..start].to_vec(),
+                file: line.file.clone(),
+                line: line.line,
+                text: line.text.clone()
+            };
+
+            match word.as_str() {
+                ".MACRO" => {
+                    self.define_macro(&line, start, &mut iter)?;
+                },
+                ".ENDM" => {
+                    return Err(line.error(CompileErrorType::UnexpectedToken(
+                                String::from(".endm")), 
+                                head.unwrap().col));
+                },
+                ".EQU" | ".CONST" => {
+                    self.define_equ(&line, start)?;
+                    if !labels.is_empty() {
+                        out.push(label_line);
+                    }
+                },
+                ".INCLUDE" => {
+                    if !labels.is_empty() {
+                        out.push(label_line);
+                    }
+                    let included = self.include(&line, start, depth)?;
+                    out.extend(included);
+                },
+                _ if self.macros.contains_key(&word) => {
+                    if !labels.is_empty() {
+                        out.push(label_line);
+                    }
+                    let expanded = self.expand(&line, start, &word, depth)?;
+                    out.extend(expanded);
+                },
+                _ => out.push(line)
+            };
+        }
+        if let Some(c) = conds.last() {
+            return Err(c.line.error(CompileErrorType::UnexpectedEnd, 
+                                    c.line.tokens[0].col));
+        }
+        return Ok(out);
+    }
+
+    /// Evaluates the expression that ends a directive line
+    fn eval(&self, line: &Line, start: usize) -> Result<i64, CompileError> {
+        let mut pos = start;
+        let e = expr::parse(line, &mut pos)?;
+        if let Some(t) = line.tokens.get(pos) {
+            return Err(line.error(CompileErrorType::UnexpectedToken(t.text()),
+                                  t.col));
+        }
+        let labels = HashMap::new();
+        return expr::eval(&e, line, None, &mut |n, c| {
+            resolve(&self.equs, &labels, n, line, c, 0)
+        });
+    }
+
+    /// Defines a symbol from `.equ NAME, expr` or `.const NAME = expr`
+    fn define_equ(&mut self, line: &Line, start: usize) 
+        -> Result<(), CompileError> {
+        let name_tok = match line.tokens.get(start + 1) {
+            Some(t) if t.ident().is_some() => t,
+            Some(t) => return Err(line.error(
+                    CompileErrorType::UnexpectedToken(t.text()), t.col)),
+            None => return Err(line.error(CompileErrorType::UnexpectedEnd,
+                                          line.tokens[start].col))
+        };
+        let name = String::from(name_tok.ident().unwrap());
+        if self.equs.contains_key(&name) {
+            return Err(line.error(CompileErrorType::Redefinition(name), 
+                                  name_tok.col));
+        }
+        let mut pos = start + 2;
+        if let Some(t) = line.tokens.get(pos) {
+            if t.is_punct(",") || t.is_punct("=") {
+                pos += 1;
+            }
+        }
+        let value = expr::parse(line, &mut pos)?;
+        if let Some(t) = line.tokens.get(pos) {
+            return Err(line.error(CompileErrorType::UnexpectedToken(t.text()),
+                                  t.col));
+        }
+        self.equs.insert(name, Equ { value, line: line.clone() });
+        return Ok(());
+    }
+
+    /// Collects a macro definition up to its `.endm`
+    fn define_macro(&mut self, line: &Line, start: usize,
+                    iter: &mut dyn Iterator<Item = Line>) 
+        -> Result<(), CompileError> {
+        let name = match line.tokens.get(start + 1) {
+            Some(t) if t.ident().is_some() => {
+                t.ident().unwrap().to_ascii_uppercase()
+            },
+            Some(t) => return Err(line.error(
+                    CompileErrorType::UnexpectedToken(t.text()), t.col)),
+            None => return Err(line.error(CompileErrorType::UnexpectedEnd,
+                                          line.tokens[start].col))
+        };
+        let mut params = Vec::new();
+        for (i, t) in line.tokens[start + 2..].iter().enumerate() {
+            let is_param = i % 2 == 0;
+            match t.ident() {
+                Some(p) if is_param => params.push(String::from(p)),
+                _ if !is_param && t.is_punct(",") => {},
+                _ => return Err(line.error(
+                        CompileErrorType::UnexpectedToken(t.text()), t.col))
+            };
+        }
+        let mut body = Vec::new();
+        loop {
+            let next = match iter.next() {
+                Some(l) => l,
+                None => return Err(line.error(CompileErrorType::UnexpectedEnd,
+                                              line.tokens[start].col))
+            };
+            let (_, k) = split_labels(&next);
+            let word = next.tokens.get(k).and_then(|t| t.ident())
+                           .map(|s| s.to_ascii_uppercase());
+            match word.as_deref() {
+                Some(".ENDM") => break,
+                Some(".MACRO") => return Err(next.error(
+                        CompileErrorType::Unsupported(
+                            String::from("nested .macro")), 
+                        next.tokens[k].col)),
+                _ => body.push(next)
+            };
+        }
+        if self.macros.contains_key(&name) {
+            return Err(line.error(CompileErrorType::Redefinition(name),
+                                  line.tokens[start + 1].col));
+        }
+        self.macros.insert(name, MacroDef { params, body });
+        return Ok(());
+    }
+
+    /// Expands a macro invocation
+    fn expand(&mut self, line: &Line, start: usize, name: &str, 
+              depth: usize) -> Result<Vec<Line>, CompileError> {
+        let col = line.tokens[start].col;
+        if depth >= MAX_DEPTH {
+            return Err(line.error(CompileErrorType::RecursionLimit(
+                        String::from(name)), col));
+        }
+
+        //split the arguments at top-level commas
+        let mut args: Vec<Vec<Token>> = Vec::new();
+        let mut nesting = 0;
+        let mut current = Vec::new();
+        for t in line.tokens[start + 1..].iter() {
+            if t.is_punct("(") {
+                nesting += 1;
+            } else if t.is_punct(")") {
+                nesting -= 1;
+            }
+            if nesting == 0 && t.is_punct(",") {
+                args.push(std::mem::take(&mut current));
+            } else {
+                current.push(t.clone());
+            }
+        }
+        if !current.is_empty() || !args.is_empty() {
+            args.push(current);
+        }
+        let def = &self.macros[name];
+        if args.len() != def.params.len() {
+            return Err(line.error(CompileErrorType::InvalidOperand(
+                        format!("{} expects {} argument(s), found {}",
+                                name, def.params.len(), args.len())), col));
+        }
+
+        //substitute the arguments and rename local labels
+        if self.expansions == MAX_EXPANSIONS {
+            return Err(line.error(CompileErrorType::RecursionLimit(
+                        String::from(name)), col));
+        }
+        self.expansions += 1;
+        let suffix = self.expansions;
+        let mut lines = Vec::new();
+        for body_line in def.body.iter() {
+            let mut tokens = Vec::new();
+            for t in body_line.tokens.iter() {
+                match t.ident() {
+                    Some(id) if def.params.iter().any(|p| p == id) => {
+                        let i = def.params.iter().position(|p| p == id)
+                                    .unwrap();
+                        for a in args[i].iter() {
+                            tokens.push(Token { kind: a.kind.clone(), 
+                                                col: t.col });
+                        }
+                    },
+                    Some(id) if id.starts_with('@') => {
+                        tokens.push(Token {
+                            kind: TokenKind::Ident(format!("{}.{}", id, 
+                                                           suffix)),
+                            col: t.col
+                        });
+                    },
+                    _ => tokens.push(t.clone())
+                };
+            }
+            if tokens.len() > MAX_TOKENS {
+                return Err(line.error(CompileErrorType::RecursionLimit(
+                            String::from(name)), col));
+            }
+            lines.push(Line {
+                tokens,
+                file: body_line.file.clone(),
+                line: body_line.line,
+                text: body_line.text.clone()
+            });
+        }
+        return self.process(lines, depth + 1);
+    }
+
+    /// Reads and expands an included file
+    fn include(&mut self, line: &Line, start: usize, depth: usize)
+        -> Result<Vec<Line>, CompileError> {
+        let (path, col) = match line.tokens.get(start + 1) {
+            Some(Token { kind: TokenKind::Str(ref s), col }) => (s.clone(), *col),
+            Some(t) => return Err(line.error(
+                    CompileErrorType::UnexpectedToken(t.text()), t.col)),
+            None => return Err(line.error(CompileErrorType::UnexpectedEnd,
+                                          line.tokens[start].col))
+        };
+        if depth >= MAX_DEPTH {
+            return Err(line.error(CompileErrorType::RecursionLimit(path), 
+                                  col));
+        }
+
+        //resolve the path relative to the including file
+        let full = match Path::new(&line.file).parent() {
+            Some(dir) => dir.join(&path),
+            None => Path::new(&path).to_path_buf()
+        };
+        let src = match fs::read_to_string(&full) {
+            Ok(s) => s,
+            Err(_) => return Err(line.error(
+                    CompileErrorType::IncludeFailed(path), col))
+        };
+        let lines = tokenize(&src, &full.to_string_lossy())?;
+        return self.process(lines, depth + 1);
+    }
+}
+
+//unit tests
+#[cfg(test)]
+mod tests {
+    //import the preprocessor
+    use super::*;
+
+    //preprocesses source and returns the text of each line
+    fn expand(src: &str) -> Result<Vec<String>, CompileError> {
+        let mut pp = Preprocessor::new();
+        let lines = pp.process(tokenize(src, "t.s")?, 0)?;
+        return Ok(lines.iter().map(|l| {
+            l.tokens.iter().map(|t| t.text()).collect::<Vec<_>>().join(" ")
+        }).collect());
+    }
+
+    //this test checks conditional assembly
+    #[test]
+    fn test_conditionals() {
+        let src = ".equ DEBUG, 1\n.if DEBUG == 0\nCLS\n.else\nRET\n\
+                   .if DEBUG\nKEY V0\n.endif\n.endif";
+        assert_eq!(expand(src).unwrap(), vec!["RET", "KEY V0"]);
+    }
+
+    //this test checks macro expansion with local labels
+    #[test]
+    fn test_macro_expansion() {
+        let src = ".macro WAIT reg, n\n@top: ADD reg, -1\n\
+                   SKIP.EQ reg, n\nJMP @top\n.endm\n\
+                   start: WAIT V1, (2 + 1)\nWAIT V2, 0";
+        let lines = expand(src).unwrap();
+        assert_eq!(lines, vec!["start :",
+                               "@top.1 : ADD V1 , - 1",
+                               "SKIP.EQ V1 , ( 2 + 1 )",
+                               "JMP @top.1",
+                               "@top.2 : ADD V2 , - 1",
+                               "SKIP.EQ V2 , 0",
+                               "JMP @top.2"]);
+    }
+
+    //this test checks error reporting
+    #[test]
+    fn test_errors() {
+        let err = expand(".if 1\nCLS").unwrap_err();
+        assert_eq!(err.get_type(), CompileErrorType::UnexpectedEnd);
+        let err = expand(".macro M a\n.endm\nM").unwrap_err();
+        assert!(matches!(err.get_type(), CompileErrorType::InvalidOperand(_)));
+        let err = expand(".include \"missing.s\"").unwrap_err();
+        assert_eq!(err.get_type(), 
+                   CompileErrorType::IncludeFailed(String::from("missing.s")));
+        let err = expand(".equ A, B\n.equ B, A\n.if A\n.endif").unwrap_err();
+        assert!(matches!(err.get_type(), CompileErrorType::RecursionLimit(_)));
+        let err = expand(".macro M\nM\nM\n.endm\nM").unwrap_err();
+        assert_eq!(err.get_type(),
+                   CompileErrorType::RecursionLimit(String::from("M")));
+        let err = expand(".macro M a\nM a a\n.endm\nM V0").unwrap_err();
+        assert_eq!(err.get_type(),
+                   CompileErrorType::RecursionLimit(String::from("M")));
+    }
+}
+
+//end of file
@@ -0,0 +1,145 @@
+/*
+ * args.rs
+ * Parses the options of ch8 subcommands
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//usage statements
+use std::collections::HashMap;
+use super::Failure;
+
+/// The parsed arguments of a subcommand
+pub struct Args {
+    /// The positional arguments
+    positional: Vec<String>,
+
+    /// The options and their values
+    options: HashMap<String, String>
+}
+
+//implementation
+impl Args {
+    /// Parses the arguments of a subcommand
+    ///
+    /// # Arguments
+    ///
+    /// * `argv` - The arguments following the subcommand
+    /// * `options` - The options the subcommand accepts,
+    ///   each of which takes a value
+    /// * `count` - The number of positional arguments expected
+    ///
+    /// # Returns
+    ///
+    /// The parsed `Args`, or a usage `Failure`
+    pub fn parse(argv: &[String], options: &[&str], count: usize) 
+        -> Result<Args, Failure> {
+        let mut args = Args {
+            positional: Vec::new(),
+            options: HashMap::new()
+        };
+        let mut iter = argv.iter();
+        while let Some(arg) = iter.next() {
+            if arg.starts_with('-') && arg.len() > 1 {
+                if !options.contains(&arg.as_str()) {
+                    return Err(Failure::Usage(format!("unknown option '{}'",
+                                                      arg)));
+                }
+                let value = match iter.next() {
+                    Some(v) => v.clone(),
+                    None => return Err(Failure::Usage(format!(
+                                "option '{}' needs a value", arg)))
+                };
+                args.options.insert(arg.clone(), value);
+            } else {
+                args.positional.push(arg.clone());
+            }
+        }
+        if args.positional.len() != count {
+            return Err(Failure::Usage(format!(
+                        "expected {} argument(s), found {}", 
+                        count, args.positional.len())));
+        }
+        return Ok(args);
+    }
+
+    /// Gets a positional argument
+    ///
+    /// # Argument
+    ///
+    /// * `idx` - The index of the argument
+    ///
+    /// # Returns
+    ///
+    /// The argument's text
+    pub fn get(&self, idx: usize) -> &str {
+        return self.positional[idx].as_str();
+    }
+
+    /// Gets the value of an option
+    ///
+    /// # Argument
+    ///
+    /// * `name` - The option, including its dashes
+    ///
+    /// # Returns
+    ///
+    /// The option's value, if it was given
+    pub fn option(&self, name: &str) -> Option<&str> {
+        return self.options.get(name).map(|v| v.as_str());
+    }
+
+    /// Gets the value of a numeric option
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The option, including its dashes
+    /// * `default` - The value to use if the option is absent
+    ///
+    /// # Returns
+    ///
+    /// The option's value, or a usage `Failure` if it is not
+    /// a decimal or `0x`-prefixed hexadecimal number
+    pub fn number(&self, name: &str, default: u64) -> Result<u64, Failure> {
+        return match self.option(name) {
+            Some(text) => parse_number(text).ok_or_else(|| {
+                Failure::Usage(format!("bad number '{}' for '{}'", 
+                                       text, name))
+            }),
+            None => Ok(default)
+        };
+    }
+}
+
+/// Parses a decimal or `0x`-prefixed hexadecimal number
+///
+/// # Argument
+///
+/// * `text` - The text to parse
+///
+/// # Returns
+///
+/// The number, or `None` if the text is not a number
+pub fn parse_number(text: &str) -> Option<u64> {
+    return match text.strip_prefix("0x").or(text.strip_prefix("0X")) {
+        Some(hex) => u64::from_str_radix(hex, 16).ok(),
+        None => text.parse::<u64>().ok()
+    };
+}
+
+//end of file
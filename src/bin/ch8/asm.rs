@@ -0,0 +1,119 @@
+/*
+ * asm.rs
+ * Implements the ch8 asm subcommand
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//usage statements
+use std::fs;
+use std::path::Path;
+use ch8_isa::asm;
+use ch8_isa::octo;
+use ch8_isa::codegen::{Binary, OutputFormat, SymbolFormat};
+use ch8_isa::error::CompileError;
+use super::args::Args;
+use super::{io_failure, Failure};
+
+/// Formats a compile error as a `file:line:col` diagnostic
+///
+/// # Arguments
+///
+/// * `err` - The error to format
+/// * `path` - The source file, for errors that don't name one
+///
+/// # Returns
+///
+/// The diagnostic text
+pub fn diagnostic(err: &CompileError, path: &str) -> String {
+    return format!("{}:{}:{}: error: {}", err.get_file().unwrap_or(path),
+                   err.get_line(), err.get_col(), err.get_type());
+}
+
+/// Writes a binary to a file, replacing any existing file
+fn create(path: &str) -> Result<Binary, Failure> {
+    if Path::new(path).exists() {
+        fs::remove_file(path).map_err(|e| io_failure(path, e))?;
+    }
+    return Binary::new(path).map_err(|e| {
+        Failure::Error(format!("ch8: error: {}: {}", path, e))
+    });
+}
+
+/// Runs the `asm` subcommand
+///
+/// # Argument
+///
+/// * `argv` - The arguments following the subcommand
+///
+/// # Returns
+///
+/// `Ok` on success, or the reason the command failed
+pub fn run(argv: &[String]) -> Result<(), Failure> {
+    let args = Args::parse(argv, &["-o", "-f", "-l", "-s", "--sym-format"],
+                           1)?;
+    let path = args.get(0);
+
+    //pick the output format
+    let (format, ext) = match args.option("-f").unwrap_or("raw") {
+        "raw" => (OutputFormat::Raw, "ch8"),
+        "hex" => (OutputFormat::IntelHex(0x200), "hex"),
+        "srec" => (OutputFormat::SRecord(0x200), "srec"),
+        "c" => (OutputFormat::CArray(String::from("rom")), "h"),
+        "rust" => (OutputFormat::RustArray(String::from("ROM")), "rs"),
+        f => return Err(Failure::Usage(format!("unknown format '{}'", f)))
+    };
+    let sym_format = match args.option("--sym-format").unwrap_or("text") {
+        "text" => SymbolFormat::Text,
+        "sym" => SymbolFormat::Sym,
+        f => return Err(Failure::Usage(format!(
+                    "unknown symbol format '{}'", f)))
+    };
+    let default_out = Path::new(path).with_extension(ext);
+    let out = match args.option("-o") {
+        Some(o) => String::from(o),
+        None => default_out.to_string_lossy().into_owned()
+    };
+
+    //compile the source
+    let src = fs::read_to_string(path).map_err(|e| io_failure(path, e))?;
+    let image = if path.ends_with(".8o") {
+        octo::compile(&src)
+    } else {
+        asm::assemble(&src, path)
+    };
+    let image = image.map_err(|e| Failure::Error(diagnostic(&e, path)))?;
+
+    //and write the outputs
+    let bin_err = |p: &str, e| {
+        Failure::Error(format!("ch8: error: {}: {}", p, e))
+    };
+    let mut bin = create(&out)?;
+    image.write_to_binary(&mut bin).map_err(|e| bin_err(&out, e))?;
+    bin.write_formatted(&format).map_err(|e| bin_err(&out, e))?;
+    if let Some(listing) = args.option("-l") {
+        bin.write_listing(listing).map_err(|e| bin_err(listing, e))?;
+    }
+    if let Some(symbols) = args.option("-s") {
+        bin.write_symbols(symbols, sym_format)
+           .map_err(|e| bin_err(symbols, e))?;
+    }
+    return Ok(());
+}
+
+//end of file
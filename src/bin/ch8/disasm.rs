@@ -0,0 +1,85 @@
+/*
+ * disasm.rs
+ * Implements the ch8 disasm subcommand
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//usage statements
+use std::fs;
+use ch8_isa::codegen::{Binary, Instruction, PROGRAM_START};
+use super::args::Args;
+use super::{emit, io_failure, Failure};
+
+/// Reads a ROM file into a binary
+///
+/// # Argument
+///
+/// * `path` - The path of the ROM
+///
+/// # Returns
+///
+/// The decoded `Binary`, or the reason it could not be read
+pub fn load(path: &str) -> Result<Binary, Failure> {
+    let bytes = fs::read(path).map_err(|e| io_failure(path, e))?;
+    return Binary::from_bytes(path, &bytes).map_err(|e| {
+        Failure::Error(format!("ch8: error: {}: {}", path, e))
+    });
+}
+
+/// Runs the `disasm` subcommand
+///
+/// # Argument
+///
+/// * `argv` - The arguments following the subcommand
+///
+/// # Returns
+///
+/// `Ok` on success, or the reason the command failed
+pub fn run(argv: &[String]) -> Result<(), Failure> {
+    let args = Args::parse(argv, &["-o"], 1)?;
+    let mut bin = load(args.get(0))?;
+
+    //label the targets of jumps and calls within the ROM
+    let end = PROGRAM_START as u32 + bin.len() as u32;
+    let words: Vec<u16> = bin.get_data().chunks(2).filter(|c| c.len() == 2)
+                             .map(|c| (c[0] as u16) << 8 | c[1] as u16)
+                             .collect();
+    for word in words {
+        let (prefix, addr) = match Instruction::decode(word) {
+            Some(Instruction::JMP(ref d)) => ("L", d.get_addr()),
+            Some(Instruction::CALL(ref d)) => ("sub", d.get_addr()),
+            _ => continue
+        };
+        if addr >= PROGRAM_START && (addr as u32) < end {
+            bin.add_label(&format!("{}_{:03X}", prefix, addr), addr);
+        }
+    }
+
+    //and print or write the listing
+    return match args.option("-o") {
+        Some(out) => fs::write(out, bin.listing())
+                         .map_err(|e| io_failure(out, e)),
+        None => {
+            emit(&bin.listing());
+            Ok(())
+        }
+    };
+}
+
+//end of file
@@ -0,0 +1,75 @@
+/*
+ * hexdump.rs
+ * Implements the ch8 hexdump subcommand
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//usage statements
+use std::fs;
+use ch8_isa::codegen::PROGRAM_START;
+use super::args::Args;
+use super::{emit, io_failure, Failure};
+
+/// Formats bytes as a hex dump
+///
+/// # Argument
+///
+/// * `data` - The bytes to dump
+///
+/// # Returns
+///
+/// Rows of 16 bytes, each with its load address and
+/// the printable characters it contains
+pub fn hexdump(data: &[u8]) -> String {
+    let mut out = String::new();
+    for (i, row) in data.chunks(16).enumerate() {
+        let mut hex = String::new();
+        for (j, b) in row.iter().enumerate() {
+            if j == 8 {
+                hex.push(' ');
+            }
+            hex.push_str(&format!("{:02X} ", b));
+        }
+        let text: String = row.iter().map(|b| {
+            if b.is_ascii_graphic() || *b == b' ' { *b as char } else { '.' }
+        }).collect();
+        out.push_str(&format!("{:04X}  {:<49} |{}|\n", 
+                              PROGRAM_START as usize + i * 16, hex, text));
+    }
+    return out;
+}
+
+/// Runs the `hexdump` subcommand
+///
+/// # Argument
+///
+/// * `argv` - The arguments following the subcommand
+///
+/// # Returns
+///
+/// `Ok` on success, or the reason the command failed
+pub fn run(argv: &[String]) -> Result<(), Failure> {
+    let args = Args::parse(argv, &[], 1)?;
+    let path = args.get(0);
+    let data = fs::read(path).map_err(|e| io_failure(path, e))?;
+    emit(&hexdump(&data));
+    return Ok(());
+}
+
+//end of file
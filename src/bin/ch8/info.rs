@@ -0,0 +1,85 @@
+/*
+ * info.rs
+ * Implements the ch8 info subcommand
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//usage statements
+use std::collections::HashMap;
+use ch8_isa::codegen::{Instruction, PROGRAM_START};
+use super::args::Args;
+use super::disasm::load;
+use super::{emit, Failure};
+
+/// Runs the `info` subcommand
+///
+/// # Argument
+///
+/// * `argv` - The arguments following the subcommand
+///
+/// # Returns
+///
+/// `Ok` on success, or the reason the command failed
+pub fn run(argv: &[String]) -> Result<(), Failure> {
+    let args = Args::parse(argv, &[], 1)?;
+    let bin = load(args.get(0))?;
+
+    //size and entry point
+    let mut out = format!("size:  {} bytes\n", bin.len());
+    if bin.is_empty() {
+        emit(&out);
+        return Ok(());
+    }
+    out.push_str(&format!("range: 0x{:03X}-0x{:03X}\n", PROGRAM_START, 
+                          PROGRAM_START as u32 + bin.len() as u32 - 1));
+    let data = bin.get_data();
+    let first = if data.len() >= 2 {
+        Instruction::decode((data[0] as u16) << 8 | data[1] as u16)
+    } else {
+        None
+    };
+    out.push_str(&match first {
+        Some(Instruction::JMP(ref d)) => {
+            format!("entry: 0x{:03X} (jumps to 0x{:03X})\n", PROGRAM_START,
+                    d.get_addr())
+        },
+        _ => format!("entry: 0x{:03X}\n", PROGRAM_START)
+    });
+
+    //and the instruction histogram
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for (_, text) in bin.get_elements() {
+        let name = if text.starts_with('.') {
+            "(data)"
+        } else {
+            text.split(' ').next().unwrap()
+        };
+        *counts.entry(name).or_insert(0) += 1;
+    }
+    let mut counts: Vec<(&str, usize)> = counts.into_iter().collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+    out.push_str("instructions:\n");
+    for (name, count) in counts {
+        out.push_str(&format!("  {:<8} {}\n", name, count));
+    }
+    emit(&out);
+    return Ok(());
+}
+
+//end of file
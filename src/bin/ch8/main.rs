@@ -0,0 +1,159 @@
+/*
+ * main.rs
+ * Entry point for the ch8 command-line tool
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//the subcommands return explicitly, like the library
+#![allow(clippy::needless_return)]
+
+//crate import
+extern crate ch8_isa;
+
+//modules
+mod args;
+mod asm;
+mod disasm;
+mod info;
+mod hexdump;
+mod run;
+
+//usage statements
+use std::env;
+use std::io::Write;
+use std::process;
+
+/// The exit code for success
+const EXIT_OK: i32 = 0;
+
+/// The exit code for failed commands, such as
+/// source errors or unreadable files
+const EXIT_FAILURE: i32 = 1;
+
+/// The exit code for malformed command lines
+const EXIT_USAGE: i32 = 2;
+
+/// The usage message
+const USAGE: &str = "\
+usage: ch8 <command> [options]
+
+commands:
+  asm <source> [-o rom] [-f raw|hex|srec|c|rust] [-l listing]
+      [-s symbols] [--sym-format text|sym]
+                     assemble a source file (.8o files are Octo)
+  disasm <rom> [-o listing]
+                     disassemble a ROM into a listing
+  info <rom>         print the size, entry point and instruction
+                     histogram of a ROM
+  hexdump <rom>      print the bytes of a ROM
+  run <rom> [-n cycles] [-k keys] [-b addr,...] [--ipf n]
+      [--quirks vip|schip] [--seed n] [--random file]
+      [--record file] [--replay file] [--dump ascii|pbm|png] [-o file]
+      [--scale n] [--palette colours] [--gif file] [--wav file]
+      [--trace file] [--trace-format text|binary] [--profile n]
+      [--gdb host:port|unix:path]
+                     run a ROM headlessly until it halts, then
+                     print the framebuffer, registers and the
+                     n hottest addresses, or serve it to GDB
+
+exit codes: 0 on success, 1 on failure, 2 on bad usage";
+
+/// The reason a command failed
+pub enum Failure {
+    /// The command line was malformed
+    Usage(String),
+
+    /// The command could not be carried out; the
+    /// message is a complete diagnostic
+    Error(String)
+}
+
+/// Builds a failure for a file that could not be accessed
+///
+/// # Arguments
+///
+/// * `path` - The path of the file
+/// * `err` - The underlying error
+///
+/// # Returns
+///
+/// A `Failure::Error` describing the problem
+pub fn io_failure(path: &str, err: std::io::Error) -> Failure {
+    return Failure::Error(format!("ch8: error: {}: {}", path, err));
+}
+
+/// Writes command output to stdout. Errors such as a closed
+/// pipe are ignored, since there is nobody left to report them to.
+///
+/// # Argument
+///
+/// * `text` - The text to write
+pub fn emit(text: &str) {
+    let _ = std::io::stdout().write_all(text.as_bytes());
+}
+
+/// Runs a command line
+///
+/// # Argument
+///
+/// * `argv` - The arguments, without the program name
+///
+/// # Returns
+///
+/// The process exit code
+fn run(argv: &[String]) -> i32 {
+    let (cmd, rest) = match argv.split_first() {
+        Some((cmd, rest)) => (cmd.as_str(), rest),
+        None => {
+            eprintln!("{}", USAGE);
+            return EXIT_USAGE;
+        }
+    };
+    let result = match cmd {
+        "asm" => asm::run(rest),
+        "disasm" => disasm::run(rest),
+        "info" => info::run(rest),
+        "hexdump" => hexdump::run(rest),
+        "run" => run::run(rest),
+        "help" | "-h" | "--help" => {
+            emit(&format!("{}\n", USAGE));
+            Ok(())
+        },
+        _ => Err(Failure::Usage(format!("unknown command '{}'", cmd)))
+    };
+    return match result {
+        Ok(()) => EXIT_OK,
+        Err(Failure::Usage(msg)) => {
+            eprintln!("ch8: {}\n\n{}", msg, USAGE);
+            EXIT_USAGE
+        },
+        Err(Failure::Error(msg)) => {
+            eprintln!("{}", msg);
+            EXIT_FAILURE
+        }
+    };
+}
+
+/// Entry point of the program
+fn main() {
+    let argv: Vec<String> = env::args().skip(1).collect();
+    process::exit(run(&argv));
+}
+
+//end of file
@@ -0,0 +1,279 @@
+/*
+ * run.rs
+ * Implements the ch8 run subcommand
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//usage statements
+use std::fs;
+use std::net::TcpListener;
+#[cfg(unix)]
+use std::os::unix::net::UnixListener;
+use ch8_isa::vm::{Debugger, GdbStub, GifRecording, Image, KeyRecording,
+                  KeyScript, Machine, Palette, Quirks, ReplayRandom, Runner};
+use super::args::{parse_number, Args};
+use super::asm::diagnostic;
+use super::{emit, io_failure, Failure};
+
+/// The number of cycles run when no limit is given
+const DEFAULT_CYCLES: u64 = 1_000_000;
+
+/// Formats the registers of a machine
+///
+/// # Argument
+///
+/// * `m` - The machine
+///
+/// # Returns
+///
+/// Two lines: the special registers, then `V0` to `VF`
+pub fn registers(m: &Machine) -> String {
+    let mut out = format!("PC=0x{:03X} I=0x{:03X} SP={} DT={} ST={}\n",
+                          m.get_pc(), m.get_i(), m.get_stack().len(),
+                          m.get_delay(), m.get_sound());
+    let v: Vec<String> = (0..16).map(|r| format!("V{:X}={:02X}", r, 
+                                                 m.get_v(r))).collect();
+    out.push_str(&v.join(" "));
+    out.push('\n');
+    return out;
+}
+
+/// Serves a machine to a GDB client until it detaches
+///
+/// # Arguments
+///
+/// * `machine` - The machine to debug
+/// * `breakpoints` - The initial breakpoints
+/// * `addr` - The TCP address, or `unix:` and a socket path
+///
+/// # Returns
+///
+/// The machine once the client detaches, or the reason serving failed
+fn serve_gdb(machine: Machine, breakpoints: &[u16], 
+             addr: &str) -> Result<Machine, Failure> {
+    let mut debugger = Debugger::new(machine);
+    for bp in breakpoints {
+        debugger.add_breakpoint(*bp);
+    }
+    let mut stub = GdbStub::new(debugger);
+    let result = match addr.strip_prefix("unix:") {
+        #[cfg(unix)]
+        Some(path) => {
+            let listener = UnixListener::bind(path)
+                .map_err(|e| io_failure(path, e))?;
+            emit(&format!("listening on {}\n", addr));
+            let result = stub.accept_unix(&listener);
+            let _ = fs::remove_file(path);
+            result
+        },
+        #[cfg(not(unix))]
+        Some(_) => {
+            return Err(Failure::Usage(String::from(
+                        "Unix sockets are not supported here")));
+        },
+        None => {
+            let listener = TcpListener::bind(addr)
+                .map_err(|e| io_failure(addr, e))?;
+            emit(&format!("listening on {}\n", addr));
+            stub.accept_tcp(&listener)
+        }
+    };
+    result.map_err(|e| io_failure(addr, e))?;
+    return Ok(stub.get_debugger().get_machine().clone());
+}
+
+/// Runs the `run` subcommand
+///
+/// # Argument
+///
+/// * `argv` - The arguments following the subcommand
+///
+/// # Returns
+///
+/// `Ok` on success, or the reason the command failed
+pub fn run(argv: &[String]) -> Result<(), Failure> {
+    let args = Args::parse(argv, &["-n", "-k", "-b", "--ipf", "--quirks",
+                                   "--seed", "--dump", "-o", "--gdb",
+                                   "--trace", "--trace-format",
+                                   "--profile", "--random", "--record",
+                                   "--replay", "--wav", "--scale",
+                                   "--palette", "--gif"], 1)?;
+    let path = args.get(0);
+    let cycles = args.number("-n", DEFAULT_CYCLES)?;
+    let ipf = args.number("--ipf", ch8_isa::vm::DEFAULT_IPF)?;
+    if ipf == 0 {
+        return Err(Failure::Usage(String::from("'--ipf' must be positive")));
+    }
+    let quirks = match args.option("--quirks").unwrap_or("vip") {
+        "vip" => Quirks::vip(),
+        "schip" => Quirks::schip(),
+        q => return Err(Failure::Usage(format!("unknown quirks '{}'", q)))
+    };
+    let dump = args.option("--dump").unwrap_or("ascii");
+    match dump {
+        "ascii" | "pbm" => (),
+        "png" if args.option("-o").is_some() => (),
+        "png" => return Err(Failure::Usage(String::from(
+                    "'--dump png' needs '-o'"))),
+        d => return Err(Failure::Usage(format!("unknown dump '{}'", d)))
+    };
+    let scale = args.number("--scale", 1)? as usize;
+    if scale == 0 {
+        return Err(Failure::Usage(String::from("'--scale' must be positive")));
+    }
+    let palette = match args.option("--palette") {
+        Some(text) => Palette::parse(text).ok_or_else(|| {
+            Failure::Usage(format!("bad palette '{}'", text))
+        })?,
+        None => Palette::default()
+    };
+    let binary_trace = match args.option("--trace-format").unwrap_or("text") {
+        "text" => false,
+        "binary" => true,
+        t => return Err(Failure::Usage(format!("unknown trace format '{}'",
+                                               t)))
+    };
+
+    //set up the machine
+    let rom = fs::read(path).map_err(|e| io_failure(path, e))?;
+    let mut machine = Machine::new(quirks);
+    machine.set_seed(args.number("--seed", 0)?);
+    if let Some(file) = args.option("--random") {
+        let bytes = fs::read(file).map_err(|e| io_failure(file, e))?;
+        if bytes.is_empty() {
+            return Err(Failure::Error(format!(
+                        "ch8: error: {}: no random bytes to replay", file)));
+        }
+        machine.set_random(Box::new(ReplayRandom::new(&bytes)));
+    }
+    machine.load(&rom).map_err(|e| {
+        Failure::Error(format!("ch8: error: {}: {}", path, e))
+    })?;
+    let mut breakpoints = Vec::new();
+    if let Some(list) = args.option("-b") {
+        for addr in list.split(',') {
+            match parse_number(addr.trim()) {
+                Some(a) if a <= 0xFFFF => breakpoints.push(a as u16),
+                _ => return Err(Failure::Usage(format!(
+                            "bad breakpoint '{}'", addr)))
+            };
+        }
+    }
+
+    //hand the machine to a debugger if asked
+    if let Some(addr) = args.option("--gdb") {
+        let m = serve_gdb(machine, &breakpoints, addr)?;
+        emit(&registers(&m));
+        return Ok(());
+    }
+    let mut runner = Runner::new(machine);
+    runner.set_ipf(ipf);
+    for bp in breakpoints {
+        runner.add_breakpoint(bp);
+    }
+    if let Some(keys) = args.option("-k") {
+        let text = fs::read_to_string(keys).map_err(|e| io_failure(keys, e))?;
+        let script = KeyScript::parse(&text).map_err(|e| {
+            Failure::Error(diagnostic(&e, keys))
+        })?;
+        runner.set_key_script(script);
+    }
+
+    if let Some(file) = args.option("--replay") {
+        let data = fs::read(file).map_err(|e| io_failure(file, e))?;
+        let recording = KeyRecording::from_bytes(&data).map_err(|e| {
+            Failure::Error(format!("ch8: error: {}: {}", file, e))
+        })?;
+        runner.set_key_replay(recording);
+    }
+    if args.option("--record").is_some() {
+        runner.start_recording();
+    }
+    if args.option("--trace").is_some() {
+        runner.enable_trace();
+    }
+    if args.option("--profile").is_some() {
+        runner.enable_profile();
+    }
+    if args.option("--wav").is_some() {
+        runner.enable_audio(ch8_isa::vm::DEFAULT_SAMPLE_RATE);
+    }
+    if args.option("--gif").is_some() {
+        runner.start_gif(GifRecording::new(scale, palette));
+    }
+
+    //run it
+    let result = runner.run(cycles);
+    if let (Some(file), Some(trace)) = (args.option("--trace"), 
+                                        runner.take_trace()) {
+        let data = if binary_trace {
+            trace.to_binary()
+        } else {
+            trace.to_text().into_bytes()
+        };
+        fs::write(file, data).map_err(|e| io_failure(file, e))?;
+    }
+    if let (Some(file), Some(recording)) = (args.option("--record"),
+                                            runner.take_recording()) {
+        fs::write(file, recording.to_bytes())
+            .map_err(|e| io_failure(file, e))?;
+    }
+    if let (Some(file), Some(audio)) = (args.option("--wav"),
+                                        runner.get_audio()) {
+        audio.write_wav(file).map_err(|e| io_failure(file, e))?;
+    }
+    if let (Some(file), Some(mut gif)) = (args.option("--gif"),
+                                          runner.take_gif()) {
+        gif.add_frame(runner.get_machine().get_display());
+        gif.write_gif(file).map_err(|e| io_failure(file, e))?;
+    }
+    let m = runner.get_machine();
+
+    //and report the final state
+    let mut out = match result {
+        Ok(ref halt) => format!("halted: {} after {} cycles\n", halt,
+                                m.get_cycles()),
+        Err(ref e) => format!("failed: {} after {} cycles\n", e, 
+                              m.get_cycles())
+    };
+    let image = Image::new(m.get_display(), scale, palette);
+    let frame = match dump {
+        "pbm" => image.to_pbm().into_bytes(),
+        "png" => image.to_png(),
+        _ => m.get_display().to_ascii().into_bytes()
+    };
+    match args.option("-o") {
+        Some(file) => {
+            fs::write(file, frame).map_err(|e| io_failure(file, e))?;
+        },
+        None => out.push_str(&String::from_utf8_lossy(&frame))
+    };
+    out.push_str(&registers(m));
+    if let Some(profile) = runner.get_profile() {
+        let count = args.number("--profile", 0)? as usize;
+        out.push_str(&profile.report(m.get_memory(), count));
+    }
+    emit(&out);
+    return match result {
+        Ok(_) => Ok(()),
+        Err(e) => Err(Failure::Error(format!("ch8: error: {}: {}", path, e)))
+    };
+}
+
+//end of file
@@ -0,0 +1,65 @@
+/*
+ * arbitrary.rs
+ * Defines a proptest strategy for instructions
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+
+//usage statements
+use proptest::prelude::*;
+use super::Instruction;
+use super::super::data;
+
+//Arbitrary implementation
+impl Arbitrary for Instruction {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Instruction>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        return prop_oneof![
+            Just(Instruction::CLS),
+            Just(Instruction::RET),
+            any::<data::JmpData>().prop_map(Instruction::JMP),
+            any::<data::CallData>().prop_map(Instruction::CALL),
+            any::<data::SkipData>().prop_map(Instruction::SKIP),
+            any::<data::MovData>().prop_map(Instruction::MOV),
+            any::<data::AddData>().prop_map(Instruction::ADD),
+            any::<data::OrData>().prop_map(Instruction::OR),
+            any::<data::AndData>().prop_map(Instruction::AND),
+            any::<data::XorData>().prop_map(Instruction::XOR),
+            any::<data::SubData>().prop_map(Instruction::SUB),
+            any::<data::ShrData>().prop_map(Instruction::SHR),
+            any::<data::SubnData>().prop_map(Instruction::SUBN),
+            any::<data::ShlData>().prop_map(Instruction::SHL),
+            any::<data::JpcData>().prop_map(Instruction::JPC),
+            any::<data::RandData>().prop_map(Instruction::RAND),
+            any::<data::DrawData>().prop_map(Instruction::DRAW),
+            any::<data::GdlData>().prop_map(Instruction::GDL),
+            any::<data::KeyData>().prop_map(Instruction::KEY),
+            any::<data::SdlData>().prop_map(Instruction::SDL),
+            any::<data::SndData>().prop_map(Instruction::SND),
+            any::<data::SchData>().prop_map(Instruction::SCH),
+            any::<data::BcdData>().prop_map(Instruction::BCD),
+            any::<data::RdpData>().prop_map(Instruction::RDP),
+            any::<data::RldData>().prop_map(Instruction::RLD)
+        ].boxed();
+    }
+}
+
+//end of file
@@ -27,12 +27,34 @@ extern crate byteorder;
 use byteorder::WriteBytesExt;
 use byteorder::BigEndian;
 use super::Instruction;
+use super::Element;
 use super::CodeGen;
+use super::{OutputFormat, PatchPoint, SourceSpan, SymbolFormat};
+use std::collections::BTreeMap;
+use std::fs;
 use std::fs::File;
 use std::path::Path;
 use std::io::prelude::*;
 use super::super::error::{BinaryError, BinaryErrorType};
 
+/// The address at which Chip-8 programs are loaded
+pub const PROGRAM_START: u16 = 0x200;
+
+/// An element of a binary, as shown in its listing
+struct ListingEntry {
+    /// The offset of the element in the binary
+    offset: u16,
+
+    /// The length of the element (in bytes)
+    len: u16,
+
+    /// The mnemonic or data directive of the element
+    text: String,
+
+    /// Where the element came from in the source, if known
+    source: Option<SourceSpan>
+}
+
 /// A Chip-8 binary
 pub struct Binary {
     /// The raw bytes that make up
@@ -43,7 +65,19 @@ pub struct Binary {
     name: String,
 
     /// The length of the binary (in bytes)
-    length: u16 
+    length: u16,
+
+    /// The addresses of labels, keyed by name
+    labels: BTreeMap<String, u16>,
+
+    /// The instructions the program rewrites as it runs
+    patches: Vec<PatchPoint>,
+
+    /// The elements added to the binary, for its listing
+    entries: Vec<ListingEntry>,
+
+    /// The source span attached to elements as they are added
+    source: Option<SourceSpan>
 }
 
 //implementation
@@ -66,11 +100,76 @@ impl Binary {
             return Ok(Binary {
                 data: Vec::new(),
                 name: String::from(new_name),
-                length: 0
+                length: 0,
+                labels: BTreeMap::new(),
+                patches: Vec::new(),
+                entries: Vec::new(),
+                source: None
             });
         }
     }
 
+    /// Constructs a `Binary` instance from the bytes of an
+    /// existing ROM, decoding each word that holds an instruction
+    ///
+    /// # Arguments
+    ///
+    /// * `new_name` - The name of the binary file
+    /// * `bytes` - The bytes of the ROM
+    ///
+    /// # Returns
+    ///
+    /// A new `Binary` instance, wrapped in a `Result`
+    pub fn from_bytes(new_name: &str, bytes: &[u8]) 
+        -> Result<Binary, BinaryError> {
+        let mut bin = Binary {
+            data: Vec::new(),
+            name: String::from(new_name),
+            length: 0,
+            labels: BTreeMap::new(),
+            patches: Vec::new(),
+            entries: Vec::new(),
+            source: None
+        };
+        if bytes.len() > (0x10000 - PROGRAM_START as usize) {
+            return Err(BinaryError::new(BinaryErrorType::DataError,
+                                        new_name));
+        }
+        for chunk in bytes.chunks(2) {
+            if chunk.len() == 1 {
+                bin.add_byte(chunk[0])?;
+                continue;
+            }
+            let word = (chunk[0] as u16) << 8 | chunk[1] as u16;
+            match Instruction::decode(word) {
+                Some(instr) => bin.add_instruction(&instr)?,
+                None => bin.add_word(word)?
+            };
+        }
+        return Ok(bin);
+    }
+
+    /// Gets the bytes of the binary
+    ///
+    /// # Returns
+    ///
+    /// The bytes added to the binary so far
+    pub fn get_data(&self) -> &[u8] {
+        return self.data.as_slice();
+    }
+
+    /// Gets the elements of the binary
+    ///
+    /// # Returns
+    ///
+    /// The address and element text of each element added
+    /// to the binary, in order
+    pub fn get_elements(&self) -> Vec<(u16, &str)> {
+        return self.entries.iter().map(|e| {
+            (PROGRAM_START + e.offset, e.text.as_str())
+        }).collect();
+    }
+
     /// Gets the length of the binary
     /// 
     /// # Returns
@@ -80,6 +179,15 @@ impl Binary {
         return self.length;
     }
 
+    /// Gets whether the binary is empty
+    ///
+    /// # Returns
+    ///
+    /// `true` if no bytes have been added to the binary
+    pub fn is_empty(&self) -> bool {
+        return self.length == 0;
+    }
+
     /// Adds an instruction to the binary
     /// 
     /// # Argument
@@ -92,8 +200,64 @@ impl Binary {
     /// `Err<BinaryError>` if the operation fails
     pub fn add_instruction(&mut self, instr: &Instruction)
         -> Result<(), BinaryError> {
-        //add the instruction and return the result
-        return self.add_word(instr.gen_opcode());
+        //add the instruction and record it in the listing
+        self.write_word(instr.gen_opcode())?;
+        self.record(2, instr.to_string());
+        return Ok(());
+    }
+
+    /// Adds an instruction that the program rewrites as it runs,
+    /// labelling it and its operand for the code that patches it
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the patch point
+    /// * `instr` - The `Instruction` to add
+    ///
+    /// # Returns
+    ///
+    /// The patch point of the instruction if it was added
+    /// successfully, `Err<BinaryError>` if the operation fails
+    /// or either label is already defined
+    pub fn add_patched_instruction(&mut self, name: &str,
+                                   instr: &Instruction)
+        -> Result<PatchPoint, BinaryError> {
+        let addr = match PROGRAM_START.checked_add(self.length) {
+            Some(addr) => addr,
+            None => return Err(BinaryError::new(BinaryErrorType::DataError,
+                                                self.name.as_str()))
+        };
+        let point = PatchPoint::new(name, addr, instr);
+        if self.labels.contains_key(name) ||
+           self.labels.contains_key(&point.get_operand_name()) {
+            return Err(BinaryError::new(BinaryErrorType::LabelExists,
+                                        self.name.as_str()));
+        }
+        self.add_instruction(instr)?;
+        self.add_label(name, point.get_addr());
+        self.add_label(&point.get_operand_name(),
+                       point.get_operand_addr());
+        self.add_patch(point.clone());
+        return Ok(point);
+    }
+
+    /// Adds a program image element to the binary
+    /// 
+    /// # Argument
+    ///
+    /// * `elem` - The `Element` to add
+    ///
+    /// # Returns
+    ///
+    /// `Ok` if the element was added successfully,
+    /// `Err<BinaryError>` if the operation fails
+    pub fn add_element(&mut self, elem: &Element) 
+        -> Result<(), BinaryError> {
+        return match *elem {
+            Element::Instr(ref instr) => self.add_instruction(instr),
+            Element::Word(word) => self.add_word(word),
+            Element::Byte(byte) => self.add_byte(byte)
+        };
     }
 
     /// Adds a 16-bit word to the binary 
@@ -107,6 +271,16 @@ impl Binary {
     /// `Ok` if the word was written successfully,
     /// `Err<BinaryError>` if the operation fails
     pub fn add_word(&mut self, word: u16) -> Result<(), BinaryError> {
+        self.write_word(word)?;
+        self.record(2, format!(".word 0x{:04X}", word));
+        return Ok(());
+    }
+
+    /// Writes a 16-bit word to the binary's data
+    fn write_word(&mut self, word: u16) -> Result<(), BinaryError> {
+        //make sure the word fits in memory
+        self.reserve(2)?;
+
         //attempt to add the word
         let res = self.data.write_u16::<BigEndian>(word);
 
@@ -131,6 +305,33 @@ impl Binary {
     /// `Ok` if the byte was written successfully,
     /// `Err<BinaryError>` if the operation fails
     pub fn add_byte(&mut self, byte: u8) -> Result<(), BinaryError> {
+        self.write_byte(byte)?;
+        self.record(1, format!(".byte 0x{:02X}", byte));
+        return Ok(());
+    }
+
+    /// Adds zero bytes to the binary without listing them
+    ///
+    /// # Argument
+    ///
+    /// * `count` - The number of bytes to add
+    ///
+    /// # Returns
+    ///
+    /// `Ok` if the bytes were written successfully,
+    /// `Err<BinaryError>` if the operation fails
+    pub fn add_padding(&mut self, count: usize) -> Result<(), BinaryError> {
+        for _ in 0..count {
+            self.write_byte(0x00)?;
+        }
+        return Ok(());
+    }
+
+    /// Writes a byte to the binary's data
+    fn write_byte(&mut self, byte: u8) -> Result<(), BinaryError> {
+        //make sure the byte fits in memory
+        self.reserve(1)?;
+
         //attempt to write the byte
         let res = self.data.write_u8(byte);
 
@@ -144,6 +345,228 @@ impl Binary {
         }
     }
 
+    /// Checks that a number of bytes can be added without the
+    /// binary running past the end of memory
+    fn reserve(&self, count: u16) -> Result<(), BinaryError> {
+        let end = self.length.checked_add(count)
+            .and_then(|len| PROGRAM_START.checked_add(len - 1));
+        return match end {
+            Some(_) => Ok(()),
+            None => Err(BinaryError::new(BinaryErrorType::DataError,
+                                         self.name.as_str()))
+        };
+    }
+
+    /// Adds a label to the binary's symbols
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the label
+    /// * `addr` - The address of the label
+    pub fn add_label(&mut self, name: &str, addr: u16) {
+        self.labels.insert(String::from(name), addr);
+    }
+
+    /// Gets the labels of the binary
+    ///
+    /// # Returns
+    ///
+    /// A map from label names to addresses
+    pub fn get_labels(&self) -> &BTreeMap<String, u16> {
+        return &self.labels;
+    }
+
+    /// Marks an instruction of the binary as one
+    /// the program rewrites as it runs
+    ///
+    /// # Argument
+    ///
+    /// * `point` - The patch point of the instruction
+    pub fn add_patch(&mut self, point: PatchPoint) {
+        self.patches.push(point);
+    }
+
+    /// Gets the instructions the program rewrites
+    ///
+    /// # Returns
+    ///
+    /// The patch points of the binary, in the order they were added
+    pub fn get_patches(&self) -> &[PatchPoint] {
+        return &self.patches;
+    }
+
+    /// Sets the source span attached to the elements 
+    /// added to the binary from now on
+    ///
+    /// # Argument
+    ///
+    /// * `span` - The source span, or `None` if unknown
+    pub fn set_source(&mut self, span: Option<SourceSpan>) {
+        self.source = span;
+    }
+
+    /// Records an element in the listing
+    fn record(&mut self, len: u16, text: String) {
+        self.entries.push(ListingEntry {
+            offset: self.length - len,
+            len,
+            text,
+            source: self.source.clone()
+        });
+    }
+
+    /// Generates a listing of the binary
+    ///
+    /// # Returns
+    ///
+    /// One `address | bytes | mnemonic | source line` row per 
+    /// element, preceded by rows for the labels at its address
+    pub fn listing(&self) -> String {
+        let mut out = String::new();
+        let mut labels: Vec<(u16, &str)> = self.labels.iter()
+            .map(|(name, addr)| (*addr, name.as_str())).collect();
+        labels.sort();
+        let mut next_label = 0;
+        let mut last_source = None;
+        for e in self.entries.iter() {
+            let addr = PROGRAM_START + e.offset;
+
+            //label rows
+            while next_label < labels.len() && labels[next_label].0 <= addr {
+                let (at, name) = labels[next_label];
+                out.push_str(&format!("{:04X} | {:<5} | {}:\n", at, "", 
+                                      name));
+                next_label += 1;
+            }
+
+            //the element, with its source line if it starts a new one
+            let start = e.offset as usize;
+            let bytes: Vec<String> = self.data[start..start + e.len as usize]
+                .iter().map(|b| format!("{:02X}", b)).collect();
+            let row = format!("{:04X} | {:<5} | {}", addr, bytes.join(" "),
+                              e.text);
+            match e.source {
+                Some(ref span) if last_source != Some(span) => {
+                    out.push_str(&format!("{:<35} | {}", row, span));
+                },
+                _ => out.push_str(&row)
+            };
+            last_source = e.source.as_ref();
+            out.push('\n');
+        }
+        for (at, name) in labels[next_label..].iter() {
+            out.push_str(&format!("{:04X} | {:<5} | {}:\n", at, "", name));
+        }
+        return out;
+    }
+
+    /// Generates a symbol file for the binary
+    ///
+    /// # Argument
+    ///
+    /// * `format` - The format of the symbol file
+    ///
+    /// # Returns
+    ///
+    /// The labels of the binary ordered by address
+    pub fn symbols(&self, format: SymbolFormat) -> String {
+        let mut labels: Vec<(u16, &str)> = self.labels.iter()
+            .map(|(name, addr)| (*addr, name.as_str())).collect();
+        labels.sort();
+        let mut out = String::new();
+        if format == SymbolFormat::Sym {
+            out.push_str("[labels]\n");
+        }
+        for (addr, name) in labels {
+            let row = match format {
+                SymbolFormat::Text => format!("{} = 0x{:04X}\n", name, addr),
+                SymbolFormat::Sym => format!("00:{:04X} {}\n", addr, name)
+            };
+            out.push_str(&row);
+        }
+        return out;
+    }
+
+    /// Writes the listing of the binary to a file
+    ///
+    /// # Argument
+    ///
+    /// * `path` - The path of the listing file
+    ///
+    /// # Returns
+    ///
+    /// `Ok` if the listing was written successfully,
+    /// `Err<BinaryError>` if the operation fails
+    pub fn write_listing(&self, path: &str) -> Result<(), BinaryError> {
+        return match fs::write(path, self.listing()) {
+            Ok(_) => Ok(()),
+            Err(_) => Err(BinaryError::new(BinaryErrorType::FileError, path))
+        };
+    }
+
+    /// Writes the symbols of the binary to a file
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the symbol file
+    /// * `format` - The format of the symbol file
+    ///
+    /// # Returns
+    ///
+    /// `Ok` if the symbols were written successfully,
+    /// `Err<BinaryError>` if the operation fails
+    pub fn write_symbols(&self, path: &str, format: SymbolFormat) 
+        -> Result<(), BinaryError> {
+        return match fs::write(path, self.symbols(format)) {
+            Ok(_) => Ok(()),
+            Err(_) => Err(BinaryError::new(BinaryErrorType::FileError, path))
+        };
+    }
+
+    /// Encodes the binary in a file format
+    ///
+    /// # Argument
+    ///
+    /// * `format` - The format to encode the binary in
+    ///
+    /// # Returns
+    ///
+    /// The contents of a file holding the binary, or a `BinaryError`
+    /// if the binary does not fit in the addresses of the format
+    pub fn encode(&self, format: &OutputFormat) 
+        -> Result<Vec<u8>, BinaryError> {
+        return format.encode(self.data.as_slice()).ok_or_else(|| {
+            return BinaryError::new(BinaryErrorType::DataError,
+                                    self.name.as_str());
+        });
+    }
+
+    /// Writes the entire binary to a file in a given format
+    ///
+    /// # Argument
+    ///
+    /// * `format` - The format to write the binary in
+    ///
+    /// # Returns
+    ///
+    /// A `Result` that on a success contains the number of bytes
+    /// written, and that on a failure contains a `BinaryError` object
+    pub fn write_formatted(&mut self, format: &OutputFormat)
+        -> Result<usize, BinaryError> {
+        //ensure that the binary has an even number of bytes
+        if !self.length.is_multiple_of(2) {
+            self.add_padding(1)?;
+        }
+
+        //and write the encoded binary
+        let contents = self.encode(format)?;
+        return match fs::write(self.name.as_str(), &contents) {
+            Ok(_) => Ok(contents.len()),
+            Err(_) => Err(BinaryError::new(BinaryErrorType::FileError,
+                                           self.name.as_str()))
+        };
+    }
+
     /// Writes the entire binary to a file
     /// 
     /// # Returns
@@ -155,6 +578,7 @@ impl Binary {
     ///
     /// This method will panic if the binary file handle 
     /// fails to be created successfully. 
+    #[allow(clippy::manual_is_multiple_of, clippy::unnecessary_unwrap)]
     pub fn write_to_file(&mut self) -> Result<usize, BinaryError> {
         //ensure that the binary has an even number of bytes
         if (self.length % 2) != 0 {
@@ -182,3 +606,93 @@ impl Binary {
     }
 }
 
+//unit tests
+#[cfg(test)]
+mod tests {
+    //import the Binary struct
+    use super::*;
+    use super::super::super::data;
+
+    //this test checks listing generation
+    #[test]
+    fn test_listing() {
+        let mut bin = Binary::new("test_listing_unwritten.c8").unwrap();
+        bin.add_label("start", 0x200);
+        bin.add_label("data", 0x202);
+        bin.set_source(Some(SourceSpan::new(1, "start: CLS")));
+        bin.add_instruction(&Instruction::CLS).unwrap();
+        bin.set_source(Some(SourceSpan::new(2, "data: .byte 1, 2")));
+        bin.add_byte(1).unwrap();
+        bin.add_byte(2).unwrap();
+        bin.set_source(None);
+        bin.add_padding(2).unwrap();
+        bin.add_word(0x00EE).unwrap();
+        assert_eq!(bin.listing(), 
+                   "0200 |       | start:\n\
+                    0200 | 00 E0 | CLS                  | 1: start: CLS\n\
+                    0202 |       | data:\n\
+                    0202 | 01    | .byte 0x01           | 2: data: .byte 1, 2\n\
+                    0203 | 02    | .byte 0x02\n\
+                    0206 | 00 EE | .word 0x00EE\n");
+    }
+
+    //this test checks decoding an existing ROM
+    #[test]
+    fn test_from_bytes() {
+        let bin = Binary::from_bytes("rom.c8", &[0x00, 0xE0, 0x01, 0x23, 
+                                                  0xA2, 0x00, 0xFF]).unwrap();
+        assert_eq!(bin.len(), 7);
+        assert_eq!(bin.get_elements(), vec![(0x200, "CLS"), 
+                                            (0x202, ".word 0x0123"),
+                                            (0x204, "MOV I, 0x200"),
+                                            (0x206, ".byte 0xFF")]);
+    }
+
+    //this test checks that a binary cannot run past the end of memory
+    #[test]
+    fn test_full_binary() {
+        let mut bin = Binary::from_bytes("full.c8", &[0; 0xFDFF]).unwrap();
+        assert!(bin.add_word(0x00E0).is_err());
+        bin.add_byte(0xFF).unwrap();
+        assert_eq!(bin.get_elements().last(), Some(&(0xFFFF, ".byte 0xFF")));
+        assert!(bin.listing().ends_with("FFFF | FF    | .byte 0xFF\n"));
+        assert!(bin.add_byte(0xFF).is_err());
+        let err = bin.add_patched_instruction("late", &Instruction::CLS)
+            .unwrap_err();
+        assert!(matches!(err.get_type(), BinaryErrorType::DataError));
+        assert!(Binary::from_bytes("big.c8", &[0; 0xFE01]).is_err());
+    }
+
+    //this test checks labelling instructions that are rewritten
+    #[test]
+    fn test_patched_instruction() {
+        let mut bin = Binary::new("test_patched_unwritten.c8").unwrap();
+        bin.add_instruction(&Instruction::CLS).unwrap();
+        let jmp = Instruction::JMP(data::JmpData::new(0x200));
+        let point = bin.add_patched_instruction("target", &jmp).unwrap();
+        assert_eq!(point.get_addr(), 0x202);
+        assert_eq!(bin.get_patches(), &[point]);
+        assert_eq!(bin.symbols(SymbolFormat::Text),
+                   "target = 0x0202\ntarget.operand = 0x0203\n");
+        assert_eq!(bin.get_data(), &[0x00, 0xE0, 0x12, 0x00]);
+        let err = bin.add_patched_instruction("target", &jmp).unwrap_err();
+        assert!(matches!(err.get_type(), BinaryErrorType::LabelExists));
+        bin.add_label("other.operand", 0x300);
+        assert!(bin.add_patched_instruction("other", &jmp).is_err());
+        assert_eq!(bin.len(), 4);
+    }
+
+    //this test checks symbol file generation
+    #[test]
+    fn test_symbols() {
+        let mut bin = Binary::new("test_symbols_unwritten.c8").unwrap();
+        bin.add_label("sprite", 0x300);
+        bin.add_label("main", 0x200);
+        assert_eq!(bin.symbols(SymbolFormat::Text), 
+                   "main = 0x0200\nsprite = 0x0300\n");
+        assert_eq!(bin.symbols(SymbolFormat::Sym), 
+                   "[labels]\n00:0200 main\n00:0300 sprite\n");
+    }
+}
+
+//end of file
@@ -0,0 +1,447 @@
+/*
+ * effects.rs
+ * Defines what an instruction reads, writes and affects
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+
+//usage statements
+use super::{CodeGen, Instruction};
+use super::super::data::Register;
+use super::super::vm::Quirks;
+
+/// The number of register IDs, `V0` to `VF` and then `I`
+pub(crate) const REGISTER_COUNT: u8 = 0x11;
+
+/// What an instruction touches when it executes: the registers
+/// it reads and writes, including `VF` set as a flag, the bytes
+/// of memory it accesses from `I`, and the parts of the machine
+/// beyond registers and memory that it affects
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Effects {
+    /// The registers read, one bit per register ID
+    reads: u32,
+
+    /// The registers written, one bit per register ID
+    writes: u32,
+
+    /// Whether `VF` is written as a flag
+    flag: bool,
+
+    /// The amount `I` is incremented by
+    i_increment: u16,
+
+    /// The bytes read from memory starting at `I`
+    memory_read: u16,
+
+    /// The bytes written to memory starting at `I`
+    memory_write: u16,
+
+    /// Whether the instruction may change the flow of control
+    control: bool,
+
+    /// Whether the instruction reads or sets a timer
+    timers: bool,
+
+    /// Whether the instruction reads the keypad
+    keypad: bool,
+
+    /// Whether the instruction changes the display
+    display: bool
+}
+
+//implementation
+impl Effects {
+    /// Finds the effects of an instruction
+    ///
+    /// # Arguments
+    ///
+    /// * `instr` - The instruction
+    /// * `quirks` - The interpreter behaviours to assume
+    ///
+    /// # Returns
+    ///
+    /// The `Effects` of the instruction
+    pub(crate) fn of(instr: &Instruction, quirks: Quirks) -> Effects {
+        let opcode = instr.gen_opcode();
+        let x = ((opcode >> 8) & 0xF) as u8;
+        let y = ((opcode >> 4) & 0xF) as u8;
+        let n = opcode & 0xF;
+        let mut e = Effects::default();
+        match *instr {
+            Instruction::CLS => e.display = true,
+            Instruction::RET | Instruction::JMP(_) |
+                Instruction::CALL(_) => e.control = true,
+            Instruction::SKIP(_) => {
+                e.control = true;
+                e.read(x);
+                match opcode >> 12 {
+                    0x5 | 0x9 => e.read(y),
+                    0xE => e.keypad = true,
+                    _ => {}
+                };
+            },
+            Instruction::MOV(_) => match opcode >> 12 {
+                0xA => e.write(Register::I.to_id()),
+                0x6 => e.write(x),
+                _ => {
+                    e.read(y);
+                    e.write(x);
+                }
+            },
+            Instruction::ADD(_) => match opcode >> 12 {
+                0x7 => {
+                    e.read(x);
+                    e.write(x);
+                },
+                0xF => {
+                    e.read(x);
+                    e.read(Register::I.to_id());
+                    e.write(Register::I.to_id());
+                },
+                _ => {
+                    e.read(x);
+                    e.read(y);
+                    e.write(x);
+                    e.set_flag();
+                }
+            },
+            Instruction::OR(_) | Instruction::AND(_) |
+                Instruction::XOR(_) => {
+                e.read(x);
+                e.read(y);
+                e.write(x);
+                if quirks.vf_reset {
+                    e.set_flag();
+                }
+            },
+            Instruction::SUB(_) | Instruction::SUBN(_) => {
+                e.read(x);
+                e.read(y);
+                e.write(x);
+                e.set_flag();
+            },
+            Instruction::SHR(_) | Instruction::SHL(_) => {
+                e.read(if quirks.shift_vy { y } else { x });
+                e.write(x);
+                e.set_flag();
+            },
+            Instruction::JPC(_) => {
+                e.control = true;
+                e.read(if quirks.jump_vx { x } else { 0 });
+            },
+            Instruction::RAND(_) => e.write(x),
+            Instruction::DRAW(_) => {
+                e.read(x);
+                e.read(y);
+                e.read(Register::I.to_id());
+                e.memory_read = match n {
+                    0 if quirks.large_sprites => 32,
+                    _ => n
+                };
+                e.set_flag();
+                e.display = true;
+            },
+            Instruction::GDL(_) => {
+                e.write(x);
+                e.timers = true;
+            },
+            Instruction::KEY(_) => {
+                e.write(x);
+                e.keypad = true;
+            },
+            Instruction::SDL(_) | Instruction::SND(_) => {
+                e.read(x);
+                e.timers = true;
+            },
+            Instruction::SCH(_) => {
+                e.read(x);
+                e.write(Register::I.to_id());
+            },
+            Instruction::BCD(_) => {
+                e.read(x);
+                e.read(Register::I.to_id());
+                e.memory_write = 3;
+            },
+            Instruction::RDP(_) | Instruction::RLD(_) => {
+                let count = x as u16 + 1;
+                let store = matches!(*instr, Instruction::RDP(_));
+                for r in 0..=x {
+                    if store {
+                        e.read(r);
+                    } else {
+                        e.write(r);
+                    }
+                }
+                e.read(Register::I.to_id());
+                if store {
+                    e.memory_write = count;
+                } else {
+                    e.memory_read = count;
+                }
+                if quirks.load_store_inc_i {
+                    e.write(Register::I.to_id());
+                    e.i_increment = count;
+                }
+            }
+        };
+        return e;
+    }
+
+    /// Marks a register as read
+    fn read(&mut self, id: u8) {
+        self.reads |= 1 << id;
+    }
+
+    /// Marks a register as written
+    fn write(&mut self, id: u8) {
+        self.writes |= 1 << id;
+    }
+
+    /// Marks `VF` as written with a flag
+    fn set_flag(&mut self) {
+        self.write(0xF);
+        self.flag = true;
+    }
+
+    /// Lists the registers whose bits are set in a mask
+    pub(crate) fn registers(mask: u32) -> Vec<Register> {
+        return (0..REGISTER_COUNT).filter(|id| mask & (1 << id) != 0)
+                                  .filter_map(Register::from_id)
+                                  .collect();
+    }
+
+    /// Gets whether a register is read
+    ///
+    /// # Argument
+    ///
+    /// * `reg` - The register
+    ///
+    /// # Returns
+    ///
+    /// Whether the instruction reads the register
+    pub fn reads(&self, reg: &Register) -> bool {
+        return self.reads & (1 << reg.to_id()) != 0;
+    }
+
+    /// Gets whether a register is written
+    ///
+    /// # Argument
+    ///
+    /// * `reg` - The register
+    ///
+    /// # Returns
+    ///
+    /// Whether the instruction writes the register
+    pub fn writes(&self, reg: &Register) -> bool {
+        return self.writes & (1 << reg.to_id()) != 0;
+    }
+
+    /// Gets whether `VF` is written as a flag, such as a carry or
+    /// a collision, rather than with a value the program chose
+    ///
+    /// # Returns
+    ///
+    /// Whether the instruction sets the flag
+    pub fn sets_flag(&self) -> bool {
+        return self.flag;
+    }
+
+    /// Gets the registers read
+    ///
+    /// # Returns
+    ///
+    /// The registers read, in ID order
+    pub fn get_reads(&self) -> Vec<Register> {
+        return Effects::registers(self.reads);
+    }
+
+    /// Gets the registers written
+    ///
+    /// # Returns
+    ///
+    /// The registers written, in ID order
+    pub fn get_writes(&self) -> Vec<Register> {
+        return Effects::registers(self.writes);
+    }
+
+    /// Gets the registers read as a mask
+    ///
+    /// # Returns
+    ///
+    /// A mask with bit `1 << id` set for each register read
+    pub fn get_read_mask(&self) -> u32 {
+        return self.reads;
+    }
+
+    /// Gets the registers written as a mask
+    ///
+    /// # Returns
+    ///
+    /// A mask with bit `1 << id` set for each register written
+    pub fn get_write_mask(&self) -> u32 {
+        return self.writes;
+    }
+
+    /// Gets the amount `I` is incremented by
+    ///
+    /// # Returns
+    ///
+    /// The increment, which is `0` unless `RDP` or `RLD`
+    /// run with the `load_store_inc_i` quirk
+    pub fn get_i_increment(&self) -> u16 {
+        return self.i_increment;
+    }
+
+    /// Gets the bytes read from memory
+    ///
+    /// # Returns
+    ///
+    /// The number of bytes read starting at `I`
+    pub fn get_memory_read(&self) -> u16 {
+        return self.memory_read;
+    }
+
+    /// Gets the bytes written to memory
+    ///
+    /// # Returns
+    ///
+    /// The number of bytes written starting at `I`
+    pub fn get_memory_write(&self) -> u16 {
+        return self.memory_write;
+    }
+
+    /// Gets whether the instruction may change the flow of
+    /// control, by jumping, calling, returning or skipping
+    ///
+    /// # Returns
+    ///
+    /// Whether control flow is affected
+    pub fn affects_control(&self) -> bool {
+        return self.control;
+    }
+
+    /// Gets whether the instruction reads or sets a timer
+    ///
+    /// # Returns
+    ///
+    /// Whether the delay or sound timer is affected
+    pub fn affects_timers(&self) -> bool {
+        return self.timers;
+    }
+
+    /// Gets whether the instruction reads the keypad
+    ///
+    /// # Returns
+    ///
+    /// Whether the keypad is used
+    pub fn affects_keypad(&self) -> bool {
+        return self.keypad;
+    }
+
+    /// Gets whether the instruction changes the display
+    ///
+    /// # Returns
+    ///
+    /// Whether the display is affected
+    pub fn affects_display(&self) -> bool {
+        return self.display;
+    }
+}
+
+//unit tests
+#[cfg(test)]
+mod tests {
+    //import the Effects struct
+    use super::*;
+    use super::super::super::data::*;
+
+    //this test checks the registers of arithmetic
+    #[test]
+    fn test_arithmetic() {
+        let vip = Quirks::vip();
+        let add = Instruction::ADD(AddData::with_register(Register::V1,
+                                                          Register::V2));
+        let e = add.effects(vip);
+        assert_eq!(e.get_reads(), vec![Register::V1, Register::V2]);
+        assert_eq!(e.get_writes(), vec![Register::V1, Register::VF]);
+        let add = Instruction::ADD(AddData::with_constant(Register::V1, 3));
+        assert!(!add.effects(vip).writes(&Register::VF));
+        assert!(!add.effects(vip).sets_flag());
+        let or = Instruction::OR(OrData::new(Register::V1, Register::V2));
+        assert!(or.effects(vip).writes(&Register::VF));
+        assert!(or.effects(vip).sets_flag());
+        assert!(!or.effects(Quirks::schip()).writes(&Register::VF));
+        assert!(!or.effects(Quirks::schip()).sets_flag());
+        let shr = Instruction::SHR(ShrData::with_source(Register::V1,
+                                                        Register::V2));
+        assert_eq!(shr.effects(vip).get_reads(), vec![Register::V2]);
+        assert_eq!(shr.effects(Quirks::schip()).get_reads(),
+                   vec![Register::V1]);
+    }
+
+    //this test checks memory and the index register
+    #[test]
+    fn test_memory() {
+        let rdp = Instruction::RDP(RdpData::new(Register::V3));
+        let e = rdp.effects(Quirks::vip());
+        assert_eq!(e.get_reads(), vec![Register::V0, Register::V1,
+                                       Register::V2, Register::V3,
+                                       Register::I]);
+        assert_eq!(e.get_memory_write(), 4);
+        assert_eq!(e.get_i_increment(), 4);
+        assert!(e.writes(&Register::I));
+        let e = rdp.effects(Quirks::schip());
+        assert_eq!(e.get_i_increment(), 0);
+        assert!(!e.writes(&Register::I));
+        let rld = Instruction::RLD(RldData::new(Register::V1));
+        let e = rld.effects(Quirks::schip());
+        assert_eq!(e.get_writes(), vec![Register::V0, Register::V1]);
+        assert_eq!(e.get_memory_read(), 2);
+        let draw = Instruction::DRAW(DrawData::new(Register::V0,
+                                                   Register::V1, 0));
+        let e = draw.effects(Quirks::schip());
+        assert_eq!(e.get_memory_read(), 32);
+        assert!(e.affects_display() && e.writes(&Register::VF));
+        assert_eq!(draw.effects(Quirks::vip()).get_memory_read(), 0);
+        let bcd = Instruction::BCD(BcdData::new(Register::V2));
+        assert_eq!(bcd.effects(Quirks::vip()).get_memory_write(), 3);
+    }
+
+    //this test checks control flow, timers and the keypad
+    #[test]
+    fn test_side_effects() {
+        let vip = Quirks::vip();
+        let skip = Instruction::SKIP(SkipData::with_key(Register::V4,
+                                                        SkipType::KeyUp));
+        let e = skip.effects(vip);
+        assert!(e.affects_control() && e.affects_keypad());
+        assert_eq!(e.get_reads(), vec![Register::V4]);
+        let jpc = Instruction::JPC(JpcData::new(0x300));
+        assert_eq!(jpc.effects(vip).get_reads(), vec![Register::V0]);
+        assert_eq!(jpc.effects(Quirks::schip()).get_reads(),
+                   vec![Register::V3]);
+        let sdl = Instruction::SDL(SdlData::new(Register::V0));
+        assert!(sdl.effects(vip).affects_timers());
+        assert_eq!(Instruction::CLS.effects(vip).get_read_mask(), 0);
+        assert!(Instruction::RET.effects(vip).affects_control());
+    }
+}
+
+//end of file
@@ -0,0 +1,115 @@
+/*
+ * element.rs
+ * Enumerates the elements that make up a Chip-8 program image
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//usage statements
+use std::fmt;
+use super::Instruction;
+use super::CodeGen;
+
+/// A single element of a program image
+#[derive(Debug, Clone, PartialEq)]
+pub enum Element {
+    /// An instruction from the Chip-8 instruction set
+    Instr(Instruction),
+
+    /// A raw 16-bit word, used for opcodes from extended
+    /// instruction sets and for address data
+    Word(u16),
+
+    /// A raw data byte
+    Byte(u8)
+}
+
+//implementation
+impl Element {
+    /// Gets the size of the element
+    ///
+    /// # Returns
+    ///
+    /// The number of bytes the element occupies
+    pub fn len(&self) -> u16 {
+        return match *self {
+            Element::Byte(_) => 1,
+            _ => 2
+        };
+    }
+
+    /// Gets whether the element is empty. Elements always
+    /// occupy at least one byte, so this is always `false`.
+    ///
+    /// # Returns
+    ///
+    /// `false`
+    pub fn is_empty(&self) -> bool {
+        return false;
+    }
+
+    /// Gets the bytes of the element
+    ///
+    /// # Returns
+    ///
+    /// The big-endian bytes that make up the element
+    pub fn to_bytes(&self) -> Vec<u8> {
+        return match *self {
+            Element::Instr(ref instr) => {
+                let code = instr.gen_opcode();
+                vec![(code >> 8) as u8, code as u8]
+            },
+            Element::Word(w) => vec![(w >> 8) as u8, w as u8],
+            Element::Byte(b) => vec![b]
+        };
+    }
+}
+
+//Display implementation
+impl fmt::Display for Element {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return match *self {
+            Element::Instr(ref instr) => write!(f, "{}", instr),
+            Element::Word(w) => write!(f, ".word 0x{:04X}", w),
+            Element::Byte(b) => write!(f, ".byte 0x{:02X}", b)
+        };
+    }
+}
+
+//unit tests
+#[cfg(test)]
+mod tests {
+    //import the Element enum
+    use super::*;
+
+    //this test checks element sizes and bytes
+    #[test]
+    fn test_element_bytes() {
+        let e1 = Element::Instr(Instruction::CLS);
+        assert_eq!(e1.len(), 2);
+        assert_eq!(e1.to_bytes(), vec![0x00, 0xE0]);
+        let e2 = Element::Word(0xF000);
+        assert_eq!(e2.to_bytes(), vec![0xF0, 0x00]);
+        let e3 = Element::Byte(0x7F);
+        assert_eq!(e3.len(), 1);
+        assert_eq!(e3.to_bytes(), vec![0x7F]);
+        assert_eq!(e3.to_string(), ".byte 0x7F");
+    }
+}
+
+//end of file
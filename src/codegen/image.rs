@@ -0,0 +1,331 @@
+/*
+ * image.rs
+ * Defines a struct that holds a laid-out Chip-8 program
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//usage statements
+use std::collections::BTreeMap;
+use super::{Binary, Element, PatchPoint, SourceSpan, PROGRAM_START};
+use super::super::error::BinaryError;
+
+/// A program image, made up of elements placed at fixed addresses
+/// along with the addresses of its labels
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Image {
+    /// The elements of the image, keyed by address
+    elements: BTreeMap<u16, Element>,
+
+    /// The labels of the image, keyed by name
+    labels: BTreeMap<String, u16>,
+
+    /// Where the elements came from in the source, keyed by address
+    spans: BTreeMap<u16, SourceSpan>,
+
+    /// The instructions the program rewrites as it runs
+    patches: Vec<PatchPoint>
+}
+
+//implementation
+impl Image {
+    /// Constructs a new, empty `Image` instance
+    ///
+    /// # Returns
+    ///
+    /// A new `Image` instance with no elements or labels
+    pub fn new() -> Image {
+        return Image {
+            elements: BTreeMap::new(),
+            labels: BTreeMap::new(),
+            spans: BTreeMap::new(),
+            patches: Vec::new()
+        };
+    }
+
+    /// Places an element at an address
+    ///
+    /// # Arguments
+    ///
+    /// * `addr` - The address to place the element at
+    /// * `elem` - The element to place
+    ///
+    /// # Returns
+    ///
+    /// `true` if the element was placed, or `false` if it lies
+    /// below `PROGRAM_START`, past the end of the address space,
+    /// or overlaps an element already in the image
+    pub fn place(&mut self, addr: u16, elem: Element) -> bool {
+        let end = addr as u32 + elem.len() as u32;
+        if addr < PROGRAM_START || end > 0x10000 {
+            return false;
+        }
+
+        //check the element before this address
+        if let Some((prev, e)) = self.elements.range(..addr).next_back() {
+            if *prev as u32 + e.len() as u32 > addr as u32 {
+                return false;
+            }
+        }
+
+        //check the element at or after this address
+        if let Some((next, _)) = self.elements.range(addr..).next() {
+            if (*next as u32) < end {
+                return false;
+            }
+        }
+
+        self.elements.insert(addr, elem);
+        return true;
+    }
+
+    /// Replaces an element already in the image with another
+    /// of the same size
+    ///
+    /// # Arguments
+    ///
+    /// * `addr` - The address of the element to replace
+    /// * `elem` - The new element
+    ///
+    /// # Returns
+    ///
+    /// `true` if the element was replaced, or `false` if there is
+    /// no element of the same size at `addr`
+    pub fn replace(&mut self, addr: u16, elem: Element) -> bool {
+        match self.elements.get_mut(&addr) {
+            Some(old) if old.len() == elem.len() => {
+                *old = elem;
+                return true;
+            },
+            _ => return false
+        };
+    }
+
+    /// Gets the element at an address
+    ///
+    /// # Argument
+    ///
+    /// * `addr` - The address of the element
+    ///
+    /// # Returns
+    ///
+    /// The element that starts at `addr`, if any
+    pub fn get_element(&self, addr: u16) -> Option<&Element> {
+        return self.elements.get(&addr);
+    }
+
+    /// Gets the elements of the image
+    ///
+    /// # Returns
+    ///
+    /// An iterator over the elements and their addresses,
+    /// in address order
+    pub fn elements(&self) -> impl Iterator<Item = (u16, &Element)> {
+        return self.elements.iter().map(|(a, e)| (*a, e));
+    }
+
+    /// Adds a label to the image
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the label
+    /// * `addr` - The address the label refers to
+    pub fn add_label(&mut self, name: &str, addr: u16) {
+        self.labels.insert(String::from(name), addr);
+    }
+
+    /// Gets the address of a label
+    ///
+    /// # Argument
+    ///
+    /// * `name` - The name of the label
+    ///
+    /// # Returns
+    ///
+    /// The address of the label, if it exists
+    pub fn get_label(&self, name: &str) -> Option<u16> {
+        return self.labels.get(name).cloned();
+    }
+
+    /// Gets the labels of the image
+    ///
+    /// # Returns
+    ///
+    /// The labels of the image, keyed by name
+    pub fn get_labels(&self) -> &BTreeMap<String, u16> {
+        return &self.labels;
+    }
+
+    /// Records where the element at an address came from
+    ///
+    /// # Arguments
+    ///
+    /// * `addr` - The address of the element
+    /// * `span` - The source span of the element
+    pub fn set_span(&mut self, addr: u16, span: SourceSpan) {
+        self.spans.insert(addr, span);
+    }
+
+    /// Gets where the element at an address came from
+    ///
+    /// # Argument
+    ///
+    /// * `addr` - The address of the element
+    ///
+    /// # Returns
+    ///
+    /// The source span of the element, if one was recorded
+    pub fn get_span(&self, addr: u16) -> Option<&SourceSpan> {
+        return self.spans.get(&addr);
+    }
+
+    /// Marks an instruction of the image as one the program
+    /// rewrites as it runs
+    ///
+    /// # Argument
+    ///
+    /// * `point` - The patch point of the instruction
+    pub fn add_patch(&mut self, point: PatchPoint) {
+        self.patches.push(point);
+    }
+
+    /// Gets the instructions the program rewrites
+    ///
+    /// # Returns
+    ///
+    /// The patch points of the image, in the order they were added
+    pub fn get_patches(&self) -> &[PatchPoint] {
+        return &self.patches;
+    }
+
+    /// Gets whether the program may write a byte of the image
+    /// without changing the kind of an instruction
+    ///
+    /// # Argument
+    ///
+    /// * `byte` - The address of the byte
+    ///
+    /// # Returns
+    ///
+    /// Whether the byte holds operand bits of a patch point
+    pub fn is_writable(&self, byte: u16) -> bool {
+        return self.patches.iter().any(|p| p.is_writable(byte));
+    }
+
+    /// Gets the address one past the last element of the image
+    ///
+    /// # Returns
+    ///
+    /// The end address of the image, or `PROGRAM_START`
+    /// if the image is empty
+    pub fn end(&self) -> u32 {
+        return match self.elements.iter().next_back() {
+            Some((addr, e)) => *addr as u32 + e.len() as u32,
+            None => PROGRAM_START as u32
+        };
+    }
+
+    /// Gets the bytes of the image
+    ///
+    /// # Returns
+    ///
+    /// The bytes from `PROGRAM_START` to the end of the image,
+    /// with gaps between elements filled with zeroes
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![0u8; (self.end() - PROGRAM_START as u32)
+                                  as usize];
+        for (addr, e) in self.elements.iter() {
+            let start = (*addr - PROGRAM_START) as usize;
+            for (i, b) in e.to_bytes().into_iter().enumerate() {
+                bytes[start + i] = b;
+            }
+        }
+        return bytes;
+    }
+
+    /// Writes the image into a binary, filling gaps
+    /// between elements with zeroes. Labels, patch points and
+    /// source spans are passed on for the binary's listing.
+    ///
+    /// # Argument
+    ///
+    /// * `bin` - The `Binary` to write to
+    ///
+    /// # Returns
+    ///
+    /// `Ok` if the image was written successfully,
+    /// `Err<BinaryError>` if the operation fails
+    pub fn write_to_binary(&self, bin: &mut Binary) 
+        -> Result<(), BinaryError> {
+        for (name, addr) in self.labels.iter() {
+            bin.add_label(name, *addr);
+        }
+        for point in self.patches.iter() {
+            bin.add_patch(point.clone());
+        }
+        let mut here = PROGRAM_START as u32;
+        for (addr, e) in self.elements.iter() {
+            bin.add_padding((*addr as u32 - here) as usize)?;
+            bin.set_source(self.spans.get(addr).cloned());
+            bin.add_element(e)?;
+            here = *addr as u32 + e.len() as u32;
+        }
+        bin.set_source(None);
+        return Ok(());
+    }
+}
+
+//unit tests
+#[cfg(test)]
+mod tests {
+    //import the Image struct
+    use super::*;
+    use super::super::Instruction;
+
+    //this test checks that overlapping elements are rejected
+    #[test]
+    fn test_overlap_rejected() {
+        let mut img = Image::new();
+        assert!(img.place(0x200, Element::Word(0x1234)));
+        assert!(!img.place(0x201, Element::Byte(0)));
+        assert!(!img.place(0x1FF, Element::Word(0)));
+        assert!(!img.place(0x100, Element::Byte(0)));
+        assert!(img.place(0x202, Element::Byte(0)));
+    }
+
+    //this test checks that gaps are zero-filled
+    #[test]
+    fn test_to_bytes_fills_gaps() {
+        let mut img = Image::new();
+        img.place(0x200, Element::Instr(Instruction::CLS));
+        img.place(0x205, Element::Byte(0xAA));
+        assert_eq!(img.to_bytes(), vec![0x00, 0xE0, 0, 0, 0, 0xAA]);
+    }
+
+    //this test checks replacing elements
+    #[test]
+    fn test_replace() {
+        let mut img = Image::new();
+        img.place(0x200, Element::Word(0x1000));
+        assert!(img.replace(0x200, Element::Instr(Instruction::RET)));
+        assert!(!img.replace(0x200, Element::Byte(0)));
+        assert_eq!(img.to_bytes(), vec![0x00, 0xEE]);
+    }
+}
+
+//end of file
@@ -21,14 +21,18 @@
  */
 
 //usage statements
+use std::fmt;
 use super::super::data;
-use super::CodeGen;
+use super::super::data::{Register, SkipType};
+use super::super::vm::Quirks;
+use super::{CodeGen, Effects};
 
 /// A Chip-8 instruction.
 /// All instructions that
 /// execute a calculation 
 /// store the result in their
 /// first argument. 
+#[derive(Debug, Clone, PartialEq)]
 pub enum Instruction {
     /// Clears the screen
     CLS,
@@ -167,6 +171,127 @@ pub enum Instruction {
     RLD(data::RldData)
 }
 
+//implementation
+impl Instruction {
+    /// Decodes an opcode into an instruction
+    ///
+    /// # Argument
+    ///
+    /// * `opcode` - The opcode to decode
+    ///
+    /// # Returns
+    ///
+    /// The `Instruction` the opcode encodes, or `None` if the
+    /// opcode is not part of the Chip-8 instruction set
+    pub fn decode(opcode: u16) -> Option<Instruction> {
+        let vx = Register::from_id(((opcode >> 8) & 0xF) as u8)?;
+        let vy = Register::from_id(((opcode >> 4) & 0xF) as u8)?;
+        let nnn = opcode & 0xFFF;
+        let nn = (opcode & 0xFF) as u8;
+        let n = (opcode & 0xF) as u8;
+        let instr = match opcode >> 12 {
+            0x0 => match opcode {
+                0x00E0 => Instruction::CLS,
+                0x00EE => Instruction::RET,
+                _ => return None
+            },
+            0x1 => Instruction::JMP(data::JmpData::new(nnn)),
+            0x2 => Instruction::CALL(data::CallData::new(nnn)),
+            0x3 => Instruction::SKIP(data::SkipData::with_constant(
+                    vx, nn, SkipType::Equals)),
+            0x4 => Instruction::SKIP(data::SkipData::with_constant(
+                    vx, nn, SkipType::NotEquals)),
+            0x5 if n == 0 => Instruction::SKIP(
+                data::SkipData::with_register(vx, vy, SkipType::Equals)),
+            0x6 => Instruction::MOV(data::MovData::with_constant(
+                    vx, nn as u16)),
+            0x7 => Instruction::ADD(data::AddData::with_constant(
+                    vx, nn as u16)),
+            0x8 => match n {
+                0x0 => Instruction::MOV(data::MovData::with_register(vx, vy)),
+                0x1 => Instruction::OR(data::OrData::new(vx, vy)),
+                0x2 => Instruction::AND(data::AndData::new(vx, vy)),
+                0x3 => Instruction::XOR(data::XorData::new(vx, vy)),
+                0x4 => Instruction::ADD(data::AddData::with_register(vx, vy)),
+                0x5 => Instruction::SUB(data::SubData::new(vx, vy)),
+                0x6 => Instruction::SHR(data::ShrData::with_source(vx, vy)),
+                0x7 => Instruction::SUBN(data::SubnData::new(vx, vy)),
+                0xE => Instruction::SHL(data::ShlData::with_source(vx, vy)),
+                _ => return None
+            },
+            0x9 if n == 0 => Instruction::SKIP(
+                data::SkipData::with_register(vx, vy, SkipType::NotEquals)),
+            0xA => Instruction::MOV(data::MovData::with_constant(
+                    Register::I, nnn)),
+            0xB => Instruction::JPC(data::JpcData::new(nnn)),
+            0xC => Instruction::RAND(data::RandData::new(vx, nn)),
+            0xD => Instruction::DRAW(data::DrawData::new(vx, vy, n)),
+            0xE => match nn {
+                0x9E => Instruction::SKIP(data::SkipData::with_key(
+                        vx, SkipType::KeyDown)),
+                0xA1 => Instruction::SKIP(data::SkipData::with_key(
+                        vx, SkipType::KeyUp)),
+                _ => return None
+            },
+            0xF => match nn {
+                0x07 => Instruction::GDL(data::GdlData::new(vx)),
+                0x0A => Instruction::KEY(data::KeyData::new(vx)),
+                0x15 => Instruction::SDL(data::SdlData::new(vx)),
+                0x18 => Instruction::SND(data::SndData::new(vx)),
+                0x1E => Instruction::ADD(data::AddData::with_register(
+                        Register::I, vx)),
+                0x29 => Instruction::SCH(data::SchData::new(vx)),
+                0x33 => Instruction::BCD(data::BcdData::new(vx)),
+                0x55 => Instruction::RDP(data::RdpData::new(vx)),
+                0x65 => Instruction::RLD(data::RldData::new(vx)),
+                _ => return None
+            },
+            _ => return None
+        };
+        return Some(instr);
+    }
+
+    /// Describes what the instruction reads, writes and affects
+    ///
+    /// # Argument
+    ///
+    /// * `quirks` - The interpreter behaviours to assume
+    ///
+    /// # Returns
+    ///
+    /// The `Effects` of the instruction
+    pub fn effects(&self, quirks: Quirks) -> Effects {
+        return Effects::of(self, quirks);
+    }
+
+    /// Gets the bits of the instruction that hold its operands,
+    /// which a program may rewrite without changing what kind of
+    /// instruction it is
+    ///
+    /// # Returns
+    ///
+    /// A mask over the opcode of the instruction
+    pub fn operand_mask(&self) -> u16 {
+        return match *self {
+            Instruction::CLS | Instruction::RET => 0x0000,
+            Instruction::JMP(_) | Instruction::CALL(_) |
+                Instruction::JPC(_) | Instruction::RAND(_) |
+                Instruction::DRAW(_) => 0x0FFF,
+            Instruction::SKIP(_) | Instruction::MOV(_) |
+                Instruction::ADD(_) => match self.gen_opcode() >> 12 {
+                0x5 | 0x8 | 0x9 => 0x0FF0,
+                0xE | 0xF => 0x0F00,
+                _ => 0x0FFF
+            },
+            Instruction::OR(_) | Instruction::AND(_) |
+                Instruction::XOR(_) | Instruction::SUB(_) |
+                Instruction::SUBN(_) | Instruction::SHR(_) |
+                Instruction::SHL(_) => 0x0FF0,
+            _ => 0x0F00
+        };
+    }
+}
+
 //CodeGen implementation
 impl CodeGen for Instruction {
     /// Generates the opcode for
@@ -208,11 +333,73 @@ impl CodeGen for Instruction {
     }
 }
 
+//Display implementation
+impl fmt::Display for Instruction {
+    /// Formats the instruction in the syntax
+    /// accepted by the `asm` module
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let code = self.gen_opcode();
+        let x = (code >> 8) & 0xF;
+        let y = (code >> 4) & 0xF;
+        let nnn = code & 0xFFF;
+        let nn = code & 0xFF;
+        return match *self {
+            Instruction::CLS => write!(f, "CLS"),
+            Instruction::RET => write!(f, "RET"),
+            Instruction::JMP(_) => write!(f, "JMP 0x{:03X}", nnn),
+            Instruction::CALL(_) => write!(f, "CALL 0x{:03X}", nnn),
+            Instruction::JPC(_) => write!(f, "JPC 0x{:03X}", nnn),
+            Instruction::SKIP(_) => match code >> 12 {
+                0x3 => write!(f, "SKIP.EQ V{:X}, 0x{:02X}", x, nn),
+                0x4 => write!(f, "SKIP.NE V{:X}, 0x{:02X}", x, nn),
+                0x5 => write!(f, "SKIP.EQ V{:X}, V{:X}", x, y),
+                0x9 => write!(f, "SKIP.NE V{:X}, V{:X}", x, y),
+                _ if nn == 0x9E => write!(f, "SKIP.KD V{:X}", x),
+                _ => write!(f, "SKIP.KU V{:X}", x)
+            },
+            Instruction::MOV(_) => match code >> 12 {
+                0x6 => write!(f, "MOV V{:X}, 0x{:02X}", x, nn),
+                0x8 => write!(f, "MOV V{:X}, V{:X}", x, y),
+                _ => write!(f, "MOV I, 0x{:03X}", nnn)
+            },
+            Instruction::ADD(_) => match code >> 12 {
+                0x7 => write!(f, "ADD V{:X}, 0x{:02X}", x, nn),
+                0x8 => write!(f, "ADD V{:X}, V{:X}", x, y),
+                _ => write!(f, "ADD I, V{:X}", x)
+            },
+            Instruction::OR(_) => write!(f, "OR V{:X}, V{:X}", x, y),
+            Instruction::AND(_) => write!(f, "AND V{:X}, V{:X}", x, y),
+            Instruction::XOR(_) => write!(f, "XOR V{:X}, V{:X}", x, y),
+            Instruction::SUB(_) => write!(f, "SUB V{:X}, V{:X}", x, y),
+            Instruction::SUBN(_) => write!(f, "SUBN V{:X}, V{:X}", x, y),
+            Instruction::SHR(_) if x == y => write!(f, "SHR V{:X}", x),
+            Instruction::SHR(_) => write!(f, "SHR V{:X}, V{:X}", x, y),
+            Instruction::SHL(_) if x == y => write!(f, "SHL V{:X}", x),
+            Instruction::SHL(_) => write!(f, "SHL V{:X}, V{:X}", x, y),
+            Instruction::RAND(_) => {
+                write!(f, "RAND V{:X}, 0x{:02X}", x, nn)
+            },
+            Instruction::DRAW(_) => {
+                write!(f, "DRAW V{:X}, V{:X}, {}", x, y, code & 0xF)
+            },
+            Instruction::GDL(_) => write!(f, "GDL V{:X}", x),
+            Instruction::KEY(_) => write!(f, "KEY V{:X}", x),
+            Instruction::SDL(_) => write!(f, "SDL V{:X}", x),
+            Instruction::SND(_) => write!(f, "SND V{:X}", x),
+            Instruction::SCH(_) => write!(f, "SCH V{:X}", x),
+            Instruction::BCD(_) => write!(f, "BCD V{:X}", x),
+            Instruction::RDP(_) => write!(f, "RDP V{:X}", x),
+            Instruction::RLD(_) => write!(f, "RLD V{:X}", x)
+        };
+    }
+}
+
 //unit tests
 #[cfg(test)]
 mod tests {
     //import the Instruction enum
     use super::*;
+    use proptest::prelude::*;
 
     //this test checks generation 
     //of the CLS opcode
@@ -229,6 +416,65 @@ mod tests {
         let instr = Instruction::RET;
         assert_eq!(instr.gen_opcode(), 0x00EE);
     }
+
+    //this test checks formatting
+    //instructions as assembly
+    #[test]
+    fn test_display() {
+        let instrs = [
+            Instruction::MOV(data::MovData::with_constant(Register::I, 
+                                                          0x2F0)),
+            Instruction::ADD(data::AddData::with_register(Register::I,
+                                                          Register::V3)),
+            Instruction::SKIP(data::SkipData::with_key(Register::VA,
+                                                       SkipType::KeyDown)),
+            Instruction::SHL(data::ShlData::new(Register::V1)),
+            Instruction::DRAW(data::DrawData::new(Register::V0, 
+                                                  Register::V1, 5))
+        ];
+        let text: Vec<String> = instrs.iter().map(|i| i.to_string())
+                                      .collect();
+        assert_eq!(text, vec!["MOV I, 0x2F0", "ADD I, V3", "SKIP.KD VA",
+                              "SHL V1", "DRAW V0, V1, 5"]);
+    }
+
+    //this test checks the operand bits of instructions
+    #[test]
+    fn test_operand_mask() {
+        let masks: Vec<u16> = [0x00EE, 0x1234, 0x6A05, 0x8016, 0xE29E,
+                               0xF155, 0xD125, 0x5120]
+            .iter().map(|op| Instruction::decode(*op).unwrap()
+                                         .operand_mask()).collect();
+        assert_eq!(masks, vec![0x0000, 0x0FFF, 0x0FFF, 0x0FF0, 0x0F00,
+                               0x0F00, 0x0FFF, 0x0FF0]);
+    }
+
+    //this test checks that decoding 
+    //round-trips every valid opcode
+    #[test]
+    fn test_decode_round_trip() {
+        let mut count = 0;
+        for opcode in 0..=0xFFFFu16 {
+            if let Some(instr) = Instruction::decode(opcode) {
+                assert_eq!(instr.gen_opcode(), opcode);
+                count += 1;
+            }
+        }
+        assert!(Instruction::decode(0x0123).is_none());
+        assert!(Instruction::decode(0x5121).is_none());
+        assert!(Instruction::decode(0xF0FF).is_none());
+        assert_eq!(count, 2 + 4096 * 10 + 256 * 11 + 16 * 11);
+    }
+
+    proptest! {
+        //this test checks that every generated
+        //instruction survives an encode and decode
+        #[test]
+        fn test_generated_round_trip(instr in any::<Instruction>()) {
+            let opcode = instr.gen_opcode();
+            prop_assert_eq!(Instruction::decode(opcode), Some(instr));
+        }
+    }
 }
 
 //end of file
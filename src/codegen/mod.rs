@@ -21,11 +21,30 @@
  */
 
 //exports
+#[allow(clippy::module_inception)]
 mod codegen;
 pub(crate) use codegen::CodeGen;
 mod instruction;
 pub use instruction::Instruction;
+mod effects;
+pub use effects::Effects;
+pub(crate) use effects::REGISTER_COUNT;
 mod binary;
 pub use binary::Binary;
+pub use binary::PROGRAM_START;
+mod element;
+pub use element::Element;
+mod patch_point;
+pub use patch_point::PatchPoint;
+mod image;
+pub use image::Image;
+mod source_span;
+pub use source_span::SourceSpan;
+mod symbol_format;
+pub use symbol_format::SymbolFormat;
+mod output_format;
+pub use output_format::OutputFormat;
+#[cfg(any(test, feature = "proptest"))]
+mod arbitrary;
 
 //end of file
@@ -0,0 +1,234 @@
+/*
+ * output_format.rs
+ * Defines an enum that selects the file format of a binary
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+/// The number of data bytes in each HEX and S-record record
+const RECORD_LEN: usize = 16;
+
+/// The number of bytes on each line of an array
+const ARRAY_LINE_LEN: usize = 12;
+
+/// The file format a binary is written in
+#[derive(Debug, Clone, PartialEq)]
+pub enum OutputFormat {
+    /// The raw bytes of the program
+    Raw,
+
+    /// Intel HEX records, with the program loaded at an address
+    IntelHex(u32),
+
+    /// Motorola S-records, with the program loaded at an address
+    SRecord(u32),
+
+    /// A C array definition with the given name
+    CArray(String),
+
+    /// A Rust constant definition with the given name
+    RustArray(String)
+}
+
+//implementation
+impl OutputFormat {
+    /// Encodes program bytes in this format
+    ///
+    /// # Argument
+    ///
+    /// * `data` - The bytes of the program
+    ///
+    /// # Returns
+    ///
+    /// The contents of a file holding the program, or `None` if
+    /// the program runs past the 32-bit addresses of HEX and
+    /// S-record files
+    pub fn encode(&self, data: &[u8]) -> Option<Vec<u8>> {
+        return match *self {
+            OutputFormat::Raw => Some(data.to_vec()),
+            OutputFormat::IntelHex(origin) => {
+                intel_hex(data, origin).map(String::into_bytes)
+            },
+            OutputFormat::SRecord(origin) => {
+                s_record(data, origin).map(String::into_bytes)
+            },
+            OutputFormat::CArray(ref name) => {
+                let body = array_body(data);
+                Some(format!("const unsigned char {}[{}] = {{\n{}}};\n",
+                             name, data.len(), body).into_bytes())
+            },
+            OutputFormat::RustArray(ref name) => {
+                let body = array_body(data);
+                Some(format!("const {}: [u8; {}] = [\n{}];\n",
+                             name, data.len(), body).into_bytes())
+            }
+        };
+    }
+}
+
+/// Formats a record of bytes as hex digits
+fn hex(bytes: &[u8]) -> String {
+    return bytes.iter().map(|b| format!("{:02X}", b)).collect();
+}
+
+/// Gets the address just past the end of a program,
+/// or `None` if it does not fit in 32 bits
+fn end_of(data: &[u8], origin: u32) -> Option<u64> {
+    let end = origin as u64 + data.len() as u64;
+    return if end <= 1 << 32 { Some(end) } else { None };
+}
+
+/// Encodes bytes as Intel HEX records
+fn intel_hex(data: &[u8], origin: u32) -> Option<String> {
+    end_of(data, origin)?;
+    let mut out = String::new();
+    let mut upper = 0;
+    let mut record = |kind: u8, addr: u16, payload: &[u8]| {
+        let mut bytes = vec![payload.len() as u8, (addr >> 8) as u8, 
+                             addr as u8, kind];
+        bytes.extend_from_slice(payload);
+        let sum = bytes.iter().fold(0u8, |s, b| s.wrapping_add(*b));
+        bytes.push(sum.wrapping_neg());
+        out.push(':');
+        out.push_str(&hex(&bytes));
+        out.push('\n');
+    };
+    let mut offset = 0;
+    while offset < data.len() {
+        let addr = origin + offset as u32;
+
+        //switch 64K segments with an extended linear address record
+        if addr >> 16 != upper {
+            upper = addr >> 16;
+            record(0x04, 0, &[(upper >> 8) as u8, upper as u8]);
+        }
+
+        //don't let a record cross into the next segment
+        let room = 0x10000 - (addr & 0xFFFF) as usize;
+        let len = RECORD_LEN.min(data.len() - offset).min(room);
+        record(0x00, addr as u16, &data[offset..offset + len]);
+        offset += len;
+    }
+    record(0x01, 0, &[]);
+    return Some(out);
+}
+
+/// Encodes bytes as Motorola S-records, with 16, 24 or 32-bit
+/// addresses depending on where the program ends
+fn s_record(data: &[u8], origin: u32) -> Option<String> {
+    let end = end_of(data, origin)?;
+    let records = data.len().div_ceil(RECORD_LEN);
+    if records > 0xFFFFFF {
+        return None;
+    }
+    let (data_kind, addr_len) = match end {
+        0..=0x10000 => (1, 2),
+        0x10001..=0x1000000 => (2, 3),
+        _ => (3, 4)
+    };
+    let mut out = String::new();
+    let mut record = |kind: u8, addr: u32, payload: &[u8]| {
+        let addr_len = match kind {
+            0 | 5 => 2,
+            6 => 3,
+            _ => addr_len
+        };
+        let mut bytes = vec![(addr_len + payload.len() + 1) as u8];
+        for i in (0..addr_len).rev() {
+            bytes.push((addr >> (8 * i)) as u8);
+        }
+        bytes.extend_from_slice(payload);
+        let sum = bytes.iter().fold(0u8, |s, b| s.wrapping_add(*b));
+        bytes.push(!sum);
+        out.push_str(&format!("S{}{}\n", kind, hex(&bytes)));
+    };
+    record(0, 0, b"ch8");
+    for (i, chunk) in data.chunks(RECORD_LEN).enumerate() {
+        let addr = origin + (i * RECORD_LEN) as u32;
+        record(data_kind, addr, chunk);
+    }
+    record(if records <= 0xFFFF { 5 } else { 6 }, records as u32, &[]);
+    record(10 - data_kind, origin, &[]);
+    return Some(out);
+}
+
+/// Formats bytes as the comma-separated body of an array
+fn array_body(data: &[u8]) -> String {
+    let mut out = String::new();
+    for line in data.chunks(ARRAY_LINE_LEN) {
+        let items: Vec<String> = line.iter().map(|b| format!("0x{:02X}", b))
+                                     .collect();
+        out.push_str(&format!("    {},\n", items.join(", ")));
+    }
+    return out;
+}
+
+//unit tests
+#[cfg(test)]
+mod tests {
+    //import the OutputFormat enum
+    use super::*;
+
+    //encodes bytes and returns the text
+    fn text(format: OutputFormat, data: &[u8]) -> String {
+        return String::from_utf8(format.encode(data).unwrap()).unwrap();
+    }
+
+    //this test checks Intel HEX output
+    #[test]
+    fn test_intel_hex() {
+        assert_eq!(text(OutputFormat::IntelHex(0x200), &[0x00, 0xE0]),
+                   ":0202000000E01C\n:00000001FF\n");
+        let hex = text(OutputFormat::IntelHex(0xFFFF), &[0xAA, 0xBB]);
+        assert_eq!(hex, ":01FFFF00AA57\n:020000040001F9\n\
+                         :01000000BB44\n:00000001FF\n");
+        assert!(OutputFormat::IntelHex(0xFFFFFFFF).encode(&[0]).is_some());
+        assert_eq!(OutputFormat::IntelHex(0xFFFFFFFF).encode(&[0, 0]), 
+                   None);
+    }
+
+    //this test checks S-record output
+    #[test]
+    fn test_s_record() {
+        assert_eq!(text(OutputFormat::SRecord(0x200), &[0x00, 0xE0]),
+                   "S0060000636838F6\nS105020000E018\n\
+                    S5030001FB\nS9030200FA\n");
+        let srec = text(OutputFormat::SRecord(0x1000000), &[0x00, 0xE0]);
+        assert_eq!(srec, "S0060000636838F6\nS3070100000000E017\n\
+                          S5030001FB\nS70501000000F9\n");
+        let data = vec![0; RECORD_LEN * 0x10000];
+        let srec = text(OutputFormat::SRecord(0), &data);
+        assert!(srec.ends_with("S604010000FA\nS804000000FB\n"));
+        assert_eq!(OutputFormat::SRecord(0xFFFFFFFF).encode(&[0, 0]), None);
+    }
+
+    //this test checks array output
+    #[test]
+    fn test_arrays() {
+        let data: Vec<u8> = (0..13).collect();
+        assert_eq!(text(OutputFormat::RustArray(String::from("ROM")), 
+                        &data),
+                   "const ROM: [u8; 13] = [\n    0x00, 0x01, 0x02, 0x03, \
+                    0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B,\n    \
+                    0x0C,\n];\n");
+        assert_eq!(text(OutputFormat::CArray(String::from("rom")), &[1]),
+                   "const unsigned char rom[1] = {\n    0x01,\n};\n");
+    }
+}
+
+//end of file
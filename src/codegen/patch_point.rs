@@ -0,0 +1,158 @@
+/*
+ * patch_point.rs
+ * Defines an instruction a program may rewrite
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+
+//usage statements
+use super::Instruction;
+
+/// An instruction that the program rewrites as it runs, such as a
+/// `JMP` whose address is replaced with `RDP`. The assembler and
+/// `Binary` label the instruction with the name of the patch point
+/// and its low byte, which holds the immediate byte or the low
+/// eight bits of the address, with the name followed by `.operand`.
+/// Octo's `:next` labels only the low byte.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PatchPoint {
+    /// The name of the patch point
+    name: String,
+
+    /// The address of the instruction
+    addr: u16,
+
+    /// The bits of the opcode that may be rewritten
+    mask: u16
+}
+
+//implementation
+impl PatchPoint {
+    /// Constructs a new `PatchPoint` instance
+    ///
+    /// # Arguments
+    ///
+    /// * `new_name` - The name of the patch point
+    /// * `new_addr` - The address of the instruction
+    /// * `instr` - The instruction as it is emitted
+    ///
+    /// # Returns
+    ///
+    /// A new `PatchPoint` instance allowing the operands
+    /// of the instruction to be rewritten
+    pub fn new(new_name: &str, new_addr: u16,
+               instr: &Instruction) -> PatchPoint {
+        return PatchPoint {
+            name: String::from(new_name),
+            addr: new_addr,
+            mask: instr.operand_mask()
+        };
+    }
+
+    /// Gets the name of the patch point
+    ///
+    /// # Returns
+    ///
+    /// The name, which labels the instruction
+    pub fn get_name(&self) -> &str {
+        return self.name.as_str();
+    }
+
+    /// Gets the name of the label on the operand
+    ///
+    /// # Returns
+    ///
+    /// The name followed by `.operand`
+    pub fn get_operand_name(&self) -> String {
+        return format!("{}.operand", self.name);
+    }
+
+    /// Gets the address of the instruction
+    ///
+    /// # Returns
+    ///
+    /// The address of the first byte of the instruction
+    pub fn get_addr(&self) -> u16 {
+        return self.addr;
+    }
+
+    /// Gets the address of the operand
+    ///
+    /// # Returns
+    ///
+    /// The address of the low byte of the instruction
+    pub fn get_operand_addr(&self) -> u16 {
+        return self.addr.wrapping_add(1);
+    }
+
+    /// Gets the bits of the instruction that may be rewritten
+    ///
+    /// # Returns
+    ///
+    /// A mask over the opcode of the instruction
+    pub fn get_mask(&self) -> u16 {
+        return self.mask;
+    }
+
+    /// Gets whether the program may write a byte of memory
+    /// without changing the kind of instruction
+    ///
+    /// # Argument
+    ///
+    /// * `byte` - The address of the byte
+    ///
+    /// # Returns
+    ///
+    /// Whether the byte holds operand bits of the instruction
+    pub fn is_writable(&self, byte: u16) -> bool {
+        return if byte == self.addr {
+            self.mask & 0xFF00 != 0
+        } else if byte == self.get_operand_addr() {
+            self.mask & 0x00FF != 0
+        } else {
+            false
+        };
+    }
+}
+
+//unit tests
+#[cfg(test)]
+mod tests {
+    //import the PatchPoint struct
+    use super::*;
+    use super::super::super::data::*;
+
+    //this test checks the writable bytes of patch points
+    #[test]
+    fn test_writable() {
+        let jmp = PatchPoint::new("target", 0x204,
+                                  &Instruction::JMP(JmpData::new(0x200)));
+        assert_eq!(jmp.get_operand_name(), "target.operand");
+        assert_eq!(jmp.get_operand_addr(), 0x205);
+        assert!(jmp.is_writable(0x204) && jmp.is_writable(0x205));
+        assert!(!jmp.is_writable(0x206));
+        let key = PatchPoint::new("key", 0x300,
+                                  &Instruction::KEY(KeyData::new(
+                                          Register::V3)));
+        assert!(key.is_writable(0x300));
+        assert!(!key.is_writable(0x301));
+    }
+}
+
+//end of file
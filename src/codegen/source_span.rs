@@ -0,0 +1,128 @@
+/*
+ * source_span.rs
+ * Defines a struct that locates program elements in their source
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//usage statement
+use std::fmt;
+
+/// The location in the source of a program element
+#[derive(Debug, Clone, PartialEq)]
+pub struct SourceSpan {
+    /// The source file, if known
+    file: Option<String>,
+
+    /// The 1-based line number
+    line: usize,
+
+    /// The text of the source line
+    text: String
+}
+
+//implementation
+impl SourceSpan {
+    /// Constructs a new `SourceSpan` instance
+    ///
+    /// # Arguments
+    ///
+    /// * `new_line` - The 1-based line number
+    /// * `new_text` - The text of the source line
+    ///
+    /// # Returns
+    ///
+    /// A new `SourceSpan` instance with no file
+    pub fn new(new_line: usize, new_text: &str) -> SourceSpan {
+        return SourceSpan {
+            file: None,
+            line: new_line,
+            text: String::from(new_text.trim())
+        };
+    }
+
+    /// Attaches a file name to the span
+    ///
+    /// # Argument
+    ///
+    /// * `new_file` - The name of the source file
+    ///
+    /// # Returns
+    ///
+    /// The span, naming the given file
+    pub fn with_file(mut self, new_file: &str) -> SourceSpan {
+        self.file = Some(String::from(new_file));
+        return self;
+    }
+
+    /// Gets the source file of the span
+    ///
+    /// # Returns
+    ///
+    /// The name of the source file, if known
+    pub fn get_file(&self) -> Option<&str> {
+        return self.file.as_deref();
+    }
+
+    /// Gets the line number of the span
+    ///
+    /// # Returns
+    ///
+    /// The 1-based line number
+    pub fn get_line(&self) -> usize {
+        return self.line;
+    }
+
+    /// Gets the source text of the span
+    ///
+    /// # Returns
+    ///
+    /// The trimmed text of the source line
+    pub fn get_text(&self) -> &str {
+        return self.text.as_str();
+    }
+}
+
+//Display implementation
+impl fmt::Display for SourceSpan {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(ref file) = self.file {
+            write!(f, "{}:", file)?;
+        }
+        return write!(f, "{}: {}", self.line, self.text);
+    }
+}
+
+//unit tests
+#[cfg(test)]
+mod tests {
+    //import the SourceSpan struct
+    use super::*;
+
+    //this test checks span formatting
+    #[test]
+    fn test_display() {
+        let span = SourceSpan::new(3, "  CLS ; clear\n");
+        assert_eq!(span.to_string(), "3: CLS ; clear");
+        let span = span.with_file("main.s");
+        assert_eq!(span.get_file(), Some("main.s"));
+        assert_eq!(span.to_string(), "main.s:3: CLS ; clear");
+    }
+}
+
+//end of file
@@ -0,0 +1,34 @@
+/*
+ * symbol_format.rs
+ * Defines an enum that selects a symbol file format
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+/// The format of a symbol file
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SymbolFormat {
+    /// One `name = 0xADDR` line per label
+    Text,
+
+    /// The `.sym` format read by common debuggers, with a
+    /// `[labels]` section of `BB:ADDR name` lines
+    Sym
+}
+
+//end of file
@@ -0,0 +1,153 @@
+/*
+ * ast.rs
+ * Defines the syntax tree of the expression language
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//usage statement
+use std::fmt;
+
+/// The value types of the language
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Type {
+    /// An 8-bit unsigned integer
+    U8,
+
+    /// A boolean, stored as `0` or `1`
+    Bool,
+
+    /// The type of calls that produce no value
+    Unit
+}
+
+//Display implementation
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Type::U8 => write!(f, "u8"),
+            Type::Bool => write!(f, "bool"),
+            Type::Unit => write!(f, "()")
+        }
+    }
+}
+
+/// Binary operators, in no particular order
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BinOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Mod,
+    And,
+    Or,
+    Xor,
+    Shl,
+    Shr,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    LogAnd,
+    LogOr
+}
+
+/// Unary operators
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UnOp {
+    /// Boolean negation
+    Not,
+
+    /// Bitwise complement
+    BitNot
+}
+
+/// The kinds of expression
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExprKind {
+    /// A numeric literal
+    Number(u32),
+
+    /// A boolean literal
+    Bool(bool),
+
+    /// A reference to a named variable or sprite
+    Name(String),
+
+    /// A unary operation
+    Unary(UnOp, Box<Expr>),
+
+    /// A binary operation
+    Binary(BinOp, Box<Expr>, Box<Expr>),
+
+    /// A call to a builtin
+    Call(String, Vec<Expr>)
+}
+
+/// An expression with its source position
+#[derive(Debug, Clone, PartialEq)]
+pub struct Expr {
+    /// The kind of the expression
+    pub kind: ExprKind,
+
+    /// The line the expression starts on
+    pub line: usize,
+
+    /// The column the expression starts on
+    pub col: usize
+}
+
+/// The kinds of statement
+#[derive(Debug, Clone, PartialEq)]
+pub enum StmtKind {
+    /// A variable declaration
+    Var(String, Type, Expr),
+
+    /// An assignment to an existing variable
+    Assign(String, Expr),
+
+    /// A sprite definition
+    Sprite(String, Vec<Expr>),
+
+    /// An expression evaluated for its side effects
+    Expr(Expr),
+
+    /// A conditional with an optional else block
+    If(Expr, Vec<Stmt>, Vec<Stmt>),
+
+    /// A pre-tested loop
+    While(Expr, Vec<Stmt>)
+}
+
+/// A statement with its source position
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stmt {
+    /// The kind of the statement
+    pub kind: StmtKind,
+
+    /// The line the statement starts on
+    pub line: usize,
+
+    /// The column the statement starts on
+    pub col: usize
+}
+
+//end of file
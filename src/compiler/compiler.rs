@@ -0,0 +1,968 @@
+/*
+ * compiler.rs
+ * Generates Chip-8 instructions from the expression language
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//usage statements
+use std::collections::HashMap;
+use super::ast::{BinOp, Expr, ExprKind, Stmt, StmtKind, Type, UnOp};
+use super::lexer::tokenize;
+use super::parser::Parser;
+use super::Program;
+use super::super::codegen::{Instruction, PROGRAM_START};
+use super::super::data;
+use super::super::data::{Register, SkipType};
+use super::super::error::{CompileError, CompileErrorType};
+
+/// The number of registers available for variables and
+/// temporaries (`V0` through `VA`). `VB` through `VE` are
+/// reserved for the multiplication and division routines
+/// and `VF` is clobbered by flag-setting instructions.
+const USER_REGISTERS: usize = 11;
+
+/// The highest address a program may occupy
+const MEMORY_END: usize = 0x1000;
+
+/// Compiles expression language source into a `Program`
+///
+/// # Argument
+///
+/// * `src` - The source text to compile
+///
+/// # Returns
+///
+/// The compiled `Program`, or the first `CompileError` encountered
+pub fn compile(src: &str) -> Result<Program, CompileError> {
+    let tokens = tokenize(src)?;
+    let stmts = Parser::new(tokens).parse_program()?;
+    return Compiler::new().compile(&stmts);
+}
+
+/// An instruction whose address operand may still be symbolic
+enum Op {
+    /// A fully resolved instruction
+    Instr(Instruction),
+
+    /// A jump to a label
+    Jmp(usize),
+
+    /// A subroutine call to a label
+    Call(usize),
+
+    /// Loads the address of a label into `I`
+    LoadI(usize),
+
+    /// Marks the position of a label
+    Label(usize),
+
+    /// A raw data byte
+    Byte(u8)
+}
+
+/// The location of an evaluated expression
+enum Operand {
+    /// A compile-time constant
+    Const(u8),
+
+    /// A register, flagged `true` if it is a temporary
+    Reg(Register, bool)
+}
+
+/// A named entity in the program
+enum Symbol {
+    /// A variable stored in a register
+    Var(Register, Type),
+
+    /// A sprite with its label and height
+    Sprite(usize, u8)
+}
+
+/// Walks a syntax tree and emits instructions
+struct Compiler {
+    /// The code emitted so far
+    ops: Vec<Op>,
+
+    /// The sprite data, placed after the code
+    data: Vec<Op>,
+
+    /// The variables and sprites in scope
+    symbols: HashMap<String, Symbol>,
+
+    /// Which of the user registers are unallocated
+    free: [bool; USER_REGISTERS],
+
+    /// The number of labels created so far
+    label_count: usize,
+
+    /// The label of the multiplication routine, if used
+    mul_label: Option<usize>,
+
+    /// The label of the division routine, if used
+    div_label: Option<usize>
+}
+
+//implementation
+impl Compiler {
+    /// Constructs a new `Compiler` instance
+    fn new() -> Compiler {
+        return Compiler {
+            ops: Vec::new(),
+            data: Vec::new(),
+            symbols: HashMap::new(),
+            free: [true; USER_REGISTERS],
+            label_count: 0,
+            mul_label: None,
+            div_label: None
+        };
+    }
+
+    /// Compiles a list of statements into a `Program`
+    fn compile(mut self, stmts: &[Stmt]) -> Result<Program, CompileError> {
+        for stmt in stmts {
+            self.gen_stmt(stmt)?;
+        }
+
+        //halt with a tight self-jump
+        let halt = self.new_label();
+        self.ops.push(Op::Label(halt));
+        self.ops.push(Op::Jmp(halt));
+
+        //append the runtime routines that were used
+        if let Some(label) = self.mul_label {
+            self.gen_mul_routine(label);
+        }
+        if let Some(label) = self.div_label {
+            self.gen_div_routine(label);
+        }
+        let data = std::mem::take(&mut self.data);
+        self.ops.extend(data);
+
+        //lay out the labels
+        let mut addrs = vec![0u16; self.label_count];
+        let mut addr = PROGRAM_START as usize;
+        for op in self.ops.iter() {
+            match *op {
+                Op::Label(l) => addrs[l] = addr as u16,
+                Op::Byte(_) => addr += 1,
+                _ => addr += 2
+            };
+        }
+        if addr > MEMORY_END {
+            return Err(CompileError::new(
+                    CompileErrorType::ProgramTooLarge(addr - 
+                                                      PROGRAM_START as usize),
+                    1, 1));
+        }
+
+        //and resolve the symbolic instructions
+        let mut instructions = Vec::new();
+        let mut bytes = Vec::new();
+        for op in self.ops.into_iter() {
+            match op {
+                Op::Instr(instr) => instructions.push(instr),
+                Op::Jmp(l) => instructions.push(Instruction::JMP(
+                        data::JmpData::new(addrs[l]))),
+                Op::Call(l) => instructions.push(Instruction::CALL(
+                        data::CallData::new(addrs[l]))),
+                Op::LoadI(l) => instructions.push(Instruction::MOV(
+                        data::MovData::with_constant(Register::I, 
+                                                     addrs[l]))),
+                Op::Label(_) => {},
+                Op::Byte(b) => bytes.push(b)
+            };
+        }
+        return Ok(Program::new(instructions, bytes));
+    }
+
+    /// Creates a new label
+    fn new_label(&mut self) -> usize {
+        self.label_count += 1;
+        return self.label_count - 1;
+    }
+
+    /// Emits a resolved instruction
+    fn emit(&mut self, instr: Instruction) {
+        self.ops.push(Op::Instr(instr));
+    }
+
+    /// Allocates a free user register
+    fn alloc(&mut self, line: usize, col: usize) 
+        -> Result<Register, CompileError> {
+        for (i, free) in self.free.iter_mut().enumerate() {
+            if *free {
+                *free = false;
+                return Ok(Register::from_id(i as u8).unwrap());
+            }
+        }
+        return Err(CompileError::new(CompileErrorType::OutOfRegisters,
+                                     line, col));
+    }
+
+    /// Frees an operand if it is a temporary register
+    fn release(&mut self, op: &Operand) {
+        if let Operand::Reg(ref r, true) = *op {
+            self.free[r.to_id() as usize] = true;
+        }
+    }
+
+    /// Emits a move of an operand into a register
+    fn emit_mov(&mut self, dest: &Register, op: &Operand) {
+        match *op {
+            Operand::Const(c) => {
+                self.emit(Instruction::MOV(
+                        data::MovData::with_constant(dest.clone(), 
+                                                     c as u16)));
+            },
+            Operand::Reg(ref r, _) => {
+                if r != dest {
+                    self.emit(Instruction::MOV(
+                            data::MovData::with_register(dest.clone(),
+                                                         r.clone())));
+                }
+            }
+        };
+    }
+
+    /// Moves an operand into a temporary register that
+    /// may be overwritten
+    fn place_in_temp(&mut self, op: Operand, e: &Expr) 
+        -> Result<Register, CompileError> {
+        if let Operand::Reg(r, true) = op {
+            return Ok(r);
+        }
+        let t = self.alloc(e.line, e.col)?;
+        self.emit_mov(&t, &op);
+        return Ok(t);
+    }
+
+    /// Moves an operand into some register, reusing the
+    /// operand's register when it already has one
+    fn place_in_reg(&mut self, op: Operand, e: &Expr) 
+        -> Result<Operand, CompileError> {
+        if let Operand::Const(_) = op {
+            let t = self.alloc(e.line, e.col)?;
+            self.emit_mov(&t, &op);
+            return Ok(Operand::Reg(t, true));
+        }
+        return Ok(op);
+    }
+
+    /// Builds a type mismatch error
+    fn mismatch(e: &Expr, expected: Type, found: Type) -> CompileError {
+        return CompileError::new(
+            CompileErrorType::TypeMismatch(
+                format!("expected {}, found {}", expected, found)),
+            e.line, e.col);
+    }
+
+    /// Generates code for a statement
+    fn gen_stmt(&mut self, stmt: &Stmt) -> Result<(), CompileError> {
+        match stmt.kind {
+            StmtKind::Var(ref name, ty, ref init) => {
+                if self.symbols.contains_key(name) {
+                    return Err(CompileError::new(
+                            CompileErrorType::Redefinition(name.clone()),
+                            stmt.line, stmt.col));
+                }
+                let (op, found) = self.gen_expr(init)?;
+                if found != ty {
+                    return Err(Compiler::mismatch(init, ty, found));
+                }
+
+                //adopt a temporary result as the variable's home
+                let reg = match op {
+                    Operand::Reg(r, true) => r,
+                    other => {
+                        let r = self.alloc(stmt.line, stmt.col)?;
+                        self.emit_mov(&r, &other);
+                        r
+                    }
+                };
+                self.symbols.insert(name.clone(), Symbol::Var(reg, ty));
+            },
+            StmtKind::Assign(ref name, ref value) => {
+                let (reg, ty) = match self.symbols.get(name) {
+                    Some(Symbol::Var(r, t)) => (r.clone(), *t),
+                    Some(Symbol::Sprite(_, _)) => {
+                        return Err(CompileError::new(
+                                CompileErrorType::TypeMismatch(
+                                    format!("cannot assign to sprite '{}'",
+                                            name)),
+                                stmt.line, stmt.col));
+                    },
+                    None => {
+                        return Err(CompileError::new(
+                                CompileErrorType::UnknownName(name.clone()),
+                                stmt.line, stmt.col));
+                    }
+                };
+                let (op, found) = self.gen_expr(value)?;
+                if found != ty {
+                    return Err(Compiler::mismatch(value, ty, found));
+                }
+                self.emit_mov(&reg, &op);
+                self.release(&op);
+            },
+            StmtKind::Sprite(ref name, ref rows) => {
+                if self.symbols.contains_key(name) {
+                    return Err(CompileError::new(
+                            CompileErrorType::Redefinition(name.clone()),
+                            stmt.line, stmt.col));
+                }
+                if rows.is_empty() || rows.len() > 15 {
+                    return Err(CompileError::new(
+                            CompileErrorType::BadSprite(name.clone()),
+                            stmt.line, stmt.col));
+                }
+                let label = self.new_label();
+                self.data.push(Op::Label(label));
+                for row in rows.iter() {
+                    match self.gen_expr(row)? {
+                        (Operand::Const(b), Type::U8) => {
+                            self.data.push(Op::Byte(b));
+                        },
+                        _ => {
+                            return Err(CompileError::new(
+                                    CompileErrorType::NotConstant(
+                                        String::from("sprite rows")),
+                                    row.line, row.col));
+                        }
+                    };
+                }
+                self.symbols.insert(name.clone(), 
+                                    Symbol::Sprite(label, rows.len() as u8));
+            },
+            StmtKind::Expr(ref e) => {
+                let (op, _) = self.gen_expr(e)?;
+                self.release(&op);
+            },
+            StmtKind::If(ref cond, ref then_body, ref else_body) => {
+                let else_label = self.new_label();
+                self.gen_branch_if_false(cond, else_label)?;
+                for s in then_body.iter() {
+                    self.gen_stmt(s)?;
+                }
+                if else_body.is_empty() {
+                    self.ops.push(Op::Label(else_label));
+                } else {
+                    let end_label = self.new_label();
+                    self.ops.push(Op::Jmp(end_label));
+                    self.ops.push(Op::Label(else_label));
+                    for s in else_body.iter() {
+                        self.gen_stmt(s)?;
+                    }
+                    self.ops.push(Op::Label(end_label));
+                }
+            },
+            StmtKind::While(ref cond, ref body) => {
+                let top_label = self.new_label();
+                let end_label = self.new_label();
+                self.ops.push(Op::Label(top_label));
+                self.gen_branch_if_false(cond, end_label)?;
+                for s in body.iter() {
+                    self.gen_stmt(s)?;
+                }
+                self.ops.push(Op::Jmp(top_label));
+                self.ops.push(Op::Label(end_label));
+            }
+        };
+        return Ok(());
+    }
+
+    /// Generates a jump to a label taken when a condition is false
+    fn gen_branch_if_false(&mut self, cond: &Expr, label: usize) 
+        -> Result<(), CompileError> {
+        let (op, ty) = self.gen_expr(cond)?;
+        if ty != Type::Bool {
+            return Err(Compiler::mismatch(cond, Type::Bool, ty));
+        }
+        let op = self.place_in_reg(op, cond)?;
+        if let Operand::Reg(ref r, _) = op {
+            self.emit(Instruction::SKIP(data::SkipData::with_constant(
+                        r.clone(), 0, SkipType::NotEquals)));
+        }
+        self.ops.push(Op::Jmp(label));
+        self.release(&op);
+        return Ok(());
+    }
+
+    /// Generates code for an expression
+    ///
+    /// # Returns
+    ///
+    /// The location and type of the expression's value
+    fn gen_expr(&mut self, e: &Expr) 
+        -> Result<(Operand, Type), CompileError> {
+        match e.kind {
+            ExprKind::Number(n) => {
+                if n > 0xFF {
+                    return Err(CompileError::new(
+                            CompileErrorType::ConstantOutOfRange(n as i64),
+                            e.line, e.col));
+                }
+                return Ok((Operand::Const(n as u8), Type::U8));
+            },
+            ExprKind::Bool(b) => {
+                return Ok((Operand::Const(b as u8), Type::Bool));
+            },
+            ExprKind::Name(ref name) => {
+                return match self.symbols.get(name) {
+                    Some(Symbol::Var(r, t)) => {
+                        Ok((Operand::Reg(r.clone(), false), *t))
+                    },
+                    Some(Symbol::Sprite(_, _)) => {
+                        Err(CompileError::new(
+                                CompileErrorType::TypeMismatch(
+                                    format!("sprite '{}' used as a value",
+                                            name)),
+                                e.line, e.col))
+                    },
+                    None => {
+                        Err(CompileError::new(
+                                CompileErrorType::UnknownName(name.clone()),
+                                e.line, e.col))
+                    }
+                };
+            },
+            ExprKind::Unary(op, ref inner) => {
+                return self.gen_unary(e, op, inner);
+            },
+            ExprKind::Binary(op, ref lhs, ref rhs) => {
+                return self.gen_binary(e, op, lhs, rhs);
+            },
+            ExprKind::Call(ref name, ref args) => {
+                return self.gen_call(e, name, args);
+            }
+        };
+    }
+
+    /// Generates code for a unary expression
+    fn gen_unary(&mut self, e: &Expr, op: UnOp, inner: &Expr)
+        -> Result<(Operand, Type), CompileError> {
+        let (val, ty) = self.gen_expr(inner)?;
+        match op {
+            UnOp::Not => {
+                if ty != Type::Bool {
+                    return Err(Compiler::mismatch(inner, Type::Bool, ty));
+                }
+                if let Operand::Const(c) = val {
+                    return Ok((Operand::Const(c ^ 1), Type::Bool));
+                }
+                let src = self.place_in_reg(val, inner)?;
+                let t = self.alloc(e.line, e.col)?;
+                if let Operand::Reg(ref r, _) = src {
+                    self.emit_mov(&t, &Operand::Const(0));
+                    self.emit(Instruction::SKIP(
+                            data::SkipData::with_constant(
+                                r.clone(), 0, SkipType::NotEquals)));
+                    self.emit_mov(&t, &Operand::Const(1));
+                }
+                self.release(&src);
+                return Ok((Operand::Reg(t, true), Type::Bool));
+            },
+            UnOp::BitNot => {
+                if ty != Type::U8 {
+                    return Err(Compiler::mismatch(inner, Type::U8, ty));
+                }
+                if let Operand::Const(c) = val {
+                    return Ok((Operand::Const(!c), Type::U8));
+                }
+                let t = self.place_in_temp(val, inner)?;
+                let mask = self.alloc(e.line, e.col)?;
+                self.emit_mov(&mask, &Operand::Const(0xFF));
+                self.emit(Instruction::XOR(data::XorData::new(
+                            t.clone(), mask.clone())));
+                self.release(&Operand::Reg(mask, true));
+                return Ok((Operand::Reg(t, true), Type::U8));
+            }
+        };
+    }
+
+    /// Folds a binary operation on two constants
+    fn fold(op: BinOp, a: u8, b: u8) -> u8 {
+        return match op {
+            BinOp::Add => a.wrapping_add(b),
+            BinOp::Sub => a.wrapping_sub(b),
+            BinOp::Mul => a.wrapping_mul(b),
+            BinOp::Div => a.checked_div(b).unwrap_or(0),
+            BinOp::Mod => a.checked_rem(b).unwrap_or(a),
+            BinOp::And | BinOp::LogAnd => a & b,
+            BinOp::Or | BinOp::LogOr => a | b,
+            BinOp::Xor => a ^ b,
+            BinOp::Shl => if b >= 8 { 0 } else { a << b },
+            BinOp::Shr => if b >= 8 { 0 } else { a >> b },
+            BinOp::Eq => (a == b) as u8,
+            BinOp::Ne => (a != b) as u8,
+            BinOp::Lt => (a < b) as u8,
+            BinOp::Le => (a <= b) as u8,
+            BinOp::Gt => (a > b) as u8,
+            BinOp::Ge => (a >= b) as u8
+        };
+    }
+
+    /// Generates code for a binary expression
+    fn gen_binary(&mut self, e: &Expr, op: BinOp, lhs: &Expr, rhs: &Expr)
+        -> Result<(Operand, Type), CompileError> {
+        let (a, at) = self.gen_expr(lhs)?;
+        let (b, bt) = self.gen_expr(rhs)?;
+
+        //check the operand types
+        let result_type = match op {
+            BinOp::Eq | BinOp::Ne => {
+                if at == Type::Unit {
+                    return Err(Compiler::mismatch(lhs, Type::U8, at));
+                }
+                if bt != at {
+                    return Err(Compiler::mismatch(rhs, at, bt));
+                }
+                Type::Bool
+            },
+            BinOp::LogAnd | BinOp::LogOr => {
+                if at != Type::Bool {
+                    return Err(Compiler::mismatch(lhs, Type::Bool, at));
+                }
+                if bt != Type::Bool {
+                    return Err(Compiler::mismatch(rhs, Type::Bool, bt));
+                }
+                Type::Bool
+            },
+            _ => {
+                if at != Type::U8 {
+                    return Err(Compiler::mismatch(lhs, Type::U8, at));
+                }
+                if bt != Type::U8 {
+                    return Err(Compiler::mismatch(rhs, Type::U8, bt));
+                }
+                match op {
+                    BinOp::Lt | BinOp::Le | BinOp::Gt | BinOp::Ge => {
+                        Type::Bool
+                    },
+                    _ => Type::U8
+                }
+            }
+        };
+
+        //fold constant operands
+        if let (Operand::Const(x), Operand::Const(y)) = (&a, &b) {
+            return Ok((Operand::Const(Compiler::fold(op, *x, *y)), 
+                       result_type));
+        }
+
+        let result = match op {
+            BinOp::Add | BinOp::Sub => {
+                let t = self.place_in_temp(a, lhs)?;
+                match b {
+                    Operand::Const(c) => {
+                        let c = if op == BinOp::Sub {
+                            c.wrapping_neg()
+                        } else {
+                            c
+                        };
+                        if c != 0 {
+                            self.emit(Instruction::ADD(
+                                    data::AddData::with_constant(
+                                        t.clone(), c as u16)));
+                        }
+                    },
+                    Operand::Reg(ref r, _) => {
+                        if op == BinOp::Sub {
+                            self.emit(Instruction::SUB(data::SubData::new(
+                                        t.clone(), r.clone())));
+                        } else {
+                            self.emit(Instruction::ADD(
+                                    data::AddData::with_register(
+                                        t.clone(), r.clone())));
+                        }
+                    }
+                };
+                self.release(&b);
+                t
+            },
+            BinOp::And | BinOp::Or | BinOp::Xor | 
+                BinOp::LogAnd | BinOp::LogOr => {
+                let t = self.place_in_temp(a, lhs)?;
+                let b = self.place_in_reg(b, rhs)?;
+                if let Operand::Reg(ref r, _) = b {
+                    let instr = match op {
+                        BinOp::And | BinOp::LogAnd => {
+                            Instruction::AND(data::AndData::new(
+                                    t.clone(), r.clone()))
+                        },
+                        BinOp::Or | BinOp::LogOr => {
+                            Instruction::OR(data::OrData::new(
+                                    t.clone(), r.clone()))
+                        },
+                        _ => {
+                            Instruction::XOR(data::XorData::new(
+                                    t.clone(), r.clone()))
+                        }
+                    };
+                    self.emit(instr);
+                }
+                self.release(&b);
+                t
+            },
+            BinOp::Shl | BinOp::Shr => {
+                let count = match b {
+                    Operand::Const(c) => c,
+                    Operand::Reg(_, _) => {
+                        return Err(CompileError::new(
+                                CompileErrorType::NotConstant(
+                                    String::from("shift amounts")),
+                                rhs.line, rhs.col));
+                    }
+                };
+                let t = self.place_in_temp(a, lhs)?;
+                if count >= 8 {
+                    self.emit_mov(&t, &Operand::Const(0));
+                } else {
+                    for _ in 0..count {
+                        if op == BinOp::Shl {
+                            self.emit(Instruction::SHL(
+                                    data::ShlData::new(t.clone())));
+                        } else {
+                            self.emit(Instruction::SHR(
+                                    data::ShrData::new(t.clone())));
+                        }
+                    }
+                }
+                t
+            },
+            BinOp::Mul | BinOp::Div | BinOp::Mod => {
+                //call the runtime routine with VC and VD as arguments
+                self.emit_mov(&Register::VC, &a);
+                self.emit_mov(&Register::VD, &b);
+                self.release(&a);
+                self.release(&b);
+                let label = if op == BinOp::Mul {
+                    self.routine_label(true)
+                } else {
+                    self.routine_label(false)
+                };
+                self.ops.push(Op::Call(label));
+                let t = self.alloc(e.line, e.col)?;
+                if op == BinOp::Mod {
+                    self.emit_mov(&t, &Operand::Reg(Register::VE, false));
+                } else {
+                    self.emit_mov(&t, &Operand::Reg(Register::VC, false));
+                }
+                t
+            },
+            BinOp::Eq | BinOp::Ne => {
+                //keep any constant on the right-hand side
+                let (a, b) = match a {
+                    Operand::Const(_) => (b, a),
+                    _ => (a, b)
+                };
+                let t = self.alloc(e.line, e.col)?;
+                self.emit_mov(&t, &Operand::Const(1));
+                let kind = if op == BinOp::Eq {
+                    SkipType::Equals
+                } else {
+                    SkipType::NotEquals
+                };
+                if let Operand::Reg(ref x, _) = a {
+                    let skip = match b {
+                        Operand::Const(c) => {
+                            data::SkipData::with_constant(x.clone(), c, kind)
+                        },
+                        Operand::Reg(ref y, _) => {
+                            data::SkipData::with_register(x.clone(), 
+                                                          y.clone(), kind)
+                        }
+                    };
+                    self.emit(Instruction::SKIP(skip));
+                }
+                self.emit_mov(&t, &Operand::Const(0));
+                self.release(&a);
+                self.release(&b);
+                t
+            },
+            BinOp::Lt | BinOp::Le | BinOp::Gt | BinOp::Ge => {
+                //VF is set when no borrow occurs, which SUB gives
+                //for a >= b and SUBN gives for b >= a
+                let t = self.place_in_temp(a, lhs)?;
+                let b = self.place_in_reg(b, rhs)?;
+                if let Operand::Reg(ref r, _) = b {
+                    if op == BinOp::Lt || op == BinOp::Ge {
+                        self.emit(Instruction::SUB(data::SubData::new(
+                                    t.clone(), r.clone())));
+                    } else {
+                        self.emit(Instruction::SUBN(data::SubnData::new(
+                                    t.clone(), r.clone())));
+                    }
+                }
+                self.release(&b);
+                if op == BinOp::Ge || op == BinOp::Le {
+                    self.emit_mov(&t, &Operand::Reg(Register::VF, false));
+                } else {
+                    self.emit_mov(&t, &Operand::Const(0));
+                    self.emit(Instruction::SKIP(
+                            data::SkipData::with_constant(
+                                Register::VF, 1, SkipType::Equals)));
+                    self.emit_mov(&t, &Operand::Const(1));
+                }
+                t
+            }
+        };
+        return Ok((Operand::Reg(result, true), result_type));
+    }
+
+    /// Gets the label of a runtime routine, marking it as used
+    fn routine_label(&mut self, mul: bool) -> usize {
+        let existing = if mul { self.mul_label } else { self.div_label };
+        if let Some(label) = existing {
+            return label;
+        }
+        let label = self.new_label();
+        if mul {
+            self.mul_label = Some(label);
+        } else {
+            self.div_label = Some(label);
+        }
+        return label;
+    }
+
+    /// Checks the argument count of a builtin call
+    fn check_args(e: &Expr, name: &str, args: &[Expr], count: usize)
+        -> Result<(), CompileError> {
+        if args.len() != count {
+            return Err(CompileError::new(
+                    CompileErrorType::TypeMismatch(
+                        format!("{} takes {} argument(s), found {}",
+                                name, count, args.len())),
+                    e.line, e.col));
+        }
+        return Ok(());
+    }
+
+    /// Generates code for a builtin call
+    fn gen_call(&mut self, e: &Expr, name: &str, args: &[Expr])
+        -> Result<(Operand, Type), CompileError> {
+        match name {
+            "clear" => {
+                Compiler::check_args(e, name, args, 0)?;
+                self.emit(Instruction::CLS);
+                return Ok((Operand::Const(0), Type::Unit));
+            },
+            "key" => {
+                Compiler::check_args(e, name, args, 0)?;
+                let t = self.alloc(e.line, e.col)?;
+                self.emit(Instruction::KEY(data::KeyData::new(t.clone())));
+                return Ok((Operand::Reg(t, true), Type::U8));
+            },
+            "random" => {
+                Compiler::check_args(e, name, args, 1)?;
+                let mask = match self.gen_expr(&args[0])? {
+                    (Operand::Const(c), Type::U8) => c,
+                    _ => {
+                        return Err(CompileError::new(
+                                CompileErrorType::NotConstant(
+                                    String::from("random masks")),
+                                args[0].line, args[0].col));
+                    }
+                };
+                let t = self.alloc(e.line, e.col)?;
+                self.emit(Instruction::RAND(data::RandData::new(
+                            t.clone(), mask)));
+                return Ok((Operand::Reg(t, true), Type::U8));
+            },
+            "draw" => {
+                Compiler::check_args(e, name, args, 3)?;
+                let (label, height) = match args[2].kind {
+                    ExprKind::Name(ref s) => match self.symbols.get(s) {
+                        Some(Symbol::Sprite(l, h)) => (*l, *h),
+                        _ => {
+                            return Err(CompileError::new(
+                                    CompileErrorType::TypeMismatch(
+                                        format!("'{}' is not a sprite", s)),
+                                    args[2].line, args[2].col));
+                        }
+                    },
+                    _ => {
+                        return Err(CompileError::new(
+                                CompileErrorType::TypeMismatch(
+                                    String::from("expected a sprite name")),
+                                args[2].line, args[2].col));
+                    }
+                };
+                let mut coords = Vec::new();
+                for arg in args[0..2].iter() {
+                    let (op, ty) = self.gen_expr(arg)?;
+                    if ty != Type::U8 {
+                        return Err(Compiler::mismatch(arg, Type::U8, ty));
+                    }
+                    coords.push(self.place_in_reg(op, arg)?);
+                }
+                self.ops.push(Op::LoadI(label));
+                if let (Operand::Reg(ref x, _), Operand::Reg(ref y, _)) = 
+                    (&coords[0], &coords[1]) {
+                    self.emit(Instruction::DRAW(data::DrawData::new(
+                                x.clone(), y.clone(), height)));
+                }
+                for op in coords.iter() {
+                    self.release(op);
+                }
+                let t = self.alloc(e.line, e.col)?;
+                self.emit_mov(&t, &Operand::Reg(Register::VF, false));
+                return Ok((Operand::Reg(t, true), Type::Bool));
+            },
+            _ => {
+                return Err(CompileError::new(
+                        CompileErrorType::UnknownName(String::from(name)),
+                        e.line, e.col));
+            }
+        };
+    }
+
+    /// Generates the multiplication routine,
+    /// computing `VC = VC * VD` by shift-and-add.
+    /// Clobbers `VD`, `VE` and `VF`.
+    fn gen_mul_routine(&mut self, label: usize) {
+        let top = self.new_label();
+        let done = self.new_label();
+        self.ops.push(Op::Label(label));
+        self.emit_mov(&Register::VE, &Operand::Const(0));
+        self.ops.push(Op::Label(top));
+        self.emit(Instruction::SKIP(data::SkipData::with_constant(
+                    Register::VD, 0, SkipType::NotEquals)));
+        self.ops.push(Op::Jmp(done));
+        self.emit(Instruction::SHR(data::ShrData::new(Register::VD)));
+        self.emit(Instruction::SKIP(data::SkipData::with_constant(
+                    Register::VF, 0, SkipType::Equals)));
+        self.emit(Instruction::ADD(data::AddData::with_register(
+                    Register::VE, Register::VC)));
+        self.emit(Instruction::SHL(data::ShlData::new(Register::VC)));
+        self.ops.push(Op::Jmp(top));
+        self.ops.push(Op::Label(done));
+        self.emit_mov(&Register::VC, &Operand::Reg(Register::VE, false));
+        self.emit(Instruction::RET);
+    }
+
+    /// Generates the division routine, computing 
+    /// `VC = VC / VD` and `VE = VC % VD` by repeated subtraction.
+    /// Division by zero yields a quotient of zero and leaves the
+    /// dividend as the remainder. Clobbers `VB` and `VF`.
+    fn gen_div_routine(&mut self, label: usize) {
+        let top = self.new_label();
+        let done = self.new_label();
+        self.ops.push(Op::Label(label));
+        self.emit_mov(&Register::VE, &Operand::Reg(Register::VC, false));
+        self.emit_mov(&Register::VC, &Operand::Const(0));
+        self.emit(Instruction::SKIP(data::SkipData::with_constant(
+                    Register::VD, 0, SkipType::NotEquals)));
+        self.ops.push(Op::Jmp(done));
+        self.ops.push(Op::Label(top));
+        self.emit_mov(&Register::VB, &Operand::Reg(Register::VE, false));
+        self.emit(Instruction::SUB(data::SubData::new(
+                    Register::VB, Register::VD)));
+        self.emit(Instruction::SKIP(data::SkipData::with_constant(
+                    Register::VF, 1, SkipType::Equals)));
+        self.ops.push(Op::Jmp(done));
+        self.emit_mov(&Register::VE, &Operand::Reg(Register::VB, false));
+        self.emit(Instruction::ADD(data::AddData::with_constant(
+                    Register::VC, 1)));
+        self.ops.push(Op::Jmp(top));
+        self.ops.push(Op::Label(done));
+        self.emit(Instruction::RET);
+    }
+}
+
+//unit tests
+#[cfg(test)]
+mod tests {
+    //import the compiler
+    use super::*;
+    use super::super::super::codegen::CodeGen;
+
+    //compiles source and returns the opcodes of the program
+    fn opcodes(src: &str) -> Vec<u16> {
+        return compile(src).unwrap().get_instructions().iter()
+                           .map(|i| i.gen_opcode()).collect();
+    }
+
+    //this test checks a declaration and constant addition
+    #[test]
+    fn test_simple_assignment() {
+        let ops = opcodes("var x: u8 = 5; x = x + 3 - 1;");
+        assert_eq!(ops, vec![0x6005, 0x8100, 0x7103, 0x71FF, 
+                             0x8010, 0x120A]);
+    }
+
+    //this test checks constant folding
+    #[test]
+    fn test_constant_folding() {
+        let ops = opcodes("var x: u8 = (2 + 3) * 4 << 1;");
+        assert_eq!(ops, vec![0x6028, 0x1202]);
+    }
+
+    //this test checks that comparisons produce booleans
+    #[test]
+    fn test_comparison() {
+        let ops = opcodes("var a: u8 = 1; var b: bool = a < 4;");
+        assert_eq!(ops, vec![0x6001, 0x8100, 0x6204, 0x8125, 
+                             0x6100, 0x3F01, 0x6101, 0x120E]);
+    }
+
+    //this test checks that the multiplication routine is 
+    //generated only when used
+    #[test]
+    fn test_mul_routine() {
+        let without = opcodes("var a: u8 = 3;");
+        assert_eq!(without.len(), 2);
+        let with = opcodes("var a: u8 = 3; a = a * a;");
+        assert_eq!(with[3], 0x220E);
+        assert_eq!(*with.last().unwrap(), 0x00EE);
+    }
+
+    //this test checks that sprites are placed after the code
+    #[test]
+    fn test_sprite_draw() {
+        let prog = compile("sprite dot = [0x80]; draw(1, 2, dot);").unwrap();
+        let ops: Vec<u16> = prog.get_instructions().iter()
+                                .map(|i| i.gen_opcode()).collect();
+        assert_eq!(ops, vec![0x6001, 0x6102, 0xA20C, 0xD011, 
+                             0x80F0, 0x120A]);
+        assert_eq!(prog.get_data(), &[0x80]);
+    }
+
+    //this test checks type errors
+    #[test]
+    fn test_type_errors() {
+        let err = compile("var a: u8 = true;").unwrap_err();
+        assert!(matches!(err.get_type(), CompileErrorType::TypeMismatch(_)));
+        let err = compile("var a: u8 = 1;\nif a { }").unwrap_err();
+        assert_eq!((err.get_line(), err.get_col()), (2, 4));
+    }
+
+    //this test checks that register exhaustion is reported
+    #[test]
+    fn test_out_of_registers() {
+        let mut src = String::new();
+        for i in 0..12 {
+            src.push_str(&format!("var v{}: u8 = key();\n", i));
+        }
+        let err = compile(&src).unwrap_err();
+        assert_eq!(err.get_type(), CompileErrorType::OutOfRegisters);
+        assert_eq!(err.get_line(), 12);
+    }
+}
+
+//end of file
@@ -0,0 +1,222 @@
+/*
+ * lexer.rs
+ * Splits expression language source into tokens
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//usage statements
+use super::token::{Token, TokenKind};
+use super::super::error::{CompileError, CompileErrorType};
+
+/// Converts source text into a list of tokens
+///
+/// # Argument
+///
+/// * `src` - The source text to tokenize
+///
+/// # Returns
+///
+/// The tokens of the source, ending with `TokenKind::Eof`,
+/// or a `CompileError` if an invalid character is found
+pub fn tokenize(src: &str) -> Result<Vec<Token>, CompileError> {
+    let chars: Vec<char> = src.chars().collect();
+    let mut tokens = Vec::new();
+    let mut pos = 0;
+    let mut line = 1;
+    let mut col = 1;
+
+    while pos < chars.len() {
+        let c = chars[pos];
+        let next = chars.get(pos + 1).cloned();
+
+        //skip whitespace
+        if c == '\n' {
+            pos += 1;
+            line += 1;
+            col = 1;
+            continue;
+        }
+        if c.is_whitespace() {
+            pos += 1;
+            col += 1;
+            continue;
+        }
+
+        //skip line comments
+        if c == '/' && next == Some('/') {
+            while pos < chars.len() && chars[pos] != '\n' {
+                pos += 1;
+            }
+            continue;
+        }
+
+        let start_col = col;
+
+        //identifiers and keywords
+        if c.is_ascii_alphabetic() || c == '_' {
+            let mut word = String::new();
+            while pos < chars.len() && 
+                (chars[pos].is_ascii_alphanumeric() || chars[pos] == '_') {
+                word.push(chars[pos]);
+                pos += 1;
+                col += 1;
+            }
+            let kind = match word.as_str() {
+                "var" => TokenKind::Var,
+                "if" => TokenKind::If,
+                "else" => TokenKind::Else,
+                "while" => TokenKind::While,
+                "true" => TokenKind::True,
+                "false" => TokenKind::False,
+                "sprite" => TokenKind::Sprite,
+                "u8" => TokenKind::U8,
+                "bool" => TokenKind::Bool,
+                _ => TokenKind::Ident(word)
+            };
+            tokens.push(Token { kind, line, col: start_col });
+            continue;
+        }
+
+        //numeric literals
+        if c.is_ascii_digit() {
+            let mut text = String::new();
+            while pos < chars.len() && 
+                (chars[pos].is_ascii_alphanumeric() || chars[pos] == '_') {
+                if chars[pos] != '_' {
+                    text.push(chars[pos]);
+                }
+                pos += 1;
+                col += 1;
+            }
+            let parsed = if text.starts_with("0x") || text.starts_with("0X") {
+                u32::from_str_radix(&text[2..], 16)
+            } else if text.starts_with("0b") || text.starts_with("0B") {
+                u32::from_str_radix(&text[2..], 2)
+            } else {
+                text.parse::<u32>()
+            };
+            match parsed {
+                Ok(n) => tokens.push(Token { 
+                    kind: TokenKind::Number(n), 
+                    line, 
+                    col: start_col 
+                }),
+                Err(_) => return Err(CompileError::new(
+                        CompileErrorType::UnexpectedToken(text),
+                        line, start_col))
+            };
+            continue;
+        }
+
+        //two-character operators
+        let double = match (c, next) {
+            ('<', Some('<')) => Some(TokenKind::Shl),
+            ('>', Some('>')) => Some(TokenKind::Shr),
+            ('=', Some('=')) => Some(TokenKind::EqEq),
+            ('!', Some('=')) => Some(TokenKind::NotEq),
+            ('<', Some('=')) => Some(TokenKind::Le),
+            ('>', Some('=')) => Some(TokenKind::Ge),
+            ('&', Some('&')) => Some(TokenKind::AndAnd),
+            ('|', Some('|')) => Some(TokenKind::OrOr),
+            _ => None
+        };
+        if let Some(kind) = double {
+            tokens.push(Token { kind, line, col: start_col });
+            pos += 2;
+            col += 2;
+            continue;
+        }
+
+        //single-character tokens
+        let kind = match c {
+            '(' => TokenKind::LParen,
+            ')' => TokenKind::RParen,
+            '{' => TokenKind::LBrace,
+            '}' => TokenKind::RBrace,
+            '[' => TokenKind::LBracket,
+            ']' => TokenKind::RBracket,
+            ',' => TokenKind::Comma,
+            ';' => TokenKind::Semi,
+            ':' => TokenKind::Colon,
+            '=' => TokenKind::Assign,
+            '+' => TokenKind::Plus,
+            '-' => TokenKind::Minus,
+            '*' => TokenKind::Star,
+            '/' => TokenKind::Slash,
+            '%' => TokenKind::Percent,
+            '&' => TokenKind::Amp,
+            '|' => TokenKind::Pipe,
+            '^' => TokenKind::Caret,
+            '~' => TokenKind::Tilde,
+            '!' => TokenKind::Bang,
+            '<' => TokenKind::Lt,
+            '>' => TokenKind::Gt,
+            _ => {
+                return Err(CompileError::new(
+                        CompileErrorType::UnexpectedChar(c), 
+                        line, start_col));
+            }
+        };
+        tokens.push(Token { kind, line, col: start_col });
+        pos += 1;
+        col += 1;
+    }
+
+    //add the end marker
+    tokens.push(Token { kind: TokenKind::Eof, line, col });
+    return Ok(tokens);
+}
+
+//unit tests
+#[cfg(test)]
+mod tests {
+    //import the tokenizer
+    use super::*;
+
+    //this test checks tokenizing a simple statement
+    #[test]
+    fn test_tokenize_statement() {
+        let toks = tokenize("var x: u8 = 0x1F << 2; // comment").unwrap();
+        let kinds: Vec<TokenKind> = toks.into_iter()
+                                        .map(|t| t.kind).collect();
+        assert_eq!(kinds, vec![TokenKind::Var,
+                               TokenKind::Ident(String::from("x")),
+                               TokenKind::Colon, TokenKind::U8,
+                               TokenKind::Assign, TokenKind::Number(0x1F),
+                               TokenKind::Shl, TokenKind::Number(2),
+                               TokenKind::Semi, TokenKind::Eof]);
+    }
+
+    //this test checks that token positions are tracked
+    #[test]
+    fn test_token_positions() {
+        let toks = tokenize("a\n  b").unwrap();
+        assert_eq!((toks[1].line, toks[1].col), (2, 3));
+    }
+
+    //this test checks that bad characters are rejected
+    #[test]
+    fn test_bad_character() {
+        let err = tokenize("x = $;").unwrap_err();
+        assert_eq!(err.get_type(), CompileErrorType::UnexpectedChar('$'));
+        assert_eq!(err.get_col(), 5);
+    }
+}
+
+//end of file
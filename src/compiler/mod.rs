@@ -0,0 +1,52 @@
+/*
+ * mod.rs
+ * Module export file for the compiler module
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! A small typed expression language that compiles to Chip-8.
+//!
+//! Programs are lists of statements. Variables are declared with
+//! `var name: u8 = expr;` or `var name: bool = expr;` and live in
+//! registers `V0` through `VA` for the rest of the program. Sprites
+//! are declared with `sprite name = [0x80, 0x40];` and are placed
+//! after the code. Expressions support 8-bit wrapping arithmetic
+//! (`+ - * / %`), bitwise operators (`& | ^ ~`), shifts by a constant
+//! (`<< >>`), comparisons (`== != < <= > >=`) and boolean operators
+//! (`! && ||`, evaluated without short-circuiting). The builtins are
+//! `draw(x, y, sprite)`, which returns whether a pixel was erased,
+//! `key()`, `random(mask)` and `clear()`. Control flow is written
+//! with `if cond { } else { }` and `while cond { }`, and the program
+//! halts on a self-jump when it runs off the end.
+//!
+//! Multiplication and division are lowered to subroutines placed
+//! after the main code, which use `VB` through `VE` as scratch space.
+
+//exports
+mod token;
+mod lexer;
+mod ast;
+mod parser;
+#[allow(clippy::module_inception)]
+mod compiler;
+pub use compiler::compile;
+mod program;
+pub use program::Program;
+
+//end of file
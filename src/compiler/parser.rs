@@ -0,0 +1,365 @@
+/*
+ * parser.rs
+ * Parses expression language tokens into a syntax tree
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//usage statements
+use super::token::{Token, TokenKind};
+use super::ast::{BinOp, Expr, ExprKind, Stmt, StmtKind, Type, UnOp};
+use super::super::error::{CompileError, CompileErrorType};
+
+/// A recursive-descent parser over a token list
+pub struct Parser {
+    /// The tokens being parsed
+    tokens: Vec<Token>,
+
+    /// The index of the current token
+    pos: usize
+}
+
+//implementation
+impl Parser {
+    /// Constructs a new `Parser` instance
+    ///
+    /// # Argument
+    ///
+    /// * `new_tokens` - The tokens to parse, ending with `Eof`
+    ///
+    /// # Returns
+    ///
+    /// A new `Parser` positioned at the first token
+    pub fn new(new_tokens: Vec<Token>) -> Parser {
+        return Parser {
+            tokens: new_tokens,
+            pos: 0
+        };
+    }
+
+    /// Parses an entire program
+    ///
+    /// # Returns
+    ///
+    /// The statements of the program, or a `CompileError`
+    pub fn parse_program(&mut self) -> Result<Vec<Stmt>, CompileError> {
+        let mut stmts = Vec::new();
+        while self.peek().kind != TokenKind::Eof {
+            stmts.push(self.parse_stmt()?);
+        }
+        return Ok(stmts);
+    }
+
+    /// Gets the current token
+    fn peek(&self) -> &Token {
+        return &self.tokens[self.pos];
+    }
+
+    /// Consumes and returns the current token
+    fn advance(&mut self) -> Token {
+        let tok = self.tokens[self.pos].clone();
+        if tok.kind != TokenKind::Eof {
+            self.pos += 1;
+        }
+        return tok;
+    }
+
+    /// Consumes the current token if it has the given kind
+    fn accept(&mut self, kind: &TokenKind) -> bool {
+        if self.peek().kind == *kind {
+            self.advance();
+            return true;
+        }
+        return false;
+    }
+
+    /// Builds an error for the current token
+    fn unexpected(&self) -> CompileError {
+        let tok = self.peek();
+        let err_type = match tok.kind {
+            TokenKind::Eof => CompileErrorType::UnexpectedEnd,
+            ref k => CompileErrorType::UnexpectedToken(format!("{}", k))
+        };
+        return CompileError::new(err_type, tok.line, tok.col);
+    }
+
+    /// Consumes a token of the given kind or fails
+    fn expect(&mut self, kind: TokenKind) -> Result<Token, CompileError> {
+        if self.peek().kind == kind {
+            return Ok(self.advance());
+        }
+        return Err(self.unexpected());
+    }
+
+    /// Consumes an identifier or fails
+    fn expect_ident(&mut self) -> Result<String, CompileError> {
+        if let TokenKind::Ident(ref name) = self.peek().kind {
+            let name = name.clone();
+            self.advance();
+            return Ok(name);
+        }
+        return Err(self.unexpected());
+    }
+
+    /// Parses a brace-delimited block of statements
+    fn parse_block(&mut self) -> Result<Vec<Stmt>, CompileError> {
+        self.expect(TokenKind::LBrace)?;
+        let mut stmts = Vec::new();
+        while !self.accept(&TokenKind::RBrace) {
+            if self.peek().kind == TokenKind::Eof {
+                return Err(self.unexpected());
+            }
+            stmts.push(self.parse_stmt()?);
+        }
+        return Ok(stmts);
+    }
+
+    /// Parses a single statement
+    fn parse_stmt(&mut self) -> Result<Stmt, CompileError> {
+        let start = self.peek().clone();
+        let kind = match start.kind {
+            TokenKind::Var => {
+                self.advance();
+                let name = self.expect_ident()?;
+                self.expect(TokenKind::Colon)?;
+                let ty = if self.accept(&TokenKind::U8) {
+                    Type::U8
+                } else if self.accept(&TokenKind::Bool) {
+                    Type::Bool
+                } else {
+                    return Err(self.unexpected());
+                };
+                self.expect(TokenKind::Assign)?;
+                let init = self.parse_expr()?;
+                self.expect(TokenKind::Semi)?;
+                StmtKind::Var(name, ty, init)
+            },
+            TokenKind::Sprite => {
+                self.advance();
+                let name = self.expect_ident()?;
+                self.expect(TokenKind::Assign)?;
+                self.expect(TokenKind::LBracket)?;
+                let mut rows = Vec::new();
+                if !self.accept(&TokenKind::RBracket) {
+                    loop {
+                        rows.push(self.parse_expr()?);
+                        if self.accept(&TokenKind::RBracket) {
+                            break;
+                        }
+                        self.expect(TokenKind::Comma)?;
+                    }
+                }
+                self.expect(TokenKind::Semi)?;
+                StmtKind::Sprite(name, rows)
+            },
+            TokenKind::If => {
+                self.advance();
+                let cond = self.parse_expr()?;
+                let then_body = self.parse_block()?;
+                let else_body = if self.accept(&TokenKind::Else) {
+                    if self.peek().kind == TokenKind::If {
+                        vec![self.parse_stmt()?]
+                    } else {
+                        self.parse_block()?
+                    }
+                } else {
+                    Vec::new()
+                };
+                StmtKind::If(cond, then_body, else_body)
+            },
+            TokenKind::While => {
+                self.advance();
+                let cond = self.parse_expr()?;
+                let body = self.parse_block()?;
+                StmtKind::While(cond, body)
+            },
+            TokenKind::Ident(ref name) 
+                if self.tokens[self.pos + 1].kind == TokenKind::Assign => {
+                let name = name.clone();
+                self.advance();
+                self.advance();
+                let value = self.parse_expr()?;
+                self.expect(TokenKind::Semi)?;
+                StmtKind::Assign(name, value)
+            },
+            _ => {
+                let expr = self.parse_expr()?;
+                self.expect(TokenKind::Semi)?;
+                StmtKind::Expr(expr)
+            }
+        };
+        return Ok(Stmt { kind, line: start.line, col: start.col });
+    }
+
+    /// Parses an expression
+    pub fn parse_expr(&mut self) -> Result<Expr, CompileError> {
+        return self.parse_binary(0);
+    }
+
+    /// Gets the binary operator and precedence level of a token
+    fn binop_of(kind: &TokenKind) -> Option<(BinOp, usize)> {
+        return match *kind {
+            TokenKind::OrOr => Some((BinOp::LogOr, 0)),
+            TokenKind::AndAnd => Some((BinOp::LogAnd, 1)),
+            TokenKind::EqEq => Some((BinOp::Eq, 2)),
+            TokenKind::NotEq => Some((BinOp::Ne, 2)),
+            TokenKind::Lt => Some((BinOp::Lt, 2)),
+            TokenKind::Le => Some((BinOp::Le, 2)),
+            TokenKind::Gt => Some((BinOp::Gt, 2)),
+            TokenKind::Ge => Some((BinOp::Ge, 2)),
+            TokenKind::Pipe => Some((BinOp::Or, 3)),
+            TokenKind::Caret => Some((BinOp::Xor, 4)),
+            TokenKind::Amp => Some((BinOp::And, 5)),
+            TokenKind::Shl => Some((BinOp::Shl, 6)),
+            TokenKind::Shr => Some((BinOp::Shr, 6)),
+            TokenKind::Plus => Some((BinOp::Add, 7)),
+            TokenKind::Minus => Some((BinOp::Sub, 7)),
+            TokenKind::Star => Some((BinOp::Mul, 8)),
+            TokenKind::Slash => Some((BinOp::Div, 8)),
+            TokenKind::Percent => Some((BinOp::Mod, 8)),
+            _ => None
+        };
+    }
+
+    /// Parses a left-associative chain of binary operators
+    /// at or above a given precedence level
+    fn parse_binary(&mut self, min_prec: usize) 
+        -> Result<Expr, CompileError> {
+        let mut lhs = self.parse_unary()?;
+        while let Some((op, prec)) = Parser::binop_of(&self.peek().kind) {
+            if prec < min_prec {
+                break;
+            }
+            self.advance();
+            let rhs = self.parse_binary(prec + 1)?;
+            let (line, col) = (lhs.line, lhs.col);
+            lhs = Expr {
+                kind: ExprKind::Binary(op, Box::new(lhs), Box::new(rhs)),
+                line,
+                col
+            };
+        }
+        return Ok(lhs);
+    }
+
+    /// Parses a unary expression
+    fn parse_unary(&mut self) -> Result<Expr, CompileError> {
+        let tok = self.peek().clone();
+        let op = match tok.kind {
+            TokenKind::Bang => UnOp::Not,
+            TokenKind::Tilde => UnOp::BitNot,
+            _ => return self.parse_primary()
+        };
+        self.advance();
+        let operand = self.parse_unary()?;
+        return Ok(Expr {
+            kind: ExprKind::Unary(op, Box::new(operand)),
+            line: tok.line,
+            col: tok.col
+        });
+    }
+
+    /// Parses a literal, name, call or parenthesized expression
+    fn parse_primary(&mut self) -> Result<Expr, CompileError> {
+        let tok = self.peek().clone();
+        match tok.kind {
+            TokenKind::Number(_) | TokenKind::True | TokenKind::False |
+                TokenKind::LParen | TokenKind::Ident(_) => self.advance(),
+            _ => return Err(self.unexpected())
+        };
+        let kind = match tok.kind {
+            TokenKind::Number(n) => ExprKind::Number(n),
+            TokenKind::True => ExprKind::Bool(true),
+            TokenKind::False => ExprKind::Bool(false),
+            TokenKind::LParen => {
+                let inner = self.parse_expr()?;
+                self.expect(TokenKind::RParen)?;
+                return Ok(inner);
+            },
+            TokenKind::Ident(name) => {
+                if self.accept(&TokenKind::LParen) {
+                    let mut args = Vec::new();
+                    if !self.accept(&TokenKind::RParen) {
+                        loop {
+                            args.push(self.parse_expr()?);
+                            if self.accept(&TokenKind::RParen) {
+                                break;
+                            }
+                            self.expect(TokenKind::Comma)?;
+                        }
+                    }
+                    ExprKind::Call(name, args)
+                } else {
+                    ExprKind::Name(name)
+                }
+            },
+            _ => unreachable!()
+        };
+        return Ok(Expr { kind, line: tok.line, col: tok.col });
+    }
+}
+
+//unit tests
+#[cfg(test)]
+mod tests {
+    //import the parser
+    use super::*;
+    use super::super::lexer::tokenize;
+
+    //parses a single expression from source
+    fn expr(src: &str) -> Expr {
+        return Parser::new(tokenize(src).unwrap()).parse_expr().unwrap();
+    }
+
+    //this test checks operator precedence
+    #[test]
+    fn test_precedence() {
+        let e = expr("1 + 2 * 3 == 7");
+        match e.kind {
+            ExprKind::Binary(BinOp::Eq, lhs, _) => match lhs.kind {
+                ExprKind::Binary(BinOp::Add, _, rhs) => {
+                    assert!(matches!(rhs.kind, 
+                                     ExprKind::Binary(BinOp::Mul, _, _)));
+                },
+                _ => panic!("expected addition")
+            },
+            _ => panic!("expected comparison")
+        };
+    }
+
+    //this test checks statement parsing
+    #[test]
+    fn test_statements() {
+        let src = "var x: u8 = 1; while x < 5 { x = x + 1; } draw(x, 0, s);";
+        let stmts = Parser::new(tokenize(src).unwrap())
+                        .parse_program().unwrap();
+        assert_eq!(stmts.len(), 3);
+        assert!(matches!(stmts[1].kind, StmtKind::While(_, _)));
+        assert!(matches!(stmts[2].kind, StmtKind::Expr(_)));
+    }
+
+    //this test checks that a missing semicolon is reported
+    #[test]
+    fn test_missing_semicolon() {
+        let err = Parser::new(tokenize("x = 1").unwrap())
+                      .parse_program().unwrap_err();
+        assert_eq!(err.get_type(), CompileErrorType::UnexpectedEnd);
+    }
+}
+
+//end of file
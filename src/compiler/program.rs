@@ -0,0 +1,97 @@
+/*
+ * program.rs
+ * Defines a struct that holds a compiled program
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//usage statements
+use super::super::codegen::{Binary, Instruction};
+use super::super::error::BinaryError;
+
+/// A compiled program, made up of code followed by sprite data
+#[derive(Debug, Clone, PartialEq)]
+pub struct Program {
+    /// The instructions of the program
+    instructions: Vec<Instruction>,
+
+    /// The data bytes placed after the instructions
+    data: Vec<u8>
+}
+
+//implementation
+impl Program {
+    /// Constructs a new `Program` instance
+    ///
+    /// # Arguments
+    ///
+    /// * `new_instructions` - The code of the program
+    /// * `new_data` - The data that follows the code
+    ///
+    /// # Returns
+    ///
+    /// A new `Program` instance with the given properties
+    pub fn new(new_instructions: Vec<Instruction>, new_data: Vec<u8>)
+        -> Program {
+        return Program {
+            instructions: new_instructions,
+            data: new_data
+        };
+    }
+
+    /// Gets the instructions of the program
+    ///
+    /// # Returns
+    ///
+    /// The program's instructions, in address order
+    pub fn get_instructions(&self) -> &[Instruction] {
+        return self.instructions.as_slice();
+    }
+
+    /// Gets the data of the program
+    ///
+    /// # Returns
+    ///
+    /// The bytes that follow the program's instructions
+    pub fn get_data(&self) -> &[u8] {
+        return self.data.as_slice();
+    }
+
+    /// Writes the program into a binary
+    ///
+    /// # Argument
+    ///
+    /// * `bin` - The `Binary` to write to
+    ///
+    /// # Returns
+    ///
+    /// `Ok` if the program was written successfully,
+    /// `Err<BinaryError>` if the operation fails
+    pub fn write_to_binary(&self, bin: &mut Binary) 
+        -> Result<(), BinaryError> {
+        for instr in self.instructions.iter() {
+            bin.add_instruction(instr)?;
+        }
+        for byte in self.data.iter() {
+            bin.add_byte(*byte)?;
+        }
+        return Ok(());
+    }
+}
+
+//end of file
@@ -0,0 +1,148 @@
+/*
+ * token.rs
+ * Defines the tokens of the expression language
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//usage statement
+use std::fmt;
+
+/// The kinds of token produced by the lexer
+#[derive(Debug, Clone, PartialEq)]
+pub enum TokenKind {
+    /// An identifier
+    Ident(String),
+
+    /// A numeric literal
+    Number(u32),
+
+    //keywords
+    Var,
+    If,
+    Else,
+    While,
+    True,
+    False,
+    Sprite,
+    U8,
+    Bool,
+
+    //punctuation
+    LParen,
+    RParen,
+    LBrace,
+    RBrace,
+    LBracket,
+    RBracket,
+    Comma,
+    Semi,
+    Colon,
+    Assign,
+
+    //operators
+    Plus,
+    Minus,
+    Star,
+    Slash,
+    Percent,
+    Amp,
+    Pipe,
+    Caret,
+    Tilde,
+    Bang,
+    Shl,
+    Shr,
+    EqEq,
+    NotEq,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    AndAnd,
+    OrOr,
+
+    /// The end of the source
+    Eof
+}
+
+//Display implementation
+impl fmt::Display for TokenKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let text = match *self {
+            TokenKind::Ident(ref s) => return write!(f, "{}", s),
+            TokenKind::Number(n) => return write!(f, "{}", n),
+            TokenKind::Var => "var",
+            TokenKind::If => "if",
+            TokenKind::Else => "else",
+            TokenKind::While => "while",
+            TokenKind::True => "true",
+            TokenKind::False => "false",
+            TokenKind::Sprite => "sprite",
+            TokenKind::U8 => "u8",
+            TokenKind::Bool => "bool",
+            TokenKind::LParen => "(",
+            TokenKind::RParen => ")",
+            TokenKind::LBrace => "{",
+            TokenKind::RBrace => "}",
+            TokenKind::LBracket => "[",
+            TokenKind::RBracket => "]",
+            TokenKind::Comma => ",",
+            TokenKind::Semi => ";",
+            TokenKind::Colon => ":",
+            TokenKind::Assign => "=",
+            TokenKind::Plus => "+",
+            TokenKind::Minus => "-",
+            TokenKind::Star => "*",
+            TokenKind::Slash => "/",
+            TokenKind::Percent => "%",
+            TokenKind::Amp => "&",
+            TokenKind::Pipe => "|",
+            TokenKind::Caret => "^",
+            TokenKind::Tilde => "~",
+            TokenKind::Bang => "!",
+            TokenKind::Shl => "<<",
+            TokenKind::Shr => ">>",
+            TokenKind::EqEq => "==",
+            TokenKind::NotEq => "!=",
+            TokenKind::Lt => "<",
+            TokenKind::Le => "<=",
+            TokenKind::Gt => ">",
+            TokenKind::Ge => ">=",
+            TokenKind::AndAnd => "&&",
+            TokenKind::OrOr => "||",
+            TokenKind::Eof => "end of input"
+        };
+        write!(f, "{}", text)
+    }
+}
+
+/// A token along with its position in the source
+#[derive(Debug, Clone, PartialEq)]
+pub struct Token {
+    /// The kind of the token
+    pub kind: TokenKind,
+
+    /// The 1-based line of the token
+    pub line: usize,
+
+    /// The 1-based column of the token
+    pub col: usize
+}
+
+//end of file
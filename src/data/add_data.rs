@@ -25,6 +25,7 @@ use super::Register;
 use super::super::codegen::CodeGen;
 
 /// Contextual data for the `ADD` instruction
+#[derive(Debug, Clone, PartialEq)]
 pub struct AddData {
     /// The first addend (also the destination register)
     first: Register,
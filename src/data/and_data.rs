@@ -25,6 +25,7 @@ use super::Register;
 use super::super::codegen::CodeGen;
 
 /// Contextual data for the `AND` instruction
+#[derive(Debug, Clone, PartialEq)]
 pub struct AndData {
     /// The first (destination) operand
     vx: Register,
@@ -0,0 +1,234 @@
+/*
+ * arbitrary.rs
+ * Defines proptest strategies for registers and instruction data
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+
+//usage statements
+use proptest::prelude::*;
+use super::*;
+
+/// Creates a strategy for the general purpose registers,
+/// `V0` through `VF`
+///
+/// # Returns
+///
+/// A strategy that never yields `Register::I`
+pub fn general_register() -> BoxedStrategy<Register> {
+    return (0x0u8..=0xF).prop_map(|id| Register::from_id(id).unwrap())
+                        .boxed();
+}
+
+/// Implements `Arbitrary` for a data type whose
+/// constructor takes a single general purpose register
+macro_rules! arbitrary_vx {
+    ($data:ident) => {
+        impl Arbitrary for $data {
+            type Parameters = ();
+            type Strategy = BoxedStrategy<$data>;
+
+            fn arbitrary_with(_: ()) -> Self::Strategy {
+                return general_register().prop_map($data::new).boxed();
+            }
+        }
+    };
+}
+
+/// Implements `Arbitrary` for a data type whose
+/// constructor takes two general purpose registers
+macro_rules! arbitrary_vx_vy {
+    ($data:ident) => {
+        impl Arbitrary for $data {
+            type Parameters = ();
+            type Strategy = BoxedStrategy<$data>;
+
+            fn arbitrary_with(_: ()) -> Self::Strategy {
+                return (general_register(), general_register())
+                    .prop_map(|(vx, vy)| $data::new(vx, vy)).boxed();
+            }
+        }
+    };
+}
+
+//Arbitrary implementations
+impl Arbitrary for Register {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Register>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        return (0x0u8..=0x10).prop_map(|id| Register::from_id(id).unwrap())
+                             .boxed();
+    }
+}
+
+impl Arbitrary for SkipType {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<SkipType>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        return prop_oneof![
+            Just(SkipType::Equals),
+            Just(SkipType::NotEquals),
+            Just(SkipType::KeyDown),
+            Just(SkipType::KeyUp)
+        ].boxed();
+    }
+}
+
+impl Arbitrary for JmpData {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<JmpData>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        return (0u16..0x1000).prop_map(JmpData::new).boxed();
+    }
+}
+
+impl Arbitrary for JpcData {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<JpcData>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        return (0u16..0x1000).prop_map(JpcData::new).boxed();
+    }
+}
+
+impl Arbitrary for CallData {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<CallData>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        return (0u16..0x1000).prop_map(CallData::new).boxed();
+    }
+}
+
+impl Arbitrary for SkipData {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<SkipData>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        let compare = prop_oneof![Just(SkipType::Equals),
+                                  Just(SkipType::NotEquals)];
+        let key = prop_oneof![Just(SkipType::KeyDown),
+                              Just(SkipType::KeyUp)];
+        return prop_oneof![
+            (general_register(), general_register(), compare.clone())
+                .prop_map(|(vx, vy, t)| {
+                    SkipData::with_register(vx, vy, t)
+                }),
+            (general_register(), any::<u8>(), compare)
+                .prop_map(|(vx, nn, t)| {
+                    SkipData::with_constant(vx, nn, t)
+                }),
+            (general_register(), key)
+                .prop_map(|(vx, t)| SkipData::with_key(vx, t))
+        ].boxed();
+    }
+}
+
+impl Arbitrary for MovData {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<MovData>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        return prop_oneof![
+            (general_register(), general_register())
+                .prop_map(|(dest, src)| MovData::with_register(dest, src)),
+            (general_register(), any::<u8>())
+                .prop_map(|(dest, nn)| {
+                    MovData::with_constant(dest, nn as u16)
+                }),
+            (0u16..0x1000)
+                .prop_map(|nnn| MovData::with_constant(Register::I, nnn))
+        ].boxed();
+    }
+}
+
+impl Arbitrary for AddData {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<AddData>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        return prop_oneof![
+            (general_register(), general_register())
+                .prop_map(|(vx, vy)| AddData::with_register(vx, vy)),
+            general_register()
+                .prop_map(|vx| AddData::with_register(Register::I, vx)),
+            (general_register(), any::<u8>())
+                .prop_map(|(vx, nn)| AddData::with_constant(vx, nn as u16))
+        ].boxed();
+    }
+}
+
+impl Arbitrary for ShrData {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<ShrData>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        return (general_register(), general_register())
+            .prop_map(|(vx, vy)| ShrData::with_source(vx, vy)).boxed();
+    }
+}
+
+impl Arbitrary for ShlData {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<ShlData>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        return (general_register(), general_register())
+            .prop_map(|(vx, vy)| ShlData::with_source(vx, vy)).boxed();
+    }
+}
+
+impl Arbitrary for RandData {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<RandData>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        return (general_register(), any::<u8>())
+            .prop_map(|(vx, nn)| RandData::new(vx, nn)).boxed();
+    }
+}
+
+impl Arbitrary for DrawData {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<DrawData>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        return (general_register(), general_register(), 0u8..0x10)
+            .prop_map(|(vx, vy, h)| DrawData::new(vx, vy, h)).boxed();
+    }
+}
+
+arbitrary_vx_vy!(OrData);
+arbitrary_vx_vy!(AndData);
+arbitrary_vx_vy!(XorData);
+arbitrary_vx_vy!(SubData);
+arbitrary_vx_vy!(SubnData);
+arbitrary_vx!(GdlData);
+arbitrary_vx!(KeyData);
+arbitrary_vx!(SdlData);
+arbitrary_vx!(SndData);
+arbitrary_vx!(SchData);
+arbitrary_vx!(BcdData);
+arbitrary_vx!(RdpData);
+arbitrary_vx!(RldData);
+
+//end of file
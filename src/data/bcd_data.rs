@@ -25,6 +25,7 @@ use super::Register;
 use super::super::codegen::CodeGen;
 
 /// Contextual data for the `BCD` instruction 
+#[derive(Debug, Clone, PartialEq)]
 pub struct BcdData {
     /// The register to get the character sprite from 
     vx: Register 
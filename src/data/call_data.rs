@@ -24,6 +24,7 @@
 use super::super::codegen::CodeGen;
 
 /// Contextual data for the `CALL` instruction
+#[derive(Debug, Clone, PartialEq)]
 pub struct CallData {
     /// The address of the subroutine to call 
     addr: u16
@@ -25,6 +25,7 @@ use super::Register;
 use super::super::codegen::CodeGen;
 
 /// Contextual data for the `DRAW` instruction
+#[derive(Debug, Clone, PartialEq)]
 pub struct DrawData {
     /// The register that contains the x-coordinate of the sprite
     vx: Register,
@@ -25,6 +25,7 @@ use super::Register;
 use super::super::codegen::CodeGen;
 
 /// Contextual data for the `GDL` instruction
+#[derive(Debug, Clone, PartialEq)]
 pub struct GdlData {
     /// The register to read the delay timer into
     vx: Register 
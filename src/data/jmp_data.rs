@@ -24,6 +24,7 @@
 use super::super::codegen::CodeGen;
 
 /// Contextual data for the `JMP` instruction
+#[derive(Debug, Clone, PartialEq)]
 pub struct JmpData {
     /// The address to jump to
     addr: u16,
@@ -90,7 +91,7 @@ mod tests {
 
     //this test checks opcode generation
     #[test]
-    fn test_opcode_gen() {;
+    fn test_opcode_gen() {
         let jpd = JmpData::new(0x0CCC);
         assert_eq!(jpd.gen_opcode(), 0x1CCC);
     }
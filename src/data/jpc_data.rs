@@ -24,6 +24,7 @@
 use super::super::codegen::CodeGen;
 
 /// Contextual data for the `JPC` instruction
+#[derive(Debug, Clone, PartialEq)]
 pub struct JpcData {
     /// The address to jump to after adding `V0`
     addr: u16,
@@ -25,6 +25,7 @@ use super::Register;
 use super::super::codegen::CodeGen;
 
 /// Contextual data for the `KEY` instruction
+#[derive(Debug, Clone, PartialEq)]
 pub struct KeyData {
     /// The register to read the keypress into
     vx: Register 
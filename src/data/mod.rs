@@ -71,5 +71,9 @@ mod rdp_data;
 pub use rdp_data::RdpData;
 mod rld_data;
 pub use rld_data::RldData;
+#[cfg(any(test, feature = "proptest"))]
+mod arbitrary;
+#[cfg(any(test, feature = "proptest"))]
+pub use arbitrary::general_register;
 
 //end of file
@@ -25,6 +25,7 @@ use super::Register;
 use super::super::codegen::CodeGen;
 
 /// Contextual data for the `MOV` instruction
+#[derive(Debug, Clone, PartialEq)]
 pub struct MovData {
     /// The destination register 
     dest: Register,
@@ -121,7 +122,7 @@ impl CodeGen for MovData {
         //handle destination objects
         match self.dest {
             Register::I => {
-                match self.src_cst.clone() {
+                match self.src_cst {
                     Some(cst) => {
                         let mut code = 0xA000;
                         code |= cst;
@@ -25,6 +25,7 @@ use super::Register;
 use super::super::codegen::CodeGen;
 
 /// Contextual data for the `OR` instruction
+#[derive(Debug, Clone, PartialEq)]
 pub struct OrData {
     /// The first (destination) operand
     vx: Register,
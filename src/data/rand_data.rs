@@ -25,6 +25,7 @@ use super::Register;
 use super::super::codegen::CodeGen;
 
 /// Contextual data for the `RAND` instruction
+#[derive(Debug, Clone, PartialEq)]
 pub struct RandData {
     /// The register to store the random value in
     vx: Register,
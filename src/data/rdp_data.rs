@@ -25,6 +25,7 @@ use super::Register;
 use super::super::codegen::CodeGen;
 
 /// Contextual data for the `RDP` instruction 
+#[derive(Debug, Clone, PartialEq)]
 pub struct RdpData {
     /// The end register of the dumped sequence
     vx: Register 
@@ -72,6 +72,38 @@ impl Register {
             Register::VF => 0xF,
         };
     }
+
+    /// Converts a numeric ID to a `Register`
+    ///
+    /// # Argument
+    ///
+    /// * `id` - The ID to convert (`0x0` to `0xF` or `0x10` for `I`)
+    ///
+    /// # Returns
+    ///
+    /// The `Register` with the given ID, or `None` if the ID is invalid
+    pub fn from_id(id: u8) -> Option<Register> {
+        return match id {
+            0x0 => Some(Register::V0),
+            0x1 => Some(Register::V1),
+            0x2 => Some(Register::V2),
+            0x3 => Some(Register::V3),
+            0x4 => Some(Register::V4),
+            0x5 => Some(Register::V5),
+            0x6 => Some(Register::V6),
+            0x7 => Some(Register::V7),
+            0x8 => Some(Register::V8),
+            0x9 => Some(Register::V9),
+            0xA => Some(Register::VA),
+            0xB => Some(Register::VB),
+            0xC => Some(Register::VC),
+            0xD => Some(Register::VD),
+            0xE => Some(Register::VE),
+            0xF => Some(Register::VF),
+            0x10 => Some(Register::I),
+            _ => None
+        };
+    }
 }
 
 //unit tests
@@ -102,6 +134,16 @@ mod tests {
         assert_eq!(Register::VE.to_id(), 0xE);
         assert_eq!(Register::VF.to_id(), 0xF);
     }
+
+    //this test checks converting
+    //an ID back to a Register
+    #[test]
+    fn test_register_from_id() {
+        for id in 0..0x11 {
+            assert_eq!(Register::from_id(id).unwrap().to_id(), id);
+        }
+        assert_eq!(Register::from_id(0x11), None);
+    }
 }
 
 //end of file
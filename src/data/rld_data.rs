@@ -25,6 +25,7 @@ use super::Register;
 use super::super::codegen::CodeGen;
 
 /// Contextual data for the `RLD` instruction 
+#[derive(Debug, Clone, PartialEq)]
 pub struct RldData {
     /// The end register of the loaded sequence
     vx: Register 
@@ -25,6 +25,7 @@ use super::Register;
 use super::super::codegen::CodeGen;
 
 /// Contextual data for the `SCH` instruction 
+#[derive(Debug, Clone, PartialEq)]
 pub struct SchData {
     /// The register to get the character sprite from 
     vx: Register 
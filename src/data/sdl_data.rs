@@ -25,6 +25,7 @@ use super::Register;
 use super::super::codegen::CodeGen;
 
 /// Contextual data for the `SDL` instruction 
+#[derive(Debug, Clone, PartialEq)]
 pub struct SdlData {
     /// The register to set the delay timer from
     vx: Register 
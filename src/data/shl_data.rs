@@ -25,9 +25,13 @@ use super::Register;
 use super::super::codegen::CodeGen;
 
 /// Contextual data for the `SHL` instruction
+#[derive(Debug, Clone, PartialEq)]
 pub struct ShlData {
     /// The register to shift
-    vx: Register 
+    vx: Register,
+
+    /// The register the shifted value is read from
+    vy: Register
 }
 
 //implementation
@@ -53,7 +57,39 @@ impl ShlData {
 
         //and return the instance
         return ShlData {
-            vx: new_vx 
+            vx: new_vx.clone(),
+            vy: new_vx
+        }
+    }
+
+    /// Constructs a new `ShlData` instance that shifts one
+    /// register and stores the result in another. Interpreters
+    /// with the shift quirk ignore the source register and shift
+    /// the destination in place.
+    ///
+    /// # Arguments
+    ///
+    /// * `new_vx` - The destination register
+    /// * `new_vy` - The register to shift left
+    /// 
+    /// # Panics
+    ///
+    /// This method will panic if either register refers to
+    /// the `I` register.
+    /// 
+    /// # Returns
+    ///
+    /// A new `ShlData` instance with the given properties
+    pub fn with_source(new_vx: Register, new_vy: Register) -> ShlData {
+        //validate the registers
+        if (new_vx == Register::I) || (new_vy == Register::I) {
+            panic!("Index register cannot be shifted");
+        }
+
+        //and return the instance
+        return ShlData {
+            vx: new_vx,
+            vy: new_vy
         }
     }
 }
@@ -68,7 +104,7 @@ impl CodeGen for ShlData {
     fn gen_opcode(&self) -> u16 {
         let mut code = 0x800E;
         code |= (self.vx.to_id() as u16) << 8;
-        code |= (self.vx.to_id() as u16) << 4;
+        code |= (self.vy.to_id() as u16) << 4;
         return code;
     }
 }
@@ -92,6 +128,13 @@ mod tests {
         let shld = ShlData::new(Register::V1);
         assert_eq!(shld.gen_opcode(), 0x811E);
     }
+
+    //this test checks opcode generation with a source register
+    #[test]
+    fn test_opcode_gen_with_source() {
+        let d = ShlData::with_source(Register::V1, Register::V2);
+        assert_eq!(d.gen_opcode(), 0x812E);
+    }
 }
 
 //end of file
@@ -25,9 +25,13 @@ use super::Register;
 use super::super::codegen::CodeGen;
 
 /// Contextual data for the `SHR` instruction
+#[derive(Debug, Clone, PartialEq)]
 pub struct ShrData {
     /// The register to shift
-    vx: Register 
+    vx: Register,
+
+    /// The register the shifted value is read from
+    vy: Register
 }
 
 //implementation
@@ -53,7 +57,39 @@ impl ShrData {
 
         //and return the instance
         return ShrData {
-            vx: new_vx 
+            vx: new_vx.clone(),
+            vy: new_vx
+        }
+    }
+
+    /// Constructs a new `ShrData` instance that shifts one
+    /// register and stores the result in another. Interpreters
+    /// with the shift quirk ignore the source register and shift
+    /// the destination in place.
+    ///
+    /// # Arguments
+    ///
+    /// * `new_vx` - The destination register
+    /// * `new_vy` - The register to shift right
+    /// 
+    /// # Panics
+    ///
+    /// This method will panic if either register refers to
+    /// the `I` register.
+    /// 
+    /// # Returns
+    ///
+    /// A new `ShrData` instance with the given properties
+    pub fn with_source(new_vx: Register, new_vy: Register) -> ShrData {
+        //validate the registers
+        if (new_vx == Register::I) || (new_vy == Register::I) {
+            panic!("Index register cannot be shifted");
+        }
+
+        //and return the instance
+        return ShrData {
+            vx: new_vx,
+            vy: new_vy
         }
     }
 }
@@ -68,7 +104,7 @@ impl CodeGen for ShrData {
     fn gen_opcode(&self) -> u16 {
         let mut code = 0x8006;
         code |= (self.vx.to_id() as u16) << 8;
-        code |= (self.vx.to_id() as u16) << 4;
+        code |= (self.vy.to_id() as u16) << 4;
         return code;
     }
 }
@@ -92,6 +128,13 @@ mod tests {
         let shrd = ShrData::new(Register::V1);
         assert_eq!(shrd.gen_opcode(), 0x8116);
     }
+
+    //this test checks opcode generation with a source register
+    #[test]
+    fn test_opcode_gen_with_source() {
+        let d = ShrData::with_source(Register::V1, Register::V2);
+        assert_eq!(d.gen_opcode(), 0x8126);
+    }
 }
 
 //end of file
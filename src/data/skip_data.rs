@@ -26,6 +26,7 @@ use super::SkipType;
 use super::super::codegen::CodeGen;
 
 /// Contextual data for the `SKIP` instruction
+#[derive(Debug, Clone, PartialEq)]
 pub struct SkipData {
     /// The LHS side of the comparison
     vx: Register,
@@ -21,7 +21,7 @@
  */
 
 /// Types of `SKIP` conditions
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum SkipType {
     /// Test equality between two registers
     /// or a register and a constant value
@@ -25,6 +25,7 @@ use super::Register;
 use super::super::codegen::CodeGen;
 
 /// Contextual data for the `SND` instruction 
+#[derive(Debug, Clone, PartialEq)]
 pub struct SndData {
     /// The register to set the sound timer from
     vx: Register 
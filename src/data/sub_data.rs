@@ -25,6 +25,7 @@ use super::Register;
 use super::super::codegen::CodeGen;
 
 /// Contextual data for the `SUB` instruction
+#[derive(Debug, Clone, PartialEq)]
 pub struct SubData {
     /// The destination register
     vx: Register,
@@ -25,6 +25,7 @@ use super::Register;
 use super::super::codegen::CodeGen;
 
 /// Contextual data for the `SUBN` instruction
+#[derive(Debug, Clone, PartialEq)]
 pub struct SubnData {
     /// The destination register
     vx: Register,
@@ -25,6 +25,7 @@ use super::Register;
 use super::super::codegen::CodeGen;
 
 /// Contextual data for the `XOR` instruction
+#[derive(Debug, Clone, PartialEq)]
 pub struct XorData {
     /// The first (destination) operand
     vx: Register,
@@ -0,0 +1,446 @@
+/*
+ * diff_test.rs
+ * Defines a differential test harness for the interpreter
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//usage statements
+use super::super::codegen::{CodeGen, PROGRAM_START};
+use super::super::vm::{Machine, Quirks, RandomSource, Step, Xorshift};
+use super::spec::{Operand, JMP, SPECS};
+use super::{Divergence, Operation, Outcome, Reference};
+
+/// The number of instructions a program is run for when no
+/// other limit is set
+pub const DEFAULT_STEPS: u64 = 1000;
+
+/// The number of instructions between timer ticks
+const TICK_INTERVAL: u64 = 10;
+
+/// The lowest data address generated programs point `I` at,
+/// leaving room for programs of up to 128 operations below it
+const DATA_START: u16 = 0x300;
+
+/// Runs random programs on both the interpreter and the 
+/// `Reference` model, comparing the registers, timers, stack,
+/// memory and screen after every instruction, and shrinks any 
+/// program that diverges to a minimal reproduction.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiffTest {
+    /// The interpreter behaviours under test
+    quirks: Quirks,
+
+    /// The most instructions to run each program for
+    steps: u64
+}
+
+//implementation
+impl DiffTest {
+    /// Constructs a new `DiffTest` instance
+    ///
+    /// # Argument
+    ///
+    /// * `new_quirks` - The interpreter behaviours to test
+    ///
+    /// # Returns
+    ///
+    /// A new `DiffTest` that runs programs for `DEFAULT_STEPS`
+    pub fn new(new_quirks: Quirks) -> DiffTest {
+        return DiffTest {
+            quirks: new_quirks,
+            steps: DEFAULT_STEPS
+        };
+    }
+
+    /// Sets the most instructions to run each program for
+    ///
+    /// # Argument
+    ///
+    /// * `new_steps` - The step limit
+    pub fn set_steps(&mut self, new_steps: u64) {
+        self.steps = new_steps;
+    }
+
+    /// Generates a random program. Jumps and calls target the
+    /// program's own instructions and `I` is pointed above it,
+    /// so most programs run to the end without leaving the model.
+    ///
+    /// # Arguments
+    ///
+    /// * `rng` - The source of randomness
+    /// * `len` - The number of operations
+    ///
+    /// # Returns
+    ///
+    /// The operations of the program
+    pub fn generate(&self, rng: &mut dyn RandomSource, 
+                    len: usize) -> Vec<Operation> {
+        let mut program = Vec::with_capacity(len);
+        for _ in 0..len {
+            let kind = below(rng, SPECS.len());
+            let nnn = match SPECS[kind].operand {
+                Operand::Constant => below(rng, 0x1000) as u16,
+                Operand::Code => {
+                    PROGRAM_START + 2 * below(rng, len + 1) as u16
+                },
+                Operand::Data => DATA_START + 
+                    below(rng, (0x1000 - DATA_START) as usize) as u16
+            };
+            let (x, y) = (rng.next_byte(), rng.next_byte());
+            program.push(Operation::new(kind, x, y, nnn));
+        }
+        return program;
+    }
+
+    /// Runs a program on the interpreter and the reference model.
+    /// A jump to the end of the program is added to stop it. The
+    /// comparison ends without a verdict once the program halts,
+    /// waits for a key, jumps out of itself or overwrites itself.
+    ///
+    /// # Argument
+    ///
+    /// * `program` - The operations to run
+    ///
+    /// # Returns
+    ///
+    /// The first difference, or `None` if the two agree
+    pub fn check(&self, program: &[Operation]) -> Option<Divergence> {
+        return self.compare(program, Machine::new(self.quirks));
+    }
+
+    /// Shrinks a diverging program to a smaller one that still
+    /// diverges, by removing operations and simplifying operands
+    ///
+    /// # Argument
+    ///
+    /// * `program` - The diverging program
+    ///
+    /// # Returns
+    ///
+    /// A program that diverges and cannot be shrunk further
+    pub fn shrink(&self, program: &[Operation]) -> Vec<Operation> {
+        return DiffTest::shrink_by(program, |p| self.check(p).is_some());
+    }
+
+    /// Shrinks a program to a smaller one that still fails a test.
+    /// Removing an operation retargets jumps past it, so the rest
+    /// of the program keeps its control flow.
+    ///
+    /// # Arguments
+    ///
+    /// * `program` - The failing program
+    /// * `fails` - Whether a program still fails
+    ///
+    /// # Returns
+    ///
+    /// A failing program that cannot be shrunk further
+    pub fn shrink_by<F>(program: &[Operation], fails: F) -> Vec<Operation>
+        where F: Fn(&[Operation]) -> bool {
+        let mut best = program.to_vec();
+        let mut improved = true;
+        while improved {
+            improved = false;
+
+            //drop operations, last first
+            for k in (0..best.len()).rev() {
+                let candidate = remove(&best, k);
+                if fails(&candidate) {
+                    best = candidate;
+                    improved = true;
+                }
+            }
+
+            //then simplify the operands of the rest
+            for k in 0..best.len() {
+                for simpler in simplify(&best[k]) {
+                    let mut candidate = best.clone();
+                    candidate[k] = simpler;
+                    if fails(&candidate) {
+                        best = candidate;
+                        improved = true;
+                        break;
+                    }
+                }
+            }
+        }
+        return best;
+    }
+
+    /// Runs random programs until one diverges
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - The seed of the program generator
+    /// * `count` - The number of programs to try
+    /// * `len` - The number of operations in each program
+    ///
+    /// # Returns
+    ///
+    /// The shrunk program and its divergence, or `None` if every
+    /// program agreed
+    pub fn search(&self, seed: u64, count: usize, 
+                  len: usize) -> Option<(Vec<Operation>, Divergence)> {
+        let mut rng = Xorshift::new(seed);
+        for _ in 0..count {
+            let program = self.generate(&mut rng, len);
+            if self.check(&program).is_some() {
+                let small = self.shrink(&program);
+                if let Some(divergence) = self.check(&small) {
+                    return Some((small, divergence));
+                }
+            }
+        }
+        return None;
+    }
+
+    /// Lists a program with the address of each operation, 
+    /// including the jump that ends it
+    ///
+    /// # Argument
+    ///
+    /// * `program` - The operations to list
+    ///
+    /// # Returns
+    ///
+    /// One line per operation
+    pub fn listing(program: &[Operation]) -> String {
+        let mut out = String::new();
+        for (k, op) in terminated(program).iter().enumerate() {
+            out.push_str(&format!("{:03X}: {}\n", 
+                                  PROGRAM_START as usize + 2 * k, op));
+        }
+        return out;
+    }
+
+    /// Runs a program on a machine and the reference model
+    fn compare(&self, program: &[Operation], 
+               mut machine: Machine) -> Option<Divergence> {
+        let program = terminated(program);
+        let rom: Vec<u8> = program.iter()
+            .flat_map(|op| op.get_instruction().gen_opcode().to_be_bytes())
+            .collect();
+        machine.load(&rom).ok()?;
+        let mut model = Reference::new(&program, machine.get_memory(), 
+                                       machine.get_random().box_clone(),
+                                       self.quirks);
+        for step in 1..=self.steps {
+            let addr = model.get_pc();
+            let expected = model.step();
+            if expected == Outcome::Unmodelled {
+                return None;
+            }
+            let op = machine.get_opcode();
+            let writes = machine.pending_write().is_some() || 
+                model.has_stored() || op & 0xF000 == 0xD000 || 
+                op == 0x00E0 || step.is_multiple_of(TICK_INTERVAL);
+            let actual = match machine.step() {
+                Ok(Step::Ran) => Outcome::Ran,
+                Ok(Step::Waiting) => Outcome::Waiting,
+                Ok(Step::Halted(_)) => Outcome::Halted,
+                Err(_) => Outcome::Failed
+            };
+            let diverged = differences(&model, &machine, expected, actual,
+                                       writes);
+            if let Some((field, e, a)) = diverged {
+                return Some(Divergence::new(step, addr, &field, &e, &a));
+            }
+            if expected != Outcome::Ran || model.is_modified() {
+                break;
+            }
+            if step.is_multiple_of(TICK_INTERVAL) {
+                machine.tick_timers();
+                model.tick_timers();
+            }
+        }
+
+        //catch stray writes that the per-step checks skipped
+        return differences(&model, &machine, Outcome::Ran, Outcome::Ran, 
+                           true).map(|(field, e, a)| {
+            Divergence::new(machine.get_cycles(), model.get_pc(), &field, 
+                            &e, &a)
+        });
+    }
+}
+
+/// Picks a random number below a bound
+fn below(rng: &mut dyn RandomSource, bound: usize) -> usize {
+    let value = (rng.next_byte() as usize) << 8 | rng.next_byte() as usize;
+    return value % bound;
+}
+
+/// Appends the jump to itself that ends a program
+fn terminated(program: &[Operation]) -> Vec<Operation> {
+    let mut out = program.to_vec();
+    let end = PROGRAM_START + 2 * program.len() as u16;
+    out.push(Operation::new(JMP, 0, 0, end));
+    return out;
+}
+
+/// Removes an operation, retargeting jumps to the ones after it
+fn remove(program: &[Operation], k: usize) -> Vec<Operation> {
+    let addr = PROGRAM_START + 2 * k as u16;
+    return program.iter().enumerate().filter(|(i, _)| *i != k)
+        .map(|(_, op)| {
+            let mut op = op.clone();
+            if op.is_jump() && op.nnn > addr {
+                op.nnn -= 2;
+            }
+            op
+        }).collect();
+}
+
+/// Lists simpler versions of an operation, with zeroed operands
+fn simplify(op: &Operation) -> Vec<Operation> {
+    let mut out = Vec::new();
+    if op.x != 0 {
+        out.push(Operation::new(op.kind, 0, op.y, op.nnn));
+    }
+    if op.y != 0 {
+        out.push(Operation::new(op.kind, op.x, 0, op.nnn));
+    }
+    let plain = match SPECS[op.kind].operand {
+        Operand::Constant => 0,
+        Operand::Code => op.nnn,
+        Operand::Data => DATA_START
+    };
+    if op.nnn != plain {
+        out.push(Operation::new(op.kind, op.x, op.y, plain));
+    }
+    return out;
+}
+
+/// Finds the first state that differs between the model and a 
+/// machine after a step, as its name and the two values. Memory
+/// and the screen are only compared after steps that may have
+/// written to them, and periodically to catch stray writes.
+fn differences(model: &Reference, machine: &Machine, expected: Outcome,
+               actual: Outcome, 
+               writes: bool) -> Option<(String, String, String)> {
+    if expected != actual {
+        return Some((String::from("outcome"), expected.to_string(), 
+                     actual.to_string()));
+    }
+    for x in 0..16 {
+        if model.get_v(x) != machine.get_v(x) {
+            return Some((format!("V{:X}", x), 
+                         format!("{:02X}", model.get_v(x)),
+                         format!("{:02X}", machine.get_v(x))));
+        }
+    }
+    let words = [("I", model.get_i(), machine.get_i()),
+                 ("PC", model.get_pc(), machine.get_pc()),
+                 ("DT", model.get_delay() as u16, machine.get_delay() as u16),
+                 ("ST", model.get_sound() as u16, machine.get_sound() as u16)];
+    for (name, e, a) in words.iter() {
+        if e != a {
+            return Some((name.to_string(), format!("{:03X}", e), 
+                         format!("{:03X}", a)));
+        }
+    }
+    if model.get_stack() != machine.get_stack() {
+        return Some((String::from("stack"), 
+                     format!("{:03X?}", model.get_stack()),
+                     format!("{:03X?}", machine.get_stack())));
+    }
+    if !writes {
+        return None;
+    }
+    let (e, a) = (model.get_memory(), machine.get_memory());
+    if e != a {
+        let addr = (0..e.len()).find(|k| e[*k] != a[*k]).unwrap_or(0);
+        return Some((format!("memory[{:03X}]", addr), 
+                     format!("{:02X}", e[addr]), format!("{:02X}", a[addr])));
+    }
+    let state = |lit: bool| String::from(if lit { "on" } else { "off" });
+    for y in 0..32 {
+        for x in 0..64 {
+            let e = model.get_pixel(x, y);
+            let a = machine.get_display().get(x, y);
+            if e != a {
+                return Some((format!("pixel ({}, {})", x, y), state(e), 
+                             state(a)));
+            }
+        }
+    }
+    return None;
+}
+
+//unit tests
+#[cfg(test)]
+mod tests {
+    //import the DiffTest struct
+    use super::*;
+
+    //creates an operation from the name of its specification
+    fn op(name: &str, x: u8, y: u8, nnn: u16) -> Operation {
+        let kind = SPECS.iter().position(|s| s.name == name).unwrap();
+        return Operation::new(kind, x, y, nnn);
+    }
+
+    //this test checks that random programs agree with the model
+    #[test]
+    fn test_agreement() {
+        let test = DiffTest::new(Quirks::vip());
+        let mut rng = Xorshift::new(7);
+        for _ in 0..50 {
+            let program = test.generate(&mut rng, 16);
+            assert_eq!(test.check(&program), None, "\n{}", 
+                       DiffTest::listing(&program));
+        }
+    }
+
+    //this test checks finding and shrinking a divergence, by
+    //running a SUPER-CHIP interpreter against the VIP model
+    #[test]
+    fn test_shrink() {
+        let test = DiffTest::new(Quirks::vip());
+        let schip = |p: &[Operation]| {
+            test.compare(p, Machine::new(Quirks::schip())).is_some()
+        };
+        let program = vec![op("MOV VX, NN", 1, 0, 0x33),
+                           op("MOV VX, NN", 2, 0, 0x06),
+                           op("ADD VX, VY", 2, 1, 0),
+                           op("CALL", 0, 0, 0x20A),
+                           op("JMP", 0, 0, 0x20E),
+                           op("SHR VX, VY", 0, 2, 0),
+                           op("RET", 0, 0, 0)];
+        assert!(schip(&program));
+        let small = DiffTest::shrink_by(&program, schip);
+        assert_eq!(small, vec![op("MOV VX, NN", 0, 0, 0x06), 
+                               op("SHR VX, VY", 0, 2, 0)]);
+        let divergence = test.compare(&small, Machine::new(Quirks::schip()))
+            .unwrap();
+        assert_eq!(divergence.to_string(), 
+                   "step 2 at 202: V0 is 03, expected 00");
+    }
+
+    //this test checks that removing operations retargets jumps
+    #[test]
+    fn test_remove() {
+        let program = vec![op("JMP", 0, 0, 0x204), op("CLS", 0, 0, 0),
+                           op("CALL", 0, 0, 0x202)];
+        let removed = remove(&program, 1);
+        assert_eq!(removed, vec![op("JMP", 0, 0, 0x202), 
+                                 op("CALL", 0, 0, 0x202)]);
+        assert_eq!(DiffTest::listing(&removed), 
+                   "200: JMP 0x202\n202: CALL 0x202\n204: JMP 0x204\n");
+    }
+}
+
+//end of file
@@ -0,0 +1,127 @@
+/*
+ * divergence.rs
+ * Defines a difference between the interpreter and the reference model
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//usage statement
+use std::fmt;
+
+/// The first point at which the interpreter and the reference
+/// model disagree while running a program
+#[derive(Debug, Clone, PartialEq)]
+pub struct Divergence {
+    /// The number of instructions executed, including this one
+    step: u64,
+
+    /// The address of the instruction that diverged
+    address: u16,
+
+    /// The state that differs, such as `VF`, `I` or `memory[0x300]`
+    field: String,
+
+    /// The value the reference model holds
+    expected: String,
+
+    /// The value the interpreter holds
+    actual: String
+}
+
+//implementation
+impl Divergence {
+    /// Constructs a new `Divergence` instance
+    ///
+    /// # Arguments
+    ///
+    /// * `new_step` - The number of instructions executed
+    /// * `new_address` - The address of the diverging instruction
+    /// * `new_field` - The state that differs
+    /// * `new_expected` - The value the reference model holds
+    /// * `new_actual` - The value the interpreter holds
+    ///
+    /// # Returns
+    ///
+    /// A new `Divergence` with the given properties
+    pub fn new(new_step: u64, new_address: u16, new_field: &str, 
+               new_expected: &str, new_actual: &str) -> Divergence {
+        return Divergence {
+            step: new_step,
+            address: new_address,
+            field: String::from(new_field),
+            expected: String::from(new_expected),
+            actual: String::from(new_actual)
+        };
+    }
+
+    /// Gets the number of instructions executed
+    ///
+    /// # Returns
+    ///
+    /// The step count, including the diverging instruction
+    pub fn get_step(&self) -> u64 {
+        return self.step;
+    }
+
+    /// Gets the address of the diverging instruction
+    ///
+    /// # Returns
+    ///
+    /// The address the instruction was fetched from
+    pub fn get_address(&self) -> u16 {
+        return self.address;
+    }
+
+    /// Gets the state that differs
+    ///
+    /// # Returns
+    ///
+    /// The name of the register, memory address or pixel
+    pub fn get_field(&self) -> &str {
+        return &self.field;
+    }
+
+    /// Gets the value the reference model holds
+    ///
+    /// # Returns
+    ///
+    /// The expected value
+    pub fn get_expected(&self) -> &str {
+        return &self.expected;
+    }
+
+    /// Gets the value the interpreter holds
+    ///
+    /// # Returns
+    ///
+    /// The actual value
+    pub fn get_actual(&self) -> &str {
+        return &self.actual;
+    }
+}
+
+//Display implementation
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return write!(f, "step {} at {:03X}: {} is {}, expected {}",
+                      self.step, self.address, self.field, self.actual,
+                      self.expected);
+    }
+}
+
+//end of file
@@ -0,0 +1,46 @@
+/*
+ * mod.rs
+ * Module export file for the difftest module
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Differential testing of the interpreter.
+//!
+//! A `DiffTest` generates random programs of `Operation`s, builds
+//! them into `codegen::Instruction`s for `vm::Machine`, and runs the
+//! same operations on a `Reference` model that executes each one
+//! from a table of semantics written independently of the opcode
+//! encoders and decoder. The registers, flags, timers, stack, memory
+//! and screen are compared after every instruction, and a program
+//! that diverges is shrunk to a minimal reproduction.
+
+//exports
+mod spec;
+mod outcome;
+pub use outcome::Outcome;
+mod operation;
+pub use operation::Operation;
+mod reference;
+pub use reference::Reference;
+mod divergence;
+pub use divergence::Divergence;
+mod diff_test;
+pub use diff_test::{DiffTest, DEFAULT_STEPS};
+
+//end of file
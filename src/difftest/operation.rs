@@ -0,0 +1,118 @@
+/*
+ * operation.rs
+ * Defines an instruction of a differential test program
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//usage statements
+use std::fmt;
+use super::super::codegen::Instruction;
+use super::spec::{Operand, SPECS};
+
+/// An instruction of a differential test program: a row of the
+/// reference specification with its operands. The same operation
+/// is built into an `Instruction` for the interpreter and executed
+/// directly by the reference model.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Operation {
+    /// The index of the operation's row in the specification
+    pub(super) kind: usize,
+
+    /// The first register operand
+    pub(super) x: u8,
+
+    /// The second register operand
+    pub(super) y: u8,
+
+    /// The constant operand, masked as the instruction needs
+    pub(super) nnn: u16
+}
+
+//implementation
+impl Operation {
+    /// Constructs a new `Operation` instance
+    ///
+    /// # Arguments
+    ///
+    /// * `new_kind` - The index of the operation's specification
+    /// * `new_x` - The first register operand
+    /// * `new_y` - The second register operand
+    /// * `new_nnn` - The constant operand
+    ///
+    /// # Returns
+    ///
+    /// A new `Operation` with its operands masked
+    pub(super) fn new(new_kind: usize, new_x: u8, new_y: u8, 
+                      new_nnn: u16) -> Operation {
+        return Operation {
+            kind: new_kind,
+            x: new_x & 0xF,
+            y: new_y & 0xF,
+            nnn: new_nnn & 0xFFF
+        };
+    }
+
+    /// Gets the name of the operation
+    ///
+    /// # Returns
+    ///
+    /// The name of the operation's specification
+    pub fn get_name(&self) -> &'static str {
+        return SPECS[self.kind].name;
+    }
+
+    /// Gets whether the constant operand is the address of an
+    /// instruction
+    ///
+    /// # Returns
+    ///
+    /// `true` for jumps and calls
+    pub fn is_jump(&self) -> bool {
+        return SPECS[self.kind].operand == Operand::Code;
+    }
+
+    /// Builds the instruction the interpreter runs
+    ///
+    /// # Returns
+    ///
+    /// The `Instruction` for the operation
+    pub fn get_instruction(&self) -> Instruction {
+        return (SPECS[self.kind].build)(self);
+    }
+
+    /// Gets the low byte of the constant operand
+    pub(super) fn nn(&self) -> u8 {
+        return (self.nnn & 0xFF) as u8;
+    }
+
+    /// Gets the low nibble of the constant operand
+    pub(super) fn n(&self) -> u8 {
+        return (self.nnn & 0xF) as u8;
+    }
+}
+
+//Display implementation
+impl fmt::Display for Operation {
+    /// Formats the operation as the instruction it builds
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return write!(f, "{}", self.get_instruction());
+    }
+}
+
+//end of file
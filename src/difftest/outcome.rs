@@ -0,0 +1,62 @@
+/*
+ * outcome.rs
+ * Defines the result of a reference model step
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//usage statement
+use std::fmt;
+
+/// The result of executing one instruction, as seen by both
+/// the interpreter and the reference model
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Outcome {
+    /// The instruction ran and execution continues
+    Ran,
+
+    /// The instruction jumped to itself
+    Halted,
+
+    /// Execution is blocked waiting for a key
+    Waiting,
+
+    /// The instruction could not execute, such as a `RET` with
+    /// an empty stack
+    Failed,
+
+    /// Execution reached a state the reference model does not
+    /// describe, such as leaving the program
+    Unmodelled
+}
+
+//Display implementation
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match *self {
+            Outcome::Ran => "ran",
+            Outcome::Halted => "halted",
+            Outcome::Waiting => "waiting",
+            Outcome::Failed => "failed",
+            Outcome::Unmodelled => "unmodelled"
+        };
+        return write!(f, "{}", name);
+    }
+}
+
+//end of file
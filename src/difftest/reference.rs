@@ -0,0 +1,268 @@
+/*
+ * reference.rs
+ * Defines the reference model of Chip-8 semantics
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//usage statements
+use super::super::codegen::PROGRAM_START;
+use super::super::vm::{Quirks, RandomSource};
+use super::spec::SPECS;
+use super::{Operation, Outcome};
+
+/// A deliberately simple model of a Chip-8 machine that runs a
+/// list of `Operation`s by looking up each one in a table of
+/// semantics, rather than by decoding opcodes. Differential tests
+/// compare it against `vm::Machine` one instruction at a time.
+#[derive(Debug, Clone)]
+pub struct Reference {
+    /// The program, placed at `PROGRAM_START`
+    program: Vec<Operation>,
+
+    /// The memory
+    pub(super) memory: Vec<u8>,
+
+    /// The `V0` through `VF` registers
+    pub(super) v: [u8; 16],
+
+    /// The `I` register
+    pub(super) i: u16,
+
+    /// The address of the next instruction
+    pub(super) pc: u16,
+
+    /// The return addresses of active calls
+    pub(super) stack: Vec<u16>,
+
+    /// The delay timer
+    pub(super) delay: u8,
+
+    /// The sound timer
+    pub(super) sound: u8,
+
+    /// The 64x32 screen, in rows
+    pub(super) screen: [[bool; 64]; 32],
+
+    /// Whether a `KEY` instruction is waiting
+    pub(super) waiting: bool,
+
+    /// Whether the program has written over its own code
+    modified: bool,
+
+    /// Whether the last operation wrote to memory or the screen
+    stored: bool,
+
+    /// The source of random bytes
+    pub(super) rng: Box<dyn RandomSource>,
+
+    /// The interpreter behaviours to model
+    pub(super) quirks: Quirks
+}
+
+//implementation
+impl Reference {
+    /// Constructs a new `Reference` instance
+    ///
+    /// # Arguments
+    ///
+    /// * `new_program` - The operations to run
+    /// * `new_memory` - The initial memory, with the program loaded
+    /// * `new_rng` - The random source, matching the interpreter's
+    /// * `new_quirks` - The interpreter behaviours to model
+    ///
+    /// # Returns
+    ///
+    /// A new `Reference` ready to run from `PROGRAM_START`
+    pub fn new(new_program: &[Operation], new_memory: &[u8], 
+               new_rng: Box<dyn RandomSource>, 
+               new_quirks: Quirks) -> Reference {
+        return Reference {
+            program: new_program.to_vec(),
+            memory: new_memory.to_vec(),
+            v: [0; 16],
+            i: 0,
+            pc: PROGRAM_START,
+            stack: Vec::new(),
+            delay: 0,
+            sound: 0,
+            screen: [[false; 64]; 32],
+            waiting: false,
+            modified: false,
+            stored: false,
+            rng: new_rng,
+            quirks: new_quirks
+        };
+    }
+
+    /// Gets the memory
+    ///
+    /// # Returns
+    ///
+    /// The bytes of memory
+    pub fn get_memory(&self) -> &[u8] {
+        return &self.memory;
+    }
+
+    /// Gets a general purpose register
+    ///
+    /// # Argument
+    ///
+    /// * `idx` - The register number, `0x0` to `0xF`
+    ///
+    /// # Returns
+    ///
+    /// The value of `V<idx>`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is greater than `0xF`
+    pub fn get_v(&self, idx: usize) -> u8 {
+        return self.v[idx];
+    }
+
+    /// Gets the `I` register
+    ///
+    /// # Returns
+    ///
+    /// The value of `I`
+    pub fn get_i(&self) -> u16 {
+        return self.i;
+    }
+
+    /// Gets the program counter
+    ///
+    /// # Returns
+    ///
+    /// The address of the next instruction
+    pub fn get_pc(&self) -> u16 {
+        return self.pc;
+    }
+
+    /// Gets the return addresses of active calls
+    ///
+    /// # Returns
+    ///
+    /// The stack, oldest call first
+    pub fn get_stack(&self) -> &[u16] {
+        return &self.stack;
+    }
+
+    /// Gets the delay timer
+    ///
+    /// # Returns
+    ///
+    /// The value of the delay timer
+    pub fn get_delay(&self) -> u8 {
+        return self.delay;
+    }
+
+    /// Gets the sound timer
+    ///
+    /// # Returns
+    ///
+    /// The value of the sound timer
+    pub fn get_sound(&self) -> u8 {
+        return self.sound;
+    }
+
+    /// Gets a pixel of the screen
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - The column, from `0` to `63`
+    /// * `y` - The row, from `0` to `31`
+    ///
+    /// # Returns
+    ///
+    /// `true` if the pixel is lit
+    pub fn get_pixel(&self, x: usize, y: usize) -> bool {
+        return self.screen[y][x];
+    }
+
+    /// Gets whether the program has written to the memory holding
+    /// its code, after which the model no longer matches what the
+    /// interpreter executes
+    ///
+    /// # Returns
+    ///
+    /// `true` once the code has been overwritten
+    pub fn is_modified(&self) -> bool {
+        return self.modified;
+    }
+
+    /// Gets whether the last operation wrote to memory or the 
+    /// screen
+    ///
+    /// # Returns
+    ///
+    /// `true` after a `BCD`, `RDP`, `DRAW` or `CLS`
+    pub fn has_stored(&self) -> bool {
+        return self.stored;
+    }
+
+    /// Decrements the timers, as happens at 60 Hz
+    pub fn tick_timers(&mut self) {
+        self.delay = self.delay.saturating_sub(1);
+        self.sound = self.sound.saturating_sub(1);
+    }
+
+    /// Executes one operation
+    ///
+    /// # Returns
+    ///
+    /// The outcome of the step. The program counter is left on an
+    /// operation that failed.
+    pub fn step(&mut self) -> Outcome {
+        self.stored = false;
+        if self.waiting {
+            return Outcome::Waiting;
+        }
+        let addr = self.pc;
+        let offset = addr.wrapping_sub(PROGRAM_START) as usize;
+        let op = match self.program.get(offset / 2) {
+            Some(op) if offset.is_multiple_of(2) => op.clone(),
+            _ => return Outcome::Unmodelled
+        };
+        self.pc = addr.wrapping_add(2);
+        let outcome = (SPECS[op.kind].exec)(self, &op, addr);
+        if outcome == Outcome::Failed {
+            self.pc = addr;
+        }
+        return outcome;
+    }
+
+    /// Notes that the current operation changed the screen
+    pub(super) fn touch_screen(&mut self) {
+        self.stored = true;
+    }
+
+    /// Stores a byte at an offset from `I`, noting writes over
+    /// the program
+    pub(super) fn store(&mut self, offset: u16, value: u8) {
+        let addr = self.i.wrapping_add(offset);
+        self.stored = true;
+        let end = PROGRAM_START as usize + self.program.len() * 2;
+        if (PROGRAM_START as usize..end).contains(&(addr as usize)) {
+            self.modified = true;
+        }
+        self.memory[addr as usize] = value;
+    }
+}
+
+//end of file
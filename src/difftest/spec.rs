@@ -0,0 +1,458 @@
+/*
+ * spec.rs
+ * Defines the table of instruction semantics used by the reference model
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//usage statements
+use super::super::codegen::Instruction;
+use super::super::data::*;
+use super::{Operation, Outcome, Reference};
+
+/// The address the reference model expects the small font at
+const FONT_ADDR: u16 = 0x000;
+
+/// The number of return addresses the reference stack holds
+const STACK_LIMIT: usize = 16;
+
+/// What the constant operand of an operation means
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) enum Operand {
+    /// A plain number
+    Constant,
+
+    /// The address of an instruction of the program
+    Code,
+
+    /// The address of data
+    Data
+}
+
+/// One row of the specification: how to build an operation as
+/// an `Instruction`, and what it does
+pub(super) struct Spec {
+    /// The name used in listings
+    pub(super) name: &'static str,
+
+    /// What the constant operand means
+    pub(super) operand: Operand,
+
+    /// Builds the instruction from the operands
+    pub(super) build: fn(&Operation) -> Instruction,
+
+    /// Executes the operation, given the address it was fetched from
+    pub(super) exec: fn(&mut Reference, &Operation, u16) -> Outcome
+}
+
+/// The index of `JMP` in `SPECS`, used to end programs
+pub(super) const JMP: usize = 0;
+
+/// Gets a general purpose register by number
+fn reg(id: u8) -> Register {
+    return Register::from_id(id & 0xF).unwrap_or(Register::V0);
+}
+
+/// Runs a skip whose condition holds
+fn skip(r: &mut Reference, cond: bool) -> Outcome {
+    if cond {
+        r.pc = r.pc.wrapping_add(2);
+    }
+    return Outcome::Ran;
+}
+
+/// Sets a register and then the flag register
+fn with_flag(r: &mut Reference, x: u8, value: u8, flag: u8) -> Outcome {
+    r.v[x as usize] = value;
+    r.v[0xF] = flag;
+    return Outcome::Ran;
+}
+
+/// Sets a register and clears the flag register if the quirks do
+fn logic(r: &mut Reference, x: u8, value: u8) -> Outcome {
+    r.v[x as usize] = value;
+    if r.quirks.vf_reset {
+        r.v[0xF] = 0;
+    }
+    return Outcome::Ran;
+}
+
+/// Gets the value a shift reads, given its source register
+fn shift_source(r: &Reference, op: &Operation) -> u8 {
+    return r.v[if r.quirks.shift_vy { op.y } else { op.x } as usize];
+}
+
+/// Draws a sprite from memory at `I`, clipping or wrapping at 
+/// the edges, and sets `VF` if a lit pixel was turned off
+fn draw(r: &mut Reference, op: &Operation) -> Outcome {
+    let (width, height) = match op.n() {
+        0 if r.quirks.large_sprites => (16, 16),
+        n => (8, n as usize)
+    };
+    let x0 = r.v[op.x as usize] as usize % 64;
+    let y0 = r.v[op.y as usize] as usize % 32;
+    let mut hit = 0;
+    for row in 0..height {
+        let addr = r.i.wrapping_add((row * width / 8) as u16);
+        let mut bits = r.memory[addr as usize] as u32;
+        if width == 16 {
+            bits = bits << 8 | r.memory[addr.wrapping_add(1) as usize] as u32;
+        }
+        for col in 0..width {
+            let (x, y) = (x0 + col, y0 + row);
+            if bits >> (width - 1 - col) & 1 == 0 ||
+                (r.quirks.clip && (x >= 64 || y >= 32)) {
+                continue;
+            }
+            let pixel = &mut r.screen[y % 32][x % 64];
+            hit |= *pixel as u8;
+            *pixel = !*pixel;
+        }
+    }
+    r.v[0xF] = hit;
+    r.touch_screen();
+    return Outcome::Ran;
+}
+
+/// The specification of every operation the differential tests
+/// generate, written from the Chip-8 documentation rather than
+/// from the interpreter's opcode decoder
+pub(super) const SPECS: [Spec; 36] = [
+    Spec { 
+        name: "JMP", operand: Operand::Code,
+        build: |op| Instruction::JMP(JmpData::new(op.nnn)),
+        exec: |r, op, addr| {
+            r.pc = op.nnn;
+            return if op.nnn == addr { Outcome::Halted } else { 
+                Outcome::Ran 
+            };
+        }
+    },
+    Spec { 
+        name: "CALL", operand: Operand::Code,
+        build: |op| Instruction::CALL(CallData::new(op.nnn)),
+        exec: |r, op, _| {
+            if r.stack.len() == STACK_LIMIT {
+                return Outcome::Failed;
+            }
+            r.stack.push(r.pc);
+            r.pc = op.nnn;
+            return Outcome::Ran;
+        }
+    },
+    Spec { 
+        name: "RET", operand: Operand::Constant,
+        build: |_| Instruction::RET,
+        exec: |r, _, _| match r.stack.pop() {
+            Some(addr) => {
+                r.pc = addr;
+                Outcome::Ran
+            },
+            None => Outcome::Failed
+        }
+    },
+    Spec { 
+        name: "CLS", operand: Operand::Constant,
+        build: |_| Instruction::CLS,
+        exec: |r, _, _| {
+            r.touch_screen();
+            r.screen = [[false; 64]; 32];
+            return Outcome::Ran;
+        }
+    },
+    Spec { 
+        name: "SKIP.EQ VX, NN", operand: Operand::Constant,
+        build: |op| Instruction::SKIP(SkipData::with_constant(
+                    reg(op.x), op.nn(), SkipType::Equals)),
+        exec: |r, op, _| skip(r, r.v[op.x as usize] == op.nn())
+    },
+    Spec { 
+        name: "SKIP.NE VX, NN", operand: Operand::Constant,
+        build: |op| Instruction::SKIP(SkipData::with_constant(
+                    reg(op.x), op.nn(), SkipType::NotEquals)),
+        exec: |r, op, _| skip(r, r.v[op.x as usize] != op.nn())
+    },
+    Spec { 
+        name: "SKIP.EQ VX, VY", operand: Operand::Constant,
+        build: |op| Instruction::SKIP(SkipData::with_register(
+                    reg(op.x), reg(op.y), SkipType::Equals)),
+        exec: |r, op, _| skip(r, r.v[op.x as usize] == r.v[op.y as usize])
+    },
+    Spec { 
+        name: "SKIP.NE VX, VY", operand: Operand::Constant,
+        build: |op| Instruction::SKIP(SkipData::with_register(
+                    reg(op.x), reg(op.y), SkipType::NotEquals)),
+        exec: |r, op, _| skip(r, r.v[op.x as usize] != r.v[op.y as usize])
+    },
+    Spec { 
+        name: "SKIP.KD", operand: Operand::Constant,
+        build: |op| Instruction::SKIP(SkipData::with_key(
+                    reg(op.x), SkipType::KeyDown)),
+        exec: |r, _, _| skip(r, false)
+    },
+    Spec { 
+        name: "SKIP.KU", operand: Operand::Constant,
+        build: |op| Instruction::SKIP(SkipData::with_key(
+                    reg(op.x), SkipType::KeyUp)),
+        exec: |r, _, _| skip(r, true)
+    },
+    Spec { 
+        name: "MOV VX, NN", operand: Operand::Constant,
+        build: |op| Instruction::MOV(MovData::with_constant(
+                    reg(op.x), op.nn() as u16)),
+        exec: |r, op, _| {
+            r.v[op.x as usize] = op.nn();
+            return Outcome::Ran;
+        }
+    },
+    Spec { 
+        name: "MOV VX, VY", operand: Operand::Constant,
+        build: |op| Instruction::MOV(MovData::with_register(
+                    reg(op.x), reg(op.y))),
+        exec: |r, op, _| {
+            r.v[op.x as usize] = r.v[op.y as usize];
+            return Outcome::Ran;
+        }
+    },
+    Spec { 
+        name: "MOV I, NNN", operand: Operand::Data,
+        build: |op| Instruction::MOV(MovData::with_constant(
+                    Register::I, op.nnn)),
+        exec: |r, op, _| {
+            r.i = op.nnn;
+            return Outcome::Ran;
+        }
+    },
+    Spec { 
+        name: "ADD VX, NN", operand: Operand::Constant,
+        build: |op| Instruction::ADD(AddData::with_constant(
+                    reg(op.x), op.nn() as u16)),
+        exec: |r, op, _| {
+            let x = op.x as usize;
+            r.v[x] = ((r.v[x] as u16 + op.nn() as u16) % 256) as u8;
+            return Outcome::Ran;
+        }
+    },
+    Spec { 
+        name: "ADD VX, VY", operand: Operand::Constant,
+        build: |op| Instruction::ADD(AddData::with_register(
+                    reg(op.x), reg(op.y))),
+        exec: |r, op, _| {
+            let sum = r.v[op.x as usize] as u16 + r.v[op.y as usize] as u16;
+            return with_flag(r, op.x, (sum % 256) as u8, (sum > 255) as u8);
+        }
+    },
+    Spec { 
+        name: "ADD I, VX", operand: Operand::Constant,
+        build: |op| Instruction::ADD(AddData::with_register(
+                    Register::I, reg(op.x))),
+        exec: |r, op, _| {
+            let sum = r.i as u32 + r.v[op.x as usize] as u32;
+            r.i = (sum % 0x10000) as u16;
+            return Outcome::Ran;
+        }
+    },
+    Spec { 
+        name: "OR", operand: Operand::Constant,
+        build: |op| Instruction::OR(OrData::new(reg(op.x), reg(op.y))),
+        exec: |r, op, _| {
+            let value = r.v[op.x as usize] | r.v[op.y as usize];
+            return logic(r, op.x, value);
+        }
+    },
+    Spec { 
+        name: "AND", operand: Operand::Constant,
+        build: |op| Instruction::AND(AndData::new(reg(op.x), reg(op.y))),
+        exec: |r, op, _| {
+            let value = r.v[op.x as usize] & r.v[op.y as usize];
+            return logic(r, op.x, value);
+        }
+    },
+    Spec { 
+        name: "XOR", operand: Operand::Constant,
+        build: |op| Instruction::XOR(XorData::new(reg(op.x), reg(op.y))),
+        exec: |r, op, _| {
+            let value = r.v[op.x as usize] ^ r.v[op.y as usize];
+            return logic(r, op.x, value);
+        }
+    },
+    Spec { 
+        name: "SUB", operand: Operand::Constant,
+        build: |op| Instruction::SUB(SubData::new(reg(op.x), reg(op.y))),
+        exec: |r, op, _| {
+            let a = r.v[op.x as usize] as i16;
+            let b = r.v[op.y as usize] as i16;
+            return with_flag(r, op.x, (a - b).rem_euclid(256) as u8, 
+                             (a >= b) as u8);
+        }
+    },
+    Spec { 
+        name: "SUBN", operand: Operand::Constant,
+        build: |op| Instruction::SUBN(SubnData::new(reg(op.x), reg(op.y))),
+        exec: |r, op, _| {
+            let a = r.v[op.x as usize] as i16;
+            let b = r.v[op.y as usize] as i16;
+            return with_flag(r, op.x, (b - a).rem_euclid(256) as u8, 
+                             (b >= a) as u8);
+        }
+    },
+    Spec { 
+        name: "SHR VX, VY", operand: Operand::Constant,
+        build: |op| Instruction::SHR(ShrData::with_source(reg(op.x), 
+                                                          reg(op.y))),
+        exec: |r, op, _| {
+            let src = shift_source(r, op);
+            return with_flag(r, op.x, src / 2, src % 2);
+        }
+    },
+    Spec { 
+        name: "SHL VX, VY", operand: Operand::Constant,
+        build: |op| Instruction::SHL(ShlData::with_source(reg(op.x), 
+                                                          reg(op.y))),
+        exec: |r, op, _| {
+            let src = shift_source(r, op);
+            return with_flag(r, op.x, (src as u16 * 2 % 256) as u8, 
+                             src / 128);
+        }
+    },
+    Spec { 
+        name: "SHR VX", operand: Operand::Constant,
+        build: |op| Instruction::SHR(ShrData::new(reg(op.x))),
+        exec: |r, op, _| {
+            let src = r.v[op.x as usize];
+            return with_flag(r, op.x, src / 2, src % 2);
+        }
+    },
+    Spec { 
+        name: "SHL VX", operand: Operand::Constant,
+        build: |op| Instruction::SHL(ShlData::new(reg(op.x))),
+        exec: |r, op, _| {
+            let src = r.v[op.x as usize];
+            return with_flag(r, op.x, (src as u16 * 2 % 256) as u8, 
+                             src / 128);
+        }
+    },
+    Spec { 
+        name: "JPC", operand: Operand::Code,
+        build: |op| Instruction::JPC(JpcData::new(op.nnn)),
+        exec: |r, op, _| {
+            let offset = if r.quirks.jump_vx { 
+                r.v[(op.nnn >> 8 & 0xF) as usize] 
+            } else { 
+                r.v[0] 
+            };
+            r.pc = op.nnn + offset as u16;
+            return Outcome::Ran;
+        }
+    },
+    Spec { 
+        name: "RAND", operand: Operand::Constant,
+        build: |op| Instruction::RAND(RandData::new(reg(op.x), op.nn())),
+        exec: |r, op, _| {
+            r.v[op.x as usize] = r.rng.next_byte() & op.nn();
+            return Outcome::Ran;
+        }
+    },
+    Spec { 
+        name: "DRAW", operand: Operand::Constant,
+        build: |op| Instruction::DRAW(DrawData::new(reg(op.x), reg(op.y), 
+                                                    op.n())),
+        exec: |r, op, _| draw(r, op)
+    },
+    Spec { 
+        name: "GDL", operand: Operand::Constant,
+        build: |op| Instruction::GDL(GdlData::new(reg(op.x))),
+        exec: |r, op, _| {
+            r.v[op.x as usize] = r.delay;
+            return Outcome::Ran;
+        }
+    },
+    Spec { 
+        name: "KEY", operand: Operand::Constant,
+        build: |op| Instruction::KEY(KeyData::new(reg(op.x))),
+        exec: |r, _, _| {
+            r.waiting = true;
+            return Outcome::Ran;
+        }
+    },
+    Spec { 
+        name: "SDL", operand: Operand::Constant,
+        build: |op| Instruction::SDL(SdlData::new(reg(op.x))),
+        exec: |r, op, _| {
+            r.delay = r.v[op.x as usize];
+            return Outcome::Ran;
+        }
+    },
+    Spec { 
+        name: "SND", operand: Operand::Constant,
+        build: |op| Instruction::SND(SndData::new(reg(op.x))),
+        exec: |r, op, _| {
+            r.sound = r.v[op.x as usize];
+            return Outcome::Ran;
+        }
+    },
+    Spec { 
+        name: "SCH", operand: Operand::Constant,
+        build: |op| Instruction::SCH(SchData::new(reg(op.x))),
+        exec: |r, op, _| {
+            r.i = FONT_ADDR + (r.v[op.x as usize] % 16) as u16 * 5;
+            return Outcome::Ran;
+        }
+    },
+    Spec { 
+        name: "BCD", operand: Operand::Constant,
+        build: |op| Instruction::BCD(BcdData::new(reg(op.x))),
+        exec: |r, op, _| {
+            let value = r.v[op.x as usize];
+            r.store(0, value / 100);
+            r.store(1, value / 10 % 10);
+            r.store(2, value % 10);
+            return Outcome::Ran;
+        }
+    },
+    Spec { 
+        name: "RDP", operand: Operand::Constant,
+        build: |op| Instruction::RDP(RdpData::new(reg(op.x))),
+        exec: |r, op, _| {
+            for k in 0..=op.x {
+                let value = r.v[k as usize];
+                r.store(k as u16, value);
+            }
+            if r.quirks.load_store_inc_i {
+                r.i = r.i.wrapping_add(op.x as u16 + 1);
+            }
+            return Outcome::Ran;
+        }
+    },
+    Spec { 
+        name: "RLD", operand: Operand::Constant,
+        build: |op| Instruction::RLD(RldData::new(reg(op.x))),
+        exec: |r, op, _| {
+            for k in 0..=op.x {
+                r.v[k as usize] = r.memory[r.i.wrapping_add(k as u16) 
+                                           as usize];
+            }
+            if r.quirks.load_store_inc_i {
+                r.i = r.i.wrapping_add(op.x as u16 + 1);
+            }
+            return Outcome::Ran;
+        }
+    }
+];
+
+//end of file
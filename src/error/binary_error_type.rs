@@ -33,7 +33,10 @@ pub enum BinaryErrorType {
     DataError,
 
     /// Error writing the binary to a file
-    FileError
+    FileError,
+
+    /// The desired label exists already
+    LabelExists
 }
 
 //Debug implementation
@@ -48,6 +51,9 @@ impl fmt::Debug for BinaryErrorType {
             },
             BinaryErrorType::FileExists => {
                 write!(f, "A binary already exists with the given name")
+            },
+            BinaryErrorType::LabelExists => {
+                write!(f, "A label already exists with the given name")
             }
         }
     }
@@ -65,6 +71,9 @@ impl fmt::Display for BinaryErrorType {
             },
             BinaryErrorType::FileExists => {
                 write!(f, "A binary already exists with the given name")
+            },
+            BinaryErrorType::LabelExists => {
+                write!(f, "A label already exists with the given name")
             }
         }
     }
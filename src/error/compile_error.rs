@@ -0,0 +1,135 @@
+/*
+ * compile_error.rs
+ * Defines a struct that holds error data from source compilation
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//usage statements
+use std::fmt;
+use super::CompileErrorType;
+
+/// A possible error resulting from compiling source code
+#[derive(Clone, PartialEq)]
+pub struct CompileError {
+    /// The error type
+    error_type: CompileErrorType,
+
+    /// The 1-based line the error occurred on
+    line: usize,
+
+    /// The 1-based column the error occurred on
+    col: usize,
+
+    /// The file the error occurred in, if known
+    file: Option<String>
+}
+
+//implementation
+impl CompileError {
+    /// Constructs a new `CompileError` instance
+    ///
+    /// # Arguments
+    ///
+    /// * `new_type` - The type of the error
+    /// * `new_line` - The line the error occurred on
+    /// * `new_col` - The column the error occurred on
+    ///
+    /// # Returns
+    ///
+    /// A new `CompileError` instance with the given properties
+    pub fn new(new_type: CompileErrorType, new_line: usize, 
+               new_col: usize) -> CompileError {
+        return CompileError {
+            error_type: new_type,
+            line: new_line,
+            col: new_col,
+            file: None
+        };
+    }
+
+    /// Attaches a file name to the error
+    ///
+    /// # Argument
+    ///
+    /// * `new_file` - The name of the file the error occurred in
+    ///
+    /// # Returns
+    ///
+    /// The error, with the given file name
+    pub fn with_file(mut self, new_file: &str) -> CompileError {
+        self.file = Some(String::from(new_file));
+        return self;
+    }
+
+    /// Gets the error type
+    ///
+    /// # Returns
+    ///
+    /// The error type
+    pub fn get_type(&self) -> CompileErrorType {
+        return self.error_type.clone();
+    }
+
+    /// Gets the line the error occurred on
+    ///
+    /// # Returns
+    ///
+    /// The 1-based line number of the error
+    pub fn get_line(&self) -> usize {
+        return self.line;
+    }
+
+    /// Gets the column the error occurred on
+    ///
+    /// # Returns
+    ///
+    /// The 1-based column number of the error
+    pub fn get_col(&self) -> usize {
+        return self.col;
+    }
+
+    /// Gets the file the error occurred in
+    ///
+    /// # Returns
+    ///
+    /// The name of the file, if one was attached
+    pub fn get_file(&self) -> Option<&str> {
+        return self.file.as_deref();
+    }
+}
+
+//Display implementation
+impl fmt::Display for CompileError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(ref file) = self.file {
+            write!(f, "{}:", file)?;
+        }
+        write!(f, "{}:{}: {}", self.line, self.col, self.error_type)
+    }
+}
+
+//Debug implementation
+impl fmt::Debug for CompileError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{{ line: {}, col: {}, error: {} }}", 
+               self.line, self.col, self.error_type)
+    }
+}
+
+//end of file
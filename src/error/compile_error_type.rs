@@ -0,0 +1,149 @@
+/*
+ * compile_error_type.rs
+ * Enumerates types of source compilation errors
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//usage statement
+use std::fmt;
+
+/// Types of source compilation errors
+#[derive(Debug, Clone, PartialEq)]
+pub enum CompileErrorType {
+    /// A character that cannot start a token
+    UnexpectedChar(char),
+
+    /// A token that does not fit the grammar
+    UnexpectedToken(String),
+
+    /// The source ended in the middle of a construct
+    UnexpectedEnd,
+
+    /// A numeric literal does not fit in its destination
+    ConstantOutOfRange(i64),
+
+    /// A name was used before it was declared
+    UnknownName(String),
+
+    /// A name was declared more than once
+    Redefinition(String),
+
+    /// An operand had the wrong type
+    TypeMismatch(String),
+
+    /// There are no free registers left for a value
+    OutOfRegisters,
+
+    /// A sprite definition is empty or too tall to draw
+    BadSprite(String),
+
+    /// An operand that must be constant was not
+    NotConstant(String),
+
+    /// The program does not fit in memory
+    ProgramTooLarge(usize),
+
+    /// Code or data was placed over existing code or data,
+    /// or outside of program memory
+    AddressConflict(u16),
+
+    /// A compile-time assertion failed
+    AssertionFailed(String),
+
+    /// A recognized feature that is not supported
+    Unsupported(String),
+
+    /// An instruction operand is not valid for its mnemonic
+    InvalidOperand(String),
+
+    /// A constant expression divides by zero
+    DivideByZero,
+
+    /// An included file could not be read
+    IncludeFailed(String),
+
+    /// Macros or symbols are nested or defined in terms of
+    /// themselves too deeply
+    RecursionLimit(String)
+}
+
+//Display implementation
+impl fmt::Display for CompileErrorType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            CompileErrorType::UnexpectedChar(c) => {
+                write!(f, "Unexpected character '{}'", c)
+            },
+            CompileErrorType::UnexpectedToken(ref t) => {
+                write!(f, "Unexpected token '{}'", t)
+            },
+            CompileErrorType::UnexpectedEnd => {
+                write!(f, "Unexpected end of input")
+            },
+            CompileErrorType::ConstantOutOfRange(n) => {
+                write!(f, "Constant {} is out of range", n)
+            },
+            CompileErrorType::UnknownName(ref n) => {
+                write!(f, "Unknown name '{}'", n)
+            },
+            CompileErrorType::Redefinition(ref n) => {
+                write!(f, "'{}' is already defined", n)
+            },
+            CompileErrorType::TypeMismatch(ref m) => {
+                write!(f, "Type mismatch: {}", m)
+            },
+            CompileErrorType::OutOfRegisters => {
+                write!(f, "Expression needs more registers than are free")
+            },
+            CompileErrorType::BadSprite(ref n) => {
+                write!(f, "Sprite '{}' must be 1 to 15 bytes tall", n)
+            },
+            CompileErrorType::NotConstant(ref m) => {
+                write!(f, "Expected a constant: {}", m)
+            },
+            CompileErrorType::ProgramTooLarge(n) => {
+                write!(f, "Program is {} bytes, which does not fit in memory",
+                       n)
+            },
+            CompileErrorType::AddressConflict(a) => {
+                write!(f, "Cannot place data at address 0x{:03X}", a)
+            },
+            CompileErrorType::AssertionFailed(ref m) => {
+                write!(f, "Assertion failed: {}", m)
+            },
+            CompileErrorType::Unsupported(ref m) => {
+                write!(f, "'{}' is not supported", m)
+            },
+            CompileErrorType::InvalidOperand(ref m) => {
+                write!(f, "Invalid operand: {}", m)
+            },
+            CompileErrorType::DivideByZero => {
+                write!(f, "Division by zero in constant expression")
+            },
+            CompileErrorType::IncludeFailed(ref m) => {
+                write!(f, "Could not include '{}'", m)
+            },
+            CompileErrorType::RecursionLimit(ref m) => {
+                write!(f, "'{}' is nested too deeply", m)
+            }
+        }
+    }
+}
+
+//end of file
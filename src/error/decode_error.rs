@@ -0,0 +1,89 @@
+/*
+ * decode_error.rs
+ * Defines an error reading binary formats
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//usage statements
+use std::fmt;
+use super::DecodeErrorType;
+
+/// A possible error resulting from reading a binary format
+#[derive(Clone, PartialEq)]
+pub struct DecodeError {
+    /// The error type
+    error_type: DecodeErrorType,
+
+    /// The offset in the data at which the error was found
+    offset: usize
+}
+
+//implementation
+impl DecodeError {
+    /// Constructs a new `DecodeError` instance
+    ///
+    /// # Arguments
+    ///
+    /// * `new_type` - The type of the error
+    /// * `new_offset` - The offset in the data of the error
+    ///
+    /// # Returns
+    ///
+    /// A new `DecodeError` instance with the given properties
+    pub fn new(new_type: DecodeErrorType, new_offset: usize) -> DecodeError {
+        return DecodeError {
+            error_type: new_type,
+            offset: new_offset
+        };
+    }
+
+    /// Gets the error type
+    ///
+    /// # Returns
+    ///
+    /// The error type
+    pub fn get_type(&self) -> DecodeErrorType {
+        return self.error_type.clone();
+    }
+
+    /// Gets the offset in the data of the error
+    ///
+    /// # Returns
+    ///
+    /// The offset, in bytes
+    pub fn get_offset(&self) -> usize {
+        return self.offset;
+    }
+}
+
+//Display implementation
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "offset {}: {}", self.offset, self.error_type)
+    }
+}
+
+//Debug implementation
+impl fmt::Debug for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{{ offset: {}, error: {} }}", self.offset, self.error_type)
+    }
+}
+
+//end of file
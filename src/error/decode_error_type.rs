@@ -0,0 +1,62 @@
+/*
+ * decode_error_type.rs
+ * Enumerates the types of errors reading binary formats
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//usage statement
+use std::fmt;
+
+/// Types of errors reading the crate's binary formats
+#[derive(Debug, Clone, PartialEq)]
+pub enum DecodeErrorType {
+    /// The data does not start with the format's magic number
+    BadMagic,
+
+    /// The data was written by an unsupported version of the format
+    UnsupportedVersion(u8),
+
+    /// The data ends partway through a record
+    Truncated,
+
+    /// A field holds a value the format does not allow
+    InvalidValue(String)
+}
+
+//Display implementation
+impl fmt::Display for DecodeErrorType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            DecodeErrorType::BadMagic => {
+                write!(f, "Unrecognized file format")
+            },
+            DecodeErrorType::UnsupportedVersion(v) => {
+                write!(f, "Unsupported format version {}", v)
+            },
+            DecodeErrorType::Truncated => {
+                write!(f, "Unexpected end of data")
+            },
+            DecodeErrorType::InvalidValue(ref what) => {
+                write!(f, "Invalid {}", what)
+            }
+        }
+    }
+}
+
+//end of file
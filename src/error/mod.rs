@@ -25,5 +25,17 @@ mod binary_error;
 pub use binary_error::BinaryError;
 mod binary_error_type;
 pub use binary_error_type::BinaryErrorType;
+mod compile_error;
+pub use compile_error::CompileError;
+mod compile_error_type;
+pub use compile_error_type::CompileErrorType;
+mod decode_error;
+pub use decode_error::DecodeError;
+mod decode_error_type;
+pub use decode_error_type::DecodeErrorType;
+mod vm_error;
+pub use vm_error::VmError;
+mod vm_error_type;
+pub use vm_error_type::VmErrorType;
 
 //end of file
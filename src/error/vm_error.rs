@@ -0,0 +1,89 @@
+/*
+ * vm_error.rs
+ * Defines a struct that represents an interpreter error
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//usage statements
+use std::fmt;
+use super::VmErrorType;
+
+/// A possible error resulting from running a program
+#[derive(Clone, PartialEq)]
+pub struct VmError {
+    /// The error type
+    error_type: VmErrorType,
+
+    /// The address of the instruction that caused the error
+    pc: u16
+}
+
+//implementation
+impl VmError {
+    /// Constructs a new `VmError` instance
+    ///
+    /// # Arguments
+    ///
+    /// * `new_type` - The type of the error
+    /// * `new_pc` - The address of the instruction that caused the error
+    ///
+    /// # Returns
+    ///
+    /// A new `VmError` instance with the given properties
+    pub fn new(new_type: VmErrorType, new_pc: u16) -> VmError {
+        return VmError {
+            error_type: new_type,
+            pc: new_pc
+        };
+    }
+
+    /// Gets the error type
+    ///
+    /// # Returns
+    ///
+    /// The error type
+    pub fn get_type(&self) -> VmErrorType {
+        return self.error_type.clone();
+    }
+
+    /// Gets the address of the instruction that caused the error
+    ///
+    /// # Returns
+    ///
+    /// The program counter at the time of the error
+    pub fn get_pc(&self) -> u16 {
+        return self.pc;
+    }
+}
+
+//Display implementation
+impl fmt::Display for VmError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "0x{:03X}: {}", self.pc, self.error_type)
+    }
+}
+
+//Debug implementation
+impl fmt::Debug for VmError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{{ pc: 0x{:03X}, error: {} }}", self.pc, self.error_type)
+    }
+}
+
+//end of file
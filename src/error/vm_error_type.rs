@@ -0,0 +1,62 @@
+/*
+ * vm_error_type.rs
+ * Enumerates types of interpreter errors
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//usage statement
+use std::fmt;
+
+/// Types of interpreter errors
+#[derive(Debug, Clone, PartialEq)]
+pub enum VmErrorType {
+    /// The opcode is not one the interpreter can execute
+    InvalidOpcode(u16),
+
+    /// A subroutine call exceeded the depth of the stack
+    StackOverflow,
+
+    /// A return was executed with an empty stack
+    StackUnderflow,
+
+    /// The program does not fit in memory
+    RomTooLarge(usize)
+}
+
+//Display implementation
+impl fmt::Display for VmErrorType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            VmErrorType::InvalidOpcode(op) => {
+                write!(f, "Invalid opcode 0x{:04X}", op)
+            },
+            VmErrorType::StackOverflow => {
+                write!(f, "Stack overflow")
+            },
+            VmErrorType::StackUnderflow => {
+                write!(f, "Return with an empty stack")
+            },
+            VmErrorType::RomTooLarge(n) => {
+                write!(f, "ROM of {} bytes does not fit in memory", n)
+            }
+        }
+    }
+}
+
+//end of file
@@ -20,9 +20,19 @@
  * along with this program.  If not, see <http://www.gnu.org/licenses/>.
  */
 
+//every function in the crate returns explicitly
+#![allow(clippy::needless_return)]
+
 //exports
 pub mod data;
 pub mod codegen;
 pub mod error;
+pub mod compiler;
+pub mod octo;
+pub mod asm;
+pub mod vm;
+pub mod difftest;
+pub mod analysis;
+pub mod lint;
 
 //end of file
@@ -0,0 +1,145 @@
+/*
+ * lint_code.rs
+ * Defines the stable codes of lint warnings
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+
+//usage statements
+use std::fmt;
+
+/// A kind of warning the linter reports. Each has a code and a
+/// name that stay the same between releases, so either can be
+/// used to suppress it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LintCode {
+    /// A value kept in `VF` is overwritten by an instruction that
+    /// sets `VF` as a flag, and the flag is then read in its place
+    FlagClobber,
+
+    /// A skip is followed by a two-word XO-CHIP instruction, which
+    /// other interpreters skip only the first word of
+    SkipLong,
+
+    /// A jump or call lands in data, or in the middle of an
+    /// instruction
+    JumpIntoData,
+
+    /// `BCD` or `RDP` writes to memory below `0x200`, where
+    /// the interpreter and its font live
+    LowMemoryWrite,
+
+    /// `DRAW` with a height of `0`, which draws a 16x16 sprite on
+    /// SUPER-CHIP but nothing on classic interpreters
+    DrawZeroHeight,
+
+    /// `BCD` or `RDP` writes over an instruction of the program,
+    /// other than the operands of a patch point
+    SelfModifying
+}
+
+//implementation
+impl LintCode {
+    /// Gets every lint code
+    ///
+    /// # Returns
+    ///
+    /// The lint codes in order
+    pub fn all() -> Vec<LintCode> {
+        return vec![LintCode::FlagClobber, LintCode::SkipLong,
+                    LintCode::JumpIntoData, LintCode::LowMemoryWrite,
+                    LintCode::DrawZeroHeight, LintCode::SelfModifying];
+    }
+
+    /// Gets the code of the lint
+    ///
+    /// # Returns
+    ///
+    /// The code, such as `L001`
+    pub fn get_code(&self) -> &'static str {
+        return match *self {
+            LintCode::FlagClobber => "L001",
+            LintCode::SkipLong => "L002",
+            LintCode::JumpIntoData => "L003",
+            LintCode::LowMemoryWrite => "L004",
+            LintCode::DrawZeroHeight => "L005",
+            LintCode::SelfModifying => "L006"
+        };
+    }
+
+    /// Gets the name of the lint
+    ///
+    /// # Returns
+    ///
+    /// The name, such as `flag-clobber`
+    pub fn get_name(&self) -> &'static str {
+        return match *self {
+            LintCode::FlagClobber => "flag-clobber",
+            LintCode::SkipLong => "skip-long",
+            LintCode::JumpIntoData => "jump-into-data",
+            LintCode::LowMemoryWrite => "low-memory-write",
+            LintCode::DrawZeroHeight => "draw-zero-height",
+            LintCode::SelfModifying => "self-modifying"
+        };
+    }
+
+    /// Looks up a lint by its code or name
+    ///
+    /// # Argument
+    ///
+    /// * `text` - The code, in either case, or the name
+    ///
+    /// # Returns
+    ///
+    /// The lint, or `None` if no lint has that code or name
+    pub fn parse(text: &str) -> Option<LintCode> {
+        let text = text.trim();
+        return LintCode::all().into_iter().find(|l| {
+            l.get_code().eq_ignore_ascii_case(text) || l.get_name() == text
+        });
+    }
+}
+
+//Display implementation
+impl fmt::Display for LintCode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return write!(f, "{}", self.get_code());
+    }
+}
+
+//unit tests
+#[cfg(test)]
+mod tests {
+    //import the LintCode enum
+    use super::*;
+
+    //this test checks that codes and names parse back
+    #[test]
+    fn test_parse() {
+        for lint in LintCode::all() {
+            assert_eq!(LintCode::parse(lint.get_code()), Some(lint));
+            assert_eq!(LintCode::parse(lint.get_name()), Some(lint));
+        }
+        assert_eq!(LintCode::parse(" l003 "), Some(LintCode::JumpIntoData));
+        assert_eq!(LintCode::parse("L999"), None);
+        assert_eq!(LintCode::SkipLong.to_string(), "L002");
+    }
+}
+
+//end of file
@@ -0,0 +1,373 @@
+/*
+ * linter.rs
+ * Defines a linter that reports likely mistakes in programs
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+
+//usage statements
+use std::collections::BTreeSet;
+use super::{LintCode, Warning};
+use super::super::analysis::{Cfg, ConstantPropagation, ReachingDefinitions};
+use super::super::codegen::{CodeGen, Element, Image, Instruction,
+                            PROGRAM_START};
+use super::super::data::Register;
+use super::super::vm::Quirks;
+
+/// The marker that starts a suppression in a source comment
+const ALLOW_MARKER: &str = "lint:";
+
+/// Scans a program image for likely mistakes. Lints can be
+/// suppressed for the whole program with `allow`, or for a single
+/// line of source with a comment such as `; lint: allow(L005)`,
+/// naming lints by code or by name.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Linter {
+    /// The interpreter behaviours assumed
+    quirks: Quirks,
+
+    /// Whether the program targets classic interpreters
+    classic: bool,
+
+    /// The lints suppressed for the whole program
+    allowed: BTreeSet<LintCode>
+}
+
+//implementation
+impl Linter {
+    /// Constructs a new `Linter` instance
+    ///
+    /// # Argument
+    ///
+    /// * `new_quirks` - The interpreter behaviours to assume
+    ///
+    /// # Returns
+    ///
+    /// A new `Linter` instance targeting classic interpreters,
+    /// with every lint enabled
+    pub fn new(new_quirks: Quirks) -> Linter {
+        return Linter {
+            quirks: new_quirks,
+            classic: true,
+            allowed: BTreeSet::new()
+        };
+    }
+
+    /// Sets whether the program targets classic interpreters,
+    /// rather than SUPER-CHIP or XO-CHIP
+    ///
+    /// # Argument
+    ///
+    /// * `new_classic` - Whether classic interpreters are targeted
+    pub fn set_classic(&mut self, new_classic: bool) {
+        self.classic = new_classic;
+    }
+
+    /// Suppresses a lint for the whole program
+    ///
+    /// # Argument
+    ///
+    /// * `code` - The lint to suppress
+    pub fn allow(&mut self, code: LintCode) {
+        self.allowed.insert(code);
+    }
+
+    /// Gets whether a lint is suppressed for the whole program
+    ///
+    /// # Argument
+    ///
+    /// * `code` - The lint
+    ///
+    /// # Returns
+    ///
+    /// Whether the lint has been allowed
+    pub fn is_allowed(&self, code: LintCode) -> bool {
+        return self.allowed.contains(&code);
+    }
+
+    /// Scans a program for problems. Data-flow lints only look at
+    /// the instructions that can be reached from `0x200`.
+    ///
+    /// # Argument
+    ///
+    /// * `image` - The program image
+    ///
+    /// # Returns
+    ///
+    /// The warnings that are not suppressed, in address order
+    pub fn lint(&self, image: &Image) -> Vec<Warning> {
+        let cfg = Cfg::from_image(PROGRAM_START, image);
+        let constants = ConstantPropagation::new(&cfg, self.quirks);
+        let reachable: Vec<(u16, &Instruction)> = cfg.flow_reachable()
+            .into_iter().flat_map(|b| cfg.get_block(b).unwrap().addressed())
+            .collect();
+        let mut found = Vec::new();
+        self.check_instructions(image, &constants, &mut found);
+        self.check_flags(&cfg, &reachable, &mut found);
+        self.check_writes(image, &constants, &reachable, &mut found);
+
+        //drop suppressed warnings and attach the source of the rest
+        found.sort_by_key(|(code, addr, _)| (*addr, *code));
+        return found.into_iter().filter(|(code, addr, _)| {
+            !self.is_allowed(*code) && !match image.get_span(*addr) {
+                Some(span) => allowed_in(span.get_text()).contains(code),
+                None => false
+            }
+        }).map(|(code, addr, message)| {
+            Warning::new(code, addr, &message,
+                         image.get_span(addr).cloned())
+        }).collect();
+    }
+
+    /// Checks each instruction on its own, whether or not
+    /// it can be reached
+    fn check_instructions(&self, image: &Image,
+                          constants: &ConstantPropagation,
+                          found: &mut Vec<(LintCode, u16, String)>) {
+        for (addr, elem) in image.elements() {
+            let instr = match *elem {
+                Element::Instr(ref instr) => instr,
+                _ => continue
+            };
+            let target = match *instr {
+                Instruction::JMP(ref d) => Some(d.get_addr()),
+                Instruction::CALL(ref d) => Some(d.get_addr()),
+                Instruction::JPC(_) => constants.get_jump_target(addr),
+                _ => None
+            };
+            if let Some(message) = target.and_then(|t| landing(image, t)) {
+                found.push((LintCode::JumpIntoData, addr, message));
+            }
+            match *instr {
+                Instruction::SKIP(_) => {
+                    let next = image.get_element(addr.wrapping_add(2));
+                    if next == Some(&Element::Word(0xF000)) {
+                        found.push((LintCode::SkipLong, addr, String::from(
+                            "skips a two-word XO-CHIP instruction, which \
+                             other interpreters skip only half of")));
+                    }
+                },
+                Instruction::DRAW(ref d) if self.classic &&
+                    d.gen_opcode() & 0xF == 0 => {
+                    found.push((LintCode::DrawZeroHeight, addr,
+                                String::from("draws nothing on classic \
+                                              interpreters, since the \
+                                              sprite has a height of 0")));
+                },
+                _ => {}
+            };
+        }
+    }
+
+    /// Checks for values in `VF` that a flag overwrites before
+    /// they are read, when the flag is read instead
+    fn check_flags(&self, cfg: &Cfg, reachable: &[(u16, &Instruction)],
+                   found: &mut Vec<(LintCode, u16, String)>) {
+        let defs = ReachingDefinitions::new(cfg, self.quirks);
+        let mut read = BTreeSet::new();
+        for (addr, instr) in reachable.iter() {
+            if instr.effects(self.quirks).reads(&Register::VF) {
+                read.extend(defs.get_reaching(*addr, &Register::VF));
+            }
+        }
+        for (addr, instr) in reachable.iter() {
+            if !instr.effects(self.quirks).sets_flag() ||
+                !read.contains(&Some(*addr)) {
+                continue;
+            }
+            for def in defs.get_reaching(*addr, &Register::VF) {
+                let store = match def {
+                    Some(d) if !read.contains(&def) => d,
+                    _ => continue
+                };
+                let kept = match reachable.iter().find(|(a, _)| *a == store) {
+                    Some((_, Instruction::RLD(_))) | None => false,
+                    Some((_, i)) => !i.effects(self.quirks).sets_flag()
+                };
+                if kept {
+                    found.push((LintCode::FlagClobber, *addr, format!(
+                        "sets VF as a flag over the value stored at \
+                         0x{:03X}, and the flag is read in its place",
+                        store)));
+                }
+            }
+        }
+    }
+
+    /// Checks the memory written by `BCD` and `RDP` where `I`
+    /// is known, allowing writes to the operands of patch points
+    fn check_writes(&self, image: &Image, constants: &ConstantPropagation,
+                    reachable: &[(u16, &Instruction)],
+                    found: &mut Vec<(LintCode, u16, String)>) {
+        for (addr, instr) in reachable.iter() {
+            let len = instr.effects(self.quirks).get_memory_write() as u32;
+            let start = match constants.get_value(*addr, &Register::I) {
+                Some(i) if len > 0 => i as u32,
+                _ => continue
+            };
+            if start < PROGRAM_START as u32 {
+                found.push((LintCode::LowMemoryWrite, *addr, format!(
+                    "writes to 0x{:03X}, below 0x{:03X}", start,
+                    PROGRAM_START)));
+                continue;
+            }
+            let overwritten = image.elements().find(|(a, e)| {
+                let a = *a as u32;
+                matches!(**e, Element::Instr(_)) && (a..a + 2).any(|b| {
+                    b >= start && b < start + len &&
+                        !image.is_writable(b as u16)
+                })
+            });
+            if let Some((a, _)) = overwritten {
+                found.push((LintCode::SelfModifying, *addr, format!(
+                    "writes over the instruction at 0x{:03X}", a)));
+            }
+        }
+    }
+}
+
+/// Describes where a jump to an address lands, if not
+/// at the start of an instruction
+fn landing(image: &Image, target: u16) -> Option<String> {
+    let (addr, elem) = image.elements().take_while(|(a, _)| *a <= target)
+                            .last()?;
+    if addr as u32 + elem.len() as u32 <= target as u32 {
+        return None;
+    }
+    let operand = addr >= 2 &&
+        image.get_element(addr - 2) == Some(&Element::Word(0xF000));
+    return match *elem {
+        Element::Byte(_) => {
+            Some(format!("jumps into data at 0x{:03X}", target))
+        },
+        _ if operand || addr != target => {
+            Some(format!("jumps into the middle of the instruction at \
+                          0x{:03X}", if operand { addr - 2 } else { addr }))
+        },
+        _ => None
+    };
+}
+
+/// Finds the lints a source line suppresses with
+/// comments such as `lint: allow(L001, skip-long)`
+fn allowed_in(text: &str) -> Vec<LintCode> {
+    let mut codes = Vec::new();
+    let mut rest = text;
+    while let Some(pos) = rest.find(ALLOW_MARKER) {
+        rest = rest[pos + ALLOW_MARKER.len()..].trim_start();
+        if rest.starts_with("allow(") {
+            if let Some(end) = rest.find(')') {
+                codes.extend(rest["allow(".len()..end].split(',')
+                                 .filter_map(LintCode::parse));
+            }
+        }
+    }
+    return codes;
+}
+
+//unit tests
+#[cfg(test)]
+mod tests {
+    //import the Linter struct
+    use super::*;
+    use super::super::super::asm;
+
+    //lints assembly source and returns the codes and addresses found
+    fn lint(linter: &Linter, src: &str) -> Vec<(LintCode, u16)> {
+        let img = asm::assemble(src, "lint.s").unwrap();
+        return linter.lint(&img).iter()
+                     .map(|w| (w.get_code(), w.get_addr())).collect();
+    }
+
+    //this test checks the lints of single instructions
+    #[test]
+    fn test_instructions() {
+        let mut linter = Linter::new(Quirks::vip());
+        let src = "start: DRAW V0, V1, 0\n\
+                   SKIP.EQ V0, 1\n\
+                   .word 0xF000, 0x0300\n\
+                   JMP data\n\
+                   CALL start + 1\n\
+                   JMP start + 6\n\
+                   data: .byte 1, 2";
+        assert_eq!(lint(&linter, src), vec![
+            (LintCode::DrawZeroHeight, 0x200),
+            (LintCode::SkipLong, 0x202),
+            (LintCode::JumpIntoData, 0x208),
+            (LintCode::JumpIntoData, 0x20A),
+            (LintCode::JumpIntoData, 0x20C)
+        ]);
+        linter.set_classic(false);
+        linter.allow(LintCode::JumpIntoData);
+        assert_eq!(lint(&linter, src), vec![(LintCode::SkipLong, 0x202)]);
+    }
+
+    //this test checks suppression with source comments
+    #[test]
+    fn test_suppression() {
+        let linter = Linter::new(Quirks::vip());
+        let src = "DRAW V0, V1, 0 ; lint: allow(L005)\n\
+                   DRAW V0, V1, 0 ; lint: allow(skip-long, draw-zero-height)\n\
+                   DRAW V0, V1, 0 ; lint: allow(L001)";
+        assert_eq!(lint(&linter, src),
+                   vec![(LintCode::DrawZeroHeight, 0x204)]);
+        assert_eq!(allowed_in("lint:allow( L004 ,nope) lint: allow(L006)"),
+                   vec![LintCode::LowMemoryWrite, LintCode::SelfModifying]);
+    }
+
+    //this test checks VF kept across a flag
+    #[test]
+    fn test_flag_clobber() {
+        let linter = Linter::new(Quirks::vip());
+        let src = "MOV VF, 5\n\
+                   ADD V0, V1\n\
+                   MOV V2, VF\n\
+                   MOV VF, 5\n\
+                   ADD V0, VF\n\
+                   MOV V2, VF\n\
+                   JMP $";
+        assert_eq!(lint(&linter, src), vec![(LintCode::FlagClobber, 0x202)]);
+    }
+
+    //this test checks writes to low memory and to instructions
+    #[test]
+    fn test_writes() {
+        let linter = Linter::new(Quirks::vip());
+        let src = "MOV I, 0x100\n\
+                   BCD V0\n\
+                   MOV I, patch\n\
+                   RDP V1\n\
+                   patch: JMP $\n\
+                   MOV I, 0x300\n\
+                   RDP V1";
+        assert_eq!(lint(&linter, src), vec![
+            (LintCode::LowMemoryWrite, 0x202),
+            (LintCode::SelfModifying, 0x206)
+        ]);
+        let src = "MOV I, patch\n\
+                   RDP V1\n\
+                   MOV I, patch.operand\n\
+                   RDP V0\n\
+                   .patch patch\n\
+                   JMP $";
+        assert!(lint(&linter, src).is_empty());
+    }
+}
+
+//end of file
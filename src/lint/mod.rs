@@ -0,0 +1,41 @@
+/*
+ * mod.rs
+ * Module export file for the lint module
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+
+//! Warnings about likely mistakes in Chip-8 programs.
+//!
+//! A `Linter` scans a program image, using the analyses of the
+//! `analysis` module to follow values through the program, and
+//! reports each problem as a `Warning`. Every kind of problem has
+//! a `LintCode` with a stable code and name, which can be allowed
+//! for the whole program or suppressed on one line of source with
+//! a comment such as `; lint: allow(L005)`.
+
+//exports
+mod lint_code;
+pub use lint_code::LintCode;
+mod warning;
+pub use warning::Warning;
+mod linter;
+pub use linter::Linter;
+
+//end of file
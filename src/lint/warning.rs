@@ -0,0 +1,140 @@
+/*
+ * warning.rs
+ * Defines a warning reported by the linter
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+
+//usage statements
+use std::fmt;
+use super::LintCode;
+use super::super::codegen::SourceSpan;
+
+/// A problem the linter found at an address of a program
+#[derive(Debug, Clone, PartialEq)]
+pub struct Warning {
+    /// The kind of problem
+    code: LintCode,
+
+    /// The address of the element the problem is at
+    addr: u16,
+
+    /// A description of the problem
+    message: String,
+
+    /// The source of the element, if known
+    span: Option<SourceSpan>
+}
+
+//implementation
+impl Warning {
+    /// Constructs a new `Warning` instance
+    ///
+    /// # Arguments
+    ///
+    /// * `new_code` - The kind of problem
+    /// * `new_addr` - The address of the element the problem is at
+    /// * `new_message` - A description of the problem
+    /// * `new_span` - The source of the element, if known
+    ///
+    /// # Returns
+    ///
+    /// A new `Warning` instance
+    pub fn new(new_code: LintCode, new_addr: u16, new_message: &str,
+               new_span: Option<SourceSpan>) -> Warning {
+        return Warning {
+            code: new_code,
+            addr: new_addr,
+            message: String::from(new_message),
+            span: new_span
+        };
+    }
+
+    /// Gets the kind of problem
+    ///
+    /// # Returns
+    ///
+    /// The lint code of the warning
+    pub fn get_code(&self) -> LintCode {
+        return self.code;
+    }
+
+    /// Gets the address of the problem
+    ///
+    /// # Returns
+    ///
+    /// The address of the element the problem is at
+    pub fn get_addr(&self) -> u16 {
+        return self.addr;
+    }
+
+    /// Gets the description of the problem
+    ///
+    /// # Returns
+    ///
+    /// The message of the warning
+    pub fn get_message(&self) -> &str {
+        return self.message.as_str();
+    }
+
+    /// Gets the source of the problem
+    ///
+    /// # Returns
+    ///
+    /// The source span of the element, if known
+    pub fn get_span(&self) -> Option<&SourceSpan> {
+        return self.span.as_ref();
+    }
+}
+
+//Display implementation
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(ref span) = self.span {
+            if let Some(file) = span.get_file() {
+                write!(f, "{}:", file)?;
+            }
+            write!(f, "{}: ", span.get_line())?;
+        }
+        return write!(f, "0x{:03X}: warning[{}]: {}", self.addr,
+                      self.code, self.message);
+    }
+}
+
+//unit tests
+#[cfg(test)]
+mod tests {
+    //import the Warning struct
+    use super::*;
+
+    //this test checks warning formatting
+    #[test]
+    fn test_display() {
+        let w = Warning::new(LintCode::DrawZeroHeight, 0x204,
+                             "draws nothing", None);
+        assert_eq!(w.to_string(), "0x204: warning[L005]: draws nothing");
+        let span = SourceSpan::new(3, "DRAW V0, V1, 0").with_file("a.s");
+        let w = Warning::new(LintCode::DrawZeroHeight, 0x204,
+                             "draws nothing", Some(span));
+        assert_eq!(w.to_string(),
+                   "a.s:3: 0x204: warning[L005]: draws nothing");
+    }
+}
+
+//end of file
@@ -0,0 +1,211 @@
+/*
+ * calc.rs
+ * Evaluates Octo compile-time expressions
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//usage statements
+use super::tokenizer::{parse_number, Token};
+use super::super::error::{CompileError, CompileErrorType};
+
+/// Evaluates an Octo `:calc` expression. As in Octo, there is no
+/// operator precedence: operators are applied right to left, so a
+/// unary operator applies to everything after it, and parentheses
+/// group subexpressions.
+///
+/// # Arguments
+///
+/// * `tokens` - The tokens of the expression, without braces
+/// * `lookup` - Resolves names to values
+///
+/// # Returns
+///
+/// The value of the expression, or a `CompileError`
+pub fn eval(tokens: &[Token], lookup: &dyn Fn(&str) -> Option<f64>)
+    -> Result<f64, CompileError> {
+    let mut pos = 0;
+    let value = eval_expr(tokens, &mut pos, lookup)?;
+    if pos < tokens.len() {
+        return Err(unexpected(&tokens[pos]));
+    }
+    return Ok(value);
+}
+
+/// Builds an error for an unexpected token
+fn unexpected(tok: &Token) -> CompileError {
+    return CompileError::new(
+        CompileErrorType::UnexpectedToken(tok.text.clone()),
+        tok.line, tok.col);
+}
+
+/// Applies a binary operator
+fn apply_binary(op: &str, a: f64, b: f64) -> Option<f64> {
+    let (ia, ib) = (a as i64, b as i64);
+    return Some(match op {
+        "+" => a + b,
+        "-" => a - b,
+        "*" => a * b,
+        "/" => a / b,
+        "%" => a % b,
+        "&" => (ia & ib) as f64,
+        "|" => (ia | ib) as f64,
+        "^" => (ia ^ ib) as f64,
+        "<<" => ia.checked_shl(ib as u32).unwrap_or(0) as f64,
+        ">>" => ia.checked_shr(ib as u32).unwrap_or(0) as f64,
+        "pow" => a.powf(b),
+        "min" => a.min(b),
+        "max" => a.max(b),
+        "<" => (a < b) as i64 as f64,
+        "<=" => (a <= b) as i64 as f64,
+        ">" => (a > b) as i64 as f64,
+        ">=" => (a >= b) as i64 as f64,
+        "==" => (a == b) as i64 as f64,
+        "!=" => (a != b) as i64 as f64,
+        _ => return None
+    });
+}
+
+/// Applies a unary operator
+fn apply_unary(op: &str, a: f64) -> Option<f64> {
+    return Some(match op {
+        "-" => -a,
+        "~" => !(a as i64) as f64,
+        "!" => (a == 0.0) as i64 as f64,
+        "sin" => a.sin(),
+        "cos" => a.cos(),
+        "tan" => a.tan(),
+        "exp" => a.exp(),
+        "log" => a.ln(),
+        "abs" => a.abs(),
+        "sqrt" => a.sqrt(),
+        "sign" => if a == 0.0 { 0.0 } else { a.signum() },
+        "ceil" => a.ceil(),
+        "floor" => a.floor(),
+        _ => return None
+    });
+}
+
+/// Evaluates a term optionally followed by an operator and
+/// the rest of the expression
+fn eval_expr(tokens: &[Token], pos: &mut usize,
+             lookup: &dyn Fn(&str) -> Option<f64>) 
+    -> Result<f64, CompileError> {
+    let lhs = eval_term(tokens, pos, lookup)?;
+    if *pos >= tokens.len() || tokens[*pos].text == ")" {
+        return Ok(lhs);
+    }
+    let op = &tokens[*pos];
+    if apply_binary(op.text.as_str(), 0.0, 1.0).is_none() {
+        return Err(unexpected(op));
+    }
+    *pos += 1;
+    let rhs = eval_expr(tokens, pos, lookup)?;
+    return Ok(apply_binary(op.text.as_str(), lhs, rhs).unwrap());
+}
+
+/// Evaluates a number, name, unary operation or 
+/// parenthesized expression
+fn eval_term(tokens: &[Token], pos: &mut usize,
+             lookup: &dyn Fn(&str) -> Option<f64>) 
+    -> Result<f64, CompileError> {
+    let tok = match tokens.get(*pos) {
+        Some(t) => t,
+        None => {
+            let (line, col) = match tokens.last() {
+                Some(t) => (t.line, t.col),
+                None => (0, 0)
+            };
+            return Err(CompileError::new(CompileErrorType::UnexpectedEnd,
+                                         line, col));
+        }
+    };
+    *pos += 1;
+    let text = tok.text.as_str();
+    if text == "(" {
+        let value = eval_expr(tokens, pos, lookup)?;
+        match tokens.get(*pos) {
+            Some(t) if t.text == ")" => *pos += 1,
+            Some(t) => return Err(unexpected(t)),
+            None => return Err(CompileError::new(
+                    CompileErrorType::UnexpectedEnd, tok.line, tok.col))
+        };
+        return Ok(value);
+    }
+    if let Some(n) = parse_number(text) {
+        return Ok(n);
+    }
+    if apply_unary(text, 0.0).is_some() {
+        let operand = eval_expr(tokens, pos, lookup)?;
+        return Ok(apply_unary(text, operand).unwrap());
+    }
+    match text {
+        "PI" => return Ok(std::f64::consts::PI),
+        "E" => return Ok(std::f64::consts::E),
+        _ => {}
+    };
+    return match lookup(text) {
+        Some(v) => Ok(v),
+        None => Err(CompileError::new(
+                CompileErrorType::UnknownName(String::from(text)),
+                tok.line, tok.col))
+    };
+}
+
+//unit tests
+#[cfg(test)]
+mod tests {
+    //import the evaluator
+    use super::*;
+    use super::super::tokenizer::tokenize;
+
+    //evaluates an expression with a single name defined
+    fn calc(src: &str) -> Result<f64, CompileError> {
+        let lookup = |name: &str| if name == "WIDTH" { Some(64.0) } 
+                                  else { None };
+        return eval(&tokenize(src), &lookup);
+    }
+
+    //this test checks right-to-left evaluation
+    #[test]
+    fn test_right_to_left() {
+        assert_eq!(calc("10 - 4 - 3").unwrap(), 9.0);
+        assert_eq!(calc("( 10 - 4 ) - 3").unwrap(), 3.0);
+        assert_eq!(calc("2 * 3 + 1").unwrap(), 8.0);
+    }
+
+    //this test checks names and unary operators
+    #[test]
+    fn test_names_and_unary() {
+        assert_eq!(calc("WIDTH >> 1").unwrap(), 32.0);
+        assert_eq!(calc("- 4 + 6").unwrap(), -10.0);
+        assert_eq!(calc("floor 7 / 2").unwrap(), 3.0);
+    }
+
+    //this test checks error reporting
+    #[test]
+    fn test_errors() {
+        let err = calc("1 + HEIGHT").unwrap_err();
+        assert_eq!(err.get_type(), 
+                   CompileErrorType::UnknownName(String::from("HEIGHT")));
+        assert!(calc("1 2").is_err());
+        assert!(calc("( 1 + 2").is_err());
+    }
+}
+
+//end of file
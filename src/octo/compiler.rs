@@ -0,0 +1,1125 @@
+/*
+ * compiler.rs
+ * Compiles Octo assembly language into a program image
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//usage statements
+use std::collections::{HashMap, VecDeque};
+use super::calc;
+use super::tokenizer::{parse_number, tokenize, Token};
+use super::super::codegen::{Element, Image, Instruction, PatchPoint,
+                            SourceSpan, PROGRAM_START};
+use super::super::data;
+use super::super::data::{Register, SkipType};
+use super::super::error::{CompileError, CompileErrorType};
+
+/// The most times a macro may be expanded, which stops macros
+/// that invoke themselves from expanding forever
+const MAX_MACRO_CALLS: usize = 4096;
+
+/// Compiles Octo source into a program image
+///
+/// # Argument
+///
+/// * `src` - The Octo source to compile
+///
+/// # Returns
+///
+/// The compiled `Image`, or the first `CompileError` encountered
+pub fn compile(src: &str) -> Result<Image, CompileError> {
+    let lines = src.lines().map(String::from).collect();
+    return Compiler::new(tokenize(src), lines).compile();
+}
+
+/// The instruction a forward reference is resolved into
+#[derive(Clone, Copy, PartialEq)]
+enum FixupKind {
+    /// `jump label`
+    Jump,
+
+    /// `jump0 label`
+    Jump0,
+
+    /// `label` or `:call label`
+    Call,
+
+    /// `i := label`
+    LoadI,
+
+    /// The second word of `i := long label`
+    Long,
+
+    /// `:pointer label`
+    Pointer,
+
+    /// `:unpack nibble label`, with the nibble
+    /// and the two target registers
+    Unpack(u8, u8, u8)
+}
+
+/// A reference to a label that is resolved after compilation
+struct Fixup {
+    /// The address of the element to patch
+    addr: u16,
+
+    /// How to patch the element
+    kind: FixupKind,
+
+    /// The token naming the label
+    name: Token
+}
+
+/// An open control flow block
+enum Block {
+    /// An `if ... begin` block, with the address of the jump
+    /// to patch when the block or its `else` part ends
+    If(u16, Token),
+
+    /// A `loop` block, with its start address and the
+    /// addresses of the jumps generated by `while`
+    Loop(u16, Vec<u16>, Token)
+}
+
+/// A user-defined macro
+struct Macro {
+    /// The names of the arguments
+    args: Vec<String>,
+
+    /// The tokens of the body
+    body: Vec<Token>,
+
+    /// The number of times the macro has been expanded
+    calls: usize
+}
+
+/// Compiles a stream of Octo tokens into an `Image`
+struct Compiler {
+    /// The tokens remaining to compile
+    tokens: VecDeque<Token>,
+
+    /// The last token consumed, for end-of-input errors
+    last: Token,
+
+    /// The image being built
+    image: Image,
+
+    /// The address of the next element
+    here: u32,
+
+    /// Constants defined by `:const` and `:calc`
+    consts: HashMap<String, f64>,
+
+    /// Register aliases defined by `:alias`
+    aliases: HashMap<String, u8>,
+
+    /// Macros defined by `:macro`
+    macros: HashMap<String, Macro>,
+
+    /// Forward references waiting to be resolved
+    fixups: Vec<Fixup>,
+
+    /// The open control flow blocks
+    blocks: Vec<Block>,
+
+    /// The names and addresses of the instructions marked by `:next`
+    nexts: Vec<(Token, u16)>,
+
+    /// The lines of the source, for the spans of emitted elements
+    lines: Vec<String>
+}
+
+//implementation
+impl Compiler {
+    /// Constructs a new `Compiler` instance
+    fn new(new_tokens: Vec<Token>, new_lines: Vec<String>) -> Compiler {
+        return Compiler {
+            tokens: VecDeque::from(new_tokens),
+            last: Token { 
+                text: String::new(), 
+                is_string: false, 
+                line: 1, 
+                col: 1 
+            },
+            image: Image::new(),
+            here: PROGRAM_START as u32,
+            consts: HashMap::new(),
+            aliases: HashMap::new(),
+            macros: HashMap::new(),
+            fixups: Vec::new(),
+            blocks: Vec::new(),
+            nexts: Vec::new(),
+            lines: new_lines
+        };
+    }
+
+    /// Compiles the whole token stream
+    fn compile(mut self) -> Result<Image, CompileError> {
+        //reserve a jump to main unless main comes first
+        let main_first = self.tokens.len() >= 2 && 
+            self.tokens[0].text == ":" && self.tokens[1].text == "main";
+        if !main_first {
+            let tok = match self.tokens.front() {
+                Some(t) => t.clone(),
+                None => self.last.clone()
+            };
+            self.fixups.push(Fixup {
+                addr: PROGRAM_START,
+                kind: FixupKind::Jump,
+                name: Token { text: String::from("main"), ..tok }
+            });
+            self.emit(Element::Word(0x1000))?;
+        }
+
+        while !self.tokens.is_empty() {
+            self.statement()?;
+        }
+
+        //check for unterminated blocks
+        if let Some(block) = self.blocks.last() {
+            let tok = match *block {
+                Block::If(_, ref t) => t,
+                Block::Loop(_, _, ref t) => t
+            };
+            return Err(CompileError::new(CompileErrorType::UnexpectedEnd,
+                                         tok.line, tok.col));
+        }
+
+        //and resolve forward references
+        let fixups = std::mem::take(&mut self.fixups);
+        for fixup in fixups.iter() {
+            let target = match self.image.get_label(&fixup.name.text) {
+                Some(addr) => addr,
+                None => return Err(Compiler::error(
+                        CompileErrorType::UnknownName(
+                            fixup.name.text.clone()), &fixup.name))
+            };
+            self.patch(fixup, target)?;
+        }
+
+        //then mark the instructions the program rewrites
+        for (name, addr) in std::mem::take(&mut self.nexts) {
+            let point = match self.image.get_element(addr) {
+                Some(Element::Instr(ref i)) => {
+                    PatchPoint::new(&name.text, addr, i)
+                },
+                _ => return Err(Compiler::error(
+                        CompileErrorType::InvalidOperand(String::from(
+                            ":next must be followed by an instruction")),
+                        &name))
+            };
+            self.image.add_patch(point);
+        }
+        return Ok(self.image);
+    }
+
+    /// Builds an error at a token
+    fn error(err_type: CompileErrorType, tok: &Token) -> CompileError {
+        return CompileError::new(err_type, tok.line, tok.col);
+    }
+
+    /// Builds an unexpected token error
+    fn unexpected(tok: &Token) -> CompileError {
+        return Compiler::error(
+            CompileErrorType::UnexpectedToken(tok.text.clone()), tok);
+    }
+
+    /// Consumes the next token
+    fn next(&mut self) -> Result<Token, CompileError> {
+        return match self.tokens.pop_front() {
+            Some(t) => {
+                self.last = t.clone();
+                Ok(t)
+            },
+            None => Err(Compiler::error(CompileErrorType::UnexpectedEnd,
+                                        &self.last))
+        };
+    }
+
+    /// Gets the text of the next token without consuming it
+    fn peek_text(&self) -> Option<&str> {
+        return self.tokens.front().map(|t| t.text.as_str());
+    }
+
+    /// Consumes the next token, which must have the given text
+    fn expect(&mut self, text: &str) -> Result<Token, CompileError> {
+        let tok = self.next()?;
+        if tok.text != text {
+            return Err(Compiler::unexpected(&tok));
+        }
+        return Ok(tok);
+    }
+
+    /// Places an element at the current address
+    fn emit(&mut self, elem: Element) -> Result<(), CompileError> {
+        let len = elem.len() as u32;
+        if self.here > 0xFFFF || !self.image.place(self.here as u16, elem) {
+            return Err(Compiler::error(
+                    CompileErrorType::AddressConflict(self.here as u16),
+                    &self.last));
+        }
+        let line = self.last.line;
+        let text = self.lines.get(line - 1).map(|l| l.as_str())
+                       .unwrap_or("");
+        self.image.set_span(self.here as u16, SourceSpan::new(line, text));
+        self.here += len;
+        return Ok(());
+    }
+
+    /// Places an instruction at the current address
+    fn emit_instr(&mut self, instr: Instruction) 
+        -> Result<(), CompileError> {
+        return self.emit(Element::Instr(instr));
+    }
+
+    /// Patches a forward reference with its resolved address
+    fn patch(&mut self, fixup: &Fixup, target: u16) 
+        -> Result<(), CompileError> {
+        if fixup.kind != FixupKind::Long && fixup.kind != FixupKind::Pointer
+            && target > 0xFFF {
+            return Err(Compiler::error(
+                    CompileErrorType::ConstantOutOfRange(target as i64),
+                    &fixup.name));
+        }
+        let elem = match fixup.kind {
+            FixupKind::Jump => Element::Instr(Instruction::JMP(
+                    data::JmpData::new(target))),
+            FixupKind::Jump0 => Element::Instr(Instruction::JPC(
+                    data::JpcData::new(target))),
+            FixupKind::Call => Element::Instr(Instruction::CALL(
+                    data::CallData::new(target))),
+            FixupKind::LoadI => Element::Instr(Instruction::MOV(
+                    data::MovData::with_constant(Register::I, target))),
+            FixupKind::Long | FixupKind::Pointer => Element::Word(target),
+            FixupKind::Unpack(nibble, hi, lo) => {
+                let hi_byte = ((nibble as u16) << 4) | (target >> 8);
+                self.image.replace(fixup.addr + 2, Element::Instr(
+                        Instruction::MOV(data::MovData::with_constant(
+                                Register::from_id(lo).unwrap(), 
+                                target & 0xFF))));
+                Element::Instr(Instruction::MOV(data::MovData::with_constant(
+                            Register::from_id(hi).unwrap(), hi_byte)))
+            }
+        };
+        self.image.replace(fixup.addr, elem);
+        return Ok(());
+    }
+
+    /// Resolves a label now if it is defined, or records a
+    /// fixup and emits a placeholder if it is not
+    fn emit_reference(&mut self, kind: FixupKind, name: Token) 
+        -> Result<(), CompileError> {
+        let fixup = Fixup { addr: self.here as u16, kind, name };
+        match kind {
+            FixupKind::Unpack(_, _, _) => {
+                self.emit(Element::Word(0x6000))?;
+                self.emit(Element::Word(0x6000))?;
+            },
+            _ => self.emit(Element::Word(0x0000))?
+        };
+        match self.image.get_label(&fixup.name.text) {
+            Some(addr) => self.patch(&fixup, addr)?,
+            None => self.fixups.push(fixup)
+        };
+        return Ok(());
+    }
+
+    /// Checks whether a token could name a label
+    fn is_identifier(&self, text: &str) -> bool {
+        return !text.is_empty() && parse_number(text).is_none() &&
+            self.register_of(text).is_none() && !text.starts_with(':') &&
+            text != "{";
+    }
+
+    /// Looks up a register by name or alias
+    fn register_of(&self, text: &str) -> Option<u8> {
+        if let Some(r) = self.aliases.get(text) {
+            return Some(*r);
+        }
+        let lower = text.to_ascii_lowercase();
+        if lower.len() == 2 && lower.starts_with('v') {
+            return u8::from_str_radix(&lower[1..], 16).ok();
+        }
+        return None;
+    }
+
+    /// Consumes a register operand
+    fn register(&mut self) -> Result<Register, CompileError> {
+        let tok = self.next()?;
+        return match self.register_of(&tok.text) {
+            Some(id) => Ok(Register::from_id(id).unwrap()),
+            None => Err(Compiler::unexpected(&tok))
+        };
+    }
+
+    /// Looks up the value of a constant or defined label
+    fn lookup(&self, name: &str) -> Option<f64> {
+        if name == "HERE" {
+            return Some(self.here as f64);
+        }
+        if let Some(v) = self.consts.get(name) {
+            return Some(*v);
+        }
+        return self.image.get_label(name).map(|a| a as f64);
+    }
+
+    /// Consumes the tokens of a brace-delimited group, 
+    /// after the opening brace has been consumed
+    fn braced(&mut self) -> Result<Vec<Token>, CompileError> {
+        let mut depth = 1;
+        let mut body = Vec::new();
+        loop {
+            let tok = self.next()?;
+            if tok.text == "{" {
+                depth += 1;
+            } else if tok.text == "}" {
+                depth -= 1;
+                if depth == 0 {
+                    return Ok(body);
+                }
+            }
+            body.push(tok);
+        }
+    }
+
+    /// Consumes a numeric operand: a literal, a constant,
+    /// a defined label or a braced `:calc` expression
+    fn value(&mut self) -> Result<(f64, Token), CompileError> {
+        let tok = self.next()?;
+        if tok.text == "{" {
+            let body = self.braced()?;
+            let v = calc::eval(&body, &|n| self.lookup(n))?;
+            return Ok((v, tok));
+        }
+        if let Some(n) = parse_number(&tok.text) {
+            return Ok((n, tok));
+        }
+        return match self.lookup(&tok.text) {
+            Some(v) => Ok((v, tok)),
+            None => Err(Compiler::error(
+                    CompileErrorType::UnknownName(tok.text.clone()), &tok))
+        };
+    }
+
+    /// Consumes a value that must lie within a range
+    fn ranged(&mut self, min: i64, max: i64) 
+        -> Result<i64, CompileError> {
+        let (v, tok) = self.value()?;
+        let n = v as i64;
+        if n < min || n > max {
+            return Err(Compiler::error(
+                    CompileErrorType::ConstantOutOfRange(n), &tok));
+        }
+        return Ok(n);
+    }
+
+    /// Consumes an 8-bit value, allowing negative numbers
+    fn byte(&mut self) -> Result<u8, CompileError> {
+        return Ok(self.ranged(-128, 255)? as u8);
+    }
+
+    /// Consumes a 4-bit value
+    fn nibble(&mut self) -> Result<u8, CompileError> {
+        return Ok(self.ranged(0, 15)? as u8);
+    }
+
+    /// Compiles a single statement
+    fn statement(&mut self) -> Result<(), CompileError> {
+        let tok = self.next()?;
+        let text = tok.text.clone();
+
+        //expand macros
+        if self.macros.contains_key(&text) {
+            return self.expand_macro(&tok);
+        }
+
+        match text.as_str() {
+            ":" => {
+                let name = self.next()?;
+                self.define_label(&name, self.here)?;
+            },
+            ":next" => {
+                let name = self.next()?;
+                self.define_label(&name, self.here + 1)?;
+                self.nexts.push((name, self.here as u16));
+            },
+            ":const" => {
+                let name = self.next()?;
+                let (v, _) = self.value()?;
+                self.consts.insert(name.text, v);
+            },
+            ":calc" => {
+                let name = self.next()?;
+                self.expect("{")?;
+                let body = self.braced()?;
+                let v = calc::eval(&body, &|n| self.lookup(n))?;
+                self.consts.insert(name.text, v);
+            },
+            ":alias" => {
+                let name = self.next()?;
+                let reg = self.register()?;
+                self.aliases.insert(name.text, reg.to_id());
+            },
+            ":org" => {
+                self.here = self.ranged(0, 0xFFFF)? as u32;
+            },
+            ":byte" => {
+                let b = self.byte()?;
+                self.emit(Element::Byte(b))?;
+            },
+            ":pointer" => self.address_operand(FixupKind::Pointer)?,
+            ":call" => self.address_operand(FixupKind::Call)?,
+            ":unpack" => {
+                let nibble = self.nibble()?;
+                let name = self.next()?;
+                let hi = self.aliases.get("unpack-hi").cloned().unwrap_or(0);
+                let lo = self.aliases.get("unpack-lo").cloned().unwrap_or(1);
+                self.emit_reference(FixupKind::Unpack(nibble, hi, lo), 
+                                    name)?;
+            },
+            ":macro" => self.define_macro()?,
+            ":assert" => {
+                let message = if self.tokens.front()
+                                      .map(|t| t.is_string)
+                                      .unwrap_or(false) {
+                    self.next()?.text
+                } else {
+                    String::from("assertion failed")
+                };
+                let (v, _) = self.value()?;
+                if v == 0.0 {
+                    return Err(Compiler::error(
+                            CompileErrorType::AssertionFailed(message), 
+                            &tok));
+                }
+            },
+            ":breakpoint" => {
+                self.next()?;
+            },
+            ":monitor" => {
+                self.value_or_register()?;
+                self.value_or_register()?;
+            },
+            "return" | ";" => self.emit_instr(Instruction::RET)?,
+            "clear" => self.emit_instr(Instruction::CLS)?,
+            "bcd" => {
+                let r = self.register()?;
+                self.emit_instr(Instruction::BCD(data::BcdData::new(r)))?;
+            },
+            "save" | "load" => self.save_load(text == "save")?,
+            "sprite" => {
+                let x = self.register()?;
+                let y = self.register()?;
+                let h = self.nibble()?;
+                self.emit_instr(Instruction::DRAW(
+                        data::DrawData::new(x, y, h)))?;
+            },
+            "jump" => self.address_operand(FixupKind::Jump)?,
+            "jump0" => self.address_operand(FixupKind::Jump0)?,
+            "native" => {
+                let addr = self.ranged(0, 0xFFF)? as u16;
+                self.emit(Element::Word(addr))?;
+            },
+            "hires" => self.emit(Element::Word(0x00FF))?,
+            "lores" => self.emit(Element::Word(0x00FE))?,
+            "exit" => self.emit(Element::Word(0x00FD))?,
+            "scroll-left" => self.emit(Element::Word(0x00FC))?,
+            "scroll-right" => self.emit(Element::Word(0x00FB))?,
+            "scroll-down" => {
+                let n = self.nibble()? as u16;
+                self.emit(Element::Word(0x00C0 | n))?;
+            },
+            "scroll-up" => {
+                let n = self.nibble()? as u16;
+                self.emit(Element::Word(0x00D0 | n))?;
+            },
+            "audio" => self.emit(Element::Word(0xF002))?,
+            "plane" => {
+                let n = self.nibble()? as u16;
+                self.emit(Element::Word(0xF001 | (n << 8)))?;
+            },
+            "saveflags" | "loadflags" => {
+                let r = self.register()?.to_id() as u16;
+                let low = if text == "saveflags" { 0x75 } else { 0x85 };
+                self.emit(Element::Word(0xF000 | (r << 8) | low))?;
+            },
+            "pitch" => {
+                self.expect(":=")?;
+                let r = self.register()?.to_id() as u16;
+                self.emit(Element::Word(0xF03A | (r << 8)))?;
+            },
+            "delay" => {
+                self.expect(":=")?;
+                let r = self.register()?;
+                self.emit_instr(Instruction::SDL(data::SdlData::new(r)))?;
+            },
+            "buzzer" => {
+                self.expect(":=")?;
+                let r = self.register()?;
+                self.emit_instr(Instruction::SND(data::SndData::new(r)))?;
+            },
+            "i" => self.index_statement()?,
+            "if" => self.if_statement()?,
+            "else" => {
+                match self.blocks.pop() {
+                    Some(Block::If(jump, open)) => {
+                        let here = self.here as u16;
+                        self.emit(Element::Word(0x1000))?;
+                        self.patch_jump(jump, self.here as u16);
+                        self.blocks.push(Block::If(here, open));
+                    },
+                    _ => return Err(Compiler::unexpected(&tok))
+                };
+            },
+            "end" => {
+                match self.blocks.pop() {
+                    Some(Block::If(jump, _)) => {
+                        self.patch_jump(jump, self.here as u16);
+                    },
+                    _ => return Err(Compiler::unexpected(&tok))
+                };
+            },
+            "loop" => {
+                self.blocks.push(Block::Loop(self.here as u16, Vec::new(), 
+                                             tok));
+            },
+            "while" => {
+                //skip the exit jump while the condition holds
+                self.condition(true)?;
+                let here = self.here as u16;
+                self.emit(Element::Word(0x1000))?;
+                match self.blocks.iter_mut().rev().find(|b| {
+                    matches!(b, Block::Loop(_, _, _))
+                }) {
+                    Some(Block::Loop(_, ref mut exits, _)) => {
+                        exits.push(here);
+                    },
+                    _ => return Err(Compiler::unexpected(&tok))
+                };
+            },
+            "again" => {
+                match self.blocks.pop() {
+                    Some(Block::Loop(start, exits, _)) => {
+                        self.emit_instr(Instruction::JMP(
+                                data::JmpData::new(start)))?;
+                        for exit in exits.iter() {
+                            self.patch_jump(*exit, self.here as u16);
+                        }
+                    },
+                    _ => return Err(Compiler::unexpected(&tok))
+                };
+            },
+            ":stringmode" => {
+                return Err(Compiler::error(
+                        CompileErrorType::Unsupported(text), &tok));
+            },
+            _ => {
+                if let Some(r) = self.register_of(&text) {
+                    return self.register_statement(r);
+                }
+
+                //numbers and constants are emitted as bytes
+                if parse_number(&text).is_some() || 
+                    self.consts.contains_key(&text) {
+                    self.tokens.push_front(tok);
+                    let b = self.byte()?;
+                    return self.emit(Element::Byte(b));
+                }
+
+                //and any other name is a subroutine call
+                if self.is_identifier(&text) {
+                    return self.emit_reference(FixupKind::Call, tok);
+                }
+                return Err(Compiler::unexpected(&tok));
+            }
+        };
+        return Ok(());
+    }
+
+    /// Defines a label at an address
+    fn define_label(&mut self, name: &Token, addr: u32) 
+        -> Result<(), CompileError> {
+        if !self.is_identifier(&name.text) {
+            return Err(Compiler::unexpected(name));
+        }
+        if self.image.get_label(&name.text).is_some() || 
+            self.consts.contains_key(&name.text) {
+            return Err(Compiler::error(
+                    CompileErrorType::Redefinition(name.text.clone()), name));
+        }
+        self.image.add_label(&name.text, addr as u16);
+        return Ok(());
+    }
+
+    /// Patches a placeholder jump to point at a target
+    fn patch_jump(&mut self, addr: u16, target: u16) {
+        self.image.replace(addr, Element::Instr(Instruction::JMP(
+                    data::JmpData::new(target))));
+    }
+
+    /// Compiles an instruction taking an address operand,
+    /// which may be a forward reference
+    fn address_operand(&mut self, kind: FixupKind) 
+        -> Result<(), CompileError> {
+        let tok = self.next()?;
+        if self.is_identifier(&tok.text) && 
+            !self.consts.contains_key(&tok.text) {
+            return self.emit_reference(kind, tok);
+        }
+        self.tokens.push_front(tok);
+        let max = if kind == FixupKind::Pointer { 0xFFFF } else { 0xFFF };
+        let addr = self.ranged(0, max)? as u16;
+        let fixup = Fixup { 
+            addr: self.here as u16, 
+            kind, 
+            name: self.last.clone() 
+        };
+        self.emit(Element::Word(0))?;
+        return self.patch(&fixup, addr);
+    }
+
+    /// Consumes either a register or a value
+    fn value_or_register(&mut self) -> Result<(), CompileError> {
+        let is_reg = match self.peek_text() {
+            Some(t) => self.register_of(t).is_some(),
+            None => false
+        };
+        if is_reg {
+            self.next()?;
+        } else {
+            self.value()?;
+        }
+        return Ok(());
+    }
+
+    /// Compiles `save` and `load`, including the XO-CHIP
+    /// register range forms
+    fn save_load(&mut self, save: bool) -> Result<(), CompileError> {
+        let vx = self.register()?;
+        if self.peek_text() == Some("-") {
+            self.next()?;
+            let vy = self.register()?;
+            let low = if save { 0x2 } else { 0x3 };
+            let word = 0x5000 | ((vx.to_id() as u16) << 8) | 
+                ((vy.to_id() as u16) << 4) | low;
+            return self.emit(Element::Word(word));
+        }
+        if save {
+            return self.emit_instr(Instruction::RDP(data::RdpData::new(vx)));
+        }
+        return self.emit_instr(Instruction::RLD(data::RldData::new(vx)));
+    }
+
+    /// Compiles statements that assign to `i`
+    fn index_statement(&mut self) -> Result<(), CompileError> {
+        let op = self.next()?;
+        match op.text.as_str() {
+            "+=" => {
+                let r = self.register()?;
+                return self.emit_instr(Instruction::ADD(
+                        data::AddData::with_register(Register::I, r)));
+            },
+            ":=" => {},
+            _ => return Err(Compiler::unexpected(&op))
+        };
+        match self.peek_text() {
+            Some("hex") => {
+                self.next()?;
+                let r = self.register()?;
+                return self.emit_instr(Instruction::SCH(
+                        data::SchData::new(r)));
+            },
+            Some("bighex") => {
+                self.next()?;
+                let r = self.register()?.to_id() as u16;
+                return self.emit(Element::Word(0xF030 | (r << 8)));
+            },
+            Some("long") => {
+                self.next()?;
+                self.emit(Element::Word(0xF000))?;
+                return self.address_operand(FixupKind::Long);
+            },
+            _ => return self.address_operand(FixupKind::LoadI)
+        };
+    }
+
+    /// Compiles statements that start with a register
+    fn register_statement(&mut self, id: u8) -> Result<(), CompileError> {
+        let vx = Register::from_id(id).unwrap();
+        let op = self.next()?;
+        let src = match self.peek_text() {
+            Some(t) => self.register_of(t),
+            None => None
+        };
+        if let Some(y) = src {
+            self.next()?;
+            let vy = Register::from_id(y).unwrap();
+            let instr = match op.text.as_str() {
+                ":=" => Instruction::MOV(data::MovData::with_register(vx, vy)),
+                "+=" => Instruction::ADD(data::AddData::with_register(vx, vy)),
+                "-=" => Instruction::SUB(data::SubData::new(vx, vy)),
+                "=-" => Instruction::SUBN(data::SubnData::new(vx, vy)),
+                "|=" => Instruction::OR(data::OrData::new(vx, vy)),
+                "&=" => Instruction::AND(data::AndData::new(vx, vy)),
+                "^=" => Instruction::XOR(data::XorData::new(vx, vy)),
+                ">>=" => Instruction::SHR(data::ShrData::with_source(vx, vy)),
+                "<<=" => Instruction::SHL(data::ShlData::with_source(vx, vy)),
+                _ => return Err(Compiler::unexpected(&op))
+            };
+            return self.emit_instr(instr);
+        }
+        match op.text.as_str() {
+            ":=" => {
+                match self.peek_text() {
+                    Some("random") => {
+                        self.next()?;
+                        let mask = self.byte()?;
+                        return self.emit_instr(Instruction::RAND(
+                                data::RandData::new(vx, mask)));
+                    },
+                    Some("key") => {
+                        self.next()?;
+                        return self.emit_instr(Instruction::KEY(
+                                data::KeyData::new(vx)));
+                    },
+                    Some("delay") => {
+                        self.next()?;
+                        return self.emit_instr(Instruction::GDL(
+                                data::GdlData::new(vx)));
+                    },
+                    _ => {
+                        let n = self.byte()?;
+                        return self.emit_instr(Instruction::MOV(
+                                data::MovData::with_constant(vx, n as u16)));
+                    }
+                };
+            },
+            "+=" | "-=" => {
+                let mut n = self.byte()?;
+                if op.text == "-=" {
+                    n = n.wrapping_neg();
+                }
+                return self.emit_instr(Instruction::ADD(
+                        data::AddData::with_constant(vx, n as u16)));
+            },
+            _ => return Err(Compiler::unexpected(&op))
+        };
+    }
+
+    /// Compiles an `if` statement
+    fn if_statement(&mut self) -> Result<(), CompileError> {
+        let tok = self.last.clone();
+        let mut cond = Vec::new();
+        loop {
+            let t = self.next()?;
+            if t.text == "then" || t.text == "begin" {
+                self.tokens.push_front(t);
+                break;
+            }
+            cond.push(t);
+        }
+        let mode = self.next()?;
+
+        //compile the condition from its own token list
+        let rest = std::mem::take(&mut self.tokens);
+        self.tokens = VecDeque::from(cond);
+        let res = self.condition(mode.text == "begin");
+        let leftover = self.tokens.front().cloned();
+        self.tokens = rest;
+        res?;
+        if let Some(t) = leftover {
+            return Err(Compiler::unexpected(&t));
+        }
+
+        if mode.text == "begin" {
+            let here = self.here as u16;
+            self.emit(Element::Word(0x1000))?;
+            self.blocks.push(Block::If(here, tok));
+        }
+        return Ok(());
+    }
+
+    /// Compiles a condition into instructions that skip the
+    /// next instruction when the condition equals `skip_when`
+    fn condition(&mut self, skip_when: bool) -> Result<(), CompileError> {
+        let vx = self.register()?;
+        let op = self.next()?;
+        let (kind, negate) = match op.text.as_str() {
+            "key" => {
+                let kind = if skip_when { 
+                    SkipType::KeyDown 
+                } else { 
+                    SkipType::KeyUp 
+                };
+                return self.emit_instr(Instruction::SKIP(
+                        data::SkipData::with_key(vx, kind)));
+            },
+            "-key" => {
+                let kind = if skip_when { 
+                    SkipType::KeyUp 
+                } else { 
+                    SkipType::KeyDown 
+                };
+                return self.emit_instr(Instruction::SKIP(
+                        data::SkipData::with_key(vx, kind)));
+            },
+            "==" => (SkipType::Equals, !skip_when),
+            "!=" => (SkipType::Equals, skip_when),
+            "<" | ">" | "<=" | ">=" => {
+                return self.ordering(vx, op.text.as_str(), skip_when);
+            },
+            _ => return Err(Compiler::unexpected(&op))
+        };
+        let kind = if negate { SkipType::NotEquals } else { kind };
+        let src = match self.peek_text() {
+            Some(t) => self.register_of(t),
+            None => None
+        };
+        let skip = match src {
+            Some(y) => {
+                self.next()?;
+                data::SkipData::with_register(vx, Register::from_id(y)
+                                                     .unwrap(), kind)
+            },
+            None => {
+                let n = self.byte()?;
+                data::SkipData::with_constant(vx, n, kind)
+            }
+        };
+        return self.emit_instr(Instruction::SKIP(skip));
+    }
+
+    /// Compiles an ordering comparison through the comparison
+    /// temporary (`vf` unless aliased with `compare-temp`), 
+    /// which clobbers `vf`
+    fn ordering(&mut self, vx: Register, op: &str, skip_when: bool) 
+        -> Result<(), CompileError> {
+        let temp = Register::from_id(self.aliases.get("compare-temp")
+                                         .cloned().unwrap_or(0xF)).unwrap();
+        let src = match self.peek_text() {
+            Some(t) => self.register_of(t),
+            None => None
+        };
+
+        //load the right-hand side into the temporary
+        let load = match src {
+            Some(y) => {
+                self.next()?;
+                data::MovData::with_register(temp.clone(), 
+                                             Register::from_id(y).unwrap())
+            },
+            None => {
+                let n = self.byte()?;
+                data::MovData::with_constant(temp.clone(), n as u16)
+            }
+        };
+        self.emit_instr(Instruction::MOV(load))?;
+
+        //VF ends up as the no-borrow flag of either vx - rhs
+        //(for < and >=) or rhs - vx (for > and <=)
+        if op == "<" || op == ">=" {
+            self.emit_instr(Instruction::SUBN(
+                    data::SubnData::new(temp, vx)))?;
+        } else {
+            self.emit_instr(Instruction::SUB(
+                    data::SubData::new(temp, vx)))?;
+        }
+
+        //the flag is set exactly when the condition holds 
+        //for >= and <=, and exactly when it fails for < and >
+        let flag_when_true = op == ">=" || op == "<=";
+        let skip_value = if skip_when == flag_when_true { 1 } else { 0 };
+        return self.emit_instr(Instruction::SKIP(
+                data::SkipData::with_constant(Register::VF, skip_value,
+                                              SkipType::Equals)));
+    }
+
+    /// Compiles a `:macro` definition
+    fn define_macro(&mut self) -> Result<(), CompileError> {
+        let name = self.next()?;
+        let mut args = Vec::new();
+        loop {
+            let t = self.next()?;
+            if t.text == "{" {
+                break;
+            }
+            args.push(t.text);
+        }
+        let body = self.braced()?;
+        self.macros.insert(name.text, Macro { args, body, calls: 0 });
+        return Ok(());
+    }
+
+    /// Expands a macro invocation into the token stream
+    fn expand_macro(&mut self, tok: &Token) -> Result<(), CompileError> {
+        let arg_count = self.macros[&tok.text].args.len();
+        let mut values = Vec::new();
+        for _ in 0..arg_count {
+            values.push(self.next()?.text);
+        }
+        let mac = self.macros.get_mut(&tok.text).unwrap();
+        if mac.calls == MAX_MACRO_CALLS {
+            return Err(Compiler::error(
+                    CompileErrorType::RecursionLimit(tok.text.clone()), tok));
+        }
+        let calls = mac.calls;
+        mac.calls += 1;
+        let mut expanded = Vec::new();
+        for t in mac.body.iter() {
+            let mut t = t.clone();
+            if !t.is_string {
+                if let Some(i) = mac.args.iter().position(|a| *a == t.text) {
+                    t.text = values[i].clone();
+                } else if t.text == "CALLS" {
+                    t.text = format!("{}", calls);
+                }
+            }
+            expanded.push(t);
+        }
+        for t in expanded.into_iter().rev() {
+            self.tokens.push_front(t);
+        }
+        return Ok(());
+    }
+}
+
+//unit tests
+#[cfg(test)]
+mod tests {
+    //import the compiler
+    use super::*;
+
+    //compiles source and returns the bytes of the image
+    fn bytes(src: &str) -> Vec<u8> {
+        return compile(src).unwrap().to_bytes();
+    }
+
+    //this test checks basic register statements
+    #[test]
+    fn test_register_statements() {
+        let rom = bytes(": main v0 := 5 v1 += v0 v1 -= 2 v2 >>= v1 
+                         v3 := random 0x0F v4 := key");
+        assert_eq!(rom, vec![0x60, 0x05, 0x81, 0x04, 0x71, 0xFE,
+                             0x82, 0x16, 0xC3, 0x0F, 0xF4, 0x0A]);
+    }
+
+    //this test checks the implicit jump to main
+    #[test]
+    fn test_jump_to_main() {
+        let rom = bytes(": sub return : main sub");
+        assert_eq!(rom, vec![0x12, 0x04, 0x00, 0xEE, 0x22, 0x02]);
+    }
+
+    //this test checks forward references
+    #[test]
+    fn test_forward_references() {
+        let rom = bytes(": main i := data jump main : data 0xFF");
+        assert_eq!(rom, vec![0xA2, 0x04, 0x12, 0x00, 0xFF]);
+    }
+
+    //this test checks if-then and if-begin-else-end
+    #[test]
+    fn test_conditionals() {
+        let rom = bytes(": main if v0 == 3 then v1 := 1
+                         if v0 key begin clear else return end");
+        assert_eq!(rom, vec![0x40, 0x03, 0x61, 0x01,
+                             0xE0, 0x9E, 0x12, 0x0C,
+                             0x00, 0xE0, 0x12, 0x0E,
+                             0x00, 0xEE]);
+    }
+
+    //this test checks loops with while
+    #[test]
+    fn test_loop_while() {
+        let rom = bytes(": main loop v0 += 1 while v0 != 10 again");
+        assert_eq!(rom, vec![0x70, 0x01, 0x40, 0x0A, 0x12, 0x08,
+                             0x12, 0x00]);
+    }
+
+    //this test checks ordering comparisons
+    #[test]
+    fn test_ordering() {
+        let rom = bytes(": main if v0 < v1 then v2 := 1");
+        assert_eq!(rom, vec![0x8F, 0x10, 0x8F, 0x07, 0x3F, 0x01,
+                             0x62, 0x01]);
+        let rom = bytes(": main if v0 > v1 then v2 := 1");
+        assert_eq!(rom, vec![0x8F, 0x10, 0x8F, 0x05, 0x3F, 0x01,
+                             0x62, 0x01]);
+        let rom = bytes(": main if v0 > 5 then v2 := 1");
+        assert_eq!(rom, vec![0x6F, 0x05, 0x8F, 0x05, 0x3F, 0x01,
+                             0x62, 0x01]);
+    }
+
+    //this test checks macros, constants and calc
+    #[test]
+    fn test_macros_and_calc() {
+        let rom = bytes(":const W 64 :calc HALF { W / 2 }
+                         :macro set reg val { reg := val }
+                         : main set v3 HALF set v4 { HALF - 1 }");
+        assert_eq!(rom, vec![0x12, 0x02, 0x63, 0x20, 0x64, 0x1F]);
+    }
+
+    //this test checks unpack and aliases
+    #[test]
+    fn test_unpack_and_alias() {
+        let rom = bytes(":alias x v5 : main :unpack 0xA tbl x := 1 : tbl");
+        assert_eq!(rom, vec![0x12, 0x02, 0x60, 0xA2, 0x61, 0x08, 
+                             0x65, 0x01]);
+    }
+
+    //this test checks that :next marks the instruction it labels
+    #[test]
+    fn test_next() {
+        let img = compile(": main i := target save v1 \
+                           :next target jump later : later").unwrap();
+        assert_eq!(img.get_label("target"), Some(0x205));
+        let patches = img.get_patches();
+        assert_eq!(patches.len(), 1);
+        assert_eq!(patches[0].get_addr(), 0x204);
+        assert!(img.is_writable(0x204) && img.is_writable(0x205));
+        let err = compile(": main :next target 0x12 0x00").unwrap_err();
+        assert!(matches!(err.get_type(),
+                         CompileErrorType::InvalidOperand(_)));
+        assert!(compile(": main :next target").is_err());
+    }
+
+    //this test checks error reporting
+    #[test]
+    fn test_errors() {
+        let err = compile(": main\n  jump nowhere").unwrap_err();
+        assert_eq!(err.get_type(), 
+                   CompileErrorType::UnknownName(String::from("nowhere")));
+        assert_eq!((err.get_line(), err.get_col()), (2, 8));
+        let err = compile(": main v0 := 300").unwrap_err();
+        assert_eq!(err.get_type(), CompileErrorType::ConstantOutOfRange(300));
+        let err = compile(": main loop v0 += 1").unwrap_err();
+        assert_eq!(err.get_type(), CompileErrorType::UnexpectedEnd);
+        let err = compile(": main : main").unwrap_err();
+        assert_eq!(err.get_type(), 
+                   CompileErrorType::Redefinition(String::from("main")));
+        let err = compile("v0 := 1").unwrap_err();
+        assert_eq!(err.get_type(), 
+                   CompileErrorType::UnknownName(String::from("main")));
+        let err = compile(":macro m { m m } : main m").unwrap_err();
+        assert_eq!(err.get_type(), 
+                   CompileErrorType::RecursionLimit(String::from("m")));
+    }
+}
+
+//end of file
@@ -0,0 +1,41 @@
+/*
+ * mod.rs
+ * Module export file for the octo module
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! A front end for the Octo assembly language.
+//!
+//! Octo sources are compiled into a `codegen::Image` that can be
+//! written to a `codegen::Binary`. Classic Chip-8 statements lower
+//! to `codegen::Instruction`s, while SCHIP and XO-CHIP statements
+//! such as `hires`, `plane` and `i := long` are emitted as raw words.
+//! The supported directives are `:`, `:next`, `:const`, `:calc`,
+//! `:alias`, `:macro`, `:org`, `:byte`, `:pointer`, `:call`,
+//! `:unpack` and `:assert`; `:breakpoint` and `:monitor` are accepted
+//! and ignored. Unless the program starts with `: main`, a `jump main`
+//! is placed at `0x200`.
+
+//exports
+mod tokenizer;
+mod calc;
+mod compiler;
+pub use compiler::compile;
+
+//end of file
@@ -0,0 +1,143 @@
+/*
+ * tokenizer.rs
+ * Splits Octo source into whitespace-delimited tokens
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+/// A token of Octo source along with its position
+#[derive(Debug, Clone, PartialEq)]
+pub struct Token {
+    /// The text of the token, without quotes for strings
+    pub text: String,
+
+    /// Whether the token is a quoted string
+    pub is_string: bool,
+
+    /// The 1-based line of the token
+    pub line: usize,
+
+    /// The 1-based column of the token
+    pub col: usize
+}
+
+/// Splits Octo source into tokens. Tokens are separated by
+/// whitespace, `#` starts a comment that runs to the end of
+/// the line, and double quotes delimit strings.
+///
+/// # Argument
+///
+/// * `src` - The source text to tokenize
+///
+/// # Returns
+///
+/// The tokens of the source, in order
+pub fn tokenize(src: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    for (line_idx, line) in src.lines().enumerate() {
+        let chars: Vec<char> = line.chars().collect();
+        let mut pos = 0;
+        while pos < chars.len() {
+            let c = chars[pos];
+            if c.is_whitespace() {
+                pos += 1;
+                continue;
+            }
+            if c == '#' {
+                break;
+            }
+            let start = pos;
+            let mut text = String::new();
+            let is_string = c == '"';
+            if is_string {
+                pos += 1;
+                while pos < chars.len() && chars[pos] != '"' {
+                    text.push(chars[pos]);
+                    pos += 1;
+                }
+                pos += 1;
+            } else {
+                while pos < chars.len() && !chars[pos].is_whitespace() {
+                    text.push(chars[pos]);
+                    pos += 1;
+                }
+            }
+            tokens.push(Token {
+                text,
+                is_string,
+                line: line_idx + 1,
+                col: start + 1
+            });
+        }
+    }
+    return tokens;
+}
+
+/// Parses an Octo numeric literal
+///
+/// # Argument
+///
+/// * `text` - The text of the literal
+///
+/// # Returns
+///
+/// The value of the literal, or `None` if it is not a number
+pub fn parse_number(text: &str) -> Option<f64> {
+    let (neg, digits) = match text.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, text)
+    };
+    let parsed = if let Some(hex) = digits.strip_prefix("0x") {
+        i64::from_str_radix(hex, 16).ok().map(|n| n as f64)
+    } else if let Some(bin) = digits.strip_prefix("0b") {
+        i64::from_str_radix(bin, 2).ok().map(|n| n as f64)
+    } else if digits.starts_with(|c: char| c.is_ascii_digit()) {
+        digits.parse::<f64>().ok()
+    } else {
+        None
+    };
+    return parsed.map(|n| if neg { -n } else { n });
+}
+
+//unit tests
+#[cfg(test)]
+mod tests {
+    //import the tokenizer
+    use super::*;
+
+    //this test checks tokenizing with comments and strings
+    #[test]
+    fn test_tokenize() {
+        let toks = tokenize("v0 := 5 # set v0\n:assert \"too big\" x");
+        let texts: Vec<&str> = toks.iter().map(|t| t.text.as_str()).collect();
+        assert_eq!(texts, vec!["v0", ":=", "5", ":assert", "too big", "x"]);
+        assert!(toks[4].is_string);
+        assert_eq!((toks[5].line, toks[5].col), (2, 19));
+    }
+
+    //this test checks number parsing
+    #[test]
+    fn test_parse_number() {
+        assert_eq!(parse_number("0x1F"), Some(31.0));
+        assert_eq!(parse_number("0b101"), Some(5.0));
+        assert_eq!(parse_number("-12"), Some(-12.0));
+        assert_eq!(parse_number("v0"), None);
+    }
+}
+
+//end of file
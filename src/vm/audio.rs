@@ -0,0 +1,262 @@
+/*
+ * audio.rs
+ * Synthesizes the beeper and XO-CHIP audio as PCM samples
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//usage statements
+use std::fs;
+use std::io;
+use std::path::Path;
+use super::{Machine, DEFAULT_PITCH};
+
+/// The sample rate used when a host does not choose one
+pub const DEFAULT_SAMPLE_RATE: u32 = 44100;
+
+/// The frequency of the square wave played by classic programs
+pub const BEEP_FREQUENCY: f64 = 440.0;
+
+/// The amplitude of the generated square waves
+const AMPLITUDE: i16 = 8192;
+
+/// The rate, in bits per second, that XO-CHIP plays patterns 
+/// at with the default pitch
+const PATTERN_RATE: f64 = 4000.0;
+
+/// Renders the sound of a `Machine` as 16-bit mono PCM samples,
+/// one 60 Hz frame at a time. While the sound timer is nonzero,
+/// the XO-CHIP pattern buffer is played at the rate set by the
+/// pitch register, or a square wave if no pattern was loaded.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Audio {
+    /// The samples per second
+    sample_rate: u32,
+
+    /// The rendered samples
+    samples: Vec<i16>,
+
+    /// Whether each rendered frame produced sound
+    frames: Vec<bool>,
+
+    /// The position in the waveform: a fraction of a cycle for
+    /// the square wave, or a bit index into the pattern
+    phase: f64
+}
+
+//implementation
+impl Audio {
+    /// Constructs a new `Audio` instance
+    ///
+    /// # Argument
+    ///
+    /// * `new_sample_rate` - The samples per second
+    ///
+    /// # Returns
+    ///
+    /// A new `Audio` with nothing rendered
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `new_sample_rate` is zero.
+    pub fn new(new_sample_rate: u32) -> Audio {
+        assert!(new_sample_rate > 0, "the sample rate must be positive");
+        return Audio {
+            sample_rate: new_sample_rate,
+            samples: Vec::new(),
+            frames: Vec::new(),
+            phase: 0.0
+        };
+    }
+
+    /// Gets the sample rate
+    ///
+    /// # Returns
+    ///
+    /// The samples per second
+    pub fn get_sample_rate(&self) -> u32 {
+        return self.sample_rate;
+    }
+
+    /// Gets the rendered samples
+    ///
+    /// # Returns
+    ///
+    /// The 16-bit mono samples
+    pub fn get_samples(&self) -> &[i16] {
+        return self.samples.as_slice();
+    }
+
+    /// Gets which rendered frames produced sound
+    ///
+    /// # Returns
+    ///
+    /// One flag per frame, set if the sound timer was running
+    pub fn get_frames(&self) -> &[bool] {
+        return self.frames.as_slice();
+    }
+
+    /// Renders one 60 Hz frame of a machine's sound. Call it once
+    /// per frame, before the timers tick.
+    ///
+    /// # Argument
+    ///
+    /// * `m` - The machine
+    pub fn render_frame(&mut self, m: &Machine) {
+        let frame = self.frames.len() as u64;
+        let rate = self.sample_rate as u64;
+        let count = (frame + 1) * rate / 60 - frame * rate / 60;
+        let sounding = m.get_sound() > 0;
+        self.frames.push(sounding);
+        if !sounding {
+            self.phase = 0.0;
+            self.samples.extend((0..count).map(|_| 0));
+            return;
+        }
+        for _ in 0..count {
+            let high = match m.get_pattern() {
+                Some(pattern) => {
+                    let bit = self.phase as usize % 128;
+                    let exp = (m.get_pitch() as f64 - DEFAULT_PITCH as f64) 
+                        / 48.0;
+                    self.phase += PATTERN_RATE * exp.exp2() / 
+                        self.sample_rate as f64;
+                    self.phase %= 128.0;
+                    pattern[bit / 8] & (0x80 >> (bit % 8)) != 0
+                },
+                None => {
+                    let high = self.phase < 0.5;
+                    self.phase += BEEP_FREQUENCY / self.sample_rate as f64;
+                    self.phase %= 1.0;
+                    high
+                }
+            };
+            self.samples.push(if high { AMPLITUDE } else { -AMPLITUDE });
+        }
+    }
+
+    /// Encodes the rendered samples as a WAV file
+    ///
+    /// # Returns
+    ///
+    /// The bytes of a 16-bit mono PCM WAV file
+    pub fn to_wav(&self) -> Vec<u8> {
+        let data_len = (self.samples.len() * 2) as u32;
+        let mut out = Vec::with_capacity(44 + data_len as usize);
+        out.extend_from_slice(b"RIFF");
+        out.extend_from_slice(&(36 + data_len).to_le_bytes());
+        out.extend_from_slice(b"WAVEfmt ");
+        out.extend_from_slice(&16u32.to_le_bytes());
+        out.extend_from_slice(&1u16.to_le_bytes());
+        out.extend_from_slice(&1u16.to_le_bytes());
+        out.extend_from_slice(&self.sample_rate.to_le_bytes());
+        out.extend_from_slice(&(self.sample_rate * 2).to_le_bytes());
+        out.extend_from_slice(&2u16.to_le_bytes());
+        out.extend_from_slice(&16u16.to_le_bytes());
+        out.extend_from_slice(b"data");
+        out.extend_from_slice(&data_len.to_le_bytes());
+        for sample in self.samples.iter() {
+            out.extend_from_slice(&sample.to_le_bytes());
+        }
+        return out;
+    }
+
+    /// Writes the rendered samples to a WAV file
+    ///
+    /// # Argument
+    ///
+    /// * `path` - The path of the file
+    ///
+    /// # Returns
+    ///
+    /// `Ok` if the file was written, or the I/O error that prevented it
+    pub fn write_wav<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        return fs::write(path, self.to_wav());
+    }
+}
+
+//unit tests
+#[cfg(test)]
+mod tests {
+    //import the Audio struct
+    use super::*;
+    use super::super::Quirks;
+
+    //creates a machine with the sound timer running
+    fn sounding(rom: &[u8], steps: usize) -> Machine {
+        let mut m = Machine::new(Quirks::vip());
+        m.load(rom).unwrap();
+        for _ in 0..steps {
+            m.step().unwrap();
+        }
+        return m;
+    }
+
+    //this test checks the square wave beeper and silence
+    #[test]
+    fn test_beeper() {
+        let mut audio = Audio::new(7040);
+        let silent = Machine::new(Quirks::vip());
+        audio.render_frame(&silent);
+        //MOV V0, 2; SND V0
+        let m = sounding(&[0x60, 0x02, 0xF0, 0x18], 2);
+        audio.render_frame(&m);
+        assert_eq!(audio.get_frames(), &[false, true]);
+        let samples = audio.get_samples();
+        assert_eq!(samples.len(), 234);
+        assert!(samples[..117].iter().all(|s| *s == 0));
+        assert_eq!(&samples[117..125], &[AMPLITUDE; 8][..]);
+        assert_eq!(&samples[125..133], &[-AMPLITUDE; 8][..]);
+    }
+
+    //this test checks playing an XO-CHIP pattern
+    #[test]
+    fn test_pattern() {
+        //MOV V0, 2; SND V0; MOV I, 0x300; AUDIO
+        let rom = [0x60, 0x02, 0xF0, 0x18, 0xA3, 0x00, 0xF0, 0x02];
+        let mut m = Machine::new(Quirks::vip());
+        m.load(&rom).unwrap();
+        m.write_memory(0x300, &[0xAA; 16]);
+        for _ in 0..4 {
+            m.step().unwrap();
+        }
+        let mut audio = Audio::new(4000);
+        audio.render_frame(&m);
+        let samples = audio.get_samples();
+        assert_eq!(samples.len(), 66);
+        assert_eq!(&samples[..4], &[AMPLITUDE, -AMPLITUDE, 
+                                    AMPLITUDE, -AMPLITUDE]);
+    }
+
+    //this test checks the WAV encoding
+    #[test]
+    fn test_wav() {
+        let mut audio = Audio::new(600);
+        let m = sounding(&[0x60, 0x02, 0xF0, 0x18], 2);
+        audio.render_frame(&m);
+        let wav = audio.to_wav();
+        assert_eq!(wav.len(), 44 + 20);
+        assert_eq!(&wav[..4], b"RIFF");
+        assert_eq!(&wav[4..8], &56u32.to_le_bytes());
+        assert_eq!(&wav[24..28], &600u32.to_le_bytes());
+        assert_eq!(&wav[40..44], &20u32.to_le_bytes());
+        assert_eq!(&wav[44..46], &AMPLITUDE.to_le_bytes());
+    }
+}
+
+//end of file
@@ -0,0 +1,69 @@
+/*
+ * comparison.rs
+ * Enumerates the comparisons of conditional breakpoints
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+/// A comparison between a register and a value
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Comparison {
+    /// The register equals the value
+    Equal,
+
+    /// The register differs from the value
+    NotEqual,
+
+    /// The register is less than the value
+    Less,
+
+    /// The register is less than or equal to the value
+    LessOrEqual,
+
+    /// The register is greater than the value
+    Greater,
+
+    /// The register is greater than or equal to the value
+    GreaterOrEqual
+}
+
+//implementation
+impl Comparison {
+    /// Applies the comparison
+    ///
+    /// # Arguments
+    ///
+    /// * `lhs` - The register's value
+    /// * `rhs` - The value to compare with
+    ///
+    /// # Returns
+    ///
+    /// Whether the comparison holds
+    pub fn holds(&self, lhs: u16, rhs: u16) -> bool {
+        return match *self {
+            Comparison::Equal => lhs == rhs,
+            Comparison::NotEqual => lhs != rhs,
+            Comparison::Less => lhs < rhs,
+            Comparison::LessOrEqual => lhs <= rhs,
+            Comparison::Greater => lhs > rhs,
+            Comparison::GreaterOrEqual => lhs >= rhs
+        };
+    }
+}
+
+//end of file
@@ -0,0 +1,80 @@
+/*
+ * condition.rs
+ * Defines the condition of a conditional breakpoint
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//usage statements
+use super::{Comparison, Machine};
+use super::super::data::Register;
+
+/// A condition on a register, checked when a breakpoint is reached
+#[derive(Debug, Clone, PartialEq)]
+pub struct Condition {
+    /// The register to check
+    register: Register,
+
+    /// How to compare the register
+    comparison: Comparison,
+
+    /// The value to compare with
+    value: u16
+}
+
+//implementation
+impl Condition {
+    /// Constructs a new `Condition` instance
+    ///
+    /// # Arguments
+    ///
+    /// * `new_register` - The register to check, `V0` to `VF` or `I`
+    /// * `new_comparison` - How to compare the register
+    /// * `new_value` - The value to compare with
+    ///
+    /// # Returns
+    ///
+    /// A new `Condition` instance with the given properties
+    pub fn new(new_register: Register, new_comparison: Comparison,
+               new_value: u16) -> Condition {
+        return Condition {
+            register: new_register,
+            comparison: new_comparison,
+            value: new_value
+        };
+    }
+
+    /// Checks the condition against a machine
+    ///
+    /// # Argument
+    ///
+    /// * `machine` - The machine to check
+    ///
+    /// # Returns
+    ///
+    /// Whether the condition holds
+    pub fn holds(&self, machine: &Machine) -> bool {
+        let lhs = match self.register {
+            Register::I => machine.get_i(),
+            ref r => machine.get_v(r.to_id() as usize) as u16
+        };
+        return self.comparison.holds(lhs, self.value);
+    }
+}
+
+//end of file
@@ -0,0 +1,477 @@
+/*
+ * debugger.rs
+ * Steps through programs with breakpoints, watchpoints and history
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//usage statements
+use std::collections::{BTreeMap, VecDeque};
+use super::{Condition, Halt, Machine, Step, Stop, DEFAULT_IPF};
+use super::machine::CpuState;
+use super::super::error::VmError;
+
+/// The default number of instructions that can be stepped back through
+pub const DEFAULT_HISTORY: usize = 1024;
+
+/// What executing one instruction changed, so it can be undone
+struct Record {
+    /// The machine state before the instruction
+    state: CpuState,
+
+    /// The address and previous contents of the memory it wrote
+    memory: Option<(u16, Vec<u8>)>
+}
+
+/// Drives a `Machine` one instruction at a time, stopping at
+/// breakpoints and watched memory writes and keeping a history
+/// of recent instructions that can be stepped back through
+pub struct Debugger {
+    /// The machine being debugged
+    machine: Machine,
+
+    /// The breakpoint addresses and their conditions, if any
+    breakpoints: BTreeMap<u16, Option<Condition>>,
+
+    /// The start addresses and lengths of watched memory
+    watchpoints: BTreeMap<u16, u16>,
+
+    /// The records of recently executed instructions
+    history: VecDeque<Record>,
+
+    /// The most records to keep
+    history_limit: usize,
+
+    /// The number of instructions executed per timer tick
+    ipf: u64
+}
+
+//implementation
+impl Debugger {
+    /// Constructs a new `Debugger` instance
+    ///
+    /// # Argument
+    ///
+    /// * `new_machine` - The machine to debug
+    ///
+    /// # Returns
+    ///
+    /// A new `Debugger` with no breakpoints, watchpoints or history
+    pub fn new(new_machine: Machine) -> Debugger {
+        return Debugger {
+            machine: new_machine,
+            breakpoints: BTreeMap::new(),
+            watchpoints: BTreeMap::new(),
+            history: VecDeque::new(),
+            history_limit: DEFAULT_HISTORY,
+            ipf: DEFAULT_IPF
+        };
+    }
+
+    /// Adds a breakpoint, replacing any at the same address
+    ///
+    /// # Argument
+    ///
+    /// * `addr` - The address to stop at
+    pub fn add_breakpoint(&mut self, addr: u16) {
+        self.breakpoints.insert(addr, None);
+    }
+
+    /// Adds a breakpoint that only stops when a condition holds,
+    /// replacing any at the same address
+    ///
+    /// # Arguments
+    ///
+    /// * `addr` - The address to stop at
+    /// * `condition` - The condition on the registers
+    pub fn add_conditional_breakpoint(&mut self, addr: u16, 
+                                      condition: Condition) {
+        self.breakpoints.insert(addr, Some(condition));
+    }
+
+    /// Removes a breakpoint
+    ///
+    /// # Argument
+    ///
+    /// * `addr` - The address of the breakpoint
+    ///
+    /// # Returns
+    ///
+    /// Whether there was a breakpoint at the address
+    pub fn remove_breakpoint(&mut self, addr: u16) -> bool {
+        return self.breakpoints.remove(&addr).is_some();
+    }
+
+    /// Watches memory for writes by `BCD` and `RDP`, replacing
+    /// any watchpoint starting at the same address
+    ///
+    /// # Arguments
+    ///
+    /// * `addr` - The first address to watch
+    /// * `len` - The number of bytes to watch
+    pub fn add_watchpoint(&mut self, addr: u16, len: u16) {
+        self.watchpoints.insert(addr, len);
+    }
+
+    /// Removes a watchpoint
+    ///
+    /// # Argument
+    ///
+    /// * `addr` - The first address of the watchpoint
+    ///
+    /// # Returns
+    ///
+    /// Whether there was a watchpoint starting at the address
+    pub fn remove_watchpoint(&mut self, addr: u16) -> bool {
+        return self.watchpoints.remove(&addr).is_some();
+    }
+
+    /// Sets how many instructions can be stepped back through,
+    /// discarding the oldest history beyond the new limit
+    ///
+    /// # Argument
+    ///
+    /// * `limit` - The most instructions to remember
+    pub fn set_history_limit(&mut self, limit: usize) {
+        self.history_limit = limit;
+        while self.history.len() > limit {
+            self.history.pop_front();
+        }
+    }
+
+    /// Gets how many instructions can currently be stepped back through
+    ///
+    /// # Returns
+    ///
+    /// The number of instructions in the history
+    pub fn get_history_len(&self) -> usize {
+        return self.history.len();
+    }
+
+    /// Sets the number of instructions executed per timer tick
+    ///
+    /// # Argument
+    ///
+    /// * `new_ipf` - The instructions per tick
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `new_ipf` is zero.
+    pub fn set_ipf(&mut self, new_ipf: u64) {
+        assert!(new_ipf > 0, "at least one instruction per tick is needed");
+        self.ipf = new_ipf;
+    }
+
+    /// Gets the machine being debugged
+    ///
+    /// # Returns
+    ///
+    /// The machine
+    pub fn get_machine(&self) -> &Machine {
+        return &self.machine;
+    }
+
+    /// Gets the machine being debugged, for modification. Changes
+    /// made this way are not undone by stepping back.
+    ///
+    /// # Returns
+    ///
+    /// The machine
+    pub fn get_machine_mut(&mut self) -> &mut Machine {
+        return &mut self.machine;
+    }
+
+    /// Executes one instruction, ignoring breakpoints
+    ///
+    /// # Returns
+    ///
+    /// `Stop::Stepped`, a watchpoint the instruction wrote to or
+    /// the halt it caused, or the `VmError` it raised
+    pub fn step(&mut self) -> Result<Stop, VmError> {
+        return self.execute();
+    }
+
+    /// Executes one instruction, running a called subroutine to 
+    /// completion if the instruction is a `CALL`
+    ///
+    /// # Argument
+    ///
+    /// * `limit` - The most cycles to execute
+    ///
+    /// # Returns
+    ///
+    /// Why execution stopped, or the `VmError` that stopped it
+    pub fn step_over(&mut self, limit: u64) -> Result<Stop, VmError> {
+        let pc = self.machine.get_pc();
+        let memory = self.machine.get_memory();
+        let is_call = (memory[pc as usize] & 0xF0) == 0x20;
+        if !is_call || self.machine.is_waiting() {
+            return self.execute();
+        }
+        let depth = self.machine.get_stack().len();
+        return self.run_until(limit, |m| m.get_stack().len() <= depth);
+    }
+
+    /// Runs until the current subroutine returns. At the top level
+    /// of a program this runs like `resume`.
+    ///
+    /// # Argument
+    ///
+    /// * `limit` - The most cycles to execute
+    ///
+    /// # Returns
+    ///
+    /// Why execution stopped, or the `VmError` that stopped it
+    pub fn step_out(&mut self, limit: u64) -> Result<Stop, VmError> {
+        let depth = self.machine.get_stack().len();
+        return self.run_until(limit, |m| m.get_stack().len() < depth);
+    }
+
+    /// Runs until a breakpoint, watchpoint or halt. Resuming from 
+    /// a breakpoint executes it rather than stopping immediately.
+    ///
+    /// # Argument
+    ///
+    /// * `limit` - The most cycles to execute
+    ///
+    /// # Returns
+    ///
+    /// Why execution stopped, or the `VmError` that stopped it
+    pub fn resume(&mut self, limit: u64) -> Result<Stop, VmError> {
+        return self.run_until(limit, |_| false);
+    }
+
+    /// Undoes the most recently executed instruction
+    ///
+    /// # Returns
+    ///
+    /// `Stop::Stepped`, or `Stop::HistoryEmpty` if there is
+    /// nothing left to undo
+    pub fn step_back(&mut self) -> Stop {
+        return match self.history.pop_back() {
+            Some(record) => {
+                self.machine.restore_cpu_state(record.state);
+                if let Some((addr, bytes)) = record.memory {
+                    self.machine.write_memory(addr, &bytes);
+                }
+                Stop::Stepped
+            },
+            None => Stop::HistoryEmpty
+        };
+    }
+
+    /// Executes instructions until a condition holds after one
+    fn run_until<F>(&mut self, limit: u64, done: F) -> Result<Stop, VmError>
+        where F: Fn(&Machine) -> bool {
+        let start = self.machine.get_cycles();
+        let mut first = true;
+        loop {
+            let cycles = self.machine.get_cycles();
+            if !first {
+                if done(&self.machine) {
+                    return Ok(Stop::Stepped);
+                }
+                if let Some(stop) = self.check_breakpoint() {
+                    return Ok(stop);
+                }
+            }
+            if cycles - start >= limit {
+                return Ok(Stop::Halted(Halt::CycleLimit));
+            }
+            first = false;
+            let stop = self.execute()?;
+            if stop != Stop::Stepped {
+                return Ok(stop);
+            }
+        }
+    }
+
+    /// Checks for a breakpoint whose condition holds at the program counter
+    fn check_breakpoint(&self) -> Option<Stop> {
+        let pc = self.machine.get_pc();
+        return match self.breakpoints.get(&pc) {
+            Some(None) => Some(Stop::Breakpoint(pc)),
+            Some(Some(ref c)) if c.holds(&self.machine) => {
+                Some(Stop::Breakpoint(pc))
+            },
+            _ => None
+        };
+    }
+
+    /// Finds the first watched address in a written range
+    fn watched(&self, addr: u16, len: u16) -> Option<u16> {
+        return (0..len).map(|k| addr.wrapping_add(k)).find(|a| {
+            return self.watchpoints.iter().any(|(start, n)| {
+                return a.wrapping_sub(*start) < *n;
+            });
+        });
+    }
+
+    /// Executes one instruction, recording it in the history
+    fn execute(&mut self) -> Result<Stop, VmError> {
+        let write = self.machine.pending_write();
+        let memory = write.map(|(addr, len)| {
+            let bytes = (0..len).map(|k| {
+                return self.machine.get_memory()
+                    [addr.wrapping_add(k) as usize];
+            }).collect();
+            return (addr, bytes);
+        });
+        let state = self.machine.cpu_state();
+        let step = self.machine.step()?;
+
+        //remember how to undo the instruction
+        if self.history_limit > 0 {
+            if self.history.len() >= self.history_limit {
+                self.history.pop_front();
+            }
+            self.history.push_back(Record { state, memory });
+        }
+
+        if let Step::Halted(halt) = step {
+            return Ok(Stop::Halted(halt));
+        }
+        if self.machine.get_cycles().is_multiple_of(self.ipf) {
+            self.machine.tick_timers();
+        }
+        if step == Step::Ran {
+            if let Some((addr, len)) = write {
+                if let Some(hit) = self.watched(addr, len) {
+                    return Ok(Stop::Watchpoint(hit));
+                }
+            }
+        }
+        return Ok(Stop::Stepped);
+    }
+}
+
+//unit tests
+#[cfg(test)]
+mod tests {
+    //import the Debugger struct
+    use super::*;
+    use super::super::{Comparison, Quirks};
+    use super::super::super::data::Register;
+
+    //creates a debugger for a program
+    fn debugger(rom: &[u8]) -> Debugger {
+        let mut m = Machine::new(Quirks::vip());
+        m.load(rom).unwrap();
+        return Debugger::new(m);
+    }
+
+    //a program that calls a subroutine storing the digits of V0
+    const CALLER: [u8; 20] = [
+        0x60, 0x05, //MOV V0, 5
+        0xA3, 0x00, //MOV I, 0x300
+        0x22, 0x10, //CALL 0x210
+        0x70, 0x01, //ADD V0, 1
+        0x12, 0x08, //JMP 0x208
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0xF0, 0x33, //BCD V0
+        0x00, 0xEE  //RET
+    ];
+
+    //creates a debugger for the caller program
+    fn caller() -> Debugger {
+        return debugger(&CALLER);
+    }
+
+    //this test checks address and conditional breakpoints
+    #[test]
+    fn test_breakpoints() {
+        //ADD V0, 1 then JMP 0x200
+        let mut d = debugger(&[0x70, 0x01, 0x12, 0x00]);
+        d.add_conditional_breakpoint(0x200,
+            Condition::new(Register::V0, Comparison::Equal, 3));
+        assert_eq!(d.resume(100).unwrap(), Stop::Breakpoint(0x200));
+        assert_eq!(d.get_machine().get_v(0), 3);
+        d.add_breakpoint(0x202);
+        assert_eq!(d.resume(100).unwrap(), Stop::Breakpoint(0x202));
+        assert_eq!(d.get_machine().get_v(0), 4);
+        assert!(d.remove_breakpoint(0x202));
+        assert!(!d.remove_breakpoint(0x202));
+        assert_eq!(d.resume(3).unwrap(), Stop::Halted(Halt::CycleLimit));
+        let mut d = caller();
+        assert_eq!(d.resume(100).unwrap(),
+                    Stop::Halted(Halt::SelfJump(0x208)));
+    }
+
+    //this test checks watchpoints on indexed writes
+    #[test]
+    fn test_watchpoints() {
+        let mut d = caller();
+        d.add_watchpoint(0x301, 2);
+        assert_eq!(d.resume(100).unwrap(), Stop::Watchpoint(0x301));
+        assert_eq!(d.get_machine().get_pc(), 0x212);
+        assert_eq!(&d.get_machine().get_memory()[0x300..0x303], &[0, 0, 5]);
+        assert!(d.remove_watchpoint(0x301));
+        let mut d = caller();
+        d.add_watchpoint(0x303, 1);
+        assert_eq!(d.resume(100).unwrap(),
+                    Stop::Halted(Halt::SelfJump(0x208)));
+    }
+
+    //this test checks stepping over and out of subroutines
+    #[test]
+    fn test_step_over_and_out() {
+        let mut d = caller();
+        assert_eq!(d.step().unwrap(), Stop::Stepped);
+        assert_eq!(d.step_over(100).unwrap(), Stop::Stepped);
+        assert_eq!(d.get_machine().get_pc(), 0x204);
+        assert_eq!(d.step_over(100).unwrap(), Stop::Stepped);
+        assert_eq!(d.get_machine().get_pc(), 0x206);
+        assert_eq!(d.get_machine().get_memory()[0x302], 5);
+        let mut d = caller();
+        d.add_breakpoint(0x212);
+        assert_eq!(d.resume(100).unwrap(), Stop::Breakpoint(0x212));
+        let mut d = caller();
+        d.add_breakpoint(0x210);
+        assert_eq!(d.resume(100).unwrap(), Stop::Breakpoint(0x210));
+        assert_eq!(d.step_out(100).unwrap(), Stop::Stepped);
+        assert_eq!(d.get_machine().get_pc(), 0x206);
+        assert!(d.get_machine().get_stack().is_empty());
+    }
+
+    //this test checks stepping backwards through the history
+    #[test]
+    fn test_step_back() {
+        let mut d = caller();
+        let start = d.get_machine().clone();
+        assert_eq!(d.resume(6).unwrap(), Stop::Halted(Halt::CycleLimit));
+        assert_eq!(d.get_machine().get_v(0), 6);
+        assert_eq!(d.get_history_len(), 6);
+        assert_eq!(d.step_back(), Stop::Stepped);
+        assert_eq!(d.get_machine().get_v(0), 5);
+        assert_eq!(d.get_machine().get_pc(), 0x206);
+        for _ in 0..5 {
+            assert_eq!(d.step_back(), Stop::Stepped);
+        }
+        assert_eq!(d.step_back(), Stop::HistoryEmpty);
+        assert!(*d.get_machine() == start);
+        d.set_history_limit(2);
+        d.resume(6).unwrap();
+        assert_eq!(d.get_history_len(), 2);
+        d.step_back();
+        d.step_back();
+        assert_eq!(d.get_machine().get_pc(), 0x212);
+        assert_eq!(d.get_machine().get_memory()[0x302], 5);
+    }
+}
+
+//end of file
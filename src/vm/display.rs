@@ -0,0 +1,331 @@
+/*
+ * display.rs
+ * Defines the framebuffer of the interpreter
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+/// The framebuffer of the interpreter, either 64x32 or 128x64
+/// pixels. Each pixel holds a bitmask of the planes it is lit in.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Display {
+    /// The width in pixels
+    pub(super) width: usize,
+
+    /// The height in pixels
+    pub(super) height: usize,
+
+    /// The pixels, in rows from the top left
+    pub(super) pixels: Vec<u8>
+}
+
+//implementation
+impl Display {
+    /// Constructs a new, blank `Display` instance
+    ///
+    /// # Argument
+    ///
+    /// * `hires` - Whether the display is 128x64 rather than 64x32
+    ///
+    /// # Returns
+    ///
+    /// A new `Display` with every pixel off
+    pub fn new(hires: bool) -> Display {
+        let (width, height) = if hires { (128, 64) } else { (64, 32) };
+        return Display {
+            width,
+            height,
+            pixels: vec![0; width * height]
+        };
+    }
+
+    /// Gets the width of the display
+    ///
+    /// # Returns
+    ///
+    /// The width in pixels
+    pub fn width(&self) -> usize {
+        return self.width;
+    }
+
+    /// Gets the height of the display
+    ///
+    /// # Returns
+    ///
+    /// The height in pixels
+    pub fn height(&self) -> usize {
+        return self.height;
+    }
+
+    /// Gets whether the display is in high resolution mode
+    ///
+    /// # Returns
+    ///
+    /// `true` if the display is 128x64
+    pub fn is_hires(&self) -> bool {
+        return self.width == 128;
+    }
+
+    /// Gets whether a pixel is lit
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - The column of the pixel
+    /// * `y` - The row of the pixel
+    ///
+    /// # Returns
+    ///
+    /// `true` if the pixel is lit in any plane
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if the pixel is off the display.
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        return self.get_planes(x, y) != 0;
+    }
+
+    /// Gets the planes a pixel is lit in
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - The column of the pixel
+    /// * `y` - The row of the pixel
+    ///
+    /// # Returns
+    ///
+    /// A bitmask with bit `n` set if the pixel is lit in plane `n`
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if the pixel is off the display.
+    pub fn get_planes(&self, x: usize, y: usize) -> u8 {
+        assert!(x < self.width && y < self.height, "pixel off the display");
+        return self.pixels[y * self.width + x];
+    }
+
+    /// Gets the raw pixels of the display
+    ///
+    /// # Returns
+    ///
+    /// The plane bitmask of each pixel, in rows from the top left
+    pub fn get_pixels(&self) -> &[u8] {
+        return self.pixels.as_slice();
+    }
+
+    /// Clears the display and sets its resolution
+    ///
+    /// # Argument
+    ///
+    /// * `hires` - Whether the display is 128x64 rather than 64x32
+    pub fn set_hires(&mut self, hires: bool) {
+        *self = Display::new(hires);
+    }
+
+    /// Turns off every pixel in the given planes
+    ///
+    /// # Argument
+    ///
+    /// * `planes` - The bitmask of planes to clear
+    pub fn clear(&mut self, planes: u8) {
+        for p in self.pixels.iter_mut() {
+            *p &= !planes;
+        }
+    }
+
+    /// Draws a sprite by XORing its rows onto the display
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - The column of the sprite's left edge
+    /// * `y` - The row of the sprite's top edge
+    /// * `rows` - The rows of the sprite, with the leftmost pixel
+    ///   in the most significant of `width` bits
+    /// * `width` - The width of the sprite, 8 or 16
+    /// * `planes` - The bitmask of planes to draw in
+    /// * `clip` - Whether pixels past the edges are clipped
+    ///   rather than wrapped
+    ///
+    /// # Returns
+    ///
+    /// `true` if any lit pixel was turned off
+    pub fn draw(&mut self, x: usize, y: usize, rows: &[u16], width: usize,
+                planes: u8, clip: bool) -> bool {
+        let x = x % self.width;
+        let y = y % self.height;
+        let mut collided = false;
+        for (dy, row) in rows.iter().enumerate() {
+            for dx in 0..width {
+                if (row >> (width - 1 - dx)) & 1 == 0 {
+                    continue;
+                }
+                let (px, py) = (x + dx, y + dy);
+                if clip && (px >= self.width || py >= self.height) {
+                    continue;
+                }
+                let idx = (py % self.height) * self.width + px % self.width;
+                if self.pixels[idx] & planes != 0 {
+                    collided = true;
+                }
+                self.pixels[idx] ^= planes;
+            }
+        }
+        return collided;
+    }
+
+    /// Scrolls the given planes down, filling with blank rows
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The number of rows to scroll by
+    /// * `planes` - The bitmask of planes to scroll
+    pub fn scroll_down(&mut self, n: usize, planes: u8) {
+        self.shift(0, n as isize, planes);
+    }
+
+    /// Scrolls the given planes up, filling with blank rows
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The number of rows to scroll by
+    /// * `planes` - The bitmask of planes to scroll
+    pub fn scroll_up(&mut self, n: usize, planes: u8) {
+        self.shift(0, -(n as isize), planes);
+    }
+
+    /// Scrolls the given planes left, filling with blank columns
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The number of columns to scroll by
+    /// * `planes` - The bitmask of planes to scroll
+    pub fn scroll_left(&mut self, n: usize, planes: u8) {
+        self.shift(-(n as isize), 0, planes);
+    }
+
+    /// Scrolls the given planes right, filling with blank columns
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The number of columns to scroll by
+    /// * `planes` - The bitmask of planes to scroll
+    pub fn scroll_right(&mut self, n: usize, planes: u8) {
+        self.shift(n as isize, 0, planes);
+    }
+
+    /// Moves the given planes by an offset
+    fn shift(&mut self, dx: isize, dy: isize, planes: u8) {
+        let old = self.pixels.clone();
+        let (w, h) = (self.width as isize, self.height as isize);
+        for y in 0..h {
+            for x in 0..w {
+                let (sx, sy) = (x - dx, y - dy);
+                let src = if sx >= 0 && sx < w && sy >= 0 && sy < h {
+                    old[(sy * w + sx) as usize] & planes
+                } else {
+                    0
+                };
+                let idx = (y * w + x) as usize;
+                self.pixels[idx] = (self.pixels[idx] & !planes) | src;
+            }
+        }
+    }
+
+    /// Renders the display as text
+    ///
+    /// # Returns
+    ///
+    /// One line per row, with `#` for lit pixels and `.` for unlit ones
+    pub fn to_ascii(&self) -> String {
+        let mut out = String::with_capacity((self.width + 1) * self.height);
+        for row in self.pixels.chunks(self.width) {
+            for p in row {
+                out.push(if *p != 0 { '#' } else { '.' });
+            }
+            out.push('\n');
+        }
+        return out;
+    }
+
+    /// Renders the display as a plain PBM image
+    ///
+    /// # Returns
+    ///
+    /// The text of a `P1` portable bitmap with lit pixels black
+    pub fn to_pbm(&self) -> String {
+        let mut out = format!("P1\n{} {}\n", self.width, self.height);
+        for row in self.pixels.chunks(self.width) {
+            for half in row.chunks(64) {
+                for p in half {
+                    out.push(if *p != 0 { '1' } else { '0' });
+                }
+                out.push('\n');
+            }
+        }
+        return out;
+    }
+}
+
+//unit tests
+#[cfg(test)]
+mod tests {
+    //import the Display struct
+    use super::*;
+
+    //this test checks drawing with collisions and clipping
+    #[test]
+    fn test_draw() {
+        let mut d = Display::new(false);
+        assert!(!d.draw(62, 0, &[0xC0, 0xF0], 8, 1, true));
+        assert!(d.get(62, 0) && d.get(63, 1) && !d.get(0, 1));
+        assert!(d.draw(62, 0, &[0x80], 8, 1, true));
+        assert!(!d.get(62, 0));
+        assert!(!d.draw(62, 2, &[0xF0], 8, 1, false));
+        assert!(d.get(0, 2) && d.get(1, 2));
+        assert!(!d.draw(66, 33, &[0x80], 8, 1, true));
+        assert!(d.get(2, 1));
+    }
+
+    //this test checks scrolling
+    #[test]
+    fn test_scroll() {
+        let mut d = Display::new(true);
+        d.draw(0, 0, &[0x8000], 16, 1, true);
+        d.scroll_down(2, 1);
+        d.scroll_right(4, 1);
+        assert!(d.get(4, 2));
+        d.scroll_left(4, 1);
+        d.scroll_up(2, 1);
+        assert!(d.get(0, 0));
+        assert_eq!(d.get_pixels().iter().filter(|p| **p != 0).count(), 1);
+    }
+
+    //this test checks text rendering
+    #[test]
+    fn test_render() {
+        let mut d = Display::new(false);
+        d.draw(1, 0, &[0x80], 8, 1, true);
+        let ascii = d.to_ascii();
+        assert!(ascii.starts_with(".#.."));
+        assert_eq!(ascii.lines().count(), 32);
+        let pbm = d.to_pbm();
+        assert!(pbm.starts_with("P1\n64 32\n0100"));
+        assert_eq!(pbm.lines().count(), 34);
+    }
+}
+
+//end of file
@@ -0,0 +1,570 @@
+/*
+ * gdb_stub.rs
+ * Serves the GDB remote serial protocol for the debugger
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//usage statements
+use std::io;
+use std::io::{Read, Write};
+use std::net::TcpListener;
+#[cfg(unix)]
+use std::os::unix::net::UnixListener;
+use super::{Debugger, Halt, Stop};
+use super::super::error::VmError;
+
+/// The number of cycles a continue runs before reporting a stop
+pub const DEFAULT_CONTINUE_LIMIT: u64 = 10_000_000;
+
+/// The largest packet accepted, advertised to the client in hex
+const PACKET_SIZE: usize = 0x1000;
+
+/// The number of registers: `V0` to `VF`, `I`, `PC`, `SP`, `DT` and `ST`
+const REGISTER_COUNT: usize = 21;
+
+/// A GDB remote serial protocol server exposing a `Debugger`.
+/// The register file is `V0` to `VF` (8 bits), `I` and `PC`
+/// (16 bits, little-endian), then the stack depth `SP` and the
+/// delay and sound timers (8 bits). `SP` is read-only.
+pub struct GdbStub {
+    /// The debugger being served
+    debugger: Debugger,
+
+    /// The most cycles a continue runs
+    continue_limit: u64
+}
+
+//implementation
+impl GdbStub {
+    /// Constructs a new `GdbStub` instance
+    ///
+    /// # Argument
+    ///
+    /// * `new_debugger` - The debugger to serve
+    ///
+    /// # Returns
+    ///
+    /// A new `GdbStub` wrapping the debugger
+    pub fn new(new_debugger: Debugger) -> GdbStub {
+        return GdbStub {
+            debugger: new_debugger,
+            continue_limit: DEFAULT_CONTINUE_LIMIT
+        };
+    }
+
+    /// Sets how many cycles a continue runs before the stub stops
+    /// the program and reports it as trapped. The protocol's
+    /// interrupt is not supported, so this bounds runaway programs.
+    ///
+    /// # Argument
+    ///
+    /// * `limit` - The most cycles to run
+    pub fn set_continue_limit(&mut self, limit: u64) {
+        self.continue_limit = limit;
+    }
+
+    /// Gets the debugger being served
+    ///
+    /// # Returns
+    ///
+    /// The debugger
+    pub fn get_debugger(&self) -> &Debugger {
+        return &self.debugger;
+    }
+
+    /// Gets the debugger being served, for modification
+    ///
+    /// # Returns
+    ///
+    /// The debugger
+    pub fn get_debugger_mut(&mut self) -> &mut Debugger {
+        return &mut self.debugger;
+    }
+
+    /// Accepts one client on a TCP listener and serves it
+    ///
+    /// # Argument
+    ///
+    /// * `listener` - The listener to accept from
+    ///
+    /// # Returns
+    ///
+    /// `Ok` once the client detaches or disconnects, or the
+    /// I/O error that ended the session
+    pub fn accept_tcp(&mut self, listener: &TcpListener) -> io::Result<()> {
+        let (mut stream, _) = listener.accept()?;
+        stream.set_nodelay(true)?;
+        return self.serve(&mut stream);
+    }
+
+    /// Accepts one client on a Unix socket listener and serves it
+    ///
+    /// # Argument
+    ///
+    /// * `listener` - The listener to accept from
+    ///
+    /// # Returns
+    ///
+    /// `Ok` once the client detaches or disconnects, or the
+    /// I/O error that ended the session
+    #[cfg(unix)]
+    pub fn accept_unix(&mut self, listener: &UnixListener) -> io::Result<()> {
+        let (mut stream, _) = listener.accept()?;
+        return self.serve(&mut stream);
+    }
+
+    /// Serves a client over a connected stream
+    ///
+    /// # Argument
+    ///
+    /// * `stream` - The connection to the client
+    ///
+    /// # Returns
+    ///
+    /// `Ok` once the client detaches, kills the program or
+    /// disconnects, or the I/O error that ended the session
+    pub fn serve<S: Read + Write>(&mut self, stream: &mut S) -> io::Result<()> {
+        let mut ack = true;
+        loop {
+            let packet = match read_packet(stream, ack)? {
+                Some(p) => p,
+                None => return Ok(())
+            };
+            if ack {
+                stream.write_all(b"+")?;
+            }
+            if packet == "k" {
+                return Ok(());
+            }
+            let (reply, done) = self.handle(&packet);
+            write_packet(stream, &reply)?;
+            if packet == "QStartNoAckMode" {
+                ack = false;
+            }
+            if done {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Handles a packet, returning the reply and whether 
+    /// the session is over
+    fn handle(&mut self, packet: &str) -> (String, bool) {
+        let mut chars = packet.chars();
+        let (cmd, args) = match chars.next() {
+            Some(c) => (c, chars.as_str()),
+            None => return (String::new(), false)
+        };
+        let reply = match cmd {
+            '?' => String::from("S05"),
+            'g' => to_hex(&self.read_registers()),
+            'G' => match from_hex(args) {
+                Some(ref bytes) if bytes.len() == self.register_bytes() => {
+                    self.write_registers(bytes);
+                    String::from("OK")
+                },
+                _ => error()
+            },
+            'p' => match parse_hex(args) {
+                Some(n) if (n as usize) < REGISTER_COUNT => {
+                    to_hex(&self.read_register(n as usize))
+                },
+                _ => error()
+            },
+            'P' => self.write_register(args).unwrap_or_else(error),
+            'm' => self.read_memory(args).unwrap_or_else(error),
+            'M' => self.write_memory(args).unwrap_or_else(error),
+            'Z' | 'z' => self.breakpoint(cmd == 'Z', args)
+                .unwrap_or_else(error),
+            'c' | 's' => {
+                if let Some(addr) = parse_hex(args) {
+                    self.debugger.get_machine_mut().set_pc(addr as u16);
+                }
+                let result = if cmd == 'c' {
+                    self.debugger.resume(self.continue_limit)
+                } else {
+                    self.debugger.step()
+                };
+                return stop_reply(result);
+            },
+            'b' if args == "s" => match self.debugger.step_back() {
+                Stop::HistoryEmpty => String::from("T05replaylog:begin;"),
+                _ => String::from("S05")
+            },
+            'D' => return (String::from("OK"), true),
+            'H' | 'T' => String::from("OK"),
+            'q' | 'Q' => self.query(packet),
+            'v' if packet == "vKill" => return (String::from("OK"), true),
+            _ => String::new()
+        };
+        return (reply, false);
+    }
+
+    /// Answers a general query packet
+    fn query(&self, packet: &str) -> String {
+        let xfer = "qXfer:features:read:target.xml:";
+        if let Some(range) = packet.strip_prefix(xfer) {
+            return read_chunk(&target_xml(), range).unwrap_or_else(error);
+        }
+        let name = packet.split(':').next().unwrap_or("");
+        return match name {
+            "qSupported" => format!("PacketSize={:x};QStartNoAckMode+;\
+                                     qXfer:features:read+;ReverseStep+",
+                                    PACKET_SIZE),
+            "QStartNoAckMode" => String::from("OK"),
+            "qAttached" => String::from("1"),
+            "qC" => String::from("QC1"),
+            "qfThreadInfo" => String::from("m1"),
+            "qsThreadInfo" => String::from("l"),
+            _ => String::new()
+        };
+    }
+
+    /// Gets the total size of the register file in bytes
+    fn register_bytes(&self) -> usize {
+        return (0..REGISTER_COUNT).map(|n| self.read_register(n).len()).sum();
+    }
+
+    /// Encodes one register in target byte order
+    fn read_register(&self, n: usize) -> Vec<u8> {
+        let m = self.debugger.get_machine();
+        return match n {
+            0..=15 => vec![m.get_v(n)],
+            16 => m.get_i().to_le_bytes().to_vec(),
+            17 => m.get_pc().to_le_bytes().to_vec(),
+            18 => vec![m.get_stack().len() as u8],
+            19 => vec![m.get_delay()],
+            _ => vec![m.get_sound()]
+        };
+    }
+
+    /// Encodes the whole register file
+    fn read_registers(&self) -> Vec<u8> {
+        return (0..REGISTER_COUNT).flat_map(|n| self.read_register(n))
+            .collect();
+    }
+
+    /// Sets one register from bytes in target byte order
+    fn set_register(&mut self, n: usize, bytes: &[u8]) {
+        let m = self.debugger.get_machine_mut();
+        let word = bytes[0] as u16 | (*bytes.get(1).unwrap_or(&0) as u16) << 8;
+        match n {
+            0..=15 => m.set_v(n, bytes[0]),
+            16 => m.set_i(word),
+            17 => m.set_pc(word),
+            19 => m.set_delay(bytes[0]),
+            20 => m.set_sound(bytes[0]),
+            _ => {}
+        };
+    }
+
+    /// Sets the whole register file from a `G` packet's bytes
+    fn write_registers(&mut self, bytes: &[u8]) {
+        let mut offset = 0;
+        for n in 0..REGISTER_COUNT {
+            let len = self.read_register(n).len();
+            self.set_register(n, &bytes[offset..offset + len]);
+            offset += len;
+        }
+    }
+
+    /// Handles a `P` packet, formatted `n=value`
+    fn write_register(&mut self, args: &str) -> Option<String> {
+        let mut parts = args.splitn(2, '=');
+        let n = parse_hex(parts.next()?)? as usize;
+        let bytes = from_hex(parts.next()?)?;
+        if n >= REGISTER_COUNT || bytes.len() != self.read_register(n).len() {
+            return None;
+        }
+        self.set_register(n, &bytes);
+        return Some(String::from("OK"));
+    }
+
+    /// Handles an `m` packet, formatted `addr,len`
+    fn read_memory(&self, args: &str) -> Option<String> {
+        let (addr, len) = parse_pair(args)?;
+        if len as usize > PACKET_SIZE / 2 {
+            return None;
+        }
+        let memory = self.debugger.get_machine().get_memory();
+        let bytes: Vec<u8> = (0..len).map(|k| {
+            return memory[(addr as u16).wrapping_add(k as u16) as usize];
+        }).collect();
+        return Some(to_hex(&bytes));
+    }
+
+    /// Handles an `M` packet, formatted `addr,len:data`
+    fn write_memory(&mut self, args: &str) -> Option<String> {
+        let mut parts = args.splitn(2, ':');
+        let (addr, len) = parse_pair(parts.next()?)?;
+        let bytes = from_hex(parts.next()?)?;
+        if bytes.len() != len as usize {
+            return None;
+        }
+        self.debugger.get_machine_mut().write_memory(addr as u16, &bytes);
+        return Some(String::from("OK"));
+    }
+
+    /// Handles a `Z` or `z` packet, formatted `type,addr,kind`
+    fn breakpoint(&mut self, insert: bool, args: &str) -> Option<String> {
+        let mut parts = args.splitn(2, ',');
+        let kind = parts.next()?;
+        let (addr, len) = parse_pair(parts.next()?)?;
+        let addr = addr as u16;
+        match (kind, insert) {
+            ("0", true) | ("1", true) => self.debugger.add_breakpoint(addr),
+            ("0", false) | ("1", false) => {
+                self.debugger.remove_breakpoint(addr);
+            },
+            ("2", true) => self.debugger.add_watchpoint(addr, len as u16),
+            ("2", false) => {
+                self.debugger.remove_watchpoint(addr);
+            },
+            _ => return Some(String::new())
+        };
+        return Some(String::from("OK"));
+    }
+}
+
+/// Formats the stop reply for the result of running the debugger
+fn stop_reply(result: Result<Stop, VmError>) -> (String, bool) {
+    return match result {
+        Ok(Stop::Watchpoint(addr)) => (format!("T05watch:{:x};", addr), false),
+        Ok(Stop::Halted(Halt::Exit)) => (String::from("W00"), true),
+        Ok(_) => (String::from("S05"), false),
+        Err(_) => (String::from("S04"), false)
+    };
+}
+
+/// Creates an error reply
+fn error() -> String {
+    return String::from("E01");
+}
+
+/// Describes the register file to the client
+fn target_xml() -> String {
+    let mut xml = String::from("<?xml version=\"1.0\"?>\
+        <!DOCTYPE target SYSTEM \"gdb-target.dtd\">\
+        <target version=\"1.0\"><feature name=\"org.ch8.cpu\">");
+    for n in 0..16 {
+        xml.push_str(&format!("<reg name=\"v{:x}\" bitsize=\"8\" \
+                               type=\"uint8\"/>", n));
+    }
+    xml.push_str("<reg name=\"i\" bitsize=\"16\" type=\"data_ptr\"/>\
+        <reg name=\"pc\" bitsize=\"16\" type=\"code_ptr\"/>\
+        <reg name=\"sp\" bitsize=\"8\" type=\"uint8\"/>\
+        <reg name=\"dt\" bitsize=\"8\" type=\"uint8\"/>\
+        <reg name=\"st\" bitsize=\"8\" type=\"uint8\"/>\
+        </feature></target>");
+    return xml;
+}
+
+/// Answers a `qXfer` read of `offset,length` from a document
+fn read_chunk(doc: &str, range: &str) -> Option<String> {
+    let (offset, len) = parse_pair(range)?;
+    let start = (offset as usize).min(doc.len());
+    let end = (start + len as usize).min(doc.len());
+    let prefix = if end == doc.len() { 'l' } else { 'm' };
+    return Some(format!("{}{}", prefix, &doc[start..end]));
+}
+
+/// Parses a hexadecimal number
+fn parse_hex(text: &str) -> Option<u32> {
+    return u32::from_str_radix(text, 16).ok();
+}
+
+/// Parses a pair of hexadecimal numbers separated by a comma
+fn parse_pair(text: &str) -> Option<(u32, u32)> {
+    let mut parts = text.splitn(2, ',');
+    let first = parse_hex(parts.next()?)?;
+    let second = parse_hex(parts.next()?)?;
+    return Some((first, second));
+}
+
+/// Encodes bytes as lowercase hexadecimal
+fn to_hex(bytes: &[u8]) -> String {
+    return bytes.iter().map(|b| format!("{:02x}", b)).collect();
+}
+
+/// Decodes hexadecimal text into bytes
+fn from_hex(text: &str) -> Option<Vec<u8>> {
+    if !text.len().is_multiple_of(2) || !text.is_ascii() {
+        return None;
+    }
+    return (0..text.len()).step_by(2).map(|k| {
+        return u8::from_str_radix(&text[k..k + 2], 16).ok();
+    }).collect();
+}
+
+/// Reads one byte from a stream, or `None` at the end of the stream
+fn read_byte<S: Read>(stream: &mut S) -> io::Result<Option<u8>> {
+    let mut byte = [0u8; 1];
+    loop {
+        return match stream.read(&mut byte) {
+            Ok(0) => Ok(None),
+            Ok(_) => Ok(Some(byte[0])),
+            Err(ref e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => Err(e)
+        };
+    }
+}
+
+/// Reads the next packet with a valid checksum, skipping
+/// acknowledgements and asking for corrupt packets again
+fn read_packet<S: Read + Write>(stream: &mut S, 
+                                ack: bool) -> io::Result<Option<String>> {
+    loop {
+        //find the start of a packet
+        loop {
+            match read_byte(stream)? {
+                Some(b'$') => break,
+                Some(_) => continue,
+                None => return Ok(None)
+            };
+        }
+
+        //read its body
+        let mut data = Vec::new();
+        let mut sum = 0u8;
+        let mut escaped = false;
+        loop {
+            let b = match read_byte(stream)? {
+                Some(b) => b,
+                None => return Ok(None)
+            };
+            if b == b'#' && !escaped {
+                break;
+            }
+            sum = sum.wrapping_add(b);
+            if escaped {
+                data.push(b ^ 0x20);
+                escaped = false;
+            } else if b == b'}' {
+                escaped = true;
+            } else {
+                data.push(b);
+            }
+        }
+
+        //and check its checksum
+        let mut digits = [0u8; 2];
+        for d in digits.iter_mut() {
+            *d = match read_byte(stream)? {
+                Some(b) => b,
+                None => return Ok(None)
+            };
+        }
+        let check = std::str::from_utf8(&digits).ok()
+            .and_then(|t| u8::from_str_radix(t, 16).ok());
+        if !ack || check == Some(sum) {
+            return Ok(Some(String::from_utf8_lossy(&data).into_owned()));
+        }
+        stream.write_all(b"-")?;
+    }
+}
+
+/// Writes a packet, escaping the protocol's special characters
+fn write_packet<S: Write>(stream: &mut S, data: &str) -> io::Result<()> {
+    let mut body = Vec::new();
+    for b in data.bytes() {
+        if b == b'$' || b == b'#' || b == b'}' || b == b'*' {
+            body.push(b'}');
+            body.push(b ^ 0x20);
+        } else {
+            body.push(b);
+        }
+    }
+    let sum = body.iter().fold(0u8, |s, b| s.wrapping_add(*b));
+    let mut packet = vec![b'$'];
+    packet.extend_from_slice(&body);
+    packet.extend_from_slice(format!("#{:02x}", sum).as_bytes());
+    stream.write_all(&packet)?;
+    return stream.flush();
+}
+
+//unit tests
+#[cfg(test)]
+mod tests {
+    //import the GdbStub struct
+    use super::*;
+    use super::super::{Machine, Quirks};
+    use std::io::Cursor;
+
+    //this test checks packet framing and escaping
+    #[test]
+    fn test_packets() {
+        let mut out = Vec::new();
+        write_packet(&mut out, "a#b").unwrap();
+        assert_eq!(out, b"$a}\x03b#43");
+        let mut input = Cursor::new(b"+$a}\x03b#43-$m0,1#fa".to_vec());
+        assert_eq!(read_packet(&mut input, true).unwrap().unwrap(), "a#b");
+        assert_eq!(read_packet(&mut input, true).unwrap().unwrap(), "m0,1");
+        assert_eq!(read_packet(&mut input, true).unwrap(), None);
+    }
+
+    //a stream reading from fixed input and recording its output
+    struct Duplex {
+        input: Cursor<Vec<u8>>,
+        output: Vec<u8>
+    }
+
+    impl Read for Duplex {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            return self.input.read(buf);
+        }
+    }
+
+    impl Write for Duplex {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            return self.output.write(buf);
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            return Ok(());
+        }
+    }
+
+    //this test checks that empty and non-ASCII packets are
+    //answered with empty replies
+    #[test]
+    fn test_malformed_packets() {
+        let mut stub = GdbStub::new(Debugger::new(Machine::new(
+                    Quirks::vip())));
+        let mut stream = Duplex {
+            input: Cursor::new(b"$#00+$\xff#ff+$\xc3\xa9#6c+$D#44+"
+                               .to_vec()),
+            output: Vec::new()
+        };
+        stub.serve(&mut stream).unwrap();
+        assert_eq!(stream.output, b"+$#00+$#00+$#00+$OK#9a");
+    }
+
+    //this test checks the hexadecimal helpers
+    #[test]
+    fn test_hex() {
+        assert_eq!(to_hex(&[0x00, 0xAB]), "00ab");
+        assert_eq!(from_hex("00ab"), Some(vec![0x00, 0xAB]));
+        assert_eq!(from_hex("0"), None);
+        assert_eq!(parse_pair("200,10"), Some((0x200, 0x10)));
+        assert_eq!(read_chunk("abcdef", "4,10"), Some(String::from("lef")));
+        assert_eq!(read_chunk("abcdef", "0,2"), Some(String::from("mab")));
+    }
+}
+
+//end of file
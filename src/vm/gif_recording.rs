@@ -0,0 +1,359 @@
+/*
+ * gif_recording.rs
+ * Defines an animated GIF recording of a framebuffer
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//usage statements
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+use super::{Display, Image, Palette};
+
+/// The bits per pixel of the LZW stream, enough for four colours
+const MIN_CODE_SIZE: u8 = 2;
+
+/// The largest code of a GIF LZW stream, plus one
+const MAX_CODES: u16 = 4096;
+
+/// Records the framebuffer once per 60 Hz frame and encodes
+/// the frames as a looping animated GIF. Frames that repeat the
+/// previous one extend how long it is shown instead of being
+/// stored again.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GifRecording {
+    /// The size of the block drawn for each pixel
+    scale: usize,
+
+    /// The colours of the planes
+    palette: Palette,
+
+    /// The distinct frames, in order
+    frames: Vec<Image>,
+
+    /// The number of 60 Hz frames each image is shown for
+    ticks: Vec<u64>
+}
+
+//implementation
+impl GifRecording {
+    /// Constructs a new `GifRecording` instance
+    ///
+    /// # Arguments
+    ///
+    /// * `new_scale` - The size of the block drawn for each pixel
+    /// * `new_palette` - The colours of the planes
+    ///
+    /// # Returns
+    ///
+    /// A new `GifRecording` with no frames
+    ///
+    /// # Panics
+    ///
+    /// Panics if `new_scale` is zero
+    pub fn new(new_scale: usize, new_palette: Palette) -> GifRecording {
+        assert!(new_scale > 0, "image scale must be positive");
+        return GifRecording {
+            scale: new_scale,
+            palette: new_palette,
+            frames: Vec::new(),
+            ticks: Vec::new()
+        };
+    }
+
+    /// Records the framebuffer for one frame. The first frame
+    /// fixes the size of the animation; later frames at another
+    /// resolution are stretched to fit it.
+    ///
+    /// # Argument
+    ///
+    /// * `display` - The framebuffer at the end of the frame
+    pub fn add_frame(&mut self, display: &Display) {
+        let image = match self.frames.first() {
+            Some(first) => Image::fit(display, first.get_width(), 
+                                      first.get_height(), self.palette),
+            None => Image::new(display, self.scale, self.palette)
+        };
+        if self.frames.last() == Some(&image) {
+            if let Some(t) = self.ticks.last_mut() {
+                *t += 1;
+            }
+        } else {
+            self.frames.push(image);
+            self.ticks.push(1);
+        }
+    }
+
+    /// Gets the distinct frames recorded
+    ///
+    /// # Returns
+    ///
+    /// The images, in order
+    pub fn get_frames(&self) -> &[Image] {
+        return self.frames.as_slice();
+    }
+
+    /// Gets how long each distinct frame is shown
+    ///
+    /// # Returns
+    ///
+    /// The number of 60 Hz frames of each image
+    pub fn get_ticks(&self) -> &[u64] {
+        return self.ticks.as_slice();
+    }
+
+    /// Encodes the recording as an animated GIF that loops
+    /// forever. GIF delays are in hundredths of a second, so
+    /// each frame is rounded to keep the total duration exact.
+    ///
+    /// # Returns
+    ///
+    /// The bytes of the GIF file
+    pub fn to_gif(&self) -> Vec<u8> {
+        let (width, height) = match self.frames.first() {
+            Some(first) => (first.get_width(), first.get_height()),
+            None => (64 * self.scale, 32 * self.scale)
+        };
+
+        //write the header, the colours and the loop extension
+        let mut out = b"GIF89a".to_vec();
+        out.extend_from_slice(&(width as u16).to_le_bytes());
+        out.extend_from_slice(&(height as u16).to_le_bytes());
+        out.extend_from_slice(&[0x81, 0, 0]);
+        for colour in self.palette.get_colours().iter() {
+            out.extend_from_slice(colour);
+        }
+        out.extend_from_slice(b"\x21\xFF\x0BNETSCAPE2.0\x03\x01\x00\x00\x00");
+
+        //then each frame with its delay
+        let mut start = 0;
+        for (image, ticks) in self.frames.iter().zip(self.ticks.iter()) {
+            let end = start + ticks;
+            let delay = centiseconds(end) - centiseconds(start);
+            start = end;
+            out.extend_from_slice(&[0x21, 0xF9, 0x04, 0x00]);
+            out.extend_from_slice(&(delay.min(0xFFFF) as u16).to_le_bytes());
+            out.extend_from_slice(&[0x00, 0x00, 0x2C, 0, 0, 0, 0]);
+            out.extend_from_slice(&(width as u16).to_le_bytes());
+            out.extend_from_slice(&(height as u16).to_le_bytes());
+            out.push(0x00);
+            out.push(MIN_CODE_SIZE);
+            for block in lzw(image.get_pixels()).chunks(255) {
+                out.push(block.len() as u8);
+                out.extend_from_slice(block);
+            }
+            out.push(0x00);
+        }
+        out.push(0x3B);
+        return out;
+    }
+
+    /// Writes the recording to a GIF file
+    ///
+    /// # Argument
+    ///
+    /// * `path` - The path of the file
+    ///
+    /// # Returns
+    ///
+    /// `Ok` if the file was written, or the I/O error that prevented it
+    pub fn write_gif<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        return fs::write(path, self.to_gif());
+    }
+}
+
+/// Converts a count of 60 Hz frames to the nearest hundredth
+/// of a second
+fn centiseconds(frames: u64) -> u64 {
+    return (frames * 100 + 30) / 60;
+}
+
+/// Packs variable-width codes into bytes, least significant 
+/// bit first
+struct BitWriter {
+    /// The packed bytes
+    bytes: Vec<u8>,
+
+    /// The bits not yet packed
+    buffer: u32,
+
+    /// The number of bits in the buffer
+    count: u8
+}
+
+impl BitWriter {
+    /// Appends a code of the given width
+    fn write(&mut self, code: u16, width: u8) {
+        self.buffer |= (code as u32) << self.count;
+        self.count += width;
+        while self.count >= 8 {
+            self.bytes.push(self.buffer as u8);
+            self.buffer >>= 8;
+            self.count -= 8;
+        }
+    }
+
+    /// Flushes any partial byte and returns the packed bytes
+    fn finish(mut self) -> Vec<u8> {
+        if self.count > 0 {
+            self.bytes.push(self.buffer as u8);
+        }
+        return self.bytes;
+    }
+}
+
+/// Compresses palette indices with GIF's variant of LZW
+fn lzw(pixels: &[u8]) -> Vec<u8> {
+    let clear = 1u16 << MIN_CODE_SIZE;
+    let end = clear + 1;
+    let mut out = BitWriter { bytes: Vec::new(), buffer: 0, count: 0 };
+    let mut table: HashMap<(u16, u8), u16> = HashMap::new();
+    let mut next = end + 1;
+    let mut width = MIN_CODE_SIZE + 1;
+    out.write(clear, width);
+    let mut iter = pixels.iter();
+    let mut prefix = match iter.next() {
+        Some(p) => *p as u16,
+        None => {
+            out.write(end, width);
+            return out.finish();
+        }
+    };
+    for p in iter {
+        if let Some(code) = table.get(&(prefix, *p)) {
+            prefix = *code;
+            continue;
+        }
+        out.write(prefix, width);
+        if next == MAX_CODES {
+            out.write(clear, width);
+            table.clear();
+            next = end + 1;
+            width = MIN_CODE_SIZE + 1;
+        } else {
+            //grow the codes when the decoder, one entry behind, will
+            if next >= 1 << width {
+                width += 1;
+            }
+            table.insert((prefix, *p), next);
+            next += 1;
+        }
+        prefix = *p as u16;
+    }
+    out.write(prefix, width);
+    out.write(end, width);
+    return out.finish();
+}
+
+//unit tests
+#[cfg(test)]
+mod tests {
+    //import the GifRecording struct
+    use super::*;
+
+    //decodes a GIF LZW stream, to check the encoder against
+    fn unlzw(data: &[u8]) -> Vec<u8> {
+        let clear = 1u16 << MIN_CODE_SIZE;
+        let (mut pos, mut width) = (0usize, MIN_CODE_SIZE + 1);
+        let mut table: Vec<Vec<u8>> = Vec::new();
+        let mut prev: Option<Vec<u8>> = None;
+        let mut out = Vec::new();
+        loop {
+            let mut code = 0u16;
+            for b in 0..width as usize {
+                let bit = (data[(pos + b) / 8] >> ((pos + b) % 8)) & 1;
+                code |= (bit as u16) << b;
+            }
+            pos += width as usize;
+            if code == clear {
+                table = (0..clear).map(|c| vec![c as u8]).collect();
+                table.push(Vec::new());
+                table.push(Vec::new());
+                width = MIN_CODE_SIZE + 1;
+                prev = None;
+                continue;
+            } else if code == clear + 1 {
+                return out;
+            }
+            let entry = match (table.get(code as usize), &prev) {
+                (Some(e), _) => e.clone(),
+                (None, Some(p)) => {
+                    let mut e = p.clone();
+                    e.push(p[0]);
+                    e
+                },
+                (None, None) => panic!("bad code")
+            };
+            if let Some(p) = prev {
+                let mut e = p.clone();
+                e.push(entry[0]);
+                table.push(e);
+            }
+            if table.len() == 1 << width && width < 12 {
+                width += 1;
+            }
+            out.extend_from_slice(&entry);
+            prev = Some(entry);
+        }
+    }
+
+    //this test checks that LZW output decodes to its input
+    #[test]
+    fn test_lzw() {
+        let mut pixels = Vec::new();
+        let mut x = 7u32;
+        for _ in 0..20000 {
+            x = x.wrapping_mul(1103515245).wrapping_add(12345);
+            pixels.push(((x >> 16) & 3) as u8);
+        }
+        for n in [0, 1, 2, 100, 20000].iter() {
+            assert_eq!(unlzw(&lzw(&pixels[..*n])), &pixels[..*n]);
+        }
+        let flat = vec![0u8; 100000];
+        assert_eq!(unlzw(&lzw(&flat)), flat);
+    }
+
+    //this test checks merging repeated frames and their delays
+    #[test]
+    fn test_frames() {
+        let mut d = Display::new(false);
+        let mut rec = GifRecording::new(2, Palette::default());
+        for _ in 0..3 {
+            rec.add_frame(&d);
+        }
+        d.draw(0, 0, &[0x80], 8, 1, false);
+        rec.add_frame(&d);
+        d.set_hires(true);
+        rec.add_frame(&d);
+        assert_eq!(rec.get_ticks(), &[3, 1, 1]);
+        assert_eq!(rec.get_frames()[2].get_width(), 128);
+
+        //three frames take five hundredths of a second
+        let gif = rec.to_gif();
+        assert_eq!(&gif[0..6], b"GIF89a");
+        assert_eq!(&gif[6..10], &[128, 0, 64, 0]);
+        assert_eq!(&gif[gif.len() - 1..], &[0x3B]);
+        let gce = gif.windows(4).position(|w| w == [0x21, 0xF9, 0x04, 0x00])
+            .unwrap();
+        assert_eq!(&gif[gce + 4..gce + 6], &[5, 0]);
+    }
+}
+
+//end of file
@@ -0,0 +1,57 @@
+/*
+ * halt.rs
+ * Enumerates the reasons a program stops running
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//usage statement
+use std::fmt;
+
+/// The reason a program stopped running
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Halt {
+    /// The program jumped to the jump instruction itself
+    /// at the given address, and so will never progress
+    SelfJump(u16),
+
+    /// The program executed the SUPER-CHIP `EXIT` instruction
+    Exit,
+
+    /// Execution reached a breakpoint at the given address
+    Breakpoint(u16),
+
+    /// The cycle limit of a run was reached
+    CycleLimit
+}
+
+//Display implementation
+impl fmt::Display for Halt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Halt::SelfJump(addr) => write!(f, "self-jump at 0x{:03X}", addr),
+            Halt::Exit => write!(f, "exit"),
+            Halt::Breakpoint(addr) => {
+                write!(f, "breakpoint at 0x{:03X}", addr)
+            },
+            Halt::CycleLimit => write!(f, "cycle limit")
+        }
+    }
+}
+
+//end of file
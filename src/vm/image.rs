@@ -0,0 +1,324 @@
+/*
+ * image.rs
+ * Defines a scaled, paletted rendering of a framebuffer
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//usage statements
+use std::fs;
+use std::io;
+use std::path::Path;
+use super::{Display, Palette};
+
+/// The largest block of uncompressed data in a deflate stream
+const STORED_BLOCK: usize = 65535;
+
+/// A framebuffer rendered for export, with each pixel scaled
+/// to a square block and mapped to a colour of a `Palette`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Image {
+    /// The width in pixels
+    width: usize,
+
+    /// The height in pixels
+    height: usize,
+
+    /// The palette index of each pixel, in rows from the top left
+    pixels: Vec<u8>,
+
+    /// The colours of the indices
+    palette: Palette
+}
+
+//implementation
+impl Image {
+    /// Constructs a new `Image` instance
+    ///
+    /// # Arguments
+    ///
+    /// * `display` - The framebuffer to render
+    /// * `scale` - The size of the block drawn for each pixel
+    /// * `new_palette` - The colours of the planes
+    ///
+    /// # Returns
+    ///
+    /// A new `Image` of the display
+    ///
+    /// # Panics
+    ///
+    /// Panics if `scale` is zero
+    pub fn new(display: &Display, scale: usize, 
+               new_palette: Palette) -> Image {
+        assert!(scale > 0, "image scale must be positive");
+        return Image::fit(display, display.width() * scale, 
+                          display.height() * scale, new_palette);
+    }
+
+    /// Constructs an `Image` of a given size, stretching the 
+    /// display to fill it. Recordings use this to keep a constant
+    /// size when a program switches between resolutions.
+    ///
+    /// # Arguments
+    ///
+    /// * `display` - The framebuffer to render
+    /// * `new_width` - The width of the image
+    /// * `new_height` - The height of the image
+    /// * `new_palette` - The colours of the planes
+    ///
+    /// # Returns
+    ///
+    /// A new `Image` of the display
+    ///
+    /// # Panics
+    ///
+    /// Panics if `new_width` or `new_height` is zero
+    pub fn fit(display: &Display, new_width: usize, new_height: usize,
+               new_palette: Palette) -> Image {
+        assert!(new_width > 0 && new_height > 0, 
+                "image size must be positive");
+        let mut pixels = Vec::with_capacity(new_width * new_height);
+        for y in 0..new_height {
+            let dy = y * display.height() / new_height;
+            for x in 0..new_width {
+                let dx = x * display.width() / new_width;
+                pixels.push(display.get_planes(dx, dy) & 3);
+            }
+        }
+        return Image {
+            width: new_width,
+            height: new_height,
+            pixels,
+            palette: new_palette
+        };
+    }
+
+    /// Gets the width of the image
+    ///
+    /// # Returns
+    ///
+    /// The width in pixels
+    pub fn get_width(&self) -> usize {
+        return self.width;
+    }
+
+    /// Gets the height of the image
+    ///
+    /// # Returns
+    ///
+    /// The height in pixels
+    pub fn get_height(&self) -> usize {
+        return self.height;
+    }
+
+    /// Gets the palette indices of the image
+    ///
+    /// # Returns
+    ///
+    /// The plane bitmask of each pixel, in rows from the top left
+    pub fn get_pixels(&self) -> &[u8] {
+        return &self.pixels;
+    }
+
+    /// Gets the colours of the image
+    ///
+    /// # Returns
+    ///
+    /// The palette
+    pub fn get_palette(&self) -> &Palette {
+        return &self.palette;
+    }
+
+    /// Encodes the image as a plain PBM bitmap, with every lit
+    /// pixel black whatever its planes. At scale 1 this matches
+    /// `Display::to_pbm`.
+    ///
+    /// # Returns
+    ///
+    /// The PBM text
+    pub fn to_pbm(&self) -> String {
+        let mut out = format!("P1\n{} {}\n", self.width, self.height);
+        for row in self.pixels.chunks(self.width) {
+            for part in row.chunks(64) {
+                for p in part {
+                    out.push(if *p != 0 { '1' } else { '0' });
+                }
+                out.push('\n');
+            }
+        }
+        return out;
+    }
+
+    /// Encodes the image as an indexed-colour PNG
+    ///
+    /// # Returns
+    ///
+    /// The bytes of the PNG file
+    pub fn to_png(&self) -> Vec<u8> {
+        //pack the rows at two bits per pixel
+        let stride = (self.width * 2).div_ceil(8);
+        let mut raw = Vec::with_capacity((stride + 1) * self.height);
+        for row in self.pixels.chunks(self.width) {
+            raw.push(0);
+            let start = raw.len();
+            raw.resize(start + stride, 0);
+            for (x, p) in row.iter().enumerate() {
+                raw[start + x / 4] |= p << (6 - 2 * (x % 4));
+            }
+        }
+
+        //and wrap them in chunks
+        let mut header = Vec::with_capacity(13);
+        header.extend_from_slice(&(self.width as u32).to_be_bytes());
+        header.extend_from_slice(&(self.height as u32).to_be_bytes());
+        header.extend_from_slice(&[2, 3, 0, 0, 0]);
+        let colours: Vec<u8> = self.palette.get_colours().iter()
+            .flat_map(|c| c.iter().cloned()).collect();
+        let mut out = vec![0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+        png_chunk(&mut out, b"IHDR", &header);
+        png_chunk(&mut out, b"PLTE", &colours);
+        png_chunk(&mut out, b"IDAT", &zlib_stored(&raw));
+        png_chunk(&mut out, b"IEND", &[]);
+        return out;
+    }
+
+    /// Writes the image to a PNG file
+    ///
+    /// # Argument
+    ///
+    /// * `path` - The path of the file
+    ///
+    /// # Returns
+    ///
+    /// `Ok` if the file was written, or the I/O error that prevented it
+    pub fn write_png<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        return fs::write(path, self.to_png());
+    }
+}
+
+/// Appends a PNG chunk with its length and checksum
+fn png_chunk(out: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    out.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    let start = out.len();
+    out.extend_from_slice(kind);
+    out.extend_from_slice(data);
+    let crc = crc32(&out[start..]);
+    out.extend_from_slice(&crc.to_be_bytes());
+}
+
+/// Computes the CRC-32 checksum used by PNG chunks
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xFFFF_FFFFu32;
+    for byte in data {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 { 
+                0xEDB8_8320 ^ (crc >> 1) 
+            } else { 
+                crc >> 1 
+            };
+        }
+    }
+    return !crc;
+}
+
+/// Wraps data in a zlib stream of uncompressed deflate blocks
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut out = vec![0x78, 0x01];
+    let mut blocks = data.chunks(STORED_BLOCK).peekable();
+    if blocks.peek().is_none() {
+        out.extend_from_slice(&[1, 0, 0, 0xFF, 0xFF]);
+    }
+    while let Some(block) = blocks.next() {
+        let last = blocks.peek().is_none();
+        let len = block.len() as u16;
+        out.push(if last { 1 } else { 0 });
+        out.extend_from_slice(&len.to_le_bytes());
+        out.extend_from_slice(&(!len).to_le_bytes());
+        out.extend_from_slice(block);
+    }
+    let (mut a, mut b) = (1u32, 0u32);
+    for byte in data {
+        a = (a + *byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    out.extend_from_slice(&((b << 16) | a).to_be_bytes());
+    return out;
+}
+
+//unit tests
+#[cfg(test)]
+mod tests {
+    //import the Image struct
+    use super::*;
+
+    //creates a display with pixels lit in both planes
+    fn display() -> Display {
+        let mut d = Display::new(false);
+        d.draw(0, 0, &[0xC0], 8, 1, false);
+        d.draw(1, 0, &[0xC0], 8, 2, false);
+        return d;
+    }
+
+    //this test checks scaling and stretching
+    #[test]
+    fn test_scale() {
+        let img = Image::new(&display(), 2, Palette::default());
+        assert_eq!((img.get_width(), img.get_height()), (128, 64));
+        assert_eq!(&img.get_pixels()[0..8], &[1, 1, 3, 3, 2, 2, 0, 0]);
+        assert_eq!(&img.get_pixels()[128..132], &[1, 1, 3, 3]);
+        let fit = Image::fit(&display(), 32, 16, Palette::default());
+        assert_eq!(&fit.get_pixels()[0..3], &[1, 2, 0]);
+    }
+
+    //this test checks that an empty image cannot be made
+    #[test]
+    #[should_panic]
+    fn test_fit_empty() {
+        Image::fit(&display(), 0, 16, Palette::default());
+    }
+
+    //this test checks PBM output
+    #[test]
+    fn test_pbm() {
+        let d = display();
+        assert_eq!(Image::new(&d, 1, Palette::default()).to_pbm(), 
+                   d.to_pbm());
+        let pbm = Image::new(&d, 3, Palette::default()).to_pbm();
+        assert!(pbm.starts_with("P1\n192 96\n111111111000"));
+    }
+
+    //this test checks the structure of PNG output
+    #[test]
+    fn test_png() {
+        let png = Image::new(&display(), 1, Palette::default()).to_png();
+        assert_eq!(&png[0..8], b"\x89PNG\r\n\x1a\n");
+        assert_eq!(&png[12..16], b"IHDR");
+        assert_eq!(&png[16..26], &[0, 0, 0, 64, 0, 0, 0, 32, 2, 3]);
+        assert_eq!(crc32(b"IEND"), 0xAE42_6082);
+        assert_eq!(&png[png.len() - 8..png.len() - 4], b"IEND");
+
+        //the first row starts with indices 1, 3, 2 and 0
+        let idat = &png[8 + 25 + 24..];
+        assert_eq!(&idat[4..8], b"IDAT");
+        assert_eq!(&idat[8..10], &[0x78, 0x01]);
+        assert_eq!(&idat[15..17], &[0, 0b0111_1000]);
+    }
+}
+
+//end of file
@@ -0,0 +1,103 @@
+/*
+ * key_event.rs
+ * Defines a struct that represents a timed key press or release
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//usage statement
+use std::fmt;
+
+/// A key being pressed or released at a given cycle
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KeyEvent {
+    /// The cycle count at which the event happens
+    cycle: u64,
+
+    /// The key, from `0x0` to `0xF`
+    key: u8,
+
+    /// Whether the key is pressed rather than released
+    down: bool
+}
+
+//implementation
+impl KeyEvent {
+    /// Constructs a new `KeyEvent` instance
+    ///
+    /// # Arguments
+    ///
+    /// * `new_cycle` - The cycle count at which the event happens
+    /// * `new_key` - The key, from `0x0` to `0xF`
+    /// * `new_down` - Whether the key is pressed rather than released
+    ///
+    /// # Returns
+    ///
+    /// A new `KeyEvent` instance with the given properties
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `new_key` is greater than `0xF`.
+    pub fn new(new_cycle: u64, new_key: u8, new_down: bool) -> KeyEvent {
+        if new_key > 0xF {
+            panic!("Key {} is not on the keypad", new_key);
+        }
+        return KeyEvent {
+            cycle: new_cycle,
+            key: new_key,
+            down: new_down
+        };
+    }
+
+    /// Gets the cycle count at which the event happens
+    ///
+    /// # Returns
+    ///
+    /// The cycle of the event
+    pub fn get_cycle(&self) -> u64 {
+        return self.cycle;
+    }
+
+    /// Gets the key of the event
+    ///
+    /// # Returns
+    ///
+    /// The key, from `0x0` to `0xF`
+    pub fn get_key(&self) -> u8 {
+        return self.key;
+    }
+
+    /// Gets whether the key is pressed
+    ///
+    /// # Returns
+    ///
+    /// `true` for a press, `false` for a release
+    pub fn is_down(&self) -> bool {
+        return self.down;
+    }
+}
+
+//Display implementation
+impl fmt::Display for KeyEvent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {} {:X}", self.cycle, 
+               if self.down { "down" } else { "up" }, self.key)
+    }
+}
+
+//end of file
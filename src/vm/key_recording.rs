@@ -0,0 +1,255 @@
+/*
+ * key_recording.rs
+ * Records the keypad state of each frame for replay
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//usage statements
+use super::super::error::{DecodeError, DecodeErrorType};
+
+/// The magic number that starts an encoded recording
+const MAGIC: &[u8; 4] = b"CH8K";
+
+/// The version of the recording format
+const VERSION: u8 = 1;
+
+/// The most frames a recording may hold, over three days at 60 Hz
+const MAX_FRAMES: u32 = 1 << 24;
+
+/// The keypad state of each frame of a session, as bitmasks with
+/// bit `k` set while key `k` is held.
+///
+/// The binary format is the magic `CH8K`, a version byte and the
+/// number of frames as a big-endian 32-bit number, then runs of
+/// identical frames: the length of the run as a LEB128 number
+/// followed by the keypad bitmask as a big-endian word. Recordings
+/// hold at most 2^24 frames, and decoding rejects any with more.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct KeyRecording {
+    /// The keypad state of each frame
+    frames: Vec<u16>
+}
+
+//implementation
+impl KeyRecording {
+    /// Constructs a new `KeyRecording` instance
+    ///
+    /// # Returns
+    ///
+    /// A new recording with no frames
+    pub fn new() -> KeyRecording {
+        return KeyRecording {
+            frames: Vec::new()
+        };
+    }
+
+    /// Adds the keypad state of the next frame. Frames past the
+    /// 2^24th are dropped, so that every recording can be decoded.
+    ///
+    /// # Argument
+    ///
+    /// * `keypad` - The bitmask of held keys
+    pub fn add(&mut self, keypad: u16) {
+        if self.frames.len() < MAX_FRAMES as usize {
+            self.frames.push(keypad);
+        }
+    }
+
+    /// Gets the keypad state of a frame
+    ///
+    /// # Argument
+    ///
+    /// * `frame` - The frame number, from zero
+    ///
+    /// # Returns
+    ///
+    /// The bitmask of held keys, or `None` past the end
+    pub fn get(&self, frame: usize) -> Option<u16> {
+        return self.frames.get(frame).copied();
+    }
+
+    /// Gets the keypad states of every frame
+    ///
+    /// # Returns
+    ///
+    /// The bitmasks of held keys, in frame order
+    pub fn get_frames(&self) -> &[u16] {
+        return self.frames.as_slice();
+    }
+
+    /// Encodes the recording in the binary format
+    ///
+    /// # Returns
+    ///
+    /// The encoded recording
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = MAGIC.to_vec();
+        out.push(VERSION);
+        out.extend_from_slice(&(self.frames.len() as u32).to_be_bytes());
+        let mut k = 0;
+        while k < self.frames.len() {
+            let mask = self.frames[k];
+            let mut run = self.frames[k..].iter()
+                .take_while(|f| **f == mask).count();
+            k += run;
+            loop {
+                let byte = (run & 0x7F) as u8;
+                run >>= 7;
+                if run == 0 {
+                    out.push(byte);
+                    break;
+                }
+                out.push(byte | 0x80);
+            }
+            out.extend_from_slice(&mask.to_be_bytes());
+        }
+        return out;
+    }
+
+    /// Decodes a recording from the binary format
+    ///
+    /// # Argument
+    ///
+    /// * `data` - The encoded recording
+    ///
+    /// # Returns
+    ///
+    /// The recording, or a `DecodeError` describing why it is malformed
+    pub fn from_bytes(data: &[u8]) -> Result<KeyRecording, DecodeError> {
+        if data.len() < 5 || &data[..4] != MAGIC {
+            return Err(DecodeError::new(DecodeErrorType::BadMagic, 0));
+        }
+        if data[4] != VERSION {
+            return Err(DecodeError::new(
+                    DecodeErrorType::UnsupportedVersion(data[4]), 4));
+        }
+        if data.len() < 9 {
+            return Err(DecodeError::new(DecodeErrorType::Truncated, 
+                                        data.len()));
+        }
+        let count = u32::from_be_bytes([data[5], data[6], data[7], data[8]]);
+        if count > MAX_FRAMES {
+            return Err(DecodeError::new(DecodeErrorType::InvalidValue(
+                        String::from("frame count")), 5));
+        }
+
+        //read and check the runs before expanding them
+        let mut runs = Vec::new();
+        let mut total = 0usize;
+        let mut pos = 9;
+        while pos < data.len() {
+            let start = pos;
+            let mut run = 0usize;
+            let mut shift = 0;
+            loop {
+                let byte = *data.get(pos).ok_or_else(|| {
+                    return DecodeError::new(DecodeErrorType::Truncated, pos);
+                })?;
+                pos += 1;
+                if shift > 28 {
+                    return Err(DecodeError::new(DecodeErrorType::InvalidValue(
+                                String::from("run length")), start));
+                }
+                run |= ((byte & 0x7F) as usize) << shift;
+                shift += 7;
+                if byte & 0x80 == 0 {
+                    break;
+                }
+            }
+            if pos + 2 > data.len() {
+                return Err(DecodeError::new(DecodeErrorType::Truncated,
+                                            data.len()));
+            }
+            if total + run > count as usize {
+                return Err(DecodeError::new(DecodeErrorType::InvalidValue(
+                            String::from("run length")), start));
+            }
+            total += run;
+            runs.push((run, u16::from_be_bytes([data[pos], data[pos + 1]])));
+            pos += 2;
+        }
+        if total != count as usize {
+            return Err(DecodeError::new(DecodeErrorType::Truncated, 
+                                        data.len()));
+        }
+        let mut recording = KeyRecording::new();
+        recording.frames.reserve(total);
+        for (run, mask) in runs {
+            recording.frames.resize(recording.frames.len() + run, mask);
+        }
+        return Ok(recording);
+    }
+}
+
+//unit tests
+#[cfg(test)]
+mod tests {
+    //import the KeyRecording struct
+    use super::*;
+
+    //this test checks that recordings round-trip compactly
+    #[test]
+    fn test_round_trip() {
+        let mut rec = KeyRecording::new();
+        for _ in 0..200 {
+            rec.add(0);
+        }
+        rec.add(0x0010);
+        rec.add(0x0010);
+        rec.add(0);
+        let data = rec.to_bytes();
+        assert_eq!(&data[..], b"CH8K\x01\x00\x00\x00\xCB\xC8\x01\x00\x00\
+                                \x02\x00\x10\x01\x00\x00");
+        assert_eq!(KeyRecording::from_bytes(&data).unwrap(), rec);
+        assert_eq!(rec.get(200), Some(0x0010));
+        assert_eq!(rec.get(203), None);
+        let err = KeyRecording::from_bytes(&data[..data.len() - 3])
+            .unwrap_err();
+        assert_eq!(err.get_type(), DecodeErrorType::Truncated);
+        assert!(KeyRecording::from_bytes(b"CH8S\x01").is_err());
+    }
+
+    //this test checks that huge frame counts and runs are rejected
+    //without allocating them
+    #[test]
+    fn test_huge_counts() {
+        let err = KeyRecording::from_bytes(b"CH8K\x01\xFF\xFF\xFF\xFF\
+                                             \xFF\xFF\xFF\xFF\x0F\x00\x00")
+            .unwrap_err();
+        assert_eq!(err.get_offset(), 5);
+        let err = KeyRecording::from_bytes(b"CH8K\x01\x01\x00\x00\x00\
+                                             \xFF\xFF\xFF\x7F\x00\x00")
+            .unwrap_err();
+        assert_eq!(err.get_type(), DecodeErrorType::InvalidValue(
+                String::from("run length")));
+    }
+
+    //this test checks that the longest recording round-trips
+    #[test]
+    fn test_longest_recording() {
+        let mut rec = KeyRecording::new();
+        for _ in 0..=MAX_FRAMES {
+            rec.add(0x0001);
+        }
+        assert_eq!(rec.get_frames().len(), MAX_FRAMES as usize);
+        assert_eq!(KeyRecording::from_bytes(&rec.to_bytes()).unwrap(), rec);
+    }
+}
+
+//end of file
@@ -0,0 +1,166 @@
+/*
+ * key_script.rs
+ * Defines a scripted sequence of key presses
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//usage statements
+use std::fmt;
+use super::KeyEvent;
+use super::super::error::{CompileError, CompileErrorType};
+
+/// A sequence of key events ordered by cycle. In text form each 
+/// line holds one event, `<cycle> down|up <key>`, with the key
+/// as a hex digit; `#` starts a comment.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct KeyScript {
+    /// The events, ordered by cycle
+    events: Vec<KeyEvent>
+}
+
+//implementation
+impl KeyScript {
+    /// Constructs a new, empty `KeyScript` instance
+    ///
+    /// # Returns
+    ///
+    /// A new `KeyScript` with no events
+    pub fn new() -> KeyScript {
+        return KeyScript {
+            events: Vec::new()
+        };
+    }
+
+    /// Parses a key script from text
+    ///
+    /// # Argument
+    ///
+    /// * `text` - The script
+    ///
+    /// # Returns
+    ///
+    /// The parsed `KeyScript`, or a `CompileError` 
+    /// locating the first malformed line
+    pub fn parse(text: &str) -> Result<KeyScript, CompileError> {
+        let mut script = KeyScript::new();
+        for (idx, line) in text.lines().enumerate() {
+            let content = line.split('#').next().unwrap();
+            let words: Vec<&str> = content.split_whitespace().collect();
+            if words.is_empty() {
+                continue;
+            }
+
+            //locate errors at the offending word
+            let err = |w: usize| {
+                let col = match words.get(w) {
+                    Some(word) => word.as_ptr() as usize - 
+                                  line.as_ptr() as usize + 1,
+                    None => content.trim_end().len() + 1
+                };
+                let err_type = match words.get(w) {
+                    Some(word) => {
+                        CompileErrorType::UnexpectedToken(String::from(*word))
+                    },
+                    None => CompileErrorType::UnexpectedEnd
+                };
+                return CompileError::new(err_type, idx + 1, col);
+            };
+            let cycle = words[0].parse::<u64>().map_err(|_| err(0))?;
+            let down = match words.get(1) {
+                Some(&"down") => true,
+                Some(&"up") => false,
+                _ => return Err(err(1))
+            };
+            let key = match words.get(2) {
+                Some(k) if k.len() == 1 => {
+                    u8::from_str_radix(k, 16).map_err(|_| err(2))?
+                },
+                _ => return Err(err(2))
+            };
+            if words.len() > 3 {
+                return Err(err(3));
+            }
+            script.add(KeyEvent::new(cycle, key, down));
+        }
+        return Ok(script);
+    }
+
+    /// Adds an event to the script, keeping it ordered by cycle
+    ///
+    /// # Argument
+    ///
+    /// * `event` - The event to add
+    pub fn add(&mut self, event: KeyEvent) {
+        let pos = self.events.iter()
+                      .position(|e| e.get_cycle() > event.get_cycle())
+                      .unwrap_or(self.events.len());
+        self.events.insert(pos, event);
+    }
+
+    /// Gets the events of the script
+    ///
+    /// # Returns
+    ///
+    /// The events, ordered by cycle
+    pub fn get_events(&self) -> &[KeyEvent] {
+        return self.events.as_slice();
+    }
+}
+
+//Display implementation
+impl fmt::Display for KeyScript {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for e in self.events.iter() {
+            writeln!(f, "{}", e)?;
+        }
+        return Ok(());
+    }
+}
+
+//unit tests
+#[cfg(test)]
+mod tests {
+    //import the KeyScript struct
+    use super::*;
+
+    //this test checks parsing and formatting scripts
+    #[test]
+    fn test_parse() {
+        let script = KeyScript::parse("# fire\n100 down 5\n\n 120 up 5 \
+                                       # release\n50 down a\n").unwrap();
+        assert_eq!(script.get_events(), &[KeyEvent::new(50, 0xA, true),
+                                          KeyEvent::new(100, 5, true),
+                                          KeyEvent::new(120, 5, false)]);
+        assert_eq!(script.to_string(), "50 down A\n100 down 5\n120 up 5\n");
+    }
+
+    //this test checks parse errors
+    #[test]
+    fn test_parse_errors() {
+        let err = KeyScript::parse("1 down 2\n  3 held 4").unwrap_err();
+        assert_eq!((err.get_line(), err.get_col()), (2, 5));
+        let err = KeyScript::parse("1 up 10").unwrap_err();
+        assert_eq!(err.get_type(), 
+                   CompileErrorType::UnexpectedToken(String::from("10")));
+        let err = KeyScript::parse("7 up").unwrap_err();
+        assert_eq!(err.get_type(), CompileErrorType::UnexpectedEnd);
+    }
+}
+
+//end of file
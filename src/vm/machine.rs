@@ -0,0 +1,869 @@
+/*
+ * machine.rs
+ * Defines the Chip-8 interpreter
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//usage statements
+use super::{Display, Halt, Quirks, RandomSource, Step, Xorshift};
+use super::font::{LARGE_FONT, LARGE_FONT_ADDR, SMALL_FONT, SMALL_FONT_ADDR};
+use super::super::codegen::PROGRAM_START;
+use super::super::error::{VmError, VmErrorType};
+
+/// The size of the interpreter's memory (in bytes)
+pub const MEMORY_SIZE: usize = 0x10000;
+
+/// The number of return addresses the stack can hold
+pub const STACK_DEPTH: usize = 16;
+
+/// The XO-CHIP pitch register of a new machine, which plays
+/// audio patterns at 4000 bits per second
+pub const DEFAULT_PITCH: u8 = 64;
+
+/// The state of a machine apart from its memory, used
+/// to rewind execution
+#[derive(Debug, Clone)]
+pub(crate) struct CpuState {
+    v: [u8; 16],
+    i: u16,
+    pc: u16,
+    stack: Vec<u16>,
+    delay: u8,
+    sound: u8,
+    display: Display,
+    keys: [bool; 16],
+    waiting: Option<u8>,
+    flags: [u8; 16],
+    rng: Box<dyn RandomSource>,
+    pattern: Option<[u8; 16]>,
+    pitch: u8,
+    cycles: u64
+}
+
+/// A Chip-8 interpreter that executes raw opcodes, including
+/// the SUPER-CHIP extensions and the XO-CHIP audio instructions
+#[derive(Debug, Clone)]
+pub struct Machine {
+    /// The memory, with the fonts at the bottom
+    pub(super) memory: Vec<u8>,
+
+    /// The `V0` through `VF` registers
+    pub(super) v: [u8; 16],
+
+    /// The `I` register
+    pub(super) i: u16,
+
+    /// The address of the next instruction
+    pub(super) pc: u16,
+
+    /// The return addresses of active subroutine calls
+    pub(super) stack: Vec<u16>,
+
+    /// The delay timer
+    pub(super) delay: u8,
+
+    /// The sound timer
+    pub(super) sound: u8,
+
+    /// The framebuffer
+    pub(super) display: Display,
+
+    /// Which keys are held down
+    pub(super) keys: [bool; 16],
+
+    /// The register waiting for a key, while a `KEY`
+    /// instruction is blocked
+    pub(super) waiting: Option<u8>,
+
+    /// The SUPER-CHIP persistent flag registers
+    pub(super) flags: [u8; 16],
+
+    /// The source of random bytes
+    pub(super) rng: Box<dyn RandomSource>,
+
+    /// The XO-CHIP audio pattern buffer, once a program loads one
+    pub(super) pattern: Option<[u8; 16]>,
+
+    /// The XO-CHIP pitch register
+    pub(super) pitch: u8,
+
+    /// The behaviours of the interpreter
+    pub(super) quirks: Quirks,
+
+    /// The number of steps executed
+    pub(super) cycles: u64
+}
+
+//implementation
+impl Machine {
+    /// Constructs a new `Machine` instance
+    ///
+    /// # Argument
+    ///
+    /// * `new_quirks` - The behaviours of the interpreter
+    ///
+    /// # Returns
+    ///
+    /// A new `Machine` with empty memory apart from the fonts,
+    /// ready to run from `PROGRAM_START`
+    pub fn new(new_quirks: Quirks) -> Machine {
+        let mut memory = vec![0; MEMORY_SIZE];
+        let small = SMALL_FONT_ADDR as usize;
+        memory[small..small + SMALL_FONT.len()].copy_from_slice(&SMALL_FONT);
+        let large = LARGE_FONT_ADDR as usize;
+        memory[large..large + LARGE_FONT.len()].copy_from_slice(&LARGE_FONT);
+        return Machine {
+            memory,
+            v: [0; 16],
+            i: 0,
+            pc: PROGRAM_START,
+            stack: Vec::new(),
+            delay: 0,
+            sound: 0,
+            display: Display::new(false),
+            keys: [false; 16],
+            waiting: None,
+            flags: [0; 16],
+            rng: Box::new(Xorshift::default()),
+            pattern: None,
+            pitch: DEFAULT_PITCH,
+            quirks: new_quirks,
+            cycles: 0
+        };
+    }
+
+    /// Loads a program into memory at `PROGRAM_START`
+    ///
+    /// # Argument
+    ///
+    /// * `rom` - The bytes of the program
+    ///
+    /// # Returns
+    ///
+    /// `Ok` if the program was loaded, or a `VmError`
+    /// if it does not fit in memory
+    pub fn load(&mut self, rom: &[u8]) -> Result<(), VmError> {
+        let start = PROGRAM_START as usize;
+        if rom.len() > MEMORY_SIZE - start {
+            return Err(VmError::new(VmErrorType::RomTooLarge(rom.len()),
+                                    self.pc));
+        }
+        self.memory[start..start + rom.len()].copy_from_slice(rom);
+        return Ok(());
+    }
+
+    /// Replaces the random source with an `Xorshift` generator
+    ///
+    /// # Argument
+    ///
+    /// * `seed` - The seed, with `0` selecting the default seed
+    pub fn set_seed(&mut self, seed: u64) {
+        self.rng = Box::new(Xorshift::new(seed));
+    }
+
+    /// Replaces the random source
+    ///
+    /// # Argument
+    ///
+    /// * `source` - The new source of random bytes
+    pub fn set_random(&mut self, source: Box<dyn RandomSource>) {
+        self.rng = source;
+    }
+
+    /// Gets the random source
+    ///
+    /// # Returns
+    ///
+    /// The source of random bytes
+    pub fn get_random(&self) -> &dyn RandomSource {
+        return self.rng.as_ref();
+    }
+
+    /// Gets the address of the next instruction
+    ///
+    /// # Returns
+    ///
+    /// The program counter
+    pub fn get_pc(&self) -> u16 {
+        return self.pc;
+    }
+
+    /// Gets the `I` register
+    ///
+    /// # Returns
+    ///
+    /// The value of `I`
+    pub fn get_i(&self) -> u16 {
+        return self.i;
+    }
+
+    /// Gets a `V` register
+    ///
+    /// # Argument
+    ///
+    /// * `idx` - The number of the register, from `0x0` to `0xF`
+    ///
+    /// # Returns
+    ///
+    /// The value of the register
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `idx` is greater than `0xF`.
+    pub fn get_v(&self, idx: usize) -> u8 {
+        return self.v[idx];
+    }
+
+    /// Gets the return addresses on the stack
+    ///
+    /// # Returns
+    ///
+    /// The stack, from the bottom
+    pub fn get_stack(&self) -> &[u16] {
+        return self.stack.as_slice();
+    }
+
+    /// Gets the delay timer
+    ///
+    /// # Returns
+    ///
+    /// The value of the delay timer
+    pub fn get_delay(&self) -> u8 {
+        return self.delay;
+    }
+
+    /// Gets the sound timer
+    ///
+    /// # Returns
+    ///
+    /// The value of the sound timer
+    pub fn get_sound(&self) -> u8 {
+        return self.sound;
+    }
+
+    /// Gets the XO-CHIP audio pattern buffer
+    ///
+    /// # Returns
+    ///
+    /// The 128 1-bit samples loaded by `F002`, or `None` if the
+    /// program has not loaded a pattern
+    pub fn get_pattern(&self) -> Option<[u8; 16]> {
+        return self.pattern;
+    }
+
+    /// Gets the XO-CHIP pitch register
+    ///
+    /// # Returns
+    ///
+    /// The pitch set by `FX3A`
+    pub fn get_pitch(&self) -> u8 {
+        return self.pitch;
+    }
+
+    /// Gets the number of steps executed
+    ///
+    /// # Returns
+    ///
+    /// The cycle count
+    pub fn get_cycles(&self) -> u64 {
+        return self.cycles;
+    }
+
+    /// Gets the memory
+    ///
+    /// # Returns
+    ///
+    /// All `MEMORY_SIZE` bytes of memory
+    pub fn get_memory(&self) -> &[u8] {
+        return self.memory.as_slice();
+    }
+
+    /// Gets the framebuffer
+    ///
+    /// # Returns
+    ///
+    /// The display
+    pub fn get_display(&self) -> &Display {
+        return &self.display;
+    }
+
+    /// Gets the behaviours of the interpreter
+    ///
+    /// # Returns
+    ///
+    /// The quirks
+    pub fn get_quirks(&self) -> Quirks {
+        return self.quirks;
+    }
+
+    /// Gets the opcode of the next instruction
+    ///
+    /// # Returns
+    ///
+    /// The big-endian word at the program counter
+    pub fn get_opcode(&self) -> u16 {
+        return self.read_word(self.pc);
+    }
+
+    /// Gets whether the program is blocked waiting for a key
+    ///
+    /// # Returns
+    ///
+    /// `true` while a `KEY` instruction is waiting
+    pub fn is_waiting(&self) -> bool {
+        return self.waiting.is_some();
+    }
+
+    /// Gets whether a key is held down
+    ///
+    /// # Argument
+    ///
+    /// * `key` - The key, from `0x0` to `0xF`
+    ///
+    /// # Returns
+    ///
+    /// `true` if the key is down
+    pub fn is_key_down(&self, key: u8) -> bool {
+        return self.keys[(key & 0xF) as usize];
+    }
+
+    /// Presses or releases a key. Releasing a key
+    /// completes a waiting `KEY` instruction.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key, from `0x0` to `0xF`
+    /// * `down` - Whether the key is pressed
+    pub fn set_key(&mut self, key: u8, down: bool) {
+        let key = key & 0xF;
+        if !down && self.keys[key as usize] {
+            if let Some(x) = self.waiting.take() {
+                self.v[x as usize] = key;
+            }
+        }
+        self.keys[key as usize] = down;
+    }
+
+    /// Gets the state of the whole keypad
+    ///
+    /// # Returns
+    ///
+    /// A bitmask with bit `k` set while key `k` is held
+    pub fn get_keypad(&self) -> u16 {
+        return (0..16).filter(|k| self.keys[*k])
+            .fold(0, |mask, k| mask | 1 << k);
+    }
+
+    /// Sets the state of the whole keypad, pressing and releasing
+    /// keys as `set_key` does, in order from key `0x0`
+    ///
+    /// # Argument
+    ///
+    /// * `mask` - A bitmask with bit `k` set if key `k` is held
+    pub fn set_keypad(&mut self, mask: u16) {
+        for k in 0..16 {
+            self.set_key(k, mask & (1 << k) != 0);
+        }
+    }
+
+    /// Sets the address of the next instruction
+    ///
+    /// # Argument
+    ///
+    /// * `new_pc` - The new program counter
+    pub fn set_pc(&mut self, new_pc: u16) {
+        self.pc = new_pc;
+    }
+
+    /// Sets the `I` register
+    ///
+    /// # Argument
+    ///
+    /// * `new_i` - The new value of `I`
+    pub fn set_i(&mut self, new_i: u16) {
+        self.i = new_i;
+    }
+
+    /// Sets a `V` register
+    ///
+    /// # Arguments
+    ///
+    /// * `idx` - The number of the register, from `0x0` to `0xF`
+    /// * `value` - The new value of the register
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `idx` is greater than `0xF`.
+    pub fn set_v(&mut self, idx: usize, value: u8) {
+        self.v[idx] = value;
+    }
+
+    /// Sets the delay timer
+    ///
+    /// # Argument
+    ///
+    /// * `new_delay` - The new value of the delay timer
+    pub fn set_delay(&mut self, new_delay: u8) {
+        self.delay = new_delay;
+    }
+
+    /// Sets the sound timer
+    ///
+    /// # Argument
+    ///
+    /// * `new_sound` - The new value of the sound timer
+    pub fn set_sound(&mut self, new_sound: u8) {
+        self.sound = new_sound;
+    }
+
+    /// Writes bytes into memory, wrapping at the end of memory
+    ///
+    /// # Arguments
+    ///
+    /// * `addr` - The address of the first byte
+    /// * `bytes` - The bytes to write
+    pub fn write_memory(&mut self, addr: u16, bytes: &[u8]) {
+        for (k, b) in bytes.iter().enumerate() {
+            self.memory[addr.wrapping_add(k as u16) as usize] = *b;
+        }
+    }
+
+    /// Gets the memory an instruction at the program counter
+    /// would write to if executed
+    ///
+    /// # Returns
+    ///
+    /// The address and length of the range written by a 
+    /// pending `BCD` or `RDP` instruction, or `None`
+    pub fn pending_write(&self) -> Option<(u16, u16)> {
+        if self.waiting.is_some() {
+            return None;
+        }
+        let op = self.read_word(self.pc);
+        return match op & 0xF0FF {
+            0xF033 => Some((self.i, 3)),
+            0xF055 => Some((self.i, ((op >> 8) & 0xF) + 1)),
+            _ => None
+        };
+    }
+
+    /// Captures the state of the machine apart from its memory
+    pub(crate) fn cpu_state(&self) -> CpuState {
+        return CpuState {
+            v: self.v,
+            i: self.i,
+            pc: self.pc,
+            stack: self.stack.clone(),
+            delay: self.delay,
+            sound: self.sound,
+            display: self.display.clone(),
+            keys: self.keys,
+            waiting: self.waiting,
+            flags: self.flags,
+            rng: self.rng.clone(),
+            pattern: self.pattern,
+            pitch: self.pitch,
+            cycles: self.cycles
+        };
+    }
+
+    /// Restores state captured by `cpu_state`
+    pub(crate) fn restore_cpu_state(&mut self, state: CpuState) {
+        self.v = state.v;
+        self.i = state.i;
+        self.pc = state.pc;
+        self.stack = state.stack;
+        self.delay = state.delay;
+        self.sound = state.sound;
+        self.display = state.display;
+        self.keys = state.keys;
+        self.waiting = state.waiting;
+        self.flags = state.flags;
+        self.rng = state.rng;
+        self.pattern = state.pattern;
+        self.pitch = state.pitch;
+        self.cycles = state.cycles;
+    }
+
+    /// Decrements the delay and sound timers, as happens at 60 Hz
+    pub fn tick_timers(&mut self) {
+        self.delay = self.delay.saturating_sub(1);
+        self.sound = self.sound.saturating_sub(1);
+    }
+
+    /// Reads a big-endian word from memory
+    fn read_word(&self, addr: u16) -> u16 {
+        let hi = self.memory[addr as usize] as u16;
+        let lo = self.memory[addr.wrapping_add(1) as usize] as u16;
+        return hi << 8 | lo;
+    }
+
+    /// Gets the next byte from the random number generator
+    fn next_random(&mut self) -> u8 {
+        return self.rng.next_byte();
+    }
+
+    /// Executes one instruction
+    ///
+    /// # Returns
+    ///
+    /// The outcome of the step, or a `VmError` if the
+    /// instruction could not be executed
+    pub fn step(&mut self) -> Result<Step, VmError> {
+        self.cycles += 1;
+        if self.waiting.is_some() {
+            return Ok(Step::Waiting);
+        }
+        let pc = self.pc;
+        let op = self.read_word(pc);
+        self.pc = pc.wrapping_add(2);
+        let result = self.execute(op, pc);
+        if result.is_err() {
+            self.pc = pc;
+        }
+        return result;
+    }
+
+    /// Executes an opcode fetched from an address
+    fn execute(&mut self, op: u16, pc: u16) -> Result<Step, VmError> {
+        let x = ((op >> 8) & 0xF) as usize;
+        let y = ((op >> 4) & 0xF) as usize;
+        let n = (op & 0xF) as u8;
+        let nn = (op & 0xFF) as u8;
+        let nnn = op & 0xFFF;
+        let invalid = Err(VmError::new(VmErrorType::InvalidOpcode(op), pc));
+        match op >> 12 {
+            0x0 => match op {
+                0x00E0 => self.display.clear(0xFF),
+                0x00EE => {
+                    self.pc = match self.stack.pop() {
+                        Some(addr) => addr,
+                        None => return Err(VmError::new(
+                                VmErrorType::StackUnderflow, pc))
+                    };
+                },
+                0x00FB => self.display.scroll_right(4, 0xFF),
+                0x00FC => self.display.scroll_left(4, 0xFF),
+                0x00FD => {
+                    self.pc = pc;
+                    return Ok(Step::Halted(Halt::Exit));
+                },
+                0x00FE => self.display.set_hires(false),
+                0x00FF => self.display.set_hires(true),
+                _ if op & 0xFFF0 == 0x00C0 => {
+                    self.display.scroll_down(n as usize, 0xFF);
+                },
+                _ => return invalid
+            },
+            0x1 => {
+                self.pc = nnn;
+                if nnn == pc {
+                    return Ok(Step::Halted(Halt::SelfJump(pc)));
+                }
+            },
+            0x2 => {
+                if self.stack.len() == STACK_DEPTH {
+                    return Err(VmError::new(VmErrorType::StackOverflow, pc));
+                }
+                self.stack.push(self.pc);
+                self.pc = nnn;
+            },
+            0x3 => self.skip_if(self.v[x] == nn),
+            0x4 => self.skip_if(self.v[x] != nn),
+            0x5 if n == 0 => self.skip_if(self.v[x] == self.v[y]),
+            0x6 => self.v[x] = nn,
+            0x7 => self.v[x] = self.v[x].wrapping_add(nn),
+            0x8 => {
+                let (vx, vy) = (self.v[x], self.v[y]);
+                match n {
+                    0x0 => self.v[x] = vy,
+                    0x1..=0x3 => {
+                        self.v[x] = match n {
+                            0x1 => vx | vy,
+                            0x2 => vx & vy,
+                            _ => vx ^ vy
+                        };
+                        if self.quirks.vf_reset {
+                            self.v[0xF] = 0;
+                        }
+                    },
+                    0x4 => {
+                        let (sum, carry) = vx.overflowing_add(vy);
+                        self.v[x] = sum;
+                        self.v[0xF] = carry as u8;
+                    },
+                    0x5 => {
+                        self.v[x] = vx.wrapping_sub(vy);
+                        self.v[0xF] = (vx >= vy) as u8;
+                    },
+                    0x7 => {
+                        self.v[x] = vy.wrapping_sub(vx);
+                        self.v[0xF] = (vy >= vx) as u8;
+                    },
+                    0x6 | 0xE => {
+                        let src = if self.quirks.shift_vy { vy } else { vx };
+                        if n == 0x6 {
+                            self.v[x] = src >> 1;
+                            self.v[0xF] = src & 1;
+                        } else {
+                            self.v[x] = src << 1;
+                            self.v[0xF] = src >> 7;
+                        }
+                    },
+                    _ => return invalid
+                };
+            },
+            0x9 if n == 0 => self.skip_if(self.v[x] != self.v[y]),
+            0xA => self.i = nnn,
+            0xB => {
+                let offset = if self.quirks.jump_vx { self.v[x] } else { 
+                    self.v[0] 
+                };
+                self.pc = nnn + offset as u16;
+            },
+            0xC => self.v[x] = self.next_random() & nn,
+            0xD => {
+                let (width, height) = match n {
+                    0 if self.quirks.large_sprites => (16, 16),
+                    _ => (8, n as usize)
+                };
+                let mut rows = Vec::with_capacity(height);
+                for r in 0..height {
+                    let addr = self.i.wrapping_add((r * width / 8) as u16);
+                    rows.push(if width == 16 {
+                        self.read_word(addr)
+                    } else {
+                        self.memory[addr as usize] as u16
+                    });
+                }
+                let hit = self.display.draw(self.v[x] as usize, 
+                                            self.v[y] as usize, &rows, 
+                                            width, 1, self.quirks.clip);
+                self.v[0xF] = hit as u8;
+            },
+            0xE => match nn {
+                0x9E => self.skip_if(self.keys[(self.v[x] & 0xF) as usize]),
+                0xA1 => self.skip_if(!self.keys[(self.v[x] & 0xF) as usize]),
+                _ => return invalid
+            },
+            0xF => match nn {
+                0x02 if x == 0 => {
+                    let mut pattern = [0u8; 16];
+                    for (k, b) in pattern.iter_mut().enumerate() {
+                        *b = self.memory[self.i.wrapping_add(k as u16) 
+                                         as usize];
+                    }
+                    self.pattern = Some(pattern);
+                },
+                0x07 => self.v[x] = self.delay,
+                0x0A => self.waiting = Some(x as u8),
+                0x15 => self.delay = self.v[x],
+                0x18 => self.sound = self.v[x],
+                0x1E => self.i = self.i.wrapping_add(self.v[x] as u16),
+                0x29 => {
+                    self.i = SMALL_FONT_ADDR + (self.v[x] & 0xF) as u16 * 5;
+                },
+                0x30 => {
+                    self.i = LARGE_FONT_ADDR + (self.v[x] & 0xF) as u16 * 10;
+                },
+                0x3A => self.pitch = self.v[x],
+                0x33 => {
+                    let vx = self.v[x];
+                    for (k, digit) in [vx / 100, vx / 10 % 10, vx % 10]
+                                      .iter().enumerate() {
+                        let addr = self.i.wrapping_add(k as u16);
+                        self.memory[addr as usize] = *digit;
+                    }
+                },
+                0x55 | 0x65 => {
+                    for r in 0..=x {
+                        let addr = self.i.wrapping_add(r as u16) as usize;
+                        if nn == 0x55 {
+                            self.memory[addr] = self.v[r];
+                        } else {
+                            self.v[r] = self.memory[addr];
+                        }
+                    }
+                    if self.quirks.load_store_inc_i {
+                        self.i = self.i.wrapping_add(x as u16 + 1);
+                    }
+                },
+                0x75 => self.flags[..=x].copy_from_slice(&self.v[..=x]),
+                0x85 => self.v[..=x].copy_from_slice(&self.flags[..=x]),
+                _ => return invalid
+            },
+            _ => return invalid
+        };
+        return Ok(Step::Ran);
+    }
+
+    /// Skips the next instruction if a condition holds
+    fn skip_if(&mut self, cond: bool) {
+        if cond {
+            self.pc = self.pc.wrapping_add(2);
+        }
+    }
+}
+
+//PartialEq implementation
+impl PartialEq for Machine {
+    fn eq(&self, other: &Machine) -> bool {
+        return self.memory == other.memory && self.v == other.v &&
+            self.i == other.i && self.pc == other.pc && 
+            self.stack == other.stack && self.delay == other.delay &&
+            self.sound == other.sound && self.display == other.display &&
+            self.keys == other.keys && self.waiting == other.waiting &&
+            self.flags == other.flags && *self.rng == *other.rng &&
+            self.pattern == other.pattern && self.pitch == other.pitch &&
+            self.quirks == other.quirks && self.cycles == other.cycles;
+    }
+}
+
+//unit tests
+#[cfg(test)]
+mod tests {
+    //import the Machine struct
+    use super::*;
+    use super::super::ReplayRandom;
+
+    //runs a program until it halts or fails
+    fn run(rom: &[u8], quirks: Quirks) -> Machine {
+        let mut m = Machine::new(quirks);
+        m.load(rom).unwrap();
+        for _ in 0..1000 {
+            if let Step::Halted(_) = m.step().unwrap() {
+                break;
+            }
+        }
+        return m;
+    }
+
+    //this test checks arithmetic and flags
+    #[test]
+    fn test_arithmetic() {
+        //V0 = 200, V1 = 100, V0 += V1, V2 = V1 - V0 (borrow)
+        let m = run(&[0x60, 0xC8, 0x61, 0x64, 0x80, 0x14, 0x82, 0x10,
+                      0x82, 0x05, 0x12, 0x0A], Quirks::vip());
+        assert_eq!(m.get_v(0), 44);
+        assert_eq!(m.get_v(2), 56);
+        assert_eq!(m.get_v(0xF), 1);
+        assert_eq!(m.get_pc(), 0x20A);
+    }
+
+    //this test checks the shift quirk
+    #[test]
+    fn test_shift_quirk() {
+        //V0 = 1, V1 = 6, SHR V0, V1
+        let rom = [0x60, 0x01, 0x61, 0x06, 0x80, 0x16, 0x12, 0x06];
+        assert_eq!(run(&rom, Quirks::vip()).get_v(0), 3);
+        let m = run(&rom, Quirks::schip());
+        assert_eq!((m.get_v(0), m.get_v(0xF)), (0, 1));
+    }
+
+    //this test checks subroutines, BCD and memory access
+    #[test]
+    fn test_calls_and_memory() {
+        //CALL 0x208, I = 0x300, RLD V2, EXIT; sub: V0 = 234, BCD, RET
+        let m = run(&[0x22, 0x08, 0xA3, 0x00, 0xF2, 0x65, 0x00, 0xFD,
+                      0x60, 0xEA, 0xA3, 0x00, 0xF0, 0x33, 0x00, 0xEE],
+                    Quirks::vip());
+        assert_eq!((m.get_v(0), m.get_v(1), m.get_v(2)), (2, 3, 4));
+        assert_eq!(m.get_i(), 0x303);
+        assert_eq!(m.get_stack().len(), 0);
+        assert_eq!(m.get_pc(), 0x206);
+    }
+
+    //this test checks waiting for a key
+    #[test]
+    fn test_key_wait() {
+        let mut m = Machine::new(Quirks::vip());
+        m.load(&[0xF3, 0x0A, 0x12, 0x02]).unwrap();
+        assert_eq!(m.step().unwrap(), Step::Ran);
+        assert_eq!(m.step().unwrap(), Step::Waiting);
+        m.set_key(7, true);
+        assert_eq!(m.step().unwrap(), Step::Waiting);
+        m.set_key(7, false);
+        assert_eq!(m.step().unwrap(), Step::Halted(Halt::SelfJump(0x202)));
+        assert_eq!(m.get_v(3), 7);
+    }
+
+    //this test checks drawing a font digit
+    #[test]
+    fn test_draw_digit() {
+        //V0 = 0xA, I = font(V0), DRAW V1, V1, 5, DRAW V1, V1, 5
+        let m = run(&[0x60, 0x0A, 0xF0, 0x29, 0xD1, 0x15, 0xD1, 0x15,
+                      0x12, 0x08], Quirks::vip());
+        assert_eq!(m.get_v(0xF), 1);
+        assert!(!m.get_display().get(0, 0));
+        let m = run(&[0x60, 0x0A, 0xF0, 0x29, 0xD1, 0x15, 0x12, 0x06],
+                    Quirks::vip());
+        assert_eq!(m.get_display().to_ascii().lines().next().unwrap()[..5],
+                   *"####.");
+    }
+
+    //this test checks the large sprite quirk
+    #[test]
+    fn test_large_sprite_quirk() {
+        //I = 0x200, DRAW V0, V0, 0
+        let rom = [0xA2, 0x00, 0xD0, 0x00, 0x12, 0x04];
+        assert!(!run(&rom, Quirks::vip()).get_display().get(0, 0));
+        let m = run(&rom, Quirks::schip());
+        assert_eq!(m.get_display().to_ascii().lines().next().unwrap()[..16],
+                   *"#.#...#.........");
+    }
+
+    //this test checks error reporting
+    #[test]
+    fn test_errors() {
+        let mut m = Machine::new(Quirks::vip());
+        m.load(&[0x00, 0xEE]).unwrap();
+        let err = m.step().unwrap_err();
+        assert_eq!(err.get_type(), VmErrorType::StackUnderflow);
+        assert_eq!(err.get_pc(), 0x200);
+        let mut m = Machine::new(Quirks::vip());
+        m.load(&[0xE0, 0x00]).unwrap();
+        assert_eq!(m.step().unwrap_err().get_type(), 
+                   VmErrorType::InvalidOpcode(0xE000));
+        assert!(m.load(&vec![0; MEMORY_SIZE]).is_err());
+    }
+
+    //this test checks seeded and replayed random bytes
+    #[test]
+    fn test_random() {
+        //RAND V0, 0xFF; RAND V1, 0x0F
+        let rom = [0xC0, 0xFF, 0xC1, 0x0F];
+        let mut a = Machine::new(Quirks::vip());
+        let mut b = Machine::new(Quirks::vip());
+        a.set_seed(99);
+        b.set_seed(99);
+        a.load(&rom).unwrap();
+        b.load(&rom).unwrap();
+        a.step().unwrap();
+        b.step().unwrap();
+        assert!(a == b);
+        let mut m = Machine::new(Quirks::vip());
+        m.load(&rom).unwrap();
+        m.set_random(Box::new(ReplayRandom::new(&[0xAB, 0xCD])));
+        m.step().unwrap();
+        m.step().unwrap();
+        assert_eq!((m.get_v(0), m.get_v(1)), (0xAB, 0x0D));
+    }
+}
+
+//end of file
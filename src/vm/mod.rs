@@ -0,0 +1,115 @@
+/*
+ * mod.rs
+ * Module header for the vm module in ch8-isa
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! A headless Chip-8 interpreter.
+//!
+//! A `Machine` executes raw opcodes, including the SUPER-CHIP
+//! extensions, with the interpreter differences selected by
+//! `Quirks`, drawing the bytes of `RAND` from a pluggable
+//! `RandomSource`: a seeded `Xorshift` generator by default, or
+//! a `ReplayRandom` that replays recorded bytes. A `Runner`
+//! drives a machine until it halts on a self-jump, `EXIT` or a
+//! breakpoint, feeding it the key presses of a `KeyScript` or
+//! the per-frame keypad states of a `KeyRecording`, and can
+//! record a `Trace` of the executed instructions or a `Profile`
+//! of their cycles. A `SaveState` captures a whole machine in a
+//! versioned binary format and restores it exactly. Hosts that
+//! present the display run a `Scheduler` one 60 Hz frame at a
+//! time, pacing instructions by count or by estimated COSMAC
+//! VIP cycle costs, and either can render the sound timer and
+//! XO-CHIP pattern buffer to `Audio` for WAV output.
+//!
+//! A `Debugger` adds conditional breakpoints, watchpoints on
+//! indexed writes, stepping over and out of subroutines, and
+//! stepping backwards, and a `GdbStub` serves it to GDB over
+//! TCP or a Unix socket. The `snapshot` module compares the
+//! framebuffers of programs against golden files in tests.
+//!
+//! An `Image` renders a framebuffer at any scale in the colours
+//! of a `Palette`, including XO-CHIP's second plane, for PNG and
+//! PBM export, and a `GifRecording` collects a frame per timer
+//! tick into an animated GIF.
+
+//exports
+mod font;
+pub(crate) use font::SMALL_FONT_ADDR;
+mod quirks;
+pub use quirks::Quirks;
+mod display;
+pub use display::Display;
+mod palette;
+pub use palette::Palette;
+mod image;
+pub use image::Image;
+mod gif_recording;
+pub use gif_recording::GifRecording;
+mod halt;
+pub use halt::Halt;
+mod step;
+pub use step::Step;
+mod random_source;
+pub use random_source::{decode_random_source, RandomSource};
+mod xorshift;
+pub use xorshift::Xorshift;
+mod replay_random;
+pub use replay_random::ReplayRandom;
+mod machine;
+pub use machine::{Machine, DEFAULT_PITCH, MEMORY_SIZE, STACK_DEPTH};
+mod key_event;
+pub use key_event::KeyEvent;
+mod key_script;
+pub use key_script::KeyScript;
+mod key_recording;
+pub use key_recording::KeyRecording;
+mod trace_entry;
+pub use trace_entry::TraceEntry;
+mod trace;
+pub use trace::Trace;
+mod subroutine_profile;
+pub use subroutine_profile::SubroutineProfile;
+mod profile;
+pub use profile::Profile;
+mod save_state;
+pub use save_state::SaveState;
+mod vip_timing;
+pub use vip_timing::{vip_cycles, VIP_CYCLES_PER_FRAME};
+mod timing;
+pub use timing::Timing;
+mod audio;
+pub use audio::{Audio, BEEP_FREQUENCY, DEFAULT_SAMPLE_RATE};
+mod scheduler;
+pub use scheduler::Scheduler;
+mod runner;
+pub use runner::{Runner, DEFAULT_IPF};
+mod comparison;
+pub use comparison::Comparison;
+mod condition;
+pub use condition::Condition;
+mod stop;
+pub use stop::Stop;
+mod debugger;
+pub use debugger::{Debugger, DEFAULT_HISTORY};
+mod gdb_stub;
+pub use gdb_stub::{GdbStub, DEFAULT_CONTINUE_LIMIT};
+pub mod snapshot;
+
+//end of file
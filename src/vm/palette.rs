@@ -0,0 +1,148 @@
+/*
+ * palette.rs
+ * Defines the colours used to export framebuffers
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//usage statements
+use std::fmt;
+
+/// The colours used to export a `Display`, indexed by the
+/// bitmask of planes a pixel is lit in: the background, the
+/// first plane, the second XO-CHIP plane, and both planes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Palette {
+    /// The RGB colour of each plane bitmask
+    colours: [[u8; 3]; 4]
+}
+
+//implementation
+impl Palette {
+    /// Constructs a new `Palette` instance
+    ///
+    /// # Argument
+    ///
+    /// * `new_colours` - The RGB colours of the background, the 
+    ///   first plane, the second plane and both planes
+    ///
+    /// # Returns
+    ///
+    /// A new `Palette` with the given colours
+    pub fn new(new_colours: [[u8; 3]; 4]) -> Palette {
+        return Palette {
+            colours: new_colours
+        };
+    }
+
+    /// Parses a palette from a list of hex colours such as 
+    /// `000000,ffffff`. Two colours give the background and the
+    /// lit pixels of classic programs, keeping the default colours
+    /// of the second plane; four give every plane bitmask.
+    ///
+    /// # Argument
+    ///
+    /// * `text` - The comma-separated colours
+    ///
+    /// # Returns
+    ///
+    /// The palette, or `None` if the text is malformed
+    pub fn parse(text: &str) -> Option<Palette> {
+        let mut colours = Palette::default().colours;
+        let list: Vec<&str> = text.split(',').map(|c| c.trim()).collect();
+        if list.len() != 2 && list.len() != 4 {
+            return None;
+        }
+        for (i, colour) in list.iter().enumerate() {
+            let hex = colour.trim_start_matches('#');
+            if hex.len() != 6 || !hex.is_ascii() {
+                return None;
+            }
+            for c in 0..3 {
+                colours[i][c] = u8::from_str_radix(&hex[c * 2..c * 2 + 2],
+                                                   16).ok()?;
+            }
+        }
+        return Some(Palette::new(colours));
+    }
+
+    /// Gets the colour of a pixel
+    ///
+    /// # Argument
+    ///
+    /// * `planes` - The bitmask of planes the pixel is lit in
+    ///
+    /// # Returns
+    ///
+    /// The RGB colour of the pixel
+    pub fn get_colour(&self, planes: u8) -> [u8; 3] {
+        return self.colours[(planes & 3) as usize];
+    }
+
+    /// Gets every colour of the palette
+    ///
+    /// # Returns
+    ///
+    /// The RGB colours, indexed by plane bitmask
+    pub fn get_colours(&self) -> &[[u8; 3]; 4] {
+        return &self.colours;
+    }
+}
+
+//Default implementation
+impl Default for Palette {
+    /// Creates white on black, with greys for the second plane
+    fn default() -> Palette {
+        return Palette::new([[0x00, 0x00, 0x00], [0xFF, 0xFF, 0xFF],
+                             [0xAA, 0xAA, 0xAA], [0x55, 0x55, 0x55]]);
+    }
+}
+
+//Display implementation
+impl fmt::Display for Palette {
+    /// Formats the palette as it is parsed
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let list: Vec<String> = self.colours.iter()
+            .map(|c| format!("{:02x}{:02x}{:02x}", c[0], c[1], c[2]))
+            .collect();
+        return write!(f, "{}", list.join(","));
+    }
+}
+
+//unit tests
+#[cfg(test)]
+mod tests {
+    //import the Palette struct
+    use super::*;
+
+    //this test checks parsing palettes
+    #[test]
+    fn test_parse() {
+        let p = Palette::parse("#102030, ffcc00").unwrap();
+        assert_eq!(p.get_colour(0), [0x10, 0x20, 0x30]);
+        assert_eq!(p.get_colour(1), [0xFF, 0xCC, 0x00]);
+        assert_eq!(p.get_colour(3), Palette::default().get_colour(3));
+        let q = Palette::parse(&p.to_string()).unwrap();
+        assert_eq!(q, p);
+        assert_eq!(Palette::parse("000000"), None);
+        assert_eq!(Palette::parse("000000,fffffg"), None);
+        assert_eq!(Palette::parse("000000,ffffff,aaaaaa"), None);
+    }
+}
+
+//end of file
@@ -0,0 +1,242 @@
+/*
+ * profile.rs
+ * Aggregates executed cycles by address and subroutine
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//usage statements
+use std::collections::BTreeMap;
+use super::{Machine, SubroutineProfile};
+use super::super::codegen::Instruction;
+
+/// An instruction-level profile of a run. Each cycle is counted
+/// against the address executed and against the subroutines on 
+/// the call stack, which are tracked by pairing calls and returns.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Profile {
+    /// The cycles executed at each address
+    addresses: BTreeMap<u16, u64>,
+
+    /// The subroutines entered, by entry address
+    subroutines: BTreeMap<u16, SubroutineProfile>,
+
+    /// The entry addresses of the active subroutines, with the 
+    /// address profiling started at on the bottom
+    frames: Vec<u16>,
+
+    /// The total cycles profiled
+    total: u64
+}
+
+//implementation
+impl Profile {
+    /// Constructs a new `Profile` instance
+    ///
+    /// # Returns
+    ///
+    /// A new empty `Profile`
+    pub fn new() -> Profile {
+        return Profile {
+            addresses: BTreeMap::new(),
+            subroutines: BTreeMap::new(),
+            frames: Vec::new(),
+            total: 0
+        };
+    }
+
+    /// Records one executed cycle
+    ///
+    /// # Arguments
+    ///
+    /// * `pc` - The address of the instruction executed
+    /// * `depth` - The stack depth before the instruction
+    /// * `after` - The machine after the instruction
+    pub fn record(&mut self, pc: u16, depth: usize, after: &Machine) {
+        self.total += 1;
+        *self.addresses.entry(pc).or_insert(0) += 1;
+        if self.frames.is_empty() {
+            self.enter(pc);
+        }
+
+        //charge the cycle to each active subroutine once
+        let top = self.frames.len() - 1;
+        for (k, entry) in self.frames.iter().enumerate() {
+            if !self.frames[k + 1..].contains(entry) {
+                if let Some(sub) = self.subroutines.get_mut(entry) {
+                    sub.add_cycle(k == top);
+                }
+            }
+        }
+
+        //and follow calls and returns
+        let now = after.get_stack().len();
+        if now > depth {
+            self.enter(after.get_pc());
+        } else if now < depth && self.frames.len() > 1 {
+            self.frames.pop();
+        }
+    }
+
+    /// Pushes a subroutine onto the frames and counts the call
+    fn enter(&mut self, entry: u16) {
+        self.frames.push(entry);
+        self.subroutines.entry(entry)
+            .or_insert_with(|| SubroutineProfile::new(entry))
+            .add_call();
+    }
+
+    /// Gets the total cycles profiled
+    ///
+    /// # Returns
+    ///
+    /// The cycle count
+    pub fn get_total(&self) -> u64 {
+        return self.total;
+    }
+
+    /// Gets the cycles executed at each address
+    ///
+    /// # Returns
+    ///
+    /// A map from addresses to cycle counts
+    pub fn get_addresses(&self) -> &BTreeMap<u16, u64> {
+        return &self.addresses;
+    }
+
+    /// Gets the subroutines entered
+    ///
+    /// # Returns
+    ///
+    /// The subroutine profiles, most inclusive cycles first
+    pub fn get_subroutines(&self) -> Vec<&SubroutineProfile> {
+        let mut subs: Vec<&SubroutineProfile> = 
+            self.subroutines.values().collect();
+        subs.sort_by(|a, b| {
+            return b.get_total_cycles().cmp(&a.get_total_cycles())
+                .then(a.get_entry().cmp(&b.get_entry()));
+        });
+        return subs;
+    }
+
+    /// Finds the addresses that executed the most cycles
+    ///
+    /// # Argument
+    ///
+    /// * `count` - The most addresses to return
+    ///
+    /// # Returns
+    ///
+    /// Address and cycle count pairs, most cycles first
+    pub fn hot_spots(&self, count: usize) -> Vec<(u16, u64)> {
+        let mut spots: Vec<(u16, u64)> = self.addresses.iter()
+            .map(|(addr, n)| (*addr, *n)).collect();
+        spots.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        spots.truncate(count);
+        return spots;
+    }
+
+    /// Formats a report of the hot spots and subroutines
+    ///
+    /// # Arguments
+    ///
+    /// * `memory` - The memory of the profiled machine, used to
+    ///   show the instructions at hot spots
+    /// * `count` - The most hot spots to list
+    ///
+    /// # Returns
+    ///
+    /// The report
+    pub fn report(&self, memory: &[u8], count: usize) -> String {
+        let mut out = format!("{} cycles\nhot spots:\n", self.total);
+        out.push_str("  addr   cycles      %  instruction\n");
+        for (addr, n) in self.hot_spots(count) {
+            let hi = memory[addr as usize] as u16;
+            let lo = memory[addr.wrapping_add(1) as usize] as u16;
+            let op = (hi << 8) | lo;
+            let text = match Instruction::decode(op) {
+                Some(instr) => format!("{}", instr),
+                None => format!(".word 0x{:04X}", op)
+            };
+            out.push_str(&format!("  {:03X}  {:>7}  {:>5.1}  {}\n", addr, n,
+                                  self.percent(n), text));
+        }
+        out.push_str("subroutines:\n");
+        out.push_str("  entry    calls     self    total      %\n");
+        for sub in self.get_subroutines() {
+            out.push_str(&format!("  {:03X}    {:>7}  {:>7}  {:>7}  {:>5.1}\n",
+                                  sub.get_entry(), sub.get_calls(),
+                                  sub.get_self_cycles(),
+                                  sub.get_total_cycles(),
+                                  self.percent(sub.get_total_cycles())));
+        }
+        return out;
+    }
+
+    /// Calculates a cycle count as a percentage of the total
+    fn percent(&self, cycles: u64) -> f64 {
+        if self.total == 0 {
+            return 0.0;
+        }
+        return cycles as f64 * 100.0 / self.total as f64;
+    }
+}
+
+//unit tests
+#[cfg(test)]
+mod tests {
+    //import the Profile struct
+    use super::*;
+    use super::super::{Quirks, Step};
+
+    //this test checks attribution to addresses and subroutines
+    #[test]
+    fn test_profile() {
+        //CALL 0x206; JMP 0x204; JMP 0x204; ADD V0, 1; RET
+        let rom = [0x22, 0x06, 0x12, 0x04, 0x12, 0x04, 
+                   0x70, 0x01, 0x00, 0xEE];
+        let mut m = Machine::new(Quirks::vip());
+        m.load(&rom).unwrap();
+        let mut profile = Profile::new();
+        for _ in 0..6 {
+            let pc = m.get_pc();
+            let depth = m.get_stack().len();
+            let step = m.step().unwrap();
+            profile.record(pc, depth, &m);
+            if let Step::Halted(_) = step {
+                break;
+            }
+        }
+        assert_eq!(profile.get_total(), 5);
+        assert_eq!(profile.hot_spots(2), vec![(0x200, 1), (0x202, 1)]);
+        let subs = profile.get_subroutines();
+        assert_eq!(subs.len(), 2);
+        assert_eq!(subs[0].get_entry(), 0x200);
+        assert_eq!(subs[0].get_self_cycles(), 3);
+        assert_eq!(subs[0].get_total_cycles(), 5);
+        assert_eq!(subs[1].get_entry(), 0x206);
+        assert_eq!(subs[1].get_calls(), 1);
+        assert_eq!(subs[1].get_self_cycles(), 2);
+        let report = profile.report(m.get_memory(), 1);
+        assert!(report.starts_with("5 cycles\n"));
+        assert!(report.contains("  200        1   20.0  CALL 0x206\n"));
+        assert!(report.contains("  206          1        2        2   40.0\n"));
+    }
+}
+
+//end of file
@@ -0,0 +1,91 @@
+/*
+ * quirks.rs
+ * Defines the behaviours that differ between Chip-8 interpreters
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+/// The behaviours that differ between Chip-8 interpreters
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Quirks {
+    /// `SHR` and `SHL` shift `VY` into `VX`, rather than shifting
+    /// `VX` in place
+    pub shift_vy: bool,
+
+    /// `RDP` and `RLD` leave `I` pointing past the last register
+    pub load_store_inc_i: bool,
+
+    /// `OR`, `AND` and `XOR` reset `VF` to zero
+    pub vf_reset: bool,
+
+    /// `JPC` adds `VX` rather than `V0`, where `X` is the
+    /// high nibble of its address
+    pub jump_vx: bool,
+
+    /// Sprites are clipped at the edges of the display
+    /// rather than wrapping around
+    pub clip: bool,
+
+    /// `DRAW` with a height of `0` draws a 16x16 sprite,
+    /// rather than drawing nothing
+    pub large_sprites: bool
+}
+
+//implementation
+impl Quirks {
+    /// Gets the quirks of the original COSMAC VIP interpreter
+    ///
+    /// # Returns
+    ///
+    /// The VIP quirks
+    pub fn vip() -> Quirks {
+        return Quirks {
+            shift_vy: true,
+            load_store_inc_i: true,
+            vf_reset: true,
+            jump_vx: false,
+            clip: true,
+            large_sprites: false
+        };
+    }
+
+    /// Gets the quirks of the SUPER-CHIP 1.1 interpreter
+    ///
+    /// # Returns
+    ///
+    /// The SUPER-CHIP quirks
+    pub fn schip() -> Quirks {
+        return Quirks {
+            shift_vy: false,
+            load_store_inc_i: false,
+            vf_reset: false,
+            jump_vx: true,
+            clip: true,
+            large_sprites: true
+        };
+    }
+}
+
+//Default implementation
+impl Default for Quirks {
+    fn default() -> Quirks {
+        return Quirks::vip();
+    }
+}
+
+//end of file
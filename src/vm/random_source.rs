@@ -0,0 +1,88 @@
+/*
+ * random_source.rs
+ * Defines the pluggable source of random bytes for RAND
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//usage statements
+use std::fmt;
+use super::{ReplayRandom, Xorshift};
+
+/// A source of the random bytes used by the `RAND` instruction.
+/// Sources must be deterministic given their state, so that runs
+/// are reproducible and machines can be compared and saved.
+pub trait RandomSource: fmt::Debug + Send {
+    /// Produces the next random byte
+    ///
+    /// # Returns
+    ///
+    /// The byte
+    fn next_byte(&mut self) -> u8;
+
+    /// Copies the source, including its position in its sequence
+    ///
+    /// # Returns
+    ///
+    /// A boxed copy of the source
+    fn box_clone(&self) -> Box<dyn RandomSource>;
+
+    /// Encodes the state of the source. The first byte identifies
+    /// the kind of source; `0` and `1` are used by `Xorshift` and
+    /// `ReplayRandom`. Sources with equal encodings must produce
+    /// equal sequences.
+    ///
+    /// # Returns
+    ///
+    /// The encoded state
+    fn encode(&self) -> Vec<u8>;
+}
+
+/// Decodes the state of one of the crate's random sources
+///
+/// # Argument
+///
+/// * `data` - State encoded by `RandomSource::encode`
+///
+/// # Returns
+///
+/// The source, or `None` if the data is not the state of an
+/// `Xorshift` or `ReplayRandom` source
+pub fn decode_random_source(data: &[u8]) -> Option<Box<dyn RandomSource>> {
+    return match data.first() {
+        Some(0) => Xorshift::decode(data).map(|r| Box::new(r) as Box<_>),
+        Some(1) => ReplayRandom::decode(data).map(|r| Box::new(r) as Box<_>),
+        _ => None
+    };
+}
+
+//Clone implementation
+impl Clone for Box<dyn RandomSource> {
+    fn clone(&self) -> Box<dyn RandomSource> {
+        return self.box_clone();
+    }
+}
+
+//PartialEq implementation
+impl PartialEq for dyn RandomSource {
+    fn eq(&self, other: &dyn RandomSource) -> bool {
+        return self.encode() == other.encode();
+    }
+}
+
+//end of file
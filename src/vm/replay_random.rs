@@ -0,0 +1,161 @@
+/*
+ * replay_random.rs
+ * Implements a random source that replays recorded bytes
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//usage statements
+use super::RandomSource;
+
+/// A random source that replays a recorded sequence of bytes,
+/// starting over once the sequence is used up
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplayRandom {
+    /// The recorded bytes
+    bytes: Vec<u8>,
+
+    /// The index of the next byte to produce
+    pos: usize,
+
+    /// The number of times the sequence has been used up
+    wraps: u64
+}
+
+//implementation
+impl ReplayRandom {
+    /// Constructs a new `ReplayRandom` instance
+    ///
+    /// # Argument
+    ///
+    /// * `new_bytes` - The bytes to replay
+    ///
+    /// # Returns
+    ///
+    /// A new `ReplayRandom` positioned at the first byte
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `new_bytes` is empty.
+    pub fn new(new_bytes: &[u8]) -> ReplayRandom {
+        assert!(!new_bytes.is_empty(), "nothing to replay");
+        return ReplayRandom {
+            bytes: new_bytes.to_vec(),
+            pos: 0,
+            wraps: 0
+        };
+    }
+
+    /// Gets the number of times the sequence has been used up.
+    /// A nonzero count means a run drew more random bytes than 
+    /// were recorded.
+    ///
+    /// # Returns
+    ///
+    /// The number of times replay started over
+    pub fn get_wraps(&self) -> u64 {
+        return self.wraps;
+    }
+
+    /// Decodes state encoded by `encode`
+    ///
+    /// # Argument
+    ///
+    /// * `data` - The encoded state
+    ///
+    /// # Returns
+    ///
+    /// The source, or `None` if the data is malformed
+    pub fn decode(data: &[u8]) -> Option<ReplayRandom> {
+        if data.len() < 14 || data[0] != 1 {
+            return None;
+        }
+        let mut word = [0u8; 4];
+        word.copy_from_slice(&data[1..5]);
+        let pos = u32::from_be_bytes(word) as usize;
+        let mut long = [0u8; 8];
+        long.copy_from_slice(&data[5..13]);
+        let wraps = u64::from_be_bytes(long);
+        let bytes = &data[13..];
+        if pos >= bytes.len() {
+            return None;
+        }
+        let mut source = ReplayRandom::new(bytes);
+        source.pos = pos;
+        source.wraps = wraps;
+        return Some(source);
+    }
+}
+
+//RandomSource implementation
+impl RandomSource for ReplayRandom {
+    fn next_byte(&mut self) -> u8 {
+        let byte = self.bytes[self.pos];
+        self.pos += 1;
+        if self.pos == self.bytes.len() {
+            self.pos = 0;
+            self.wraps += 1;
+        }
+        return byte;
+    }
+
+    fn box_clone(&self) -> Box<dyn RandomSource> {
+        return Box::new(self.clone());
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        let mut out = vec![1];
+        out.extend_from_slice(&(self.pos as u32).to_be_bytes());
+        out.extend_from_slice(&self.wraps.to_be_bytes());
+        out.extend_from_slice(&self.bytes);
+        return out;
+    }
+}
+
+//unit tests
+#[cfg(test)]
+mod tests {
+    //import the ReplayRandom struct
+    use super::*;
+    use super::super::{decode_random_source, Xorshift};
+
+    //this test checks replaying and wrapping
+    #[test]
+    fn test_replay() {
+        let mut r = ReplayRandom::new(&[1, 2, 3]);
+        let bytes: Vec<u8> = (0..4).map(|_| r.next_byte()).collect();
+        assert_eq!(bytes, vec![1, 2, 3, 1]);
+        assert_eq!(r.get_wraps(), 1);
+    }
+
+    //this test checks encoding the built-in sources
+    #[test]
+    fn test_encode() {
+        let mut r = ReplayRandom::new(&[1, 2, 3]);
+        r.next_byte();
+        let mut copy = decode_random_source(&r.encode()).unwrap();
+        assert_eq!(copy.next_byte(), 2);
+        let mut x = Xorshift::new(7);
+        let mut copy = decode_random_source(&x.encode()).unwrap();
+        assert_eq!(copy.next_byte(), x.next_byte());
+        assert!(decode_random_source(&[2]).is_none());
+        assert!(decode_random_source(&[1, 0, 0, 0, 9]).is_none());
+    }
+}
+
+//end of file
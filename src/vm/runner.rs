@@ -0,0 +1,410 @@
+/*
+ * runner.rs
+ * Runs programs headlessly until they halt
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//usage statements
+use std::collections::BTreeSet;
+use super::{Audio, GifRecording, Halt, KeyRecording, KeyScript, Machine};
+use super::{Profile, Step, Trace};
+use super::TraceEntry;
+use super::trace_entry::register_file;
+use super::super::error::VmError;
+
+/// The default number of instructions executed per 60 Hz timer tick
+pub const DEFAULT_IPF: u64 = 10;
+
+/// Runs a `Machine` without a window, feeding it scripted keys
+/// and stopping at breakpoints
+pub struct Runner {
+    /// The machine being run
+    machine: Machine,
+
+    /// The addresses to stop at
+    breakpoints: BTreeSet<u16>,
+
+    /// The scripted key events
+    keys: KeyScript,
+
+    /// The index of the next key event to apply
+    next_key: usize,
+
+    /// The number of instructions executed per timer tick
+    ipf: u64,
+
+    /// The trace of executed instructions, if tracing
+    trace: Option<Trace>,
+
+    /// The profile of executed instructions, if profiling
+    profile: Option<Profile>,
+
+    /// The keypad states being replayed, one per frame
+    replay: Option<KeyRecording>,
+
+    /// The keypad states recorded so far, if recording
+    recording: Option<KeyRecording>,
+
+    /// The sound rendered so far, if rendering audio
+    audio: Option<Audio>,
+
+    /// The frames recorded so far, if recording an animation
+    gif: Option<GifRecording>
+}
+
+//implementation
+impl Runner {
+    /// Constructs a new `Runner` instance
+    ///
+    /// # Argument
+    ///
+    /// * `new_machine` - The machine to run
+    ///
+    /// # Returns
+    ///
+    /// A new `Runner` with no breakpoints or key events
+    pub fn new(new_machine: Machine) -> Runner {
+        return Runner {
+            machine: new_machine,
+            breakpoints: BTreeSet::new(),
+            keys: KeyScript::new(),
+            next_key: 0,
+            ipf: DEFAULT_IPF,
+            trace: None,
+            profile: None,
+            replay: None,
+            recording: None,
+            audio: None,
+            gif: None
+        };
+    }
+
+    /// Adds a breakpoint
+    ///
+    /// # Argument
+    ///
+    /// * `addr` - The address to stop at
+    pub fn add_breakpoint(&mut self, addr: u16) {
+        self.breakpoints.insert(addr);
+    }
+
+    /// Sets the scripted key events. Events scheduled before the
+    /// current cycle are applied at the start of the next run.
+    ///
+    /// # Argument
+    ///
+    /// * `script` - The key events
+    pub fn set_key_script(&mut self, script: KeyScript) {
+        self.keys = script;
+        self.next_key = 0;
+    }
+
+    /// Replays recorded keypad states, setting the whole keypad at
+    /// the start of each frame the recording covers. Keys keep
+    /// their last state once the recording runs out.
+    ///
+    /// # Argument
+    ///
+    /// * `recording` - The keypad state of each frame
+    pub fn set_key_replay(&mut self, recording: KeyRecording) {
+        self.replay = Some(recording);
+    }
+
+    /// Starts recording the keypad state at the start of each frame.
+    /// Frames are numbered from the machine's first cycle, so frames
+    /// before recording started hold the keypad state at its start.
+    pub fn start_recording(&mut self) {
+        if self.recording.is_none() {
+            self.recording = Some(KeyRecording::new());
+        }
+    }
+
+    /// Stops recording and takes the keypad states recorded so far
+    ///
+    /// # Returns
+    ///
+    /// The recording, or `None` if recording was not started
+    pub fn take_recording(&mut self) -> Option<KeyRecording> {
+        return self.recording.take();
+    }
+
+    /// Starts rendering the machine's sound at each timer tick
+    ///
+    /// # Argument
+    ///
+    /// * `sample_rate` - The samples per second
+    pub fn enable_audio(&mut self, sample_rate: u32) {
+        self.audio = Some(Audio::new(sample_rate));
+    }
+
+    /// Gets the sound rendered so far
+    ///
+    /// # Returns
+    ///
+    /// The audio, or `None` if rendering was not enabled
+    pub fn get_audio(&self) -> Option<&Audio> {
+        return self.audio.as_ref();
+    }
+
+    /// Starts recording the framebuffer at each timer tick
+    ///
+    /// # Argument
+    ///
+    /// * `gif` - The recording to add the frames to
+    pub fn start_gif(&mut self, gif: GifRecording) {
+        self.gif = Some(gif);
+    }
+
+    /// Stops recording and takes the frames recorded so far
+    ///
+    /// # Returns
+    ///
+    /// The recording, or `None` if recording was not started
+    pub fn take_gif(&mut self) -> Option<GifRecording> {
+        return self.gif.take();
+    }
+
+    /// Sets the number of instructions executed per timer tick
+    ///
+    /// # Argument
+    ///
+    /// * `new_ipf` - The instructions per tick
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `new_ipf` is zero.
+    pub fn set_ipf(&mut self, new_ipf: u64) {
+        assert!(new_ipf > 0, "at least one instruction per tick is needed");
+        self.ipf = new_ipf;
+    }
+
+    /// Starts recording a trace of the executed instructions
+    pub fn enable_trace(&mut self) {
+        if self.trace.is_none() {
+            self.trace = Some(Trace::new());
+        }
+    }
+
+    /// Stops tracing and takes the trace recorded so far
+    ///
+    /// # Returns
+    ///
+    /// The trace, or `None` if tracing was not enabled
+    pub fn take_trace(&mut self) -> Option<Trace> {
+        return self.trace.take();
+    }
+
+    /// Starts profiling the executed instructions
+    pub fn enable_profile(&mut self) {
+        if self.profile.is_none() {
+            self.profile = Some(Profile::new());
+        }
+    }
+
+    /// Gets the profile recorded so far
+    ///
+    /// # Returns
+    ///
+    /// The profile, or `None` if profiling was not enabled
+    pub fn get_profile(&self) -> Option<&Profile> {
+        return self.profile.as_ref();
+    }
+
+    /// Gets the machine being run
+    ///
+    /// # Returns
+    ///
+    /// The machine
+    pub fn get_machine(&self) -> &Machine {
+        return &self.machine;
+    }
+
+    /// Gets the machine being run, for modification
+    ///
+    /// # Returns
+    ///
+    /// The machine
+    pub fn get_machine_mut(&mut self) -> &mut Machine {
+        return &mut self.machine;
+    }
+
+    /// Runs the machine until it halts, reaches a breakpoint
+    /// or executes a number of cycles. A run that starts on a
+    /// breakpoint executes it rather than stopping immediately.
+    ///
+    /// # Argument
+    ///
+    /// * `limit` - The most cycles to execute
+    ///
+    /// # Returns
+    ///
+    /// Why the run stopped, or the `VmError` that stopped it
+    pub fn run(&mut self, limit: u64) -> Result<Halt, VmError> {
+        let start = self.machine.get_cycles();
+        loop {
+            let cycles = self.machine.get_cycles();
+            if cycles - start >= limit {
+                return Ok(Halt::CycleLimit);
+            }
+
+            //apply the key events that are due
+            let events = self.keys.get_events();
+            while self.next_key < events.len() && 
+                events[self.next_key].get_cycle() <= cycles {
+                let e = events[self.next_key];
+                self.machine.set_key(e.get_key(), e.is_down());
+                self.next_key += 1;
+            }
+
+            //replay and record the keypad at the start of each frame
+            if cycles.is_multiple_of(self.ipf) {
+                let frame = (cycles / self.ipf) as usize;
+                if let Some(ref replay) = self.replay {
+                    if let Some(keypad) = replay.get(frame) {
+                        self.machine.set_keypad(keypad);
+                    }
+                }
+                if let Some(ref mut recording) = self.recording {
+                    while recording.get_frames().len() <= frame {
+                        recording.add(self.machine.get_keypad());
+                    }
+                }
+            }
+
+            //stop at breakpoints
+            let pc = self.machine.get_pc();
+            if cycles != start && self.breakpoints.contains(&pc) {
+                return Ok(Halt::Breakpoint(pc));
+            }
+
+            //and execute an instruction
+            let opcode = self.machine.get_opcode();
+            let depth = self.machine.get_stack().len();
+            let before = register_file(&self.machine);
+            let step = self.machine.step()?;
+            if let Some(ref mut trace) = self.trace {
+                trace.add(TraceEntry::capture(pc, opcode, &before, 
+                                              &self.machine));
+            }
+            if let Some(ref mut profile) = self.profile {
+                profile.record(pc, depth, &self.machine);
+            }
+            if let Step::Halted(halt) = step {
+                return Ok(halt);
+            }
+            if self.machine.get_cycles().is_multiple_of(self.ipf) {
+                if let Some(ref mut audio) = self.audio {
+                    audio.render_frame(&self.machine);
+                }
+                if let Some(ref mut gif) = self.gif {
+                    gif.add_frame(self.machine.get_display());
+                }
+                self.machine.tick_timers();
+            }
+        }
+    }
+}
+
+//unit tests
+#[cfg(test)]
+mod tests {
+    //import the Runner struct
+    use super::*;
+    use super::super::{KeyEvent, Quirks};
+
+    //creates a runner for a program
+    fn runner(rom: &[u8]) -> Runner {
+        let mut m = Machine::new(Quirks::vip());
+        m.load(rom).unwrap();
+        return Runner::new(m);
+    }
+
+    //this test checks halting, breakpoints and cycle limits
+    #[test]
+    fn test_halts() {
+        //V0 += 1, SKIP.EQ V0, 3, JMP 0x200, JMP 0x206
+        let rom = [0x70, 0x01, 0x30, 0x03, 0x12, 0x00, 0x12, 0x06];
+        let mut r = runner(&rom);
+        r.add_breakpoint(0x202);
+        assert_eq!(r.run(100).unwrap(), Halt::Breakpoint(0x202));
+        assert_eq!(r.get_machine().get_v(0), 1);
+        assert_eq!(r.run(100).unwrap(), Halt::Breakpoint(0x202));
+        assert_eq!(r.get_machine().get_v(0), 2);
+        assert_eq!(r.run(1).unwrap(), Halt::CycleLimit);
+        let mut r = runner(&rom);
+        assert_eq!(r.run(100).unwrap(), Halt::SelfJump(0x206));
+        assert_eq!(r.get_machine().get_cycles(), 9);
+    }
+
+    //this test checks scripted keys and timers
+    #[test]
+    fn test_keys_and_timers() {
+        //V1 = 30, SDL V1, KEY V2, GDL V3, JMP 0x208
+        let mut r = runner(&[0x61, 0x1E, 0xF1, 0x15, 0xF2, 0x0A, 0xF3, 0x07,
+                             0x12, 0x08]);
+        let mut script = KeyScript::new();
+        script.add(KeyEvent::new(40, 0xC, true));
+        script.add(KeyEvent::new(100, 0xC, false));
+        r.set_key_script(script);
+        r.set_ipf(5);
+        assert_eq!(r.run(1000).unwrap(), Halt::SelfJump(0x208));
+        let m = r.get_machine();
+        assert_eq!(m.get_v(2), 0xC);
+        assert_eq!(m.get_v(3), 30 - 20);
+    }
+
+    //this test checks that recorded keypad states replay identically
+    #[test]
+    fn test_record_and_replay() {
+        //KEY V2, ADD V2, 1, JMP 0x204
+        let rom = [0xF2, 0x0A, 0x72, 0x01, 0x12, 0x04];
+        let mut r = runner(&rom);
+        let mut script = KeyScript::new();
+        script.add(KeyEvent::new(40, 0x7, true));
+        script.add(KeyEvent::new(60, 0x7, false));
+        r.set_key_script(script);
+        r.start_recording();
+        assert_eq!(r.run(1000).unwrap(), Halt::SelfJump(0x204));
+        let recording = r.take_recording().unwrap();
+        assert_eq!(recording.get_frames().len(), 7);
+        assert_eq!(recording.get(4), Some(0x0080));
+        assert_eq!(recording.get(6), Some(0));
+        let mut replayed = runner(&rom);
+        replayed.set_key_replay(recording);
+        assert_eq!(replayed.run(1000).unwrap(), Halt::SelfJump(0x204));
+        assert!(replayed.get_machine() == r.get_machine());
+        assert_eq!(r.get_machine().get_v(2), 8);
+    }
+
+    //this test checks recording the framebuffer at each tick
+    #[test]
+    fn test_gif() {
+        //V0 = 0, FONT V0, DRW V0, V0, 5, ADD V1, 1, JMP 0x206
+        let mut r = runner(&[0x60, 0x00, 0xF0, 0x29, 0xD0, 0x05, 0x71, 0x01,
+                             0x12, 0x06]);
+        r.start_gif(GifRecording::new(1, Default::default()));
+        assert_eq!(r.run(40).unwrap(), Halt::CycleLimit);
+        let gif = r.take_gif().unwrap();
+        assert_eq!(gif.get_ticks(), &[4]);
+        assert_eq!(&gif.get_frames()[0].get_pixels()[0..5], &[1, 1, 1, 1, 0]);
+        assert!(r.take_gif().is_none());
+    }
+}
+
+//end of file
@@ -0,0 +1,448 @@
+/*
+ * save_state.rs
+ * Serializes and restores complete interpreter states
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//usage statements
+use super::{decode_random_source, Display, Machine, Quirks};
+use super::{MEMORY_SIZE, STACK_DEPTH};
+use super::super::error::{DecodeError, DecodeErrorType};
+
+/// The magic number that starts an encoded save state
+const MAGIC: &[u8; 4] = b"CH8S";
+
+/// The version of the save state format
+const VERSION: u8 = 1;
+
+/// The most differences `differences` reports for memory
+const MAX_MEMORY_DIFFERENCES: usize = 8;
+
+/// A complete snapshot of a `Machine`: memory, registers, stack,
+/// timers, framebuffer, keypad, random number generator state,
+/// audio registers and quirks. States compare equal only if restoring them gives
+/// identical machines.
+///
+/// The binary format is the magic `CH8S` and a version byte, then
+/// the quirks as a bitmask, `PC` and `I`, `V0` to `VF`, the delay
+/// and sound timers, the stack depth and return addresses, the 
+/// waiting register (`0xFF` if none), the held keys as a bitmask,
+/// the SUPER-CHIP flags, the 32-bit length and encoding of the random
+/// source, the XO-CHIP pattern buffer (a flag byte, then its 16
+/// bytes if loaded) and pitch, the cycle count, the resolution and
+/// plane mask of each pixel, and memory. Multi-byte values are
+/// big-endian. Only the crate's own random sources can be restored
+/// from the binary format.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SaveState {
+    /// A copy of the machine
+    machine: Machine
+}
+
+/// Reads fields from encoded data, tracking the offset for errors
+struct Reader<'a> {
+    /// The encoded data
+    data: &'a [u8],
+
+    /// The offset of the next byte
+    pos: usize
+}
+
+//implementation
+impl<'a> Reader<'a> {
+    /// Reads a number of bytes
+    fn bytes(&mut self, count: usize) -> Result<&'a [u8], DecodeError> {
+        if self.data.len() - self.pos < count {
+            return Err(DecodeError::new(DecodeErrorType::Truncated, 
+                                        self.data.len()));
+        }
+        let bytes = &self.data[self.pos..self.pos + count];
+        self.pos += count;
+        return Ok(bytes);
+    }
+
+    /// Reads a byte
+    fn byte(&mut self) -> Result<u8, DecodeError> {
+        return Ok(self.bytes(1)?[0]);
+    }
+
+    /// Reads a big-endian word
+    fn word(&mut self) -> Result<u16, DecodeError> {
+        let b = self.bytes(2)?;
+        return Ok(u16::from_be_bytes([b[0], b[1]]));
+    }
+
+    /// Reads a big-endian 32-bit number
+    fn dword(&mut self) -> Result<u32, DecodeError> {
+        let mut b = [0u8; 4];
+        b.copy_from_slice(self.bytes(4)?);
+        return Ok(u32::from_be_bytes(b));
+    }
+
+    /// Reads a big-endian 64-bit number
+    fn long(&mut self) -> Result<u64, DecodeError> {
+        let mut b = [0u8; 8];
+        b.copy_from_slice(self.bytes(8)?);
+        return Ok(u64::from_be_bytes(b));
+    }
+
+    /// Creates an invalid value error for the last byte read
+    fn invalid(&self, what: &str) -> DecodeError {
+        return DecodeError::new(DecodeErrorType::InvalidValue(
+                String::from(what)), self.pos - 1);
+    }
+}
+
+impl SaveState {
+    /// Captures the state of a machine
+    ///
+    /// # Argument
+    ///
+    /// * `machine` - The machine to capture
+    ///
+    /// # Returns
+    ///
+    /// A new `SaveState` of the machine
+    pub fn capture(machine: &Machine) -> SaveState {
+        return SaveState {
+            machine: machine.clone()
+        };
+    }
+
+    /// Restores the captured machine
+    ///
+    /// # Returns
+    ///
+    /// A machine identical to the captured one
+    pub fn restore(&self) -> Machine {
+        return self.machine.clone();
+    }
+
+    /// Encodes the state in the binary format
+    ///
+    /// # Returns
+    ///
+    /// The encoded state
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let m = &self.machine;
+        let mut out = MAGIC.to_vec();
+        out.push(VERSION);
+        let q = m.quirks;
+        out.push(q.shift_vy as u8 | (q.load_store_inc_i as u8) << 1 | 
+                 (q.vf_reset as u8) << 2 | (q.jump_vx as u8) << 3 | 
+                 (q.clip as u8) << 4 | (q.large_sprites as u8) << 5);
+        out.extend_from_slice(&m.pc.to_be_bytes());
+        out.extend_from_slice(&m.i.to_be_bytes());
+        out.extend_from_slice(&m.v);
+        out.push(m.delay);
+        out.push(m.sound);
+        out.push(m.stack.len() as u8);
+        for addr in m.stack.iter() {
+            out.extend_from_slice(&addr.to_be_bytes());
+        }
+        out.push(m.waiting.unwrap_or(0xFF));
+        out.extend_from_slice(&m.get_keypad().to_be_bytes());
+        out.extend_from_slice(&m.flags);
+        let rng = m.rng.encode();
+        out.extend_from_slice(&(rng.len() as u32).to_be_bytes());
+        out.extend_from_slice(&rng);
+        match m.pattern {
+            Some(ref pattern) => {
+                out.push(1);
+                out.extend_from_slice(pattern);
+            },
+            None => out.push(0)
+        };
+        out.push(m.pitch);
+        out.extend_from_slice(&m.cycles.to_be_bytes());
+        out.push(m.display.is_hires() as u8);
+        out.extend_from_slice(&m.display.pixels);
+        out.extend_from_slice(&m.memory);
+        return out;
+    }
+
+    /// Decodes a state from the binary format
+    ///
+    /// # Argument
+    ///
+    /// * `data` - The encoded state
+    ///
+    /// # Returns
+    ///
+    /// The state, or a `DecodeError` describing why it is malformed
+    pub fn from_bytes(data: &[u8]) -> Result<SaveState, DecodeError> {
+        if data.len() < 5 || &data[..4] != MAGIC {
+            return Err(DecodeError::new(DecodeErrorType::BadMagic, 0));
+        }
+        let version = data[4];
+        if version != VERSION {
+            return Err(DecodeError::new(
+                    DecodeErrorType::UnsupportedVersion(version), 4));
+        }
+        let mut r = Reader { data, pos: 5 };
+        let bits = r.byte()?;
+        if bits >= 0x40 {
+            return Err(r.invalid("quirks"));
+        }
+        let mut m = Machine::new(Quirks {
+            shift_vy: bits & 0x01 != 0,
+            load_store_inc_i: bits & 0x02 != 0,
+            vf_reset: bits & 0x04 != 0,
+            jump_vx: bits & 0x08 != 0,
+            clip: bits & 0x10 != 0,
+            large_sprites: bits & 0x20 != 0
+        });
+        m.pc = r.word()?;
+        m.i = r.word()?;
+        m.v.copy_from_slice(r.bytes(16)?);
+        m.delay = r.byte()?;
+        m.sound = r.byte()?;
+        let depth = r.byte()? as usize;
+        if depth > STACK_DEPTH {
+            return Err(r.invalid("stack depth"));
+        }
+        for _ in 0..depth {
+            let addr = r.word()?;
+            m.stack.push(addr);
+        }
+        m.waiting = match r.byte()? {
+            0xFF => None,
+            x @ 0x0..=0xF => Some(x),
+            _ => return Err(r.invalid("waiting register"))
+        };
+        let keys = r.word()?;
+        for (k, key) in m.keys.iter_mut().enumerate() {
+            *key = keys & (1 << k) != 0;
+        }
+        m.flags.copy_from_slice(r.bytes(16)?);
+        let len = r.dword()? as usize;
+        m.rng = decode_random_source(r.bytes(len)?).ok_or_else(|| {
+            return r.invalid("random source");
+        })?;
+        m.pattern = match r.byte()? {
+            0 => None,
+            1 => {
+                let mut pattern = [0u8; 16];
+                pattern.copy_from_slice(r.bytes(16)?);
+                Some(pattern)
+            },
+            _ => return Err(r.invalid("pattern flag"))
+        };
+        m.pitch = r.byte()?;
+        m.cycles = r.long()?;
+        m.display = match r.byte()? {
+            0 => Display::new(false),
+            1 => Display::new(true),
+            _ => return Err(r.invalid("resolution"))
+        };
+        let count = m.display.pixels.len();
+        m.display.pixels.copy_from_slice(r.bytes(count)?);
+        m.memory.copy_from_slice(r.bytes(MEMORY_SIZE)?);
+        if r.pos != data.len() {
+            return Err(DecodeError::new(DecodeErrorType::InvalidValue(
+                        String::from("trailing data")), r.pos));
+        }
+        return Ok(SaveState { machine: m });
+    }
+
+    /// Describes how this state differs from another, to explain
+    /// why two execution paths did not converge
+    ///
+    /// # Argument
+    ///
+    /// * `other` - The state to compare with
+    ///
+    /// # Returns
+    ///
+    /// One line per differing part of the machine, empty if the
+    /// states are equal
+    pub fn differences(&self, other: &SaveState) -> Vec<String> {
+        let (a, b) = (&self.machine, &other.machine);
+        let mut diffs = Vec::new();
+        if a.quirks != b.quirks {
+            diffs.push(format!("quirks: {:?} != {:?}", a.quirks, b.quirks));
+        }
+        if a.pc != b.pc {
+            diffs.push(format!("PC: 0x{:03X} != 0x{:03X}", a.pc, b.pc));
+        }
+        if a.i != b.i {
+            diffs.push(format!("I: 0x{:03X} != 0x{:03X}", a.i, b.i));
+        }
+        for r in (0..16).filter(|r| a.v[*r] != b.v[*r]) {
+            diffs.push(format!("V{:X}: 0x{:02X} != 0x{:02X}", r, a.v[r], 
+                               b.v[r]));
+        }
+        if a.delay != b.delay || a.sound != b.sound {
+            diffs.push(format!("timers: {}/{} != {}/{}", a.delay, a.sound,
+                               b.delay, b.sound));
+        }
+        if a.stack != b.stack {
+            diffs.push(format!("stack: {:03X?} != {:03X?}", a.stack, b.stack));
+        }
+        if a.keys != b.keys || a.waiting != b.waiting {
+            diffs.push(String::from("keypad differs"));
+        }
+        if a.flags != b.flags {
+            diffs.push(String::from("flag registers differ"));
+        }
+        if *a.rng != *b.rng {
+            diffs.push(String::from("random number generator differs"));
+        }
+        if a.pattern != b.pattern || a.pitch != b.pitch {
+            diffs.push(String::from("audio registers differ"));
+        }
+        if a.cycles != b.cycles {
+            diffs.push(format!("cycles: {} != {}", a.cycles, b.cycles));
+        }
+        if a.display.is_hires() != b.display.is_hires() {
+            diffs.push(String::from("resolution differs"));
+        } else if a.display != b.display {
+            let n = a.display.pixels.iter().zip(b.display.pixels.iter())
+                .filter(|(p, q)| p != q).count();
+            diffs.push(format!("framebuffer: {} pixel(s) differ", n));
+        }
+        let changed: Vec<usize> = (0..MEMORY_SIZE)
+            .filter(|k| a.memory[*k] != b.memory[*k]).collect();
+        for k in changed.iter().take(MAX_MEMORY_DIFFERENCES) {
+            diffs.push(format!("memory at 0x{:03X}: 0x{:02X} != 0x{:02X}", 
+                               k, a.memory[*k], b.memory[*k]));
+        }
+        if changed.len() > MAX_MEMORY_DIFFERENCES {
+            diffs.push(format!("memory: {} more byte(s) differ", 
+                               changed.len() - MAX_MEMORY_DIFFERENCES));
+        }
+        return diffs;
+    }
+}
+
+//unit tests
+#[cfg(test)]
+mod tests {
+    //import the SaveState struct
+    use super::*;
+    use super::super::ReplayRandom;
+
+    //creates a machine part way through a program
+    fn machine() -> Machine {
+        //CALL 0x206; JMP 0x204; ...; RAND V2, 0xFF; BCD V2; SCH V2; KEY V3
+        let rom = [0x22, 0x06, 0x12, 0x04, 0x00, 0x00, 0xC2, 0xFF, 
+                   0xA3, 0x00, 0xF2, 0x33, 0xF2, 0x29, 0xD0, 0x15,
+                   0xF3, 0x0A];
+        let mut m = Machine::new(Quirks::schip());
+        m.load(&rom).unwrap();
+        m.set_seed(42);
+        m.set_key(7, true);
+        for _ in 0..8 {
+            m.step().unwrap();
+        }
+        m.set_delay(9);
+        return m;
+    }
+
+    //this test checks that states restore exactly
+    #[test]
+    fn test_round_trip() {
+        let m = machine();
+        assert!(m.is_waiting());
+        let state = SaveState::capture(&m);
+        let data = state.to_bytes();
+        let decoded = SaveState::from_bytes(&data).unwrap();
+        assert_eq!(decoded, state);
+        assert!(decoded.restore() == m);
+
+        //and that restored machines continue identically
+        let mut a = m.clone();
+        let mut b = decoded.restore();
+        for _ in 0..3 {
+            a.step().unwrap();
+            b.step().unwrap();
+        }
+        a.set_key(7, false);
+        b.set_key(7, false);
+        assert!(SaveState::capture(&a).differences(
+                &SaveState::capture(&b)).is_empty());
+    }
+
+    //this test checks malformed states
+    #[test]
+    fn test_errors() {
+        let data = SaveState::capture(&machine()).to_bytes();
+        let err = SaveState::from_bytes(&data[..data.len() - 1]).unwrap_err();
+        assert_eq!(err.get_type(), DecodeErrorType::Truncated);
+        let err = SaveState::from_bytes(b"CH8T\x01").unwrap_err();
+        assert_eq!(err.get_type(), DecodeErrorType::BadMagic);
+        let mut bad = data.clone();
+        bad[4] = 9;
+        let err = SaveState::from_bytes(&bad).unwrap_err();
+        assert_eq!(err.get_type(), DecodeErrorType::UnsupportedVersion(9));
+        let mut bad = data.clone();
+        bad.push(0);
+        assert!(SaveState::from_bytes(&bad).is_err());
+        let mut bad = data;
+        bad[5] = 0xFF;
+        let err = SaveState::from_bytes(&bad).unwrap_err();
+        assert_eq!(err.get_offset(), 5);
+    }
+
+    //this test checks saving a replay too long for a 16-bit length
+    #[test]
+    fn test_large_replay() {
+        let mut m = machine();
+        let bytes: Vec<u8> = (0..0x12000).map(|n| n as u8).collect();
+        m.set_random(Box::new(ReplayRandom::new(&bytes)));
+        let data = SaveState::capture(&m).to_bytes();
+        assert!(SaveState::from_bytes(&data).unwrap().restore() == m);
+    }
+
+    //this test checks saving the XO-CHIP audio registers
+    #[test]
+    fn test_audio_registers() {
+        //MOV V1, 0x50; MOV I, 0x300; AUDIO; PITCH V1
+        let mut m = Machine::new(Quirks::vip());
+        m.load(&[0x61, 0x50, 0xA3, 0x00, 0xF0, 0x02, 0xF1, 0x3A]).unwrap();
+        m.write_memory(0x300, &[0xF0; 16]);
+        for _ in 0..4 {
+            m.step().unwrap();
+        }
+        assert_eq!(m.get_pattern(), Some([0xF0; 16]));
+        assert_eq!(m.get_pitch(), 0x50);
+        let state = SaveState::capture(&m);
+        let decoded = SaveState::from_bytes(&state.to_bytes()).unwrap();
+        assert!(decoded.restore() == m);
+        let fresh = SaveState::capture(&Machine::new(Quirks::vip()));
+        assert!(fresh.differences(&state).contains(
+                &String::from("audio registers differ")));
+    }
+
+    //this test checks describing differences
+    #[test]
+    fn test_differences() {
+        let a = machine();
+        let mut b = a.clone();
+        b.set_v(0xA, 1);
+        b.write_memory(0x400, &[1, 2]);
+        let diffs = SaveState::capture(&a)
+            .differences(&SaveState::capture(&b));
+        assert_eq!(diffs, vec![String::from("VA: 0x00 != 0x01"),
+                               String::from("memory at 0x400: 0x00 != 0x01"),
+                               String::from("memory at 0x401: 0x00 != 0x02")]);
+    }
+}
+
+//end of file
@@ -0,0 +1,267 @@
+/*
+ * scheduler.rs
+ * Paces instructions against the 60 Hz timers
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//usage statements
+use super::{vip_cycles, Audio, Halt, Machine, Step, Timing};
+use super::VIP_CYCLES_PER_FRAME;
+use super::super::error::VmError;
+
+/// Runs a `Machine` one 60 Hz frame at a time for hosts that
+/// present its display, decoupling the instruction rate from the
+/// delay and sound timers, which tick once per frame
+pub struct Scheduler {
+    /// The machine being run
+    machine: Machine,
+
+    /// How instructions are paced
+    timing: Timing,
+
+    /// The VIP machine cycles the last frame overran its budget by
+    debt: u32,
+
+    /// The number of frames run
+    frames: u64,
+
+    /// The sound rendered so far, if rendering audio
+    audio: Option<Audio>
+}
+
+//implementation
+impl Scheduler {
+    /// Constructs a new `Scheduler` instance
+    ///
+    /// # Arguments
+    ///
+    /// * `new_machine` - The machine to run
+    /// * `new_timing` - How to pace its instructions
+    ///
+    /// # Returns
+    ///
+    /// A new `Scheduler` that has run no frames
+    pub fn new(new_machine: Machine, new_timing: Timing) -> Scheduler {
+        return Scheduler {
+            machine: new_machine,
+            timing: new_timing,
+            debt: 0,
+            frames: 0,
+            audio: None
+        };
+    }
+
+    /// Gets the machine being run
+    ///
+    /// # Returns
+    ///
+    /// The machine
+    pub fn get_machine(&self) -> &Machine {
+        return &self.machine;
+    }
+
+    /// Gets the machine being run, for modification
+    ///
+    /// # Returns
+    ///
+    /// The machine
+    pub fn get_machine_mut(&mut self) -> &mut Machine {
+        return &mut self.machine;
+    }
+
+    /// Gets how instructions are paced
+    ///
+    /// # Returns
+    ///
+    /// The timing
+    pub fn get_timing(&self) -> Timing {
+        return self.timing;
+    }
+
+    /// Sets how instructions are paced from the next frame on
+    ///
+    /// # Argument
+    ///
+    /// * `new_timing` - The new timing
+    pub fn set_timing(&mut self, new_timing: Timing) {
+        self.timing = new_timing;
+        self.debt = 0;
+    }
+
+    /// Gets the number of frames run
+    ///
+    /// # Returns
+    ///
+    /// The frame count
+    pub fn get_frames(&self) -> u64 {
+        return self.frames;
+    }
+
+    /// Starts rendering the machine's sound once per frame
+    ///
+    /// # Argument
+    ///
+    /// * `sample_rate` - The samples per second
+    pub fn enable_audio(&mut self, sample_rate: u32) {
+        self.audio = Some(Audio::new(sample_rate));
+    }
+
+    /// Gets the sound rendered so far
+    ///
+    /// # Returns
+    ///
+    /// The audio, or `None` if rendering was not enabled
+    pub fn get_audio(&self) -> Option<&Audio> {
+        return self.audio.as_ref();
+    }
+
+    /// Runs one frame: executes instructions until the frame's 
+    /// instruction count or VIP cycle budget is spent, then ticks
+    /// the timers, rendering the frame's sound first if enabled. 
+    /// The frame ends early if the program halts or waits for a 
+    /// key, since it cannot progress until the host changes the 
+    /// keypad. A VIP frame whose last instruction runs
+    /// past the budget shortens the next frame by the overrun.
+    ///
+    /// # Returns
+    ///
+    /// `None` if the program is still running, the `Halt` that
+    /// stopped it, or the `VmError` it raised. Timers are not
+    /// ticked for a frame that raised an error.
+    pub fn run_frame(&mut self) -> Result<Option<Halt>, VmError> {
+        let result = match self.timing {
+            Timing::InstructionsPerFrame(count) => self.run_count(count),
+            Timing::Vip => self.run_vip()
+        }?;
+        if let Some(ref mut audio) = self.audio {
+            audio.render_frame(&self.machine);
+        }
+        self.machine.tick_timers();
+        self.frames += 1;
+        return Ok(result);
+    }
+
+    /// Executes up to a number of instructions
+    fn run_count(&mut self, count: u64) -> Result<Option<Halt>, VmError> {
+        for _ in 0..count {
+            match self.machine.step()? {
+                Step::Ran => {},
+                Step::Waiting => return Ok(None),
+                Step::Halted(halt) => return Ok(Some(halt))
+            };
+        }
+        return Ok(None);
+    }
+
+    /// Executes instructions until a VIP frame's cycles are spent
+    fn run_vip(&mut self) -> Result<Option<Halt>, VmError> {
+        let mut spent = self.debt;
+        self.debt = 0;
+        while spent < VIP_CYCLES_PER_FRAME {
+            spent += vip_cycles(self.machine.get_opcode());
+            match self.machine.step()? {
+                Step::Ran => {},
+                Step::Waiting => return Ok(None),
+                Step::Halted(halt) => return Ok(Some(halt))
+            };
+        }
+        self.debt = spent - VIP_CYCLES_PER_FRAME;
+        return Ok(None);
+    }
+}
+
+//unit tests
+#[cfg(test)]
+mod tests {
+    //import the Scheduler struct
+    use super::*;
+    use super::super::Quirks;
+
+    //creates a scheduler for a program
+    fn scheduler(rom: &[u8], timing: Timing) -> Scheduler {
+        let mut m = Machine::new(Quirks::vip());
+        m.load(rom).unwrap();
+        return Scheduler::new(m, timing);
+    }
+
+    //a program that counts in V0 forever after setting the delay timer
+    const COUNTER: [u8; 8] = [0x61, 0x0A, 0xF1, 0x15, 0x70, 0x01, 0x12, 0x04];
+
+    //this test checks fixed instruction counts per frame
+    #[test]
+    fn test_instructions_per_frame() {
+        let mut s = scheduler(&COUNTER, Timing::InstructionsPerFrame(20));
+        assert_eq!(s.run_frame().unwrap(), None);
+        assert_eq!(s.get_machine().get_cycles(), 20);
+        assert_eq!(s.get_machine().get_delay(), 9);
+        for _ in 0..3 {
+            s.run_frame().unwrap();
+        }
+        assert_eq!(s.get_frames(), 4);
+        assert_eq!(s.get_machine().get_delay(), 6);
+        assert_eq!(s.get_machine().get_cycles(), 80);
+    }
+
+    //this test checks VIP cycle budgets
+    #[test]
+    fn test_vip() {
+        let mut s = scheduler(&COUNTER, Timing::Vip);
+        s.run_frame().unwrap();
+        //MOV and SDL take 16 cycles, then ADD and JMP pairs take 33
+        assert_eq!(s.get_machine().get_cycles(), 2 + 2 * 111);
+        for _ in 0..59 {
+            s.run_frame().unwrap();
+        }
+        //overruns carry over, so a second holds 60 frames of cycles
+        let pairs = (60 * VIP_CYCLES_PER_FRAME - 16).div_ceil(33) as u64;
+        assert_eq!(s.get_machine().get_cycles(), 2 + 2 * pairs);
+    }
+
+    //this test checks frames that end early
+    #[test]
+    fn test_early_end() {
+        //KEY V0; JMP 0x202
+        let mut s = scheduler(&[0xF0, 0x0A, 0x12, 0x02], 
+                              Timing::InstructionsPerFrame(10));
+        assert_eq!(s.run_frame().unwrap(), None);
+        assert_eq!(s.get_machine().get_cycles(), 2);
+        s.get_machine_mut().set_key(3, true);
+        s.get_machine_mut().set_key(3, false);
+        assert_eq!(s.run_frame().unwrap(), Some(Halt::SelfJump(0x202)));
+        assert_eq!(s.get_machine().get_v(0), 3);
+    }
+
+    //this test checks rendering sound once per frame
+    #[test]
+    fn test_audio() {
+        //MOV V1, 2; SND V1; JMP 0x204
+        let mut s = scheduler(&[0x61, 0x02, 0xF1, 0x18, 0x12, 0x04],
+                              Timing::InstructionsPerFrame(10));
+        s.enable_audio(600);
+        for _ in 0..4 {
+            s.run_frame().unwrap();
+        }
+        let samples = s.get_audio().unwrap().get_samples();
+        assert_eq!(samples.len(), 40);
+        assert!(samples[..20].iter().all(|&x| x != 0));
+        assert!(samples[20..].iter().all(|&x| x == 0));
+    }
+}
+
+//end of file
@@ -0,0 +1,203 @@
+/*
+ * snapshot.rs
+ * Compares framebuffers against stored golden files
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//usage statements
+use std::env;
+use std::fs;
+use std::path::Path;
+use super::{Display, Machine, Quirks, Runner};
+use super::super::codegen::Binary;
+use super::super::error::VmError;
+
+/// The environment variable that, when set to anything but
+/// `0`, makes `assert_snapshot` write goldens instead of
+/// comparing against them
+pub const BLESS_VAR: &str = "CH8_BLESS";
+
+/// Runs a binary in a fresh interpreter with the default quirks
+///
+/// # Arguments
+///
+/// * `bin` - The binary to run
+/// * `cycles` - The most cycles to run for
+///
+/// # Returns
+///
+/// The framebuffer once the program halts or the cycles
+/// run out, or the `VmError` that stopped the program
+pub fn run_binary(bin: &Binary, cycles: u64) -> Result<Display, VmError> {
+    let mut machine = Machine::new(Quirks::default());
+    machine.load(bin.get_data())?;
+    let mut runner = Runner::new(machine);
+    runner.run(cycles)?;
+    return Ok(runner.get_machine().get_display().clone());
+}
+
+/// Compares a framebuffer against an ASCII-art golden
+///
+/// # Arguments
+///
+/// * `golden` - The expected frame, as rendered by `Display::to_ascii`
+/// * `actual` - The framebuffer to check
+///
+/// # Returns
+///
+/// `None` if the frames match, or a description of the
+/// differences with `+` marking pixels that are lit but
+/// shouldn't be and `-` marking pixels that should be lit
+pub fn diff_frame(golden: &str, actual: &Display) -> Option<String> {
+    let rows: Vec<&str> = golden.lines().collect();
+    let (w, h) = (actual.width(), actual.height());
+    let golden_w = rows.first().map(|r| r.chars().count()).unwrap_or(0);
+    if rows.len() != h || rows.iter().any(|r| r.chars().count() != golden_w) 
+        || golden_w != w {
+        return Some(format!("golden is {}x{} but the display is {}x{}",
+                            golden_w, rows.len(), w, h));
+    }
+    let mut out = String::new();
+    let mut count = 0;
+    for (y, row) in rows.iter().enumerate() {
+        let mut line = String::with_capacity(w);
+        let mut differs = false;
+        for (x, c) in row.chars().enumerate() {
+            let lit = actual.get(x, y);
+            line.push(match (c != '.', lit) {
+                (true, true) => '#',
+                (false, false) => '.',
+                (false, true) => '+',
+                (true, false) => '-'
+            });
+            if (c != '.') != lit {
+                differs = true;
+                count += 1;
+            }
+        }
+        if differs {
+            out.push_str(&format!("{:>3} |{}|\n", y, line));
+        }
+    }
+    if count == 0 {
+        return None;
+    }
+    return Some(format!("{} pixel(s) differ (+ lit unexpectedly, \
+                         - missing):\n{}", count, out));
+}
+
+/// Checks a framebuffer against a golden file, or writes the file
+///
+/// # Arguments
+///
+/// * `actual` - The framebuffer to check
+/// * `golden` - The path of the golden file
+/// * `bless` - Whether to write the golden instead of checking it
+///
+/// # Returns
+///
+/// `Ok` if the frame matches or was blessed, or a 
+/// description of why the check failed
+pub fn check_snapshot(actual: &Display, golden: &Path, bless: bool) 
+    -> Result<(), String> {
+    if bless {
+        if let Some(dir) = golden.parent() {
+            fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+        }
+        return fs::write(golden, actual.to_ascii()).map_err(|e| {
+            format!("cannot write {}: {}", golden.display(), e)
+        });
+    }
+    let text = match fs::read_to_string(golden) {
+        Ok(t) => t,
+        Err(e) => return Err(format!("cannot read {}: {} (set {}=1 to \
+                                      create it)", golden.display(), e, 
+                                     BLESS_VAR))
+    };
+    return match diff_frame(&text, actual) {
+        Some(diff) => Err(format!("{} does not match the framebuffer \
+                                   (set {}=1 to bless it): {}", 
+                                  golden.display(), BLESS_VAR, diff)),
+        None => Ok(())
+    };
+}
+
+/// Runs a binary and asserts that its final framebuffer matches
+/// a golden file. When the `CH8_BLESS` environment variable is
+/// set, the golden file is written instead.
+///
+/// # Arguments
+///
+/// * `bin` - The binary to run
+/// * `cycles` - The most cycles to run for
+/// * `golden` - The path of the golden file
+///
+/// # Panics
+///
+/// This function will panic if the program fails or the
+/// framebuffer does not match the golden file.
+pub fn assert_snapshot<P: AsRef<Path>>(bin: &Binary, cycles: u64, golden: P) {
+    let display = match run_binary(bin, cycles) {
+        Ok(d) => d,
+        Err(e) => panic!("program failed: {}", e)
+    };
+    let bless = env::var(BLESS_VAR).map(|v| v != "0").unwrap_or(false);
+    if let Err(msg) = check_snapshot(&display, golden.as_ref(), bless) {
+        panic!("{}", msg);
+    }
+}
+
+//unit tests
+#[cfg(test)]
+mod tests {
+    //import the snapshot functions
+    use super::*;
+
+    //this test checks the pixel diff
+    #[test]
+    fn test_diff_frame() {
+        let mut d = Display::new(false);
+        assert_eq!(diff_frame(&d.to_ascii(), &d), None);
+        let golden = d.to_ascii().replacen(".", "#", 1);
+        d.draw(2, 3, &[0x80], 8, 1, true);
+        let diff = diff_frame(&golden, &d).unwrap();
+        assert!(diff.starts_with("2 pixel(s) differ"));
+        assert!(diff.contains(&format!("  0 |-{}|", ".".repeat(63))));
+        assert!(diff.contains(&format!("  3 |..+{}|", ".".repeat(61))));
+        let diff = diff_frame(&golden, &Display::new(true)).unwrap();
+        assert_eq!(diff, "golden is 64x32 but the display is 128x64");
+    }
+
+    //this test checks blessing and checking a golden file
+    #[test]
+    fn test_check_snapshot() {
+        let path = Path::new("test_snapshot_tmp/frame.txt");
+        let mut d = Display::new(true);
+        assert!(check_snapshot(&d, path, false).is_err());
+        check_snapshot(&d, path, true).unwrap();
+        let same = check_snapshot(&d, path, false);
+        d.draw(0, 0, &[0x80], 8, 1, true);
+        let changed = check_snapshot(&d, path, false);
+        fs::remove_dir_all("test_snapshot_tmp").unwrap();
+        assert!(same.is_ok());
+        assert!(changed.unwrap_err().contains("1 pixel(s) differ"));
+    }
+}
+
+//end of file
@@ -0,0 +1,39 @@
+/*
+ * step.rs
+ * Enumerates the outcomes of executing one instruction
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//usage statement
+use super::Halt;
+
+/// The outcome of a single interpreter step
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Step {
+    /// An instruction was executed
+    Ran,
+
+    /// The program is blocked waiting for a key
+    Waiting,
+
+    /// The program has stopped
+    Halted(Halt)
+}
+
+//end of file
@@ -0,0 +1,63 @@
+/*
+ * stop.rs
+ * Enumerates the reasons the debugger stops
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//usage statements
+use std::fmt;
+use super::Halt;
+
+/// The reason a debugger command returned control
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Stop {
+    /// The requested step completed
+    Stepped,
+
+    /// Execution reached a breakpoint at the given address
+    Breakpoint(u16),
+
+    /// An instruction wrote to watched memory at the given address
+    Watchpoint(u16),
+
+    /// The program halted, or the cycle limit was reached
+    Halted(Halt),
+
+    /// There is no history left to step back through
+    HistoryEmpty
+}
+
+//Display implementation
+impl fmt::Display for Stop {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Stop::Stepped => write!(f, "stepped"),
+            Stop::Breakpoint(addr) => {
+                write!(f, "breakpoint at 0x{:03X}", addr)
+            },
+            Stop::Watchpoint(addr) => {
+                write!(f, "write to watched 0x{:03X}", addr)
+            },
+            Stop::Halted(ref halt) => write!(f, "{}", halt),
+            Stop::HistoryEmpty => write!(f, "no history")
+        }
+    }
+}
+
+//end of file
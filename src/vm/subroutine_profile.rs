@@ -0,0 +1,109 @@
+/*
+ * subroutine_profile.rs
+ * Defines the profile of one subroutine
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+/// The cycles spent in one subroutine, identified by its entry address
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubroutineProfile {
+    /// The entry address of the subroutine
+    entry: u16,
+
+    /// The number of times the subroutine was entered
+    calls: u64,
+
+    /// The cycles spent in the subroutine's own instructions
+    self_cycles: u64,
+
+    /// The cycles spent in the subroutine and those it called
+    total_cycles: u64
+}
+
+//implementation
+impl SubroutineProfile {
+    /// Constructs a new `SubroutineProfile` instance
+    ///
+    /// # Argument
+    ///
+    /// * `new_entry` - The entry address of the subroutine
+    ///
+    /// # Returns
+    ///
+    /// A new `SubroutineProfile` with no calls or cycles
+    pub fn new(new_entry: u16) -> SubroutineProfile {
+        return SubroutineProfile {
+            entry: new_entry,
+            calls: 0,
+            self_cycles: 0,
+            total_cycles: 0
+        };
+    }
+
+    /// Gets the entry address of the subroutine
+    ///
+    /// # Returns
+    ///
+    /// The address of the subroutine's first instruction
+    pub fn get_entry(&self) -> u16 {
+        return self.entry;
+    }
+
+    /// Gets the number of times the subroutine was entered
+    ///
+    /// # Returns
+    ///
+    /// The call count
+    pub fn get_calls(&self) -> u64 {
+        return self.calls;
+    }
+
+    /// Gets the cycles spent in the subroutine's own instructions
+    ///
+    /// # Returns
+    ///
+    /// The exclusive cycle count
+    pub fn get_self_cycles(&self) -> u64 {
+        return self.self_cycles;
+    }
+
+    /// Gets the cycles spent in the subroutine and those it called
+    ///
+    /// # Returns
+    ///
+    /// The inclusive cycle count
+    pub fn get_total_cycles(&self) -> u64 {
+        return self.total_cycles;
+    }
+
+    /// Counts an entry into the subroutine
+    pub(crate) fn add_call(&mut self) {
+        self.calls += 1;
+    }
+
+    /// Counts a cycle spent in the subroutine, or in one it called
+    pub(crate) fn add_cycle(&mut self, own: bool) {
+        self.total_cycles += 1;
+        if own {
+            self.self_cycles += 1;
+        }
+    }
+}
+
+//end of file
@@ -0,0 +1,44 @@
+/*
+ * timing.rs
+ * Enumerates how a scheduler paces instructions
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//usage statement
+use super::DEFAULT_IPF;
+
+/// How many instructions a `Scheduler` executes per 60 Hz frame
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Timing {
+    /// A fixed number of instructions per frame
+    InstructionsPerFrame(u64),
+
+    /// As many instructions as fit in a frame of the COSMAC VIP,
+    /// using the costs estimated by `vip_cycles`
+    Vip
+}
+
+//Default implementation
+impl Default for Timing {
+    fn default() -> Timing {
+        return Timing::InstructionsPerFrame(DEFAULT_IPF);
+    }
+}
+
+//end of file
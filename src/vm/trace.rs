@@ -0,0 +1,238 @@
+/*
+ * trace.rs
+ * Records execution traces in text and binary formats
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//usage statements
+use super::TraceEntry;
+use super::super::data::Register;
+use super::super::error::{DecodeError, DecodeErrorType};
+
+/// The magic number that starts a binary trace
+const MAGIC: &[u8; 4] = b"CH8T";
+
+/// The version of the binary trace format
+const VERSION: u8 = 1;
+
+/// A trace of executed instructions. The binary format is the
+/// magic `CH8T` and a version byte, then one record per entry: 
+/// the cycles since the previous entry as a LEB128 number, the 
+/// address and opcode as big-endian words, a count of deltas, and 
+/// each delta's register number (`0x10` for `I`) and new value, 
+/// one byte for `V` registers and a big-endian word for `I`.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Trace {
+    /// The executed instructions, in order
+    entries: Vec<TraceEntry>
+}
+
+//implementation
+impl Trace {
+    /// Constructs a new `Trace` instance
+    ///
+    /// # Returns
+    ///
+    /// A new empty `Trace`
+    pub fn new() -> Trace {
+        return Trace {
+            entries: Vec::new()
+        };
+    }
+
+    /// Adds an entry to the end of the trace
+    ///
+    /// # Argument
+    ///
+    /// * `entry` - The executed instruction
+    pub fn add(&mut self, entry: TraceEntry) {
+        self.entries.push(entry);
+    }
+
+    /// Gets the entries of the trace
+    ///
+    /// # Returns
+    ///
+    /// The executed instructions, in order
+    pub fn get_entries(&self) -> &[TraceEntry] {
+        return self.entries.as_slice();
+    }
+
+    /// Formats the trace as text, one instruction per line
+    ///
+    /// # Returns
+    ///
+    /// Lines of the cycle, address, opcode, instruction and the
+    /// registers it changed
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        for entry in self.entries.iter() {
+            text.push_str(&format!("{}\n", entry));
+        }
+        return text;
+    }
+
+    /// Encodes the trace in the binary format
+    ///
+    /// # Returns
+    ///
+    /// The encoded trace
+    pub fn to_binary(&self) -> Vec<u8> {
+        let mut out = MAGIC.to_vec();
+        out.push(VERSION);
+        let mut last = 0;
+        for entry in self.entries.iter() {
+            let mut gap = entry.get_cycle().wrapping_sub(last);
+            last = entry.get_cycle();
+            loop {
+                let byte = (gap & 0x7F) as u8;
+                gap >>= 7;
+                if gap == 0 {
+                    out.push(byte);
+                    break;
+                }
+                out.push(byte | 0x80);
+            }
+            out.extend_from_slice(&entry.get_pc().to_be_bytes());
+            out.extend_from_slice(&entry.get_opcode().to_be_bytes());
+            out.push(entry.get_deltas().len() as u8);
+            for (reg, value) in entry.get_deltas() {
+                out.push(reg.to_id());
+                match *reg {
+                    Register::I => out.extend_from_slice(&value.to_be_bytes()),
+                    _ => out.push(*value as u8)
+                };
+            }
+        }
+        return out;
+    }
+
+    /// Decodes a trace from the binary format
+    ///
+    /// # Argument
+    ///
+    /// * `data` - The encoded trace
+    ///
+    /// # Returns
+    ///
+    /// The trace, or a `DecodeError` describing why it is malformed
+    pub fn from_binary(data: &[u8]) -> Result<Trace, DecodeError> {
+        if data.len() < 5 || &data[..4] != MAGIC {
+            return Err(DecodeError::new(DecodeErrorType::BadMagic, 0));
+        }
+        if data[4] != VERSION {
+            return Err(DecodeError::new(
+                    DecodeErrorType::UnsupportedVersion(data[4]), 4));
+        }
+        let mut trace = Trace::new();
+        let mut pos = 5;
+        let mut cycle = 0u64;
+
+        //reads the next byte
+        let next = |pos: &mut usize| -> Result<u8, DecodeError> {
+            let byte = *data.get(*pos).ok_or_else(|| {
+                return DecodeError::new(DecodeErrorType::Truncated, *pos);
+            })?;
+            *pos += 1;
+            return Ok(byte);
+        };
+        while pos < data.len() {
+            let mut gap = 0u64;
+            let mut shift = 0;
+            loop {
+                let byte = next(&mut pos)?;
+                if shift > 63 {
+                    return Err(DecodeError::new(DecodeErrorType::InvalidValue(
+                                String::from("cycle count")), pos - 1));
+                }
+                gap |= ((byte & 0x7F) as u64) << shift;
+                shift += 7;
+                if byte & 0x80 == 0 {
+                    break;
+                }
+            }
+            cycle = cycle.wrapping_add(gap);
+            let pc = u16::from_be_bytes([next(&mut pos)?, next(&mut pos)?]);
+            let op = u16::from_be_bytes([next(&mut pos)?, next(&mut pos)?]);
+            let count = next(&mut pos)?;
+            let mut deltas = Vec::new();
+            for _ in 0..count {
+                let id = next(&mut pos)?;
+                let reg = Register::from_id(id).ok_or_else(|| {
+                    return DecodeError::new(DecodeErrorType::InvalidValue(
+                            format!("register 0x{:02X}", id)), pos - 1);
+                })?;
+                let value = match reg {
+                    Register::I => {
+                        u16::from_be_bytes([next(&mut pos)?, next(&mut pos)?])
+                    },
+                    _ => next(&mut pos)? as u16
+                };
+                deltas.push((reg, value));
+            }
+            trace.add(TraceEntry::new(cycle, pc, op, deltas));
+        }
+        return Ok(trace);
+    }
+}
+
+//unit tests
+#[cfg(test)]
+mod tests {
+    //import the Trace struct
+    use super::*;
+
+    //creates a small trace
+    fn sample() -> Trace {
+        let mut trace = Trace::new();
+        trace.add(TraceEntry::new(1, 0x200, 0x6005, 
+                                  vec![(Register::V0, 5)]));
+        trace.add(TraceEntry::new(2, 0x202, 0xA300, 
+                                  vec![(Register::I, 0x300)]));
+        trace.add(TraceEntry::new(300, 0x204, 0x0000, Vec::new()));
+        return trace;
+    }
+
+    //this test checks the text format
+    #[test]
+    fn test_text() {
+        let text = sample().to_text();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[0], "       1 200 6005  MOV V0, 0x05         V0=05");
+        assert_eq!(lines[1], "       2 202 A300  MOV I, 0x300         I=300");
+        assert_eq!(lines[2], "     300 204 0000  .word 0x0000");
+    }
+
+    //this test checks that the binary format round-trips
+    #[test]
+    fn test_binary() {
+        let trace = sample();
+        let data = trace.to_binary();
+        assert_eq!(&data[..12], b"CH8T\x01\x01\x02\x00\x60\x05\x01\x00");
+        assert_eq!(Trace::from_binary(&data).unwrap(), trace);
+        assert_eq!(Trace::from_binary(b"CH8X\x01").unwrap_err().get_type(),
+                   DecodeErrorType::BadMagic);
+        assert_eq!(Trace::from_binary(b"CH8T\x02").unwrap_err().get_type(),
+                   DecodeErrorType::UnsupportedVersion(2));
+        let err = Trace::from_binary(&data[..data.len() - 1]).unwrap_err();
+        assert_eq!(err.get_type(), DecodeErrorType::Truncated);
+    }
+}
+
+//end of file
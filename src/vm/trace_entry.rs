@@ -0,0 +1,181 @@
+/*
+ * trace_entry.rs
+ * Defines one executed instruction in an execution trace
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//usage statements
+use std::fmt;
+use super::Machine;
+use super::super::codegen::Instruction;
+use super::super::data::Register;
+
+/// The number of registers compared for deltas: `V0` to `VF` and `I`
+pub(crate) const TRACED_REGISTERS: usize = 17;
+
+/// Captures the registers compared for deltas
+///
+/// # Argument
+///
+/// * `m` - The machine
+///
+/// # Returns
+///
+/// `V0` to `VF`, then `I`
+pub(crate) fn register_file(m: &Machine) -> [u16; TRACED_REGISTERS] {
+    let mut regs = [0u16; TRACED_REGISTERS];
+    for (r, reg) in regs.iter_mut().enumerate().take(16) {
+        *reg = m.get_v(r) as u16;
+    }
+    regs[16] = m.get_i();
+    return regs;
+}
+
+/// One instruction executed by the interpreter
+#[derive(Debug, Clone, PartialEq)]
+pub struct TraceEntry {
+    /// The cycle the instruction executed on
+    cycle: u64,
+
+    /// The address of the instruction
+    pc: u16,
+
+    /// The opcode of the instruction
+    opcode: u16,
+
+    /// The registers the instruction changed, with their new values
+    deltas: Vec<(Register, u16)>
+}
+
+//implementation
+impl TraceEntry {
+    /// Constructs a new `TraceEntry` instance
+    ///
+    /// # Arguments
+    ///
+    /// * `new_cycle` - The cycle the instruction executed on
+    /// * `new_pc` - The address of the instruction
+    /// * `new_opcode` - The opcode of the instruction
+    /// * `new_deltas` - The changed registers and their new values
+    ///
+    /// # Returns
+    ///
+    /// A new `TraceEntry` instance with the given properties
+    pub fn new(new_cycle: u64, new_pc: u16, new_opcode: u16, 
+               new_deltas: Vec<(Register, u16)>) -> TraceEntry {
+        return TraceEntry {
+            cycle: new_cycle,
+            pc: new_pc,
+            opcode: new_opcode,
+            deltas: new_deltas
+        };
+    }
+
+    /// Constructs an entry by comparing registers before and 
+    /// after an instruction
+    ///
+    /// # Arguments
+    ///
+    /// * `pc` - The address of the instruction
+    /// * `opcode` - The opcode of the instruction
+    /// * `before` - The registers before the instruction
+    /// * `after` - The machine after the instruction
+    ///
+    /// # Returns
+    ///
+    /// The entry, on the machine's current cycle
+    pub(crate) fn capture(pc: u16, opcode: u16, 
+                          before: &[u16; TRACED_REGISTERS],
+                          after: &Machine) -> TraceEntry {
+        let now = register_file(after);
+        let deltas = (0..TRACED_REGISTERS).filter(|r| before[*r] != now[*r])
+            .filter_map(|r| Register::from_id(r as u8).map(|id| (id, now[r])))
+            .collect();
+        return TraceEntry::new(after.get_cycles(), pc, opcode, deltas);
+    }
+
+    /// Gets the cycle the instruction executed on
+    ///
+    /// # Returns
+    ///
+    /// The cycle, counting from one
+    pub fn get_cycle(&self) -> u64 {
+        return self.cycle;
+    }
+
+    /// Gets the address of the instruction
+    ///
+    /// # Returns
+    ///
+    /// The program counter before the instruction
+    pub fn get_pc(&self) -> u16 {
+        return self.pc;
+    }
+
+    /// Gets the opcode of the instruction
+    ///
+    /// # Returns
+    ///
+    /// The opcode
+    pub fn get_opcode(&self) -> u16 {
+        return self.opcode;
+    }
+
+    /// Decodes the instruction
+    ///
+    /// # Returns
+    ///
+    /// The instruction, or `None` for opcodes outside the 
+    /// classic instruction set
+    pub fn get_instruction(&self) -> Option<Instruction> {
+        return Instruction::decode(self.opcode);
+    }
+
+    /// Gets the registers the instruction changed
+    ///
+    /// # Returns
+    ///
+    /// The changed registers and their new values
+    pub fn get_deltas(&self) -> &[(Register, u16)] {
+        return self.deltas.as_slice();
+    }
+}
+
+//Display implementation
+impl fmt::Display for TraceEntry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let text = match self.get_instruction() {
+            Some(instr) => format!("{}", instr),
+            None => format!(".word 0x{:04X}", self.opcode)
+        };
+        let mut line = format!("{:>8} {:03X} {:04X}  {:<20}", self.cycle,
+                               self.pc, self.opcode, text);
+        for (reg, value) in self.deltas.iter() {
+            match *reg {
+                Register::I => line.push_str(&format!(" I={:03X}", value)),
+                ref v => {
+                    line.push_str(&format!(" V{:X}={:02X}", v.to_id(), value))
+                }
+            };
+        }
+        write!(f, "{}", line.trim_end())
+    }
+}
+
+//end of file
@@ -0,0 +1,81 @@
+/*
+ * vip_timing.rs
+ * Estimates instruction costs on the COSMAC VIP
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+/// The machine cycles the COSMAC VIP executes per 60 Hz frame:
+/// a 1.7609 MHz clock with eight clocks per machine cycle
+pub const VIP_CYCLES_PER_FRAME: u32 = 3668;
+
+/// Estimates the machine cycles the original COSMAC VIP interpreter
+/// takes to execute an instruction, including fetching and decoding
+/// it. Drawing is charged per sprite row, skips as if not taken and
+/// `KEY` per poll. Opcodes the VIP lacks cost as much as a jump.
+///
+/// # Argument
+///
+/// * `opcode` - The opcode of the instruction
+///
+/// # Returns
+///
+/// The approximate cost in VIP machine cycles
+pub fn vip_cycles(opcode: u16) -> u32 {
+    let x = ((opcode >> 8) & 0xF) as u32;
+    let n = (opcode & 0xF) as u32;
+    return match opcode >> 12 {
+        0x0 if opcode == 0x00E0 => 24,
+        0x1 | 0x2 | 0xB | 0x0 => 23,
+        0x3 | 0x4 | 0xA => 12,
+        0x5 | 0x9 | 0xE => 16,
+        0x6 => 6,
+        0x7 => 10,
+        0x8 => 44,
+        0xC => 36,
+        0xD => 38 + 46 * n,
+        _ => match opcode & 0xFF {
+            0x1E => 19,
+            0x29 => 20,
+            0x33 => 204,
+            0x55 | 0x65 => 14 + 8 * (x + 1),
+            0x07 | 0x0A | 0x15 | 0x18 => 10,
+            _ => 23
+        }
+    };
+}
+
+//unit tests
+#[cfg(test)]
+mod tests {
+    //import the vip_cycles function
+    use super::*;
+
+    //this test checks a few instruction costs
+    #[test]
+    fn test_costs() {
+        assert_eq!(vip_cycles(0x00E0), 24);
+        assert_eq!(vip_cycles(0x00EE), 23);
+        assert_eq!(vip_cycles(0x6012), 6);
+        assert_eq!(vip_cycles(0xD015), 38 + 46 * 5);
+        assert_eq!(vip_cycles(0xF355), 14 + 32);
+        assert_eq!(vip_cycles(0xF033), 204);
+    }
+}
+
+//end of file
@@ -0,0 +1,101 @@
+/*
+ * xorshift.rs
+ * Implements the default seeded random source
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//usage statements
+use super::RandomSource;
+
+/// The state used in place of a zero seed, which xorshift cannot use
+const DEFAULT_SEED: u64 = 0x2545_F491_4F6C_DD1D;
+
+/// A seeded xorshift generator, the default random source
+#[derive(Debug, Clone, PartialEq)]
+pub struct Xorshift {
+    /// The state of the generator, never zero
+    state: u64
+}
+
+//implementation
+impl Xorshift {
+    /// Constructs a new `Xorshift` instance
+    ///
+    /// # Argument
+    ///
+    /// * `seed` - The seed, with `0` selecting the default seed
+    ///
+    /// # Returns
+    ///
+    /// A new `Xorshift` generator
+    pub fn new(seed: u64) -> Xorshift {
+        return Xorshift {
+            state: if seed == 0 { DEFAULT_SEED } else { seed }
+        };
+    }
+
+    /// Decodes state encoded by `encode`
+    ///
+    /// # Argument
+    ///
+    /// * `data` - The encoded state
+    ///
+    /// # Returns
+    ///
+    /// The generator, or `None` if the data is malformed
+    pub fn decode(data: &[u8]) -> Option<Xorshift> {
+        if data.len() != 9 || data[0] != 0 {
+            return None;
+        }
+        let mut state = [0u8; 8];
+        state.copy_from_slice(&data[1..]);
+        return Some(Xorshift::new(u64::from_be_bytes(state)));
+    }
+}
+
+//RandomSource implementation
+impl RandomSource for Xorshift {
+    fn next_byte(&mut self) -> u8 {
+        let mut x = self.state;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.state = x;
+        return (x >> 32) as u8;
+    }
+
+    fn box_clone(&self) -> Box<dyn RandomSource> {
+        return Box::new(self.clone());
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        let mut out = vec![0];
+        out.extend_from_slice(&self.state.to_be_bytes());
+        return out;
+    }
+}
+
+//Default implementation
+impl Default for Xorshift {
+    fn default() -> Xorshift {
+        return Xorshift::new(0);
+    }
+}
+
+//end of file
@@ -0,0 +1,99 @@
+/*
+ * test_asm.rs
+ * Integration tests for the ch8-isa assembler
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+extern crate ch8_isa;
+use ch8_isa::*;
+use std::fs;
+
+//this test checks assembling a file with includes and macros
+#[test]
+fn test_asm_with_include() {
+    //write the source files
+    fs::create_dir_all("test_asm_tmp").unwrap();
+    fs::write("test_asm_tmp/defs.inc", "
+        .equ HEIGHT, 4
+        .macro CLAMP reg, max
+            SKIP.NE reg, max
+            MOV reg, 0
+        .endm
+    ").unwrap();
+    fs::write("test_asm_tmp/main.s", "
+        .include \"defs.inc\"
+        start:  MOV I, ball         ; point at the sprite
+                MOV V0, 0
+        @loop:  DRAW V0, V1, HEIGHT
+                ADD V0, 1
+                CLAMP V0, 64 - 8
+                JMP @loop
+        .if HEIGHT == 4
+        ball:   .byte 0x60, 0xF0, 0xF0, 0x60
+        .endif
+    ").unwrap();
+
+    //assemble them
+    let img = asm::assemble_file("test_asm_tmp/main.s");
+    fs::remove_dir_all("test_asm_tmp").unwrap();
+    let img = img.unwrap();
+    assert_eq!(img.get_label("ball"), Some(0x20E));
+
+    //and verify the bytes
+    assert_eq!(img.to_bytes(), vec![0xA2, 0x0E, 0x60, 0x00, 0xD0, 0x14,
+                                    0x70, 0x01, 0x40, 0x38, 0x60, 0x00,
+                                    0x12, 0x04, 0x60, 0xF0, 0xF0, 0x60]);
+}
+
+//this test checks that errors name the file they occur in
+#[test]
+fn test_asm_error_location() {
+    let err = asm::assemble("CLS\n  MOV V0, 300", "game.s").unwrap_err();
+    assert_eq!(err.get_file(), Some("game.s"));
+    assert_eq!(err.get_line(), 2);
+    assert_eq!(err.get_col(), 11);
+}
+
+//this test checks the listing and symbols of an assembled binary
+#[test]
+fn test_asm_listing() {
+    let img = asm::assemble("main: CLS\nloop: JMP loop", "game.s").unwrap();
+    let mut bin = codegen::Binary::new("test_asm_listing_tmp.c8").unwrap();
+    img.write_to_binary(&mut bin).unwrap();
+    let listing = bin.listing();
+    assert!(listing.contains("0202 | 12 02 | JMP 0x202"));
+    assert!(listing.contains("game.s:2: loop: JMP loop"));
+    assert_eq!(bin.symbols(codegen::SymbolFormat::Sym),
+               "[labels]\n00:0200 main\n00:0202 loop\n");
+}
+
+//this test checks that the disassembly of every valid
+//opcode reassembles to the same opcode
+#[test]
+fn test_asm_disassembly_round_trip() {
+    for opcode in 0..=0xFFFFu16 {
+        if let Some(instr) = codegen::Instruction::decode(opcode) {
+            let text = instr.to_string();
+            let img = asm::assemble(&text, "round.s").unwrap();
+            assert_eq!(img.to_bytes(), opcode.to_be_bytes(), "{}", text);
+        }
+    }
+}
+
+//end of file
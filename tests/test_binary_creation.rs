@@ -80,4 +80,22 @@ fn test_binary_creation() {
     fs::remove_file("test_tmp.c8").unwrap();
 }
 
+//this test checks writing a binary as Intel HEX
+#[test]
+fn test_binary_intel_hex() {
+    //create a binary with an odd number of bytes
+    let mut rom = codegen::Binary::new("test_tmp.hex").unwrap();
+    rom.add_instruction(&codegen::Instruction::CLS).unwrap();
+    rom.add_byte(0xAB).unwrap();
 
+    //write it out loaded at the program start
+    let fmt = codegen::OutputFormat::IntelHex(
+                                codegen::PROGRAM_START as u32);
+    let sz = rom.write_formatted(&fmt).unwrap();
+
+    //and verify the padded records
+    let text = fs::read_to_string("test_tmp.hex").unwrap();
+    fs::remove_file("test_tmp.hex").unwrap();
+    assert_eq!(text, ":0402000000E0AB006F\n:00000001FF\n");
+    assert_eq!(sz, text.len());
+}
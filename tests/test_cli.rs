@@ -0,0 +1,241 @@
+/*
+ * test_cli.rs
+ * Integration tests for the ch8 command-line tool
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+extern crate ch8_isa;
+use ch8_isa::*;
+use std::fs;
+use std::process::{Command, Output};
+
+//runs the ch8 binary with arguments
+#[allow(clippy::needless_return)]
+fn ch8(args: &[&str]) -> Output {
+    return Command::new(env!("CARGO_BIN_EXE_ch8")).args(args)
+               .output().unwrap();
+}
+
+//this test checks assembling and inspecting a ROM
+#[test]
+fn test_cli_asm_and_disasm() {
+    //assemble a source file
+    fs::create_dir_all("test_cli_tmp").unwrap();
+    fs::write("test_cli_tmp/p.s", "start: CLS\nloop: JMP loop\n").unwrap();
+    let asm = ch8(&["asm", "test_cli_tmp/p.s", "-s", "test_cli_tmp/p.sym"]);
+    let rom = fs::read("test_cli_tmp/p.ch8");
+    let sym = fs::read_to_string("test_cli_tmp/p.sym");
+
+    //disassemble and dump it
+    let disasm = ch8(&["disasm", "test_cli_tmp/p.ch8"]);
+    let info = ch8(&["info", "test_cli_tmp/p.ch8"]);
+    let dump = ch8(&["hexdump", "test_cli_tmp/p.ch8"]);
+    fs::remove_dir_all("test_cli_tmp").unwrap();
+
+    //and check the results
+    assert_eq!(asm.status.code(), Some(0));
+    assert_eq!(rom.unwrap(), vec![0x00, 0xE0, 0x12, 0x02]);
+    assert_eq!(sym.unwrap(), "start = 0x0200\nloop = 0x0202\n");
+    let listing = String::from_utf8(disasm.stdout).unwrap();
+    assert!(listing.contains("0202 |       | L_202:"));
+    assert!(listing.contains("0202 | 12 02 | JMP 0x202"));
+    let info = String::from_utf8(info.stdout).unwrap();
+    assert!(info.contains("size:  4 bytes"));
+    assert!(info.contains("  CLS      1"));
+    let dump = String::from_utf8(dump.stdout).unwrap();
+    assert!(dump.starts_with("0200  00 E0 12 02"));
+}
+
+//this test checks diagnostics and exit codes
+#[test]
+fn test_cli_errors() {
+    fs::create_dir_all("test_cli_err_tmp").unwrap();
+    fs::write("test_cli_err_tmp/bad.s", "CLS\n  JMP nowhere\n").unwrap();
+    let bad = ch8(&["asm", "test_cli_err_tmp/bad.s"]);
+    fs::remove_dir_all("test_cli_err_tmp").unwrap();
+    assert_eq!(bad.status.code(), Some(1));
+    let err = String::from_utf8(bad.stderr).unwrap();
+    assert!(err.starts_with("test_cli_err_tmp/bad.s:2:7: error: "));
+
+    assert_eq!(ch8(&[]).status.code(), Some(2));
+    assert_eq!(ch8(&["frobnicate"]).status.code(), Some(2));
+    assert_eq!(ch8(&["asm", "x.s", "-f", "elf"]).status.code(), Some(2));
+    assert_eq!(ch8(&["info", "missing.ch8"]).status.code(), Some(1));
+}
+
+//this test checks running a ROM headlessly with scripted keys
+#[test]
+fn test_cli_run() {
+    //assemble a program that draws the key it waits for
+    fs::create_dir_all("test_cli_run_tmp").unwrap();
+    fs::write("test_cli_run_tmp/k.s", 
+              "KEY V0\nSCH V0\nDRAW V1, V1, 5\nend: JMP end\n").unwrap();
+    fs::write("test_cli_run_tmp/keys.txt", "5 down 1\n9 up 1\n").unwrap();
+    ch8(&["asm", "test_cli_run_tmp/k.s"]);
+    let run = ch8(&["run", "test_cli_run_tmp/k.ch8", "-k", 
+                    "test_cli_run_tmp/keys.txt"]);
+    let brk = ch8(&["run", "test_cli_run_tmp/k.ch8", "-b", "0x202", 
+                    "-k", "test_cli_run_tmp/keys.txt", "--dump", "pbm"]);
+    fs::remove_dir_all("test_cli_run_tmp").unwrap();
+
+    //and check the final state
+    assert_eq!(run.status.code(), Some(0));
+    let out = String::from_utf8(run.stdout).unwrap();
+    let lines: Vec<&str> = out.lines().collect();
+    assert_eq!(lines[0], "halted: self-jump at 0x206 after 12 cycles");
+    assert_eq!(&lines[1..6], &["..#.....", ".##.....", "..#.....",
+                               "..#.....", ".###...."].iter()
+               .map(|r| format!("{:.<64}", r)).collect::<Vec<_>>()[..]);
+    assert!(lines[33].starts_with("PC=0x206 I=0x005"));
+    assert!(lines[34].starts_with("V0=01 V1=00"));
+    let out = String::from_utf8(brk.stdout).unwrap();
+    assert!(out.starts_with("halted: breakpoint at 0x202 after 1 cycles\n\
+                             P1\n64 32\n"));
+}
+
+//this test checks tracing and profiling a run
+#[test]
+fn test_cli_trace() {
+    //assemble a program and trace it in both formats
+    fs::create_dir_all("test_cli_trace_tmp").unwrap();
+    fs::write("test_cli_trace_tmp/t.s", "MOV V0, 5\nend: JMP end\n").unwrap();
+    ch8(&["asm", "test_cli_trace_tmp/t.s"]);
+    let run = ch8(&["run", "test_cli_trace_tmp/t.ch8", "--profile", "1",
+                    "--trace", "test_cli_trace_tmp/t.txt"]);
+    ch8(&["run", "test_cli_trace_tmp/t.ch8", "--trace-format", "binary",
+          "--trace", "test_cli_trace_tmp/t.bin"]);
+    let text = fs::read_to_string("test_cli_trace_tmp/t.txt").unwrap();
+    let data = fs::read("test_cli_trace_tmp/t.bin").unwrap();
+    fs::remove_dir_all("test_cli_trace_tmp").unwrap();
+
+    //and check the results
+    assert_eq!(run.status.code(), Some(0));
+    assert_eq!(text, "       1 200 6005  MOV V0, 0x05         V0=05\n       \
+                      2 202 1202  JMP 0x202\n");
+    let trace = vm::Trace::from_binary(&data).unwrap();
+    assert_eq!(trace.to_text(), text);
+    let out = String::from_utf8(run.stdout).unwrap();
+    assert!(out.contains("2 cycles\nhot spots:\n"));
+    assert!(out.contains("  200        1   50.0  MOV V0, 0x05\n"));
+}
+
+//this test checks replaying random bytes
+#[test]
+fn test_cli_random() {
+    fs::create_dir_all("test_cli_random_tmp").unwrap();
+    fs::write("test_cli_random_tmp/r.s", 
+              "RAND V0, 0xFF\nend: JMP end\n").unwrap();
+    fs::write("test_cli_random_tmp/r.bin", [0x5A]).unwrap();
+    fs::write("test_cli_random_tmp/empty.bin", []).unwrap();
+    ch8(&["asm", "test_cli_random_tmp/r.s"]);
+    let run = ch8(&["run", "test_cli_random_tmp/r.ch8", "--random", 
+                    "test_cli_random_tmp/r.bin"]);
+    let empty = ch8(&["run", "test_cli_random_tmp/r.ch8", "--random", 
+                      "test_cli_random_tmp/empty.bin"]);
+    fs::remove_dir_all("test_cli_random_tmp").unwrap();
+    let out = String::from_utf8(run.stdout).unwrap();
+    assert!(out.contains("\nV0=5A V1=00"));
+    assert_eq!(empty.status.code(), Some(1));
+}
+
+//this test checks recording and replaying the keypad
+#[test]
+fn test_cli_record_and_replay() {
+    //record a run driven by a key script
+    fs::create_dir_all("test_cli_replay_tmp").unwrap();
+    fs::write("test_cli_replay_tmp/k.s", "KEY V0\nend: JMP end\n").unwrap();
+    fs::write("test_cli_replay_tmp/keys.txt", "20 down 9\n30 up 9\n")
+        .unwrap();
+    ch8(&["asm", "test_cli_replay_tmp/k.s"]);
+    let recorded = ch8(&["run", "test_cli_replay_tmp/k.ch8", "-k", 
+                         "test_cli_replay_tmp/keys.txt", "--record",
+                         "test_cli_replay_tmp/k.keys"]);
+
+    //and replay it without the script
+    let replayed = ch8(&["run", "test_cli_replay_tmp/k.ch8", "--replay",
+                         "test_cli_replay_tmp/k.keys"]);
+    let bad = ch8(&["run", "test_cli_replay_tmp/k.ch8", "--replay",
+                    "test_cli_replay_tmp/keys.txt"]);
+    fs::remove_dir_all("test_cli_replay_tmp").unwrap();
+    assert_eq!(recorded.status.code(), Some(0));
+    assert_eq!(replayed.status.code(), Some(0));
+    assert_eq!(replayed.stdout, recorded.stdout);
+    assert!(String::from_utf8(replayed.stdout).unwrap().contains("V0=09"));
+    assert_eq!(bad.status.code(), Some(1));
+}
+
+//this test checks rendering the sound of a run to a WAV file
+#[test]
+fn test_cli_wav() {
+    //run a program that beeps for six frames
+    fs::create_dir_all("test_cli_wav_tmp").unwrap();
+    fs::write("test_cli_wav_tmp/b.s", 
+              "MOV V0, 6\nSND V0\nloop: ADD V1, 1\nJMP loop\n").unwrap();
+    ch8(&["asm", "test_cli_wav_tmp/b.s"]);
+    let run = ch8(&["run", "test_cli_wav_tmp/b.ch8", "-n", "200", "--ipf",
+                    "10", "--wav", "test_cli_wav_tmp/b.wav"]);
+    let wav = fs::read("test_cli_wav_tmp/b.wav");
+    fs::remove_dir_all("test_cli_wav_tmp").unwrap();
+    assert_eq!(run.status.code(), Some(0));
+
+    //and check that the samples are loud only while the timer runs
+    let wav = wav.unwrap();
+    let per_frame = (vm::DEFAULT_SAMPLE_RATE / 60) as usize;
+    let samples: Vec<i16> = wav[44..].chunks(2)
+        .map(|b| i16::from_le_bytes([b[0], b[1]])).collect();
+    assert_eq!(&wav[0..4], b"RIFF");
+    assert_eq!(&wav[8..12], b"WAVE");
+    assert_eq!(samples.len(), 20 * per_frame);
+    assert!(samples[..6 * per_frame].iter().all(|&s| s != 0));
+    assert!(samples[6 * per_frame..].iter().all(|&s| s == 0));
+}
+
+//this test checks exporting the screen as images
+#[test]
+fn test_cli_images() {
+    //run a program that draws a digit
+    fs::create_dir_all("test_cli_images_tmp").unwrap();
+    fs::write("test_cli_images_tmp/d.s", 
+              "MOV V0, 0\nSCH V0\nDRAW V0, V0, 5\nend: JMP end\n").unwrap();
+    ch8(&["asm", "test_cli_images_tmp/d.s"]);
+    let png = ch8(&["run", "test_cli_images_tmp/d.ch8", "--dump", "png", 
+                    "-o", "test_cli_images_tmp/d.png", "--scale", "2",
+                    "--palette", "000000,ff8800", "--gif", 
+                    "test_cli_images_tmp/d.gif"]);
+    let pbm = ch8(&["run", "test_cli_images_tmp/d.ch8", "--dump", "pbm",
+                    "--scale", "2"]);
+    let no_file = ch8(&["run", "test_cli_images_tmp/d.ch8", "--dump", "png"]);
+    let bad = ch8(&["run", "test_cli_images_tmp/d.ch8", "--palette", "red"]);
+    let png_file = fs::read("test_cli_images_tmp/d.png");
+    let gif_file = fs::read("test_cli_images_tmp/d.gif");
+    fs::remove_dir_all("test_cli_images_tmp").unwrap();
+
+    //and check the files and errors
+    assert_eq!(png.status.code(), Some(0));
+    assert!(png_file.unwrap().starts_with(b"\x89PNG"));
+    let gif_file = gif_file.unwrap();
+    assert!(gif_file.starts_with(b"GIF89a"));
+    assert_eq!(&gif_file[6..10], &[128, 0, 64, 0]);
+    assert!(String::from_utf8(pbm.stdout).unwrap()
+                .contains("\nP1\n128 64\n11111111000"));
+    assert_eq!(no_file.status.code(), Some(2));
+    assert_eq!(bad.status.code(), Some(2));
+}
+
+//end of file
@@ -0,0 +1,66 @@
+/*
+ * test_compiler.rs
+ * Defines integration tests for the expression language compiler
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+extern crate ch8_isa;
+use ch8_isa::*;
+use std::fs;
+
+//this test checks compiling a program into a binary
+#[test]
+fn test_compile_to_binary() {
+    //compile a small program
+    let src = "sprite ball = [0x60, 0xF0, 0xF0, 0x60];\n\
+               var x: u8 = 10;\n\
+               var y: u8 = x / 2;\n\
+               while x < 50 {\n\
+                   draw(x, y, ball);\n\
+                   x = x + 4;\n\
+               }\n";
+    let prog = compiler::compile(src).unwrap();
+
+    //write it to a binary
+    let mut rom = codegen::Binary::new("test_compiler_tmp.c8").unwrap();
+    prog.write_to_binary(&mut rom).unwrap();
+    let expected = prog.get_instructions().len() * 2 + 
+        prog.get_data().len();
+    assert_eq!(rom.len() as usize, expected);
+    let sz = rom.write_to_file().unwrap();
+    assert_eq!(sz, expected);
+
+    //verify the sprite data ends the file
+    let bytes = fs::read("test_compiler_tmp.c8").unwrap();
+    assert_eq!(&bytes[bytes.len() - 4..], &[0x60, 0xF0, 0xF0, 0x60]);
+
+    //and delete the file
+    fs::remove_file("test_compiler_tmp.c8").unwrap();
+}
+
+//this test checks that compile errors carry source positions
+#[test]
+fn test_compile_error_position() {
+    let err = compiler::compile("var x: u8 = 1;\nx = y;").unwrap_err();
+    assert_eq!(err.get_type(), 
+               error::CompileErrorType::UnknownName(String::from("y")));
+    assert_eq!(format!("{}", err), "2:5: Unknown name 'y'");
+}
+
+//end of file
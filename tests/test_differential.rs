@@ -0,0 +1,49 @@
+/*
+ * test_differential.rs
+ * Differential tests of the interpreter against the reference model
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+extern crate ch8_isa;
+use ch8_isa::difftest::*;
+use ch8_isa::vm::Quirks;
+
+//searches for a divergence, failing with a minimal repro if found
+fn search(quirks: Quirks, seed: u64, len: usize) {
+    let test = DiffTest::new(quirks);
+    if let Some((program, divergence)) = test.search(seed, 200, len) {
+        panic!("{}\n{}", divergence, DiffTest::listing(&program));
+    }
+}
+
+//this test checks the interpreter with the VIP quirks
+#[test]
+fn test_vip_agrees() {
+    search(Quirks::vip(), 1, 8);
+    search(Quirks::vip(), 2, 32);
+}
+
+//this test checks the interpreter with the SUPER-CHIP quirks
+#[test]
+fn test_schip_agrees() {
+    search(Quirks::schip(), 3, 8);
+    search(Quirks::schip(), 4, 32);
+}
+
+//end of file
@@ -0,0 +1,134 @@
+/*
+ * test_gdb.rs
+ * Tests the GDB stub with a scripted client
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+extern crate ch8_isa;
+use ch8_isa::*;
+use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::thread;
+
+//MOV V0, 5; MOV I, 0x300; BCD V0; ADD V0, 1; JMP 0x208
+const ROM: [u8; 10] = [0x60, 0x05, 0xA3, 0x00, 0xF0, 0x33, 
+                       0x70, 0x01, 0x12, 0x08];
+
+//creates a stub serving the test program
+#[allow(clippy::needless_return)]
+fn stub() -> vm::GdbStub {
+    let mut m = vm::Machine::new(vm::Quirks::vip());
+    m.load(&ROM).unwrap();
+    return vm::GdbStub::new(vm::Debugger::new(m));
+}
+
+//sends a packet and returns the reply, as a scripted client
+#[allow(clippy::needless_return)]
+fn exchange<S: Read + Write>(stream: &mut S, packet: &str) -> String {
+    let sum = packet.bytes().fold(0u8, |s, b| s.wrapping_add(b));
+    let framed = format!("${}#{:02x}", packet, sum);
+    stream.write_all(framed.as_bytes()).unwrap();
+    let mut reply = Vec::new();
+    let mut byte = [0u8; 1];
+    let mut started = false;
+    loop {
+        stream.read_exact(&mut byte).unwrap();
+        match byte[0] {
+            b'$' if !started => started = true,
+            b'#' if started => break,
+            b if started => reply.push(b),
+            _ => {}
+        };
+    }
+    let mut check = [0u8; 2];
+    stream.read_exact(&mut check).unwrap();
+    //the server may already have closed after a final reply
+    let _ = stream.write_all(b"+");
+    return String::from_utf8(reply).unwrap();
+}
+
+//this test checks registers, memory, breakpoints and stepping over TCP
+#[test]
+#[allow(clippy::needless_return)]
+fn test_gdb_tcp() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let server = thread::spawn(move || {
+        let mut s = stub();
+        s.accept_tcp(&listener).unwrap();
+        return s.get_debugger().get_machine().get_pc();
+    });
+    let mut c = TcpStream::connect(addr).unwrap();
+    c.set_nodelay(true).unwrap();
+    assert!(exchange(&mut c, "qSupported:swbreak+").contains("PacketSize"));
+    assert_eq!(exchange(&mut c, "?"), "S05");
+    let regs = format!("{}00000002000000", "00".repeat(16));
+    assert_eq!(exchange(&mut c, "g"), regs);
+    assert!(exchange(&mut c, "qXfer:features:read:target.xml:0,fff")
+            .contains("name=\"pc\""));
+
+    //run to a breakpoint and inspect memory
+    assert_eq!(exchange(&mut c, "Z0,206,2"), "OK");
+    assert_eq!(exchange(&mut c, "c"), "S05");
+    assert_eq!(exchange(&mut c, "p11"), "0602");
+    assert_eq!(exchange(&mut c, "p10"), "0003");
+    assert_eq!(exchange(&mut c, "m300,3"), "000005");
+    assert_eq!(exchange(&mut c, "M300,1:aa"), "OK");
+    assert_eq!(exchange(&mut c, "m300,1"), "aa");
+
+    //step forwards and backwards
+    assert_eq!(exchange(&mut c, "P0=09"), "OK");
+    assert_eq!(exchange(&mut c, "s"), "S05");
+    assert_eq!(exchange(&mut c, "p0"), "0a");
+    assert_eq!(exchange(&mut c, "bs"), "S05");
+    assert_eq!(exchange(&mut c, "p0"), "09");
+    assert_eq!(exchange(&mut c, "p15"), "E01");
+
+    //and run to the end
+    assert_eq!(exchange(&mut c, "z0,206,2"), "OK");
+    assert_eq!(exchange(&mut c, "c"), "S05");
+    assert_eq!(exchange(&mut c, "p11"), "0802");
+    assert_eq!(exchange(&mut c, "D"), "OK");
+    assert_eq!(server.join().unwrap(), 0x208);
+}
+
+//this test checks watchpoints and no-ack mode over a Unix socket
+#[cfg(unix)]
+#[test]
+#[allow(clippy::needless_return)]
+fn test_gdb_unix() {
+    use std::os::unix::net::{UnixListener, UnixStream};
+    let path = std::env::temp_dir().join(format!("ch8_gdb_{}.sock", 
+                                                 std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let listener = UnixListener::bind(&path).unwrap();
+    let server = thread::spawn(move || {
+        return stub().accept_unix(&listener).is_ok();
+    });
+    let mut c = UnixStream::connect(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(exchange(&mut c, "QStartNoAckMode"), "OK");
+    assert_eq!(exchange(&mut c, "Z2,302,1"), "OK");
+    assert_eq!(exchange(&mut c, "c"), "T05watch:302;");
+    assert_eq!(exchange(&mut c, "p11"), "0602");
+    assert_eq!(exchange(&mut c, "vKill"), "OK");
+    assert!(server.join().unwrap());
+}
+
+//end of file
@@ -0,0 +1,62 @@
+/*
+ * test_octo.rs
+ * Defines integration tests for the Octo front end
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+extern crate ch8_isa;
+use ch8_isa::*;
+use std::fs;
+
+//this test checks compiling an Octo program into a binary
+#[test]
+fn test_octo_to_binary() {
+    //compile a small program
+    let src = "
+        : ball 0x60 0xF0 0xF0 0x60
+        :const SPEED 2
+        : main
+            v0 := 10  v1 := 8
+            i := ball
+            loop
+                sprite v0 v1 4
+                v0 += SPEED
+                while v0 != 50
+            again
+            loop again
+    ";
+    let img = octo::compile(src).unwrap();
+    assert_eq!(img.get_label("ball"), Some(0x202));
+    assert_eq!(img.get_label("main"), Some(0x206));
+
+    //write it to a binary
+    let mut rom = codegen::Binary::new("test_octo_tmp.c8").unwrap();
+    img.write_to_binary(&mut rom).unwrap();
+    rom.write_to_file().unwrap();
+
+    //and verify the bytes on disk
+    let bytes = fs::read("test_octo_tmp.c8").unwrap();
+    assert_eq!(bytes, vec![0x12, 0x06, 0x60, 0xF0, 0xF0, 0x60,
+                           0x60, 0x0A, 0x61, 0x08, 0xA2, 0x02,
+                           0xD0, 0x14, 0x70, 0x02, 0x40, 0x32,
+                           0x12, 0x16, 0x12, 0x0C, 0x12, 0x16]);
+    fs::remove_file("test_octo_tmp.c8").unwrap();
+}
+
+//end of file
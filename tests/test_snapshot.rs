@@ -0,0 +1,70 @@
+/*
+ * test_snapshot.rs
+ * Framebuffer snapshot tests for generated ROMs
+ * Created on 10/18/2026
+ * Created by Andrew Davis
+ *
+ * Copyright (C) 2026  Andrew Davis
+ *
+ * This program is free software: you can redistribute it and/or modify   
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ * 
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+extern crate ch8_isa;
+use ch8_isa::*;
+use ch8_isa::vm::snapshot::assert_snapshot;
+
+//assembles a program into an unwritten binary
+#[allow(clippy::needless_return)]
+fn build(name: &str, src: &str) -> codegen::Binary {
+    let img = asm::assemble(src, name).unwrap();
+    let mut bin = codegen::Binary::new(name).unwrap();
+    img.write_to_binary(&mut bin).unwrap();
+    return bin;
+}
+
+//this test checks a 64x32 frame against its golden
+#[test]
+fn test_snapshot_lores() {
+    let bin = build("snapshot_lores.c8", "
+        MOV V0, 0xC
+        MOV V1, 8
+        MOV V2, 4
+        SCH V0
+        DRAW V1, V2, 5
+        MOV I, box
+        ADD V1, 8
+        DRAW V1, V2, 4
+        end: JMP end
+        box: .byte 0xF0, 0x90, 0x90, 0xF0
+    ");
+    assert_snapshot(&bin, 1000, "tests/snapshots/lores.txt");
+}
+
+//this test checks a 128x64 frame against its golden
+#[test]
+fn test_snapshot_hires() {
+    let bin = build("snapshot_hires.c8", "
+        .word 0x00FF            ; hires
+        MOV V0, 8
+        MOV V1, 60
+        MOV V2, 20
+        SCH V0
+        .word 0xF030            ; large digit of V0
+        DRAW V1, V2, 10
+        end: JMP end
+    ");
+    assert_snapshot(&bin, 1000, "tests/snapshots/hires.txt");
+}
+
+//end of file